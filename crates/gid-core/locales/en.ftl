@@ -0,0 +1,919 @@
+# English messages (the fallback for every other language)
+#
+# Ids are grouped by the module that prints them. Keep zh.ftl in the same order.
+
+## Shared file and directory errors
+
+file-read-failed = Could not read { $path }
+file-write-failed = Could not write { $path }
+file-delete-failed = Could not delete { $path }
+dir-create-failed = Could not create directory { $path }
+data-dir-create-failed = Could not create data directory { $path }
+home-dir-unknown = Could not determine the home directory
+file-create-failed = Could not create { $path }
+file-replace-failed = Could not replace { $path }
+file-not-utf8 = { $path } is not valid UTF-8
+file-readback-mismatch = { $path } does not read back as written
+file-locked = { $path } is locked by another gid process (remove it if none is running)
+permissions-restrict-failed = Could not restrict permissions of { $path }
+permissions-mode = mode { $mode }
+permissions-accessible-to = accessible to { $principals }
+permissions-icacls-failed = icacls { $path } failed: { $error }
+permissions-no-username = USERNAME is not set
+error-label = Error:
+chdir-failed = Cannot change to { $path }
+
+## Configuration (config.toml)
+
+config-path-invalid = Could not resolve config file path { $path }
+config-dir-unknown = Could not determine the config directory
+config-dir-create-failed = Could not create config directory { $path }
+config-read-failed = Could not read config file { $path }
+config-invalid = Invalid config file format
+config-serialize-failed = Could not serialize config
+config-write-failed = Could not write config file { $path }
+config-roundtrip-failed = The new config does not parse back, { $path } was left unchanged
+project-read-failed = Could not read .gid file: { $path }
+project-write-failed = Could not write .gid file: { $path }
+project-invalid = .gid file format error: { $error }
+project-identity-invalid = Invalid identity ID format in .gid file: { $id }
+identity-exists = Identity '{ $id }' already exists
+identity-not-found = Identity '{ $id }' not found
+rule-index-out-of-range = Rule index { $index } is out of range
+rule-not-found = Rule { $id } does not exist (see `gid rule list`)
+source-project = project config ({ $path })
+source-rule = rule { $rule }
+source-fallback = fallback identity
+
+## Identity validation
+
+identity-id-empty = Identity ID must not be empty
+identity-id-invalid = Identity ID may only contain letters, digits, underscores and hyphens
+identity-name-empty = Name must not be empty
+identity-email-empty = Email must not be empty
+identity-email-invalid = Invalid email address
+identity-ssh-key-missing = SSH key file does not exist: { $path }
+
+## Operation journal, state and credentials
+
+journal-read-failed = Could not read operation journal { $path }
+journal-invalid = Invalid operation journal format
+journal-serialize-failed = Could not serialize operation journal
+journal-write-failed = Could not write operation journal { $path }
+state-read-failed = Could not read state file { $path }
+state-invalid = Invalid state file format
+state-serialize-failed = Could not serialize state
+state-write-failed = Could not write state file { $path }
+credentials-read-failed = Could not read credentials file { $path }
+credentials-invalid = Invalid credentials file format
+credentials-serialize-failed = Could not serialize credentials
+credentials-write-failed = Could not write credentials file { $path }
+
+## External programs
+
+ssh-add-failed = Could not execute ssh-add
+ssh-public-key-read-failed = Could not read public key file: { $path }
+ssh-keygen-unexpected = Unexpected ssh-keygen output: { $output }
+ssh-keygen-failed = ssh-keygen failed: { $error }
+ssh-key-exists = Key file already exists: { $path }
+ssh-connecting = Connecting to { $host }
+ssh-no-response = no response
+ssh-config-read-failed = Could not read SSH config file
+ssh-config-write-failed = Could not write SSH config file
+ssh-config-roundtrip-failed = The new SSH config does not parse back as intended
+ssh-alias-is-hostname = Alias template must not produce the plain host name
+ssh-alias-template-no-identity = settings.ssh_alias_template '{ $template }' must contain {"{"}identity{"}"}
+ssh-alias-template-no-host = settings.ssh_alias_template '{ $template }' must contain {"{"}host{"}"} or {"{"}host_slug{"}"}
+ssh-alias-template-invalid-char = settings.ssh_alias_template '{ $template }' must not contain whitespace, '*' or '?'
+ssh-agent-add-failed = Failed to add key to ssh-agent: { $error }
+ssh-agent-remove-failed = Failed to remove key from ssh-agent: { $error }
+ssh-agent-clear-failed = Failed to clear ssh-agent: { $error }
+gpg-failed = Could not execute gpg
+gpg-write-failed = Could not write to gpg
+gpg-signature-not-text = gpg did not output a text signature
+gpg-sign-failed = signing failed
+curl-failed = Could not execute curl
+program-failed = Could not execute { $program }
+program-write-failed = Could not write to { $program }
+git-failed = Could not execute git
+git-command-failed = git { $command } failed: { $error }
+gh-failed = Could not execute gh
+gh-command-failed = gh { $command } failed: { $error }
+gh-unexpected-response = Unexpected API response
+age-missing = age is not installed (https://age-encryption.org)
+age-failed = Could not execute age
+age-stdin-failed = Could not open age stdin
+age-encrypt-failed = age encryption failed
+age-decrypt-failed = age decryption failed
+
+## Git repositories and config files
+
+git-not-a-repo = Current directory is not a Git repository
+git-path-not-a-repo = { $path } is not a Git repository
+git-operation-in-progress = A { $operation } is in progress. Finish it (git { $operation } --continue) or abort it (git { $operation } --abort) first
+git-set-failed = Could not set { $key }
+git-unset-failed = Could not unset { $key }
+git-credential-unset-failed = Could not unset credential helper
+git-include-add-failed = Could not add includeIf gitdir:{ $gitdir }
+git-include-remove-failed = Could not remove includeIf gitdir:{ $gitdir }
+git-ref-update-failed = Could not update { $name }
+git-key-invalid = Invalid config key: { $key }
+git-range-invalid = Invalid range
+git-commit-not-utf8 = Commit is not valid UTF-8
+signature-good = good signature by { $signer }
+signature-expired = signed by { $signer } with an expired key
+signature-bad = bad signature
+signature-unknown-key = unknown key ({ $key })
+signature-error = could not verify: { $error }
+signature-x509-unsupported = X.509 signatures are not supported
+signature-no-allowed-signers = gpg.ssh.allowedSignersFile is not set
+signature-not-allowed = not in the allowed signers file
+signature-gpg-none = gpg reported no signature
+
+## Offline mode
+
+offline-needs-network = { $action } needs the network, but gid is offline (--offline or settings.offline)
+skipped-offline = skipped (offline)
+
+## gid switch
+
+switch-done = Switched to { $scope } identity: { $identity } { $name } <{ $email }>
+switch-scope-global = global
+switch-scope-project = project
+switch-ssh-key = SSH key configured
+switch-gpg-signing = GPG signing enabled
+switch-no-changes = No configuration changes
+switch-changes = Changes:
+switch-not-a-repo = Current directory is not a Git repository. Use --global for global switch
+switch-no-worktree = Current directory is not a Git repository with a working tree
+switch-global-needs-confirmation = Global switch requires confirmation. Use --yes to skip the prompt
+switch-global-current = Current global identity: { $name } <{ $email }>
+switch-global-warning = Changing the global identity affects every repository without a local override
+switch-global-confirm = Switch global identity to [{ $id }]?
+switch-dir-not-found = Directory not found: { $path }
+switch-recursive-start = Applying identity { $identity } to { $count } repositories under { $path }
+switch-recursive-done = Switched { $count } repositories to { $identity } { $name } <{ $email }>
+switch-recursive-failed = { $count } repositories failed
+switch-unchanged = unchanged
+switch-would-change = would change { $keys }
+switch-wrote-host = wrote Host { $alias }
+switch-include-if-done = Switched to includeIf identity: { $identity } { $name } <{ $email }>
+switch-fragment = Fragment:
+switch-no-submodules = No initialized submodules
+switch-submodules = Submodules:
+switch-superproject = superproject
+switch-submodule-unmatched = no .gid or rule
+switch-submodules-failed = { $count } submodules could not be switched
+switch-unpushed = { $count } unpushed commits were authored as { $email }:
+switch-unpushed-more = ... and { $count } more
+switch-unpushed-fix = Fix them before pushing: { $command }
+switch-gh-missing = gh not installed, skipping account switch to { $account }
+switch-gh-switched = gh account switched to { $account }
+switch-gh-failed = Failed to switch gh account: { $error }
+switch-post-command-status = post_switch_command exited with { $status }
+switch-post-command-failed = Failed to run post_switch_command: { $error }
+switch-ssh-agent-added = SSH key added to agent
+switch-ssh-agent-failed = Failed to add key to ssh-agent: { $error }
+switch-ssh-agent-missing = ssh-agent not running, skipping key addition
+switch-ssh-agent-tip = Tip: Run 'ssh-add { $path }' after starting ssh-agent
+switch-ssh-config-failed = Failed to configure SSH ({ $host }): { $error }
+
+## gid export
+
+export-nothing = Nothing to export
+export-done = Configuration exported to { $path }
+export-encrypted = Encrypted with age
+export-summary = { $identities } identities, { $rules } rules
+
+## Shared prompts
+
+operation-cancelled = Operation cancelled
+confirm-remove = Are you sure you want to remove?
+
+## gid undo
+
+undo-nothing = Nothing to undo
+undo-edited = edited
+undo-edited-since = Files changed after { $command }; use --force to revert anyway
+undo-restored = restored
+undo-removed = removed
+undo-done = Undid { $command } ({ $when })
+undo-empty = No operations recorded
+undo-list-header = Recent Operations (newest first):
+undo-list-files = ({ $count } files)
+
+## gid prune
+
+prune-kind-rule = rule
+prune-kind-default = default
+prune-kind-ssh-host = ssh host
+prune-kind-fragment = fragment
+prune-kind-usage = usage
+prune-kind-credential = credential
+prune-usage = switch statistics of { $id }
+prune-credential = HTTPS token of { $identity } for { $host }
+prune-subscribed-kept = { $count } subscribed rules reference identities not configured here (kept, managed by `gid ruleset`)
+prune-nothing = Nothing to prune
+prune-header = Orphaned Artifacts:
+prune-dry-run = Dry run: { $count } artifacts would be removed
+prune-confirm = Remove { $count } artifacts?
+prune-done = Removed { $count } artifacts
+
+## gid rule
+
+rule-type-path = Path
+rule-type-remote = Remote URL
+rule-type-branch = Branch
+rule-type-all-of = All-of
+rule-type-any-of = Any-of
+rule-added = Added { $kind } rule: { $pattern } -> { $identity }
+rule-identity-unknown = Identity '{ $id }' does not exist
+rule-parts-remote-only = --host, --org and --repo only apply to remote rules
+rule-when-composite-only = all-of and any-of rules take their conditions from --when (and only they do)
+rule-condition-invalid = Invalid condition '{ $spec }' (expected path=, remote=, branch=, host=, org= or repo=)
+rule-incomplete = A { $kind } rule needs { $needs }
+rule-needs-pattern = a pattern
+rule-needs-remote = a pattern or --host, --org or --repo
+rule-needs-conditions = conditions (--when)
+rule-none = No rules configured
+rule-add-hint = Use { $command } to add rules
+rule-examples = Example:
+rule-example-path = Add path rule
+rule-example-remote = Add remote rule
+rule-example-branch = Add branch rule
+rule-list-header = Configured Rules:
+rule-from-ruleset = (ruleset { $name })
+rule-priority = Priority: { $priority }
+rule-total = Total { $count } rules
+rule-updated = Updated rule { $id }: { $rule }
+rule-editing = Editing rule
+rule-prompt-type = Type
+rule-prompt-pattern = Pattern
+rule-prompt-identity = Identity
+rule-prompt-priority = Priority (lower number = higher priority)
+rule-prompt-description = Description (optional)
+rule-already-enabled = Rule { $id } is already enabled
+rule-already-disabled = Rule { $id } is already disabled
+rule-enabled = Rule { $id } enabled: { $rule }
+rule-disabled = Rule { $id } disabled: { $rule }
+rule-moved = Rule { $id } priority { $old } { $arrow } { $new } (now { $position } of { $total })
+rule-read-only = Rule { $id } belongs to subscribed ruleset '{ $source }' and is read-only (use `gid ruleset unsubscribe { $source }`)
+rule-remove-about = About to remove rule: { $pattern } -> { $identity }
+rule-removed = Rule removed
+rule-test-header = Test Rule Matching:
+rule-test-path = Path: { $path }
+rule-test-remote = Remote: { $remote }
+rule-test-branch = Branch: { $branch }
+rule-test-no-match = No matching rules
+rule-test-matched = Matched Rules:
+rule-test-matched-specific = Matched Rules (most specific first):
+rule-test-identity = Will use identity: { $identity } { $name } <{ $email }>
+
+## gid auto
+
+auto-using-project = Using project config (.gid): { $identity }
+auto-matched-rule = Matched rule: { $pattern } -> { $identity }
+auto-using-fallback = No matching rules, using fallback identity: { $identity }
+auto-source = Source:   { $source }
+auto-source-none = none
+auto-identity = Identity: { $identity }
+auto-nothing-would-change = Nothing would change
+auto-gid-file-hint = Or create a .gid file in the project root to specify identity
+auto-current-identity = Current identity: { $name } <{ $email }>
+auto-resolved = Resolved { $identity } from { $source }
+auto-global-already-set = Global identity already set
+auto-would-switch-global = Would switch the global identity to { $identity }
+auto-already-using = Already using { $name } <{ $email }>
+auto-not-set = not set
+auto-would-switch = Would switch: { $current } { $arrow } { $name } <{ $email }>
+auto-no-repos = No Git repositories found under { $path }
+auto-scan-drifted = { $current }, expected { $expected } ({ $source })
+auto-scan-summary = { $total } repositories: { $ok } ok, { $drifted } drifted, { $unmatched } unmatched
+auto-scan-fixed = Fixed { $count } repositories
+auto-scan-unfixed = { $count } repositories could not be fixed
+auto-scan-fix-hint = Run { $command } to fix
+auto-scan-mismatch = { $count } repositories use the wrong identity
+auto-status-expected = Expected: { $identity } ({ $source })
+auto-status-unexpected = Expected: { $reason }
+auto-status-no-match = no matching .gid or rule
+auto-status-current = Current:  { $current }
+auto-status-mismatch = Identity does not match, run { $command }
+auto-status-match = Identity matches
+auto-unmatched-none = No unmatched repositories recorded
+auto-unmatched-hint = Run { $command } in repositories to collect them
+auto-suggestions = { $count } unmatched repositories, suggested rules:
+auto-suggestions-placeholder = Replace { $placeholder } with the identity to use
+
+## gid add
+
+add-header = Add new Git identity
+add-prompt-id = Identity ID (e.g., work, personal)
+add-prompt-name = Name
+add-prompt-email = Email
+add-prompt-description = Description (optional, press Enter to skip)
+add-prompt-ssh = Configure SSH key?
+add-prompt-gpg = Configure GPG signing key?
+add-done = Identity added: { $identity } { $name } <{ $email }>
+add-gpg-signing = GPG signing configured
+add-prompt-switch = Switch to this identity now?
+add-prompt-switch-global = Switch to global configuration?
+add-ssh-header = SSH Key Configuration:
+add-ssh-existing = Use existing key
+add-ssh-generate = Generate new key
+add-skip = Skip
+add-prompt-choice = Select [1/2/3]
+add-prompt-ssh-path = SSH private key path
+add-ssh-generating = Generating new SSH key...
+add-ssh-generated = Key generated: { $path }
+add-ssh-public-key = Public key content (add to GitHub/GitLab):
+add-gpg-missing = GPG not installed, skipping configuration
+add-gpg-header = GPG Signing Configuration:
+add-gpg-found = Found matching GPG key: { $key }
+add-prompt-gpg-use = Use this key?
+add-gpg-enter = Enter existing key ID
+add-gpg-list = List all keys
+add-prompt-gpg-id = GPG Key ID
+add-gpg-invalid = Invalid GPG Key ID
+add-gpg-none = No GPG keys found
+add-prompt-gpg-index = Select key index (press Enter to skip)
+add-index-invalid = Invalid index
+add-index-out-of-range = Index out of range
+
+## gid fix-commit
+
+fix-commit-dirty = Uncommitted changes detected. Please commit or stash changes before fixing history.
+fix-commit-no-email = Could not get current email
+fix-commit-unknown-identity = Current identity not in configuration list
+fix-commit-resign-no-key = [{ $id }] has no gpg_key to re-sign with. Set one with gid edit
+fix-commit-non-head = Fixing non-HEAD commits requires --range argument
+fix-commit-example = Example: { $command }
+fix-commit-non-head-unsupported = Fixing non-HEAD commits is not supported
+fix-commit-single-header = Fixing commit identity...
+fix-commit-commit = Commit: { $id }
+fix-commit-message = Message: { $message }
+fix-commit-current-identity = Current Identity: { $name } <{ $email }>
+fix-commit-new-identity-aligned = New Identity:   { $name } <{ $email }>
+fix-commit-confirm = Confirm fix?
+fix-commit-fixed = Commit fixed
+fix-commit-new-commit = New commit: { $id }
+fix-commit-hash-changed = Commit hash changed, use { $command } to force push if already pushed
+fix-commit-range-header = Batch fixing commits...
+fix-commit-range = Range: { $range }
+fix-commit-new-identity = New Identity: { $name } <{ $email }>
+fix-commit-range-format = Please use range format, e.g., HEAD~3..HEAD
+fix-commit-range-empty = No commits in range
+fix-commit-will-fix = Will fix { $count } commits
+fix-commit-warning = WARNING:
+fix-commit-range-warning = This will modify commit history, all subsequent commit hashes will change
+fix-commit-range-force-push = If pushed, you will need to use git push --force
+fix-commit-range-backup = A backup ref of the rewritten branch is created first
+fix-commit-confirm-continue = Confirm continue?
+fix-commit-email-header = Fixing commits by email...
+fix-commit-author = Author: { $email }
+fix-commit-email-none = No commits authored by { $email } on this branch
+fix-commit-email-will-fix = Will fix { $count } commits in the history of the current branch
+fix-commit-email-warning = This rewrites history from the first matching commit onwards
+fix-commit-email-new-hashes = Every later commit gets a new hash; other branches keep the old commits
+fix-commit-email-force-push = If pushed, you will need to force push and collaborators must re-clone or rebase
+fix-commit-email-backup = A backup ref of the current branch is created first
+fix-commit-done = Fixed { $count } commits
+fix-commit-new-head = New HEAD: { $id }
+fix-commit-backup = Backup:   { $backup }
+fix-commit-restore = Restore with: { $command }
+fix-commit-hashes-changed = Commit hashes changed, use { $command } to force push if already pushed
+fix-commit-no-gpg-key = [{ $id }] has no gpg_key
+fix-commit-sign-failed = Could not sign with GPG key { $key }
+fix-commit-signatures-dropped = { $count } rewritten commits were signed; their signatures were dropped
+fix-commit-signatures-dropped-resign = { $count } rewritten commits were signed; their signatures were dropped (use --resign)
+fix-commit-range-end = The range must end at HEAD ({ $head }) or a branch name
+fix-commit-branch-no-commit = { $branch } does not point at a commit
+fix-commit-backup-failed = Could not create backup ref { $backup }
+
+## gid import
+
+import-file-not-found = File not found: { $path }
+import-age-missing = File is encrypted but age is not installed (https://age-encryption.org)
+import-not-utf8 = Configuration file is not valid UTF-8
+import-invalid = Configuration file format error
+import-script = Script exports cannot be imported
+import-empty = No valid configuration found in file
+import-found = Found { $identities } identities, { $rules } rules
+import-dry-run = Dry run: no changes written
+import-options = Import Options:
+import-option-merge = Merge (keep existing, add new)
+import-option-replace = Replace (delete existing configuration)
+import-option-cancel = Cancel
+import-replace-confirm = Are you sure you want to replace existing configuration? This cannot be undone
+import-backed-up = Backed up to: { $path }
+import-replaced = Configuration replaced: { $identities } identities, { $rules } rules
+import-imported = Configuration imported: { $identities } identities, { $rules } rules
+import-conflict-overwritten = [{ $id }] overwritten ({ $fields })
+import-conflict-renamed = [{ $id }] imported as [{ $new_id }] ({ $fields })
+import-conflict-skipped = [{ $id }] skipped, differs in { $fields }
+import-rule-overwritten = { $rule } overwritten (was [{ $local }])
+import-rule-skipped = { $rule } skipped, pattern maps to [{ $local }] locally
+import-complete = Import complete:
+import-identities = Identities: Added { $added }, Skipped { $skipped } (already exists)
+import-rules = Rules: Added { $added }, Skipped { $skipped } (duplicate or conflicting)
+import-conflicts = Conflicts:
+import-conflicts-hint = Use { $overwrite } or { $rename } to apply them
+import-plan-identities = Identities:
+import-plan-unchanged = (unchanged, skipped)
+import-plan-overwritten = overwritten
+import-plan-renamed = imported as [{ $new_id }]
+import-plan-skipped-on-merge = skipped on merge, overwritten on replace
+import-plan-differs = (differs in { $fields }: { $resolution })
+import-plan-removed = (removed on replace)
+import-plan-rules = Rules:
+import-plan-duplicate = (already exists, skipped)
+import-plan-skipped = skipped
+import-plan-rule-conflict = (pattern maps to [{ $local }] locally: { $resolution })
+import-commands = Commands run after switching to these identities:
+import-commands-confirm = Store these commands? They run on every switch
+import-commands-need-confirmation = The imported identities run commands after switching. Review them and pass --yes to import
+import-gitconfig-none = No new identities found in Git config
+import-gitconfig-found = Found { $count } identities in Git config
+import-gitconfig-from = from { $path }
+import-gitconfig-signing-key = signing key { $key }
+import-gitconfig-create = Create identity?
+import-gitconfig-id = Identity ID
+import-gitconfig-added = Added identity [{ $id }]
+import-gitconfig-rule-confirm = Add path rule { $pattern } { $arrow } { $id }?
+import-gitconfig-rule-added = Added rule: path { $pattern } { $arrow } { $id }
+import-gitconfig-done = Import complete: { $identities } identities, { $rules } rules
+
+## gid hook
+
+hook-mismatch = Committing as { $name } <{ $email }>, but { $source } expects { $identity } { $expected_name } <{ $expected_email }>
+hook-no-name = (no name)
+hook-no-email = (no email)
+hook-fix = To fix:  { $command }
+hook-skip = To skip: { $command }
+hook-or = Or:      { $command }
+hook-mismatch-error = Git identity does not match [{ $id }]
+hook-no-repo-path = Could not get repository path
+hook-exists = pre-commit hook already exists
+hook-overwrite = Overwrite?
+hook-write-failed = Could not write hook file
+hook-installed = pre-commit hook installed
+hook-hooks-path-failed = Failed to set core.hooksPath
+hook-global-installed = Global pre-commit hook installed
+hook-hooks-path-set = Set { $key } = { $path }
+hook-missing = hook does not exist
+hook-not-gid = This is not a gid hook, skipping removal
+hook-uninstalled = pre-commit hook uninstalled
+hook-global-removed = Global hook removed
+hook-hooks-path-removed = core.hooksPath configuration removed
+hook-status = Git Hook Status:
+hook-local = Local hook
+hook-global = Global hook
+hook-framework = pre-commit framework
+hook-state-installed = Installed (gid)
+hook-state-foreign = Exists (non-gid)
+hook-state-not-installed = Not installed
+hook-state-framework = gid-check configured
+hook-state-no-framework = gid-check not in .pre-commit-config.yaml
+hook-state-no-repo = Not in a Git repository
+hook-state-not-configured = Not configured
+
+## gid doctor
+
+doctor-header = Checking Git identity configuration...
+doctor-not-a-repo = Current directory is not a Git repository
+doctor-current-identity = Current Identity:
+doctor-identity = Identity:
+doctor-unknown-identity = Current identity is not in the configuration list
+doctor-no-user = Git user information not configured
+doctor-project-config = Project Config (.gid):
+doctor-expected-identity = Expected Identity: { $identity }
+doctor-project-mismatch = Current identity does not match project config (expected: [{ $id }])
+doctor-project-unknown = Project configured identity '{ $id }' does not exist
+doctor-identity-matches = Identity matches
+doctor-remote = Remote URL:
+doctor-matched-rules = Matched Rules:
+doctor-rule-mismatch = Current identity does not match rule (expected: [{ $id }])
+doctor-signature = Last Commit Signature:
+doctor-signature-good = Good signature by { $signer }
+doctor-signature-bad = The last commit has a bad signature
+doctor-no-issues = No issues found
+doctor-issues = Found { $count } issues:
+doctor-fixing = Fixing...
+doctor-suggestions = Suggested actions:
+doctor-fix-hint = Use { $command } to fix automatically
+doctor-restricted = Restricted { $path } to its owner
+doctor-not-owner-only = { $path } is not owner-only ({ $access }), ssh refuses it (gid doctor --fix)
+doctor-submodules = Submodules:
+doctor-submodule-unresolved = no .gid or rule
+doctor-submodule-unknown = Submodule { $label } resolves to unknown identity '{ $id }'
+doctor-submodule-switched = { $label } switched to { $identity }
+doctor-submodule-expected = { $label } expected { $identity } ({ $source })
+doctor-submodule-mismatch = Submodule { $label } does not use its expected identity [{ $id }]
+
+## gid ruleset
+
+ruleset-name-invalid = Ruleset name can only contain letters, numbers, underscores, and hyphens
+ruleset-exists = Ruleset '{ $name }' is already subscribed
+ruleset-subscribed = Subscribed to { $name }: { $count } rules
+ruleset-update-hint = Run { $command } to fetch changes
+ruleset-not-subscribed = Ruleset '{ $name }' is not subscribed
+ruleset-none = No rulesets subscribed
+ruleset-updated = { $name }: { $count } rules ({ $added }, { $removed })
+ruleset-update-failed = { $count } rulesets could not be updated
+ruleset-unsubscribed = Unsubscribed from { $name } ({ $count } rules removed)
+ruleset-subscribe-hint = Use { $command } to subscribe
+ruleset-list-header = Subscribed Rulesets:
+ruleset-rule-count = { $count } rules
+ruleset-updated-at = Updated: { $time }
+ruleset-downloading = Downloading { $url }
+ruleset-download-failed = Failed to download { $url }: { $error }
+ruleset-not-utf8 = Ruleset is not valid UTF-8
+ruleset-read-failed = Could not read ruleset: { $path }
+ruleset-empty = No rules found in { $url }
+ruleset-unknown-identities = Rules reference identities not configured here: { $ids }
+
+## gid onboard
+
+onboard-not-interactive = gid onboard is interactive. Pass --yes to accept the defaults
+onboard-welcome = Welcome to gid
+onboard-intro = This sets up identities, rules and automatic switching in four steps.
+onboard-step-identities = Identities
+onboard-no-identity = No Git identity found. Run `gid add` to create one
+onboard-step-ssh = SSH keys
+onboard-step-rules = Rules
+onboard-step-integration = Integration
+onboard-hook-confirm = Install the global pre-commit identity check?
+onboard-ready = gid is ready
+onboard-hint-list = { $command } to see your identities
+onboard-hint-doctor = { $command } to check the current repository
+onboard-hint-apply = { $command } to fix every repository at once
+onboard-all-keys = Every identity has an SSH key
+onboard-missing-keys = { $count } identities have no SSH key (add one with `gid edit`)
+onboard-generate-key = Generate an SSH key for [{ $id }]?
+onboard-public-key = Add this public key to your Git hosting account:
+onboard-all-rules = Every identity has a path rule
+onboard-missing-rules = No path rules for: { $ids } (add them with `gid rule add`)
+onboard-workspace = Workspace directory for [{ $id }] (e.g. ~/work, Enter to skip)
+onboard-workspace-missing = { $path } does not exist, skipped
+onboard-unknown-shell = Unknown shell, see { $command } to switch identities on cd
+onboard-shell-present = Shell integration already in { $path }
+onboard-shell-confirm = Switch identities automatically on cd (adds a line to { $path })?
+onboard-shell-added = Added shell integration to { $path }
+onboard-shell-activate = Open a new shell to activate it
+
+## gid current
+
+current-header = Current Git Identity:
+current-project = Project:
+current-global = Global:
+current-not-set = Not set
+current-effective = Effective:
+current-identity-id = Identity ID:
+current-possibly = Possibly:
+current-name-mismatch = (Name mismatch)
+current-no-match = No configured identity matched
+current-no-user = No valid Git user configuration found
+current-add-hint = Run { $command } to add identity
+current-switch-hint = Run { $command } to switch identity
+current-remote = Remote:
+current-expects-unknown = { $source } expects unknown identity { $identity }
+current-should-use = This repository should use [{ $id }] ({ $source })
+current-run = Run: { $command }
+current-sources = Sources:
+current-overrides = overrides { $value } from { $origin }
+current-unknown-file = unknown file
+current-origin-include = { $level } include: { $file }
+current-host-managed = Host { $patterns } (gid managed)
+current-no-host-block = no Host block
+current-ssh-key = SSH key:
+current-default-keys = ssh-agent / default keys
+current-key-mismatch = Pushes authenticate with the SSH key of { $identity }, but commits are authored as { $email }
+
+## gid list
+
+list-empty = No identities configured
+list-add-hint = Run { $command } to add a new identity
+list-no-match = No identities match the given filter
+list-header = Configured Identities:
+list-verified = Verified
+list-total = Total { $count } identities
+list-showing = Showing { $count } of { $total } identities
+list-ssh-key-missing = SSH key { $path } not found
+list-ssh-key-unreadable = SSH key { $path } is not readable
+list-gpg-unavailable = GPG key { $key } cannot be checked: gpg not found
+list-gpg-expired = GPG key { $key } has expired
+list-gpg-revoked = GPG key { $key } has been revoked
+list-gpg-missing = GPG key { $key } not found in keyring
+list-host-unresolved = SSH host { $host } does not resolve
+list-hosts-skipped-offline = SSH host resolution skipped (offline)
+list-verify-failed = { $failed } of { $total } identities failed verification
+list-active = active
+list-expected-by = expected by { $source }
+list-legend-active = active in this repository
+list-column-id = ID
+list-column-name = NAME
+list-column-email = EMAIL
+list-column-tags = TAGS
+list-column-last-used = LAST USED
+list-column-uses = USES
+list-column-status = STATUS
+list-never = never
+
+## Relative times
+
+time-just-now = just now
+time-minutes-ago = { $count }m ago
+time-hours-ago = { $count }h ago
+time-days-ago = { $count }d ago
+
+## gid audit
+
+audit-header = Auditing Git commit history...
+audit-target = Target: { $path }
+audit-no-repo = No Git repository found
+audit-complete = Audit complete: { $repos } repositories, { $issues } issues
+audit-shallow-summary = { $count } shallow clones audited on partial history (see { $command })
+audit-fix-unsupported = Automatic fix does not support commit history modification yet
+audit-fix-rewrite = Modifying commit history requires git rebase or git filter-branch
+audit-fix-tools = Manual handling or specialized tools like git-filter-repo are recommended
+audit-total-commits = Total Commits: { $count }
+audit-shallow = Shallow clone: statistics only cover the fetched commits, mixed identity check skipped
+audit-unshallow = Fetch the full history: { $command }
+audit-usage = Identity Usage Statistics
+audit-unknown = Unknown
+audit-commits = { $count } commits
+audit-more = ... and { $count } more
+audit-issue-unknown-identity = Unknown Identity
+audit-issue-identity-mismatch = Identity Mismatch
+audit-issue-mixed-identities = Mixed Identities
+audit-issue-bad-signature = Bad Signature
+
+## gid show
+
+show-private = (private)
+show-ssh-key = SSH key
+show-fingerprint = Fingerprint
+show-unavailable = unavailable
+show-ssh-agent = SSH agent
+show-ssh-hosts = SSH hosts
+show-signing = Signing
+show-gh-account = gh account
+show-tags = Tags
+show-post-switch = Post-switch
+show-commit-template = Commit template
+show-last-used = Last used
+show-last-used-value = { $time } ({ $count } switches)
+show-git-config = Git config:
+show-rules = Rules:
+show-no-rules = No rules reference this identity
+show-repos = Repositories:
+show-no-repos = No repositories use this identity
+show-signing-on = gpg { $key } (commits signed)
+show-signing-off = gpg { $key } (signing off)
+show-signing-none = none
+
+## gid credential
+
+credential-prompt = Token for { $username }@{ $host }
+credential-stdin-failed = Could not read token from stdin
+credential-empty = Token is empty
+credential-stored = Stored HTTPS credential for { $identity } on { $host }
+credential-next-switch = Applied on the next switch: gid switch { $id }
+credential-none = No HTTPS credentials stored
+credential-add-hint = Add one with: { $command }
+credential-header = HTTPS Credentials:
+credential-missing = No credential stored for [{ $id }] on { $host }
+credential-removed = Removed HTTPS credential for [{ $id }] on { $host }
+credential-request-failed = Could not read credential request
+
+## gid stats
+
+stats-identities = Identities:
+stats-column-repos = REPOS
+stats-column-switches = SWITCHES
+stats-scanned = { $scanned } repositories scanned, { $configured } use a configured identity
+stats-rule-hits = Rule Hits:
+stats-unmatched = Unmatched Repositories ({ $count }):
+stats-suggest-hint = Run { $command } to propose rules
+
+## gid whoami
+
+whoami-no-email = No Git identity configured here (user.email is unset)
+whoami-not-gid = (not a gid identity)
+whoami-unknown-email = { $email } does not belong to a gid identity, nothing to compare against
+whoami-checking = Checking remote accounts
+whoami-gh-missing = gh is not installed
+whoami-token = Token ({ $host })
+whoami-emails = Account emails
+whoami-match = Remote accounts match [{ $id }]
+whoami-mismatch = Remote accounts do not match [{ $id }]
+whoami-account-unknown = authenticated, account unknown
+whoami-via-ssh = SSH key
+whoami-via-token = token
+whoami-login-mismatch = The { $how } authenticates as { $login }, [{ $id }] expects { $expected }
+whoami-logins-differ = The SSH key authenticates as { $ssh_login } but the token as { $api_login }
+whoami-email-not-on-account = { $email } is not an email of { $account }, commits will not be attributed to it
+whoami-the-account = the account
+
+## gid sync-gitconfig
+
+sync-no-rules = No path rules to sync
+sync-header = includeIf blocks in { $path } (later blocks win):
+sync-rule-removed = (rule removed)
+sync-dry-run = Dry run: { $path } not changed
+sync-done = Synced { $count } includeIf blocks from path rules
+sync-automatic = Rule changes are synced automatically from now on (undo with --remove)
+sync-in-sync = Already in sync
+sync-resynced = Synced includeIf blocks in the global config
+sync-resync-failed = Could not sync includeIf blocks: { $error }
+sync-none = No synced includeIf blocks
+sync-remove-dry-run = Dry run: global config not changed
+sync-removed = Removed { $count } includeIf blocks and { $fragments } unused fragments
+
+## gid serve
+
+serve-already-listening = gid serve is already listening on { $path }
+serve-stale-socket = Could not remove stale socket { $path }
+serve-listen-failed = Could not listen on { $path }
+serve-listening = Listening on { $path }
+serve-connection-failed = Connection failed: { $error }
+serve-connection-closed = Connection closed: { $error }
+serve-no-unix-sockets = Unix sockets are not available on this platform, use --stdio
+serve-read-failed = Could not read request
+serve-missing-method = Missing method
+serve-unknown-method = Unknown method: { $method }
+serve-not-a-repo = { $path } is not in a Git repository
+serve-no-resolution = No .gid file or rule matches, pass an identity
+serve-missing-path = Missing path parameter
+serve-path-relative = Path must be absolute: { $path }
+
+## gid apply
+
+apply-no-repos = No Git repositories found
+apply-no-rule = no rule
+apply-would-set = would set { $keys }
+apply-set = set { $keys }
+apply-would-configure-ssh = would configure SSH for { $identity } ({ $hosts })
+apply-default-hosts = default hosts
+apply-wrote-host = wrote Host { $alias }
+apply-summary = { $total } repositories: { $updated } updated, { $unchanged } unchanged, { $unmatched } unmatched, { $failed } failed
+apply-summary-dry-run = { $total } repositories: { $updated } to update, { $unchanged } unchanged, { $unmatched } unmatched, { $failed } failed
+apply-failed = { $count } repositories could not be applied
+
+## gid repos
+
+repos-no-roots = No directories to scan. Pass --scan <DIR> or set settings.workspace_roots
+repos-column-path = PATH
+repos-column-current = CURRENT
+repos-column-expected = EXPECTED
+repos-column-remote = REMOTE
+repos-not-set = not set
+repos-summary = { $total } repositories: { $ok } ok, { $drifted } drifted, { $unmatched } unmatched
+repos-fix-hint = Run { $command } to fix
+
+## gid init
+
+init-already-pinned = { $path } already pins [{ $id }]. Use --force to overwrite
+init-nothing-to-pin = No rule or current identity to pin. Pass one: gid init <identity>
+init-wrote = Wrote { $path } pinning { $id }
+init-gitignore-added = Added .gid to .gitignore
+init-committed = Committed .gid
+
+## gid edit
+
+edit-created = Configuration file created: { $path }
+edit-editing = Editing configuration file using { $editor }...
+edit-valid = Configuration valid, contains { $identities } identities, { $rules } rules
+edit-invalid = Configuration format error: { $error }
+edit-fix-hint = Please fix the configuration file and try again
+edit-editor-failed = Failed to start editor: { $editor }
+edit-editor-exited = Editor exited abnormally
+
+## gid template
+
+template-editing = Editing commit template of [{ $id }] using { $editor }...
+template-saved = Commit template saved, applied on the next switch: { $command }
+template-none = [{ $id }] has no commit template
+template-none-hint = [{ $id }] has no commit template. Create one: gid template edit { $id }
+template-removed = Removed commit template of [{ $id }] (repositories drop it on the next switch)
+
+## gid remove
+
+remove-about-to = About to remove identity: { $id } { $name } <{ $email }>
+remove-done = Identity '{ $id }' removed
+remove-prune-hint = Run { $command } to clean up its rules, SSH aliases and includeIf files
+
+## gid verify
+
+verify-header = Verifying { $id } { $name } <{ $email }>
+verify-summary = { $passed } passed, { $warnings } warnings, { $failed } failed
+verify-failed = [{ $id }] failed verification
+verify-name = Name
+verify-name-empty = empty
+verify-email = Email
+verify-email-invalid = '{ $email }' is not a valid address
+verify-ssh-key = SSH key
+verify-ssh-key-none = no SSH key configured
+verify-not-found = { $path } not found
+verify-permissions = Permissions
+verify-owner-only = owner only
+verify-too-open = { $access } is too open, ssh refuses the key (gid doctor --fix)
+verify-fingerprint = Fingerprint
+verify-agent-loaded = key loaded
+verify-agent-none = no agent running
+verify-agent-not-loaded = key not loaded (ssh-add { $path })
+verify-forge = Forge { $host }
+verify-forge-skipped = skipped (pass --forge)
+verify-gpg-key = GPG key
+verify-gpg-key-unset = gpg_sign is enabled but no gpg_key is set
+verify-gpg-key-none = no signing key configured
+verify-gpg-missing = gpg not found
+verify-gpg-expired = { $key } has expired
+verify-gpg-revoked = { $key } has been revoked
+verify-gpg-not-in-keyring = { $key } not found in keyring
+verify-gpg-uid-mismatch = key is for { $key_email }, commits use { $email }
+verify-gpg-signing = GPG signing
+verify-gpg-signing-ok = test signature created and verified
+
+## gid history
+
+history-empty = No switches recorded
+history-empty-repo = No switches recorded for { $path }
+history-title = Switch History (newest first):
+history-global = (global)
+history-none = (none)
+
+## gid watch
+
+watch-no-paths = No paths to watch. Pass paths or set settings.workspace_roots
+watch-mode-check = check only
+watch-mode-enforce = enforce
+watch-started = Watching { $paths } (every { $interval }s, { $mode })
+watch-config-invalid = { $error } (keeping the last valid config)
+watch-unknown-identity = Rule references unknown identity '{ $id }'
+watch-drifted = uses { $current }, expected { $expected } ({ $source })
+
+## gid env and gid direnv
+
+env-no-identity = No identity resolved for this directory, name one explicitly
+direnv-wrote = Wrote { $id } stanza to { $path }
+direnv-allow-hint = Run { $command } to allow it
+
+## gid check
+
+check-ok-unexpected = ok: no identity expected here
+check-ok = ok: { $id }
+check-mismatch = mismatch: { $email } is in use, { $source } expects { $id }
+check-no-email = (no email)
+
+## gid mailmap
+
+mailmap-wrote = Wrote { $path } with { $count } mappings
+mailmap-unknown = { $count } authors do not match any identity and were left out:
+mailmap-commits = { $count } commits
+
+## gid clone
+
+clone-cloning-url = Cloning { $url }
+clone-no-rule = No rule matches { $url }. Pass --identity <id> or add a rule with `gid rule add`
+clone-cloning-as = Cloning as { $id } ({ $source })
+clone-failed = git clone failed
+clone-wrote = Wrote { $path }
+clone-enter-failed = Could not enter { $path }
+
+## gid tui
+
+tui-needs-terminal = gid tui requires an interactive terminal
+tui-not-a-repo = Not started inside a Git repository
+tui-no-identity = No identity selected
+tui-switched = Switched to [{ $id }]
+tui-no-expected = No .gid or rule matches this repository
+tui-rule-at-edge = Rule is already at the edge
+tui-rule-moved-up = Moved rule { $id } above { $other }
+tui-rule-moved-down = Moved rule { $id } below { $other }
+tui-reloaded = Reloaded configuration
+tui-help = Tab pane  ↑↓ select  Enter switch  Shift+↑↓ move rule  f fix repo  r reload  q quit
+tui-expected-unknown = Expected identity '{ $id }' does not exist
+tui-drifted = Current identity does not match { $source } (expected: [{ $id }]), press f to fix
+tui-identities = Identities
+tui-rules = Rules
+tui-repository = Repository
+tui-outside-repo = Not inside a Git repository
+tui-path = Path:
+tui-remote = Remote:
+tui-current = Current:
+tui-expected = Expected:
+tui-no-match = no .gid or rule matches
+
+## --format templates
+
+format-unclosed = Unclosed '{"{"}' in --format: { $template }
+format-invalid-field = Invalid field '{"{"}{ $name }{"}"}' in --format
+format-unmatched = Unmatched '{"}"}' in --format (write {"}}"} for a literal brace)
+format-unknown-field = Unknown field '{ $field }' in --format (available: { $available })
+format-not-an-object = Field '{ $field }' in --format has no '{ $key }'
+
+## Plugins (gid-<name> on PATH)
+
+plugin-missing = Missing subcommand
+plugin-unknown = Unrecognized subcommand '{ $name }' (no gid-{ $name } on PATH). Run gid --help
+plugin-failed = Could not execute { $program }
@@ -0,0 +1,919 @@
+# 中文消息（缺失的消息使用 en.ftl）
+#
+# 与 en.ftl 保持相同的顺序
+
+## 通用文件和目录错误
+
+file-read-failed = 无法读取文件: { $path }
+file-write-failed = 无法写入文件: { $path }
+file-delete-failed = 无法删除文件: { $path }
+dir-create-failed = 无法创建目录: { $path }
+data-dir-create-failed = 无法创建数据目录: { $path }
+home-dir-unknown = 无法获取用户主目录
+file-create-failed = 无法创建 { $path }
+file-replace-failed = 无法替换 { $path }
+file-not-utf8 = { $path } 不是有效的 UTF-8
+file-readback-mismatch = { $path } 读回的内容与写入的不一致
+file-locked = { $path } 已被另一个 gid 进程锁定 (如果没有 gid 在运行, 请删除它)
+permissions-restrict-failed = 无法限制 { $path } 的权限
+permissions-mode = 权限 { $mode }
+permissions-accessible-to = { $principals } 可访问
+permissions-icacls-failed = icacls { $path } 失败: { $error }
+permissions-no-username = 未设置 USERNAME
+error-label = 错误:
+chdir-failed = 无法切换到目录 { $path }
+
+## 配置文件 (config.toml)
+
+config-path-invalid = 无法解析配置文件路径: { $path }
+config-dir-unknown = 无法获取配置目录
+config-dir-create-failed = 无法创建配置目录: { $path }
+config-read-failed = 无法读取配置文件: { $path }
+config-invalid = 配置文件格式错误
+config-serialize-failed = 无法序列化配置
+config-write-failed = 无法写入配置文件: { $path }
+config-roundtrip-failed = 新配置无法重新解析，未修改 { $path }
+project-read-failed = 无法读取 .gid 文件: { $path }
+project-write-failed = 无法写入 .gid 文件: { $path }
+project-invalid = .gid 文件格式错误: { $error }
+project-identity-invalid = .gid 文件中的身份 ID 格式无效: { $id }
+identity-exists = 身份 '{ $id }' 已存在
+identity-not-found = 找不到身份 '{ $id }'
+rule-index-out-of-range = 规则索引 { $index } 超出范围
+rule-not-found = 规则 { $id } 不存在（见 `gid rule list`）
+source-project = 项目配置 ({ $path })
+source-rule = 规则 { $rule }
+source-fallback = 后备身份
+
+## 身份验证
+
+identity-id-empty = 身份 ID 不能为空
+identity-id-invalid = 身份 ID 只能包含字母、数字、下划线和连字符
+identity-name-empty = 姓名不能为空
+identity-email-empty = 邮箱不能为空
+identity-email-invalid = 邮箱格式不正确
+identity-ssh-key-missing = SSH 密钥文件不存在: { $path }
+
+## 操作日志、状态和凭据
+
+journal-read-failed = 无法读取操作日志: { $path }
+journal-invalid = 操作日志格式错误
+journal-serialize-failed = 无法序列化操作日志
+journal-write-failed = 无法写入操作日志: { $path }
+state-read-failed = 无法读取状态文件: { $path }
+state-invalid = 状态文件格式错误
+state-serialize-failed = 无法序列化状态
+state-write-failed = 无法写入状态文件: { $path }
+credentials-read-failed = 无法读取凭据文件: { $path }
+credentials-invalid = 凭据文件格式错误
+credentials-serialize-failed = 无法序列化凭据
+credentials-write-failed = 无法写入凭据文件: { $path }
+
+## 外部程序
+
+ssh-add-failed = 无法执行 ssh-add
+ssh-public-key-read-failed = 无法读取公钥文件: { $path }
+ssh-keygen-unexpected = ssh-keygen 输出异常: { $output }
+ssh-keygen-failed = ssh-keygen 失败: { $error }
+ssh-key-exists = 密钥文件已存在: { $path }
+ssh-connecting = 连接 { $host }
+ssh-no-response = 没有响应
+ssh-config-read-failed = 无法读取 SSH 配置文件
+ssh-config-write-failed = 无法写入 SSH 配置文件
+ssh-config-roundtrip-failed = 新的 SSH 配置无法按预期重新解析
+ssh-alias-is-hostname = 别名模板不能生成原始主机名
+ssh-alias-template-no-identity = settings.ssh_alias_template '{ $template }' 必须包含 {"{"}identity{"}"}
+ssh-alias-template-no-host = settings.ssh_alias_template '{ $template }' 必须包含 {"{"}host{"}"} 或 {"{"}host_slug{"}"}
+ssh-alias-template-invalid-char = settings.ssh_alias_template '{ $template }' 不能包含空白字符、'*' 或 '?'
+ssh-agent-add-failed = 无法将密钥添加到 ssh-agent: { $error }
+ssh-agent-remove-failed = 无法从 ssh-agent 移除密钥: { $error }
+ssh-agent-clear-failed = 无法清空 ssh-agent: { $error }
+gpg-failed = 无法执行 gpg 命令
+gpg-write-failed = 无法写入 gpg 输入
+gpg-signature-not-text = gpg 输出的签名不是有效文本
+gpg-sign-failed = 签名失败
+curl-failed = 无法执行 curl
+program-failed = 无法执行 { $program }
+program-write-failed = 无法写入 { $program } 的输入
+git-failed = 无法执行 git
+git-command-failed = git { $command } 失败: { $error }
+gh-failed = 无法执行 gh
+gh-command-failed = gh { $command } 失败: { $error }
+gh-unexpected-response = API 响应格式异常
+age-missing = 未安装 age (https://age-encryption.org)
+age-failed = 无法执行 age
+age-stdin-failed = 无法打开 age 的标准输入
+age-encrypt-failed = age 加密失败
+age-decrypt-failed = age 解密失败
+
+## Git 仓库和配置文件
+
+git-not-a-repo = 当前目录不是 Git 仓库
+git-path-not-a-repo = { $path } 不是 Git 仓库
+git-operation-in-progress = 正在进行 { $operation }, 请先完成 (git { $operation } --continue) 或中止 (git { $operation } --abort)
+git-set-failed = 无法设置 { $key }
+git-unset-failed = 无法取消设置 { $key }
+git-credential-unset-failed = 无法移除凭据助手
+git-include-add-failed = 无法添加 includeIf gitdir:{ $gitdir }
+git-include-remove-failed = 无法移除 includeIf gitdir:{ $gitdir }
+git-ref-update-failed = 无法更新 { $name }
+git-key-invalid = 无效的配置键: { $key }
+git-range-invalid = 无效的范围
+git-commit-not-utf8 = 提交不是有效的 UTF-8
+signature-good = { $signer } 的有效签名
+signature-expired = 由 { $signer } 使用已过期的密钥签名
+signature-bad = 签名无效
+signature-unknown-key = 未知密钥 ({ $key })
+signature-error = 无法验证: { $error }
+signature-x509-unsupported = 不支持 X.509 签名
+signature-no-allowed-signers = 未设置 gpg.ssh.allowedSignersFile
+signature-not-allowed = 不在允许的签名者文件中
+signature-gpg-none = gpg 没有报告签名
+
+## 离线模式
+
+offline-needs-network = { $action } 需要网络, 但 gid 处于离线模式 (--offline 或 settings.offline)
+skipped-offline = 已跳过 (离线)
+
+## gid switch
+
+switch-done = 已切换到{ $scope }身份: { $identity } { $name } <{ $email }>
+switch-scope-global = 全局
+switch-scope-project = 项目
+switch-ssh-key = 已配置 SSH 密钥
+switch-gpg-signing = 已启用 GPG 签名
+switch-no-changes = 配置无变化
+switch-changes = 变更:
+switch-not-a-repo = 当前目录不是 Git 仓库, 全局切换请使用 --global
+switch-no-worktree = 当前目录不是带工作区的 Git 仓库
+switch-global-needs-confirmation = 全局切换需要确认, 使用 --yes 跳过提示
+switch-global-current = 当前全局身份: { $name } <{ $email }>
+switch-global-warning = 修改全局身份会影响所有没有本地配置的仓库
+switch-global-confirm = 将全局身份切换为 [{ $id }]?
+switch-dir-not-found = 目录不存在: { $path }
+switch-recursive-start = 正在将身份 { $identity } 应用到 { $path } 下的 { $count } 个仓库
+switch-recursive-done = 已将 { $count } 个仓库切换为 { $identity } { $name } <{ $email }>
+switch-recursive-failed = { $count } 个仓库切换失败
+switch-unchanged = 无变化
+switch-would-change = 将修改 { $keys }
+switch-wrote-host = 已写入 Host { $alias }
+switch-include-if-done = 已切换到 includeIf 身份: { $identity } { $name } <{ $email }>
+switch-fragment = 配置片段:
+switch-no-submodules = 没有已初始化的子模块
+switch-submodules = 子模块:
+switch-superproject = 父仓库
+switch-submodule-unmatched = 没有 .gid 或匹配的规则
+switch-submodules-failed = { $count } 个子模块切换失败
+switch-unpushed = { $count } 个未推送的提交使用了 { $email }:
+switch-unpushed-more = ... 另有 { $count } 个
+switch-unpushed-fix = 推送前请先修正: { $command }
+switch-gh-missing = 未安装 gh, 跳过切换账号 { $account }
+switch-gh-switched = 已切换 gh 账号: { $account }
+switch-gh-failed = 切换 gh 账号失败: { $error }
+switch-post-command-status = post_switch_command 退出状态: { $status }
+switch-post-command-failed = 无法运行 post_switch_command: { $error }
+switch-ssh-agent-added = SSH 密钥已添加到 agent
+switch-ssh-agent-failed = 无法将密钥添加到 ssh-agent: { $error }
+switch-ssh-agent-missing = ssh-agent 未运行, 跳过添加密钥
+switch-ssh-agent-tip = 提示: 启动 ssh-agent 后运行 'ssh-add { $path }'
+switch-ssh-config-failed = 配置 SSH 失败 ({ $host }): { $error }
+
+## gid export
+
+export-nothing = 没有配置可导出
+export-done = 配置已导出到: { $path }
+export-encrypted = 已使用 age 加密
+export-summary = { $identities } 个身份, { $rules } 条规则
+
+## Shared prompts
+
+operation-cancelled = 操作已取消
+confirm-remove = 确定要删除吗?
+
+## gid undo
+
+undo-nothing = 没有可撤销的操作
+undo-edited = 已修改
+undo-edited-since = 这些文件在 { $command } 之后被修改过, 使用 --force 仍然撤销
+undo-restored = 已还原
+undo-removed = 已删除
+undo-done = 已撤销 { $command } ({ $when })
+undo-empty = 没有记录的操作
+undo-list-header = 最近的操作 (最新的在前):
+undo-list-files = ({ $count } 个文件)
+
+## gid prune
+
+prune-kind-rule = 规则
+prune-kind-default = 默认身份
+prune-kind-ssh-host = SSH 主机
+prune-kind-fragment = 配置片段
+prune-kind-usage = 使用统计
+prune-kind-credential = 凭据
+prune-usage = { $id } 的切换统计
+prune-credential = { $identity } 在 { $host } 上的 HTTPS 令牌
+prune-subscribed-kept = { $count } 条订阅规则引用了本机未配置的身份 (已保留, 由 `gid ruleset` 管理)
+prune-nothing = 没有需要清理的内容
+prune-header = 遗留内容:
+prune-dry-run = 试运行: 将删除 { $count } 项
+prune-confirm = 删除这 { $count } 项?
+prune-done = 已删除 { $count } 项
+
+## gid rule
+
+rule-type-path = 路径
+rule-type-remote = 远程 URL
+rule-type-branch = 分支
+rule-type-all-of = 全部满足
+rule-type-any-of = 任一满足
+rule-added = 已添加{ $kind }规则: { $pattern } -> { $identity }
+rule-identity-unknown = 身份 '{ $id }' 不存在
+rule-parts-remote-only = --host、--org 和 --repo 只能用于 remote 规则
+rule-when-composite-only = all-of 和 any-of 规则的条件来自 --when (也只有它们使用 --when)
+rule-condition-invalid = 无效的条件 '{ $spec }' (应为 path=、remote=、branch=、host=、org= 或 repo=)
+rule-incomplete = { $kind } 规则需要{ $needs }
+rule-needs-pattern = 匹配模式
+rule-needs-remote = 匹配模式或 --host、--org、--repo
+rule-needs-conditions = 条件 (--when)
+rule-none = 没有配置规则
+rule-add-hint = 使用 { $command } 添加规则
+rule-examples = 示例:
+rule-example-path = 添加路径规则
+rule-example-remote = 添加远程规则
+rule-example-branch = 添加分支规则
+rule-list-header = 已配置的规则:
+rule-from-ruleset = (规则集 { $name })
+rule-priority = 优先级: { $priority }
+rule-total = 共 { $count } 条规则
+rule-updated = 已更新规则 { $id }: { $rule }
+rule-editing = 编辑规则
+rule-prompt-type = 类型
+rule-prompt-pattern = 匹配模式
+rule-prompt-identity = 身份
+rule-prompt-priority = 优先级 (数字越小优先级越高)
+rule-prompt-description = 描述 (可选)
+rule-already-enabled = 规则 { $id } 已经是启用状态
+rule-already-disabled = 规则 { $id } 已经是停用状态
+rule-enabled = 已启用规则 { $id }: { $rule }
+rule-disabled = 已停用规则 { $id }: { $rule }
+rule-moved = 规则 { $id } 的优先级 { $old } { $arrow } { $new } (现为第 { $position } 条, 共 { $total } 条)
+rule-read-only = 规则 { $id } 来自订阅的规则集 '{ $source }', 只读 (使用 `gid ruleset unsubscribe { $source }` 取消订阅)
+rule-remove-about = 即将删除规则: { $pattern } -> { $identity }
+rule-removed = 规则已删除
+rule-test-header = 规则匹配测试:
+rule-test-path = 路径: { $path }
+rule-test-remote = 远程: { $remote }
+rule-test-branch = 分支: { $branch }
+rule-test-no-match = 没有匹配的规则
+rule-test-matched = 匹配的规则:
+rule-test-matched-specific = 匹配的规则 (最具体的在前):
+rule-test-identity = 将使用身份: { $identity } { $name } <{ $email }>
+
+## gid auto
+
+auto-using-project = 使用项目配置 (.gid): { $identity }
+auto-matched-rule = 匹配规则: { $pattern } -> { $identity }
+auto-using-fallback = 没有匹配的规则, 使用后备身份: { $identity }
+auto-source = 来源: { $source }
+auto-source-none = 无
+auto-identity = 身份: { $identity }
+auto-nothing-would-change = 不会有任何变更
+auto-gid-file-hint = 或在项目根目录创建 .gid 文件指定身份
+auto-current-identity = 当前身份: { $name } <{ $email }>
+auto-resolved = 从{ $source }解析出 { $identity }
+auto-global-already-set = 全局身份已是该身份
+auto-would-switch-global = 将把全局身份切换为 { $identity }
+auto-already-using = 已在使用 { $name } <{ $email }>
+auto-not-set = 未设置
+auto-would-switch = 将切换: { $current } { $arrow } { $name } <{ $email }>
+auto-no-repos = { $path } 下没有找到 Git 仓库
+auto-scan-drifted = { $current }, 应为 { $expected } ({ $source })
+auto-scan-summary = { $total } 个仓库: { $ok } 个正常, { $drifted } 个不一致, { $unmatched } 个未匹配
+auto-scan-fixed = 已修正 { $count } 个仓库
+auto-scan-unfixed = { $count } 个仓库无法修正
+auto-scan-fix-hint = 运行 { $command } 修正
+auto-scan-mismatch = { $count } 个仓库使用了错误的身份
+auto-status-expected = 应为: { $identity } ({ $source })
+auto-status-unexpected = 应为: { $reason }
+auto-status-no-match = 没有匹配的 .gid 或规则
+auto-status-current = 当前: { $current }
+auto-status-mismatch = 身份不一致, 请运行 { $command }
+auto-status-match = 身份一致
+auto-unmatched-none = 没有记录未匹配的仓库
+auto-unmatched-hint = 在仓库中运行 { $command } 以收集
+auto-suggestions = { $count } 个未匹配的仓库, 建议的规则:
+auto-suggestions-placeholder = 将 { $placeholder } 替换为要使用的身份
+
+## gid add
+
+add-header = 添加新的 Git 身份
+add-prompt-id = 身份 ID (例如 work, personal)
+add-prompt-name = 姓名
+add-prompt-email = 邮箱
+add-prompt-description = 描述 (可选, 按回车跳过)
+add-prompt-ssh = 配置 SSH 密钥?
+add-prompt-gpg = 配置 GPG 签名密钥?
+add-done = 已添加身份: { $identity } { $name } <{ $email }>
+add-gpg-signing = 已配置 GPG 签名
+add-prompt-switch = 现在切换到这个身份?
+add-prompt-switch-global = 切换全局配置?
+add-ssh-header = SSH 密钥配置:
+add-ssh-existing = 使用已有密钥
+add-ssh-generate = 生成新密钥
+add-skip = 跳过
+add-prompt-choice = 选择 [1/2/3]
+add-prompt-ssh-path = SSH 私钥路径
+add-ssh-generating = 正在生成新的 SSH 密钥...
+add-ssh-generated = 密钥已生成: { $path }
+add-ssh-public-key = 公钥内容 (添加到 GitHub/GitLab):
+add-gpg-missing = 未安装 GPG, 跳过配置
+add-gpg-header = GPG 签名配置:
+add-gpg-found = 找到匹配的 GPG 密钥: { $key }
+add-prompt-gpg-use = 使用这个密钥?
+add-gpg-enter = 输入已有密钥 ID
+add-gpg-list = 列出所有密钥
+add-prompt-gpg-id = GPG 密钥 ID
+add-gpg-invalid = 无效的 GPG 密钥 ID
+add-gpg-none = 没有找到 GPG 密钥
+add-prompt-gpg-index = 选择密钥序号 (按回车跳过)
+add-index-invalid = 无效的序号
+add-index-out-of-range = 序号超出范围
+
+## gid fix-commit
+
+fix-commit-dirty = 检测到未提交的修改, 请先提交或 stash 后再修复历史
+fix-commit-no-email = 无法获取当前邮箱
+fix-commit-unknown-identity = 当前身份不在配置列表中
+fix-commit-resign-no-key = [{ $id }] 没有用于重新签名的 gpg_key, 请用 gid edit 设置
+fix-commit-non-head = 修复非 HEAD 提交需要 --range 参数
+fix-commit-example = 示例: { $command }
+fix-commit-non-head-unsupported = 不支持修复非 HEAD 提交
+fix-commit-single-header = 正在修复提交身份...
+fix-commit-commit = 提交: { $id }
+fix-commit-message = 说明: { $message }
+fix-commit-current-identity = 当前身份: { $name } <{ $email }>
+fix-commit-new-identity-aligned = 新身份:   { $name } <{ $email }>
+fix-commit-confirm = 确认修复?
+fix-commit-fixed = 提交已修复
+fix-commit-new-commit = 新提交: { $id }
+fix-commit-hash-changed = 提交哈希已改变, 如果已推送, 请使用 { $command } 强制推送
+fix-commit-range-header = 正在批量修复提交...
+fix-commit-range = 范围: { $range }
+fix-commit-new-identity = 新身份: { $name } <{ $email }>
+fix-commit-range-format = 请使用范围格式, 例如 HEAD~3..HEAD
+fix-commit-range-empty = 范围内没有提交
+fix-commit-will-fix = 将修复 { $count } 个提交
+fix-commit-warning = 警告:
+fix-commit-range-warning = 这会修改提交历史, 之后所有提交的哈希都会改变
+fix-commit-range-force-push = 如果已推送, 需要使用 git push --force
+fix-commit-range-backup = 会先为被改写的分支创建备份引用
+fix-commit-confirm-continue = 确认继续?
+fix-commit-email-header = 正在按邮箱修复提交...
+fix-commit-author = 作者: { $email }
+fix-commit-email-none = 当前分支上没有 { $email } 的提交
+fix-commit-email-will-fix = 将修复当前分支历史中的 { $count } 个提交
+fix-commit-email-warning = 这会从第一个匹配的提交开始改写历史
+fix-commit-email-new-hashes = 之后的每个提交都会得到新哈希, 其他分支保留旧提交
+fix-commit-email-force-push = 如果已推送, 需要强制推送, 协作者需要重新克隆或 rebase
+fix-commit-email-backup = 会先为当前分支创建备份引用
+fix-commit-done = 已修复 { $count } 个提交
+fix-commit-new-head = 新 HEAD: { $id }
+fix-commit-backup = 备份:   { $backup }
+fix-commit-restore = 恢复命令: { $command }
+fix-commit-hashes-changed = 提交哈希已改变, 如果已推送, 请使用 { $command } 强制推送
+fix-commit-no-gpg-key = [{ $id }] 没有 gpg_key
+fix-commit-sign-failed = 无法用 GPG 密钥 { $key } 签名
+fix-commit-signatures-dropped = 有 { $count } 个被改写的提交原本已签名, 它们的签名已被丢弃
+fix-commit-signatures-dropped-resign = 有 { $count } 个被改写的提交原本已签名, 它们的签名已被丢弃 (使用 --resign)
+fix-commit-range-end = 范围必须以 HEAD ({ $head }) 或分支名结尾
+fix-commit-branch-no-commit = { $branch } 没有指向提交
+fix-commit-backup-failed = 无法创建备份引用 { $backup }
+
+## gid import
+
+import-file-not-found = 文件不存在: { $path }
+import-age-missing = 文件已加密, 但没有安装 age (https://age-encryption.org)
+import-not-utf8 = 配置文件不是有效的 UTF-8
+import-invalid = 配置文件格式错误
+import-script = 脚本格式的导出无法导入
+import-empty = 文件中没有有效的配置
+import-found = 找到 { $identities } 个身份, { $rules } 条规则
+import-dry-run = 试运行: 没有写入任何修改
+import-options = 导入方式:
+import-option-merge = 合并 (保留现有配置, 添加新内容)
+import-option-replace = 替换 (删除现有配置)
+import-option-cancel = 取消
+import-replace-confirm = 确定要替换现有配置吗? 此操作无法撤销
+import-backed-up = 已备份到: { $path }
+import-replaced = 配置已替换: { $identities } 个身份, { $rules } 条规则
+import-imported = 配置已导入: { $identities } 个身份, { $rules } 条规则
+import-conflict-overwritten = [{ $id }] 已覆盖 ({ $fields })
+import-conflict-renamed = [{ $id }] 已导入为 [{ $new_id }] ({ $fields })
+import-conflict-skipped = [{ $id }] 已跳过, 不同的字段: { $fields }
+import-rule-overwritten = { $rule } 已覆盖 (原为 [{ $local }])
+import-rule-skipped = { $rule } 已跳过, 该模式在本地映射到 [{ $local }]
+import-complete = 导入完成:
+import-identities = 身份: 新增 { $added }, 跳过 { $skipped } (已存在)
+import-rules = 规则: 新增 { $added }, 跳过 { $skipped } (重复或冲突)
+import-conflicts = 冲突:
+import-conflicts-hint = 使用 { $overwrite } 或 { $rename } 应用这些修改
+import-plan-identities = 身份:
+import-plan-unchanged = (未改变, 跳过)
+import-plan-overwritten = 覆盖
+import-plan-renamed = 导入为 [{ $new_id }]
+import-plan-skipped-on-merge = 合并时跳过, 替换时覆盖
+import-plan-differs = (不同的字段 { $fields }: { $resolution })
+import-plan-removed = (替换时删除)
+import-plan-rules = 规则:
+import-plan-duplicate = (已存在, 跳过)
+import-plan-skipped = 跳过
+import-plan-rule-conflict = (该模式在本地映射到 [{ $local }]: { $resolution })
+import-commands = 切换到这些身份后会执行的命令:
+import-commands-confirm = 保存这些命令? 每次切换时都会执行
+import-commands-need-confirmation = 导入的身份会在切换后执行命令, 请检查后传入 --yes 导入
+import-gitconfig-none = Git 配置中没有新的身份
+import-gitconfig-found = 在 Git 配置中找到 { $count } 个身份
+import-gitconfig-from = 来自 { $path }
+import-gitconfig-signing-key = 签名密钥 { $key }
+import-gitconfig-create = 创建身份?
+import-gitconfig-id = 身份 ID
+import-gitconfig-added = 已添加身份 [{ $id }]
+import-gitconfig-rule-confirm = 添加路径规则 { $pattern } { $arrow } { $id }?
+import-gitconfig-rule-added = 已添加规则: 路径 { $pattern } { $arrow } { $id }
+import-gitconfig-done = 导入完成: { $identities } 个身份, { $rules } 条规则
+
+## gid hook
+
+hook-mismatch = 正在以 { $name } <{ $email }> 提交, 但 { $source } 要求 { $identity } { $expected_name } <{ $expected_email }>
+hook-no-name = (无姓名)
+hook-no-email = (无邮箱)
+hook-fix = 修复:  { $command }
+hook-skip = 跳过: { $command }
+hook-or = 或者: { $command }
+hook-mismatch-error = Git 身份与 [{ $id }] 不匹配
+hook-no-repo-path = 无法获取仓库路径
+hook-exists = pre-commit hook 已存在
+hook-overwrite = 覆盖?
+hook-write-failed = 无法写入 hook 文件
+hook-installed = pre-commit hook 已安装
+hook-hooks-path-failed = 设置 core.hooksPath 失败
+hook-global-installed = 全局 pre-commit hook 已安装
+hook-hooks-path-set = 已设置 { $key } = { $path }
+hook-missing = hook 不存在
+hook-not-gid = 这不是 gid 的 hook, 跳过删除
+hook-uninstalled = pre-commit hook 已卸载
+hook-global-removed = 全局 hook 已删除
+hook-hooks-path-removed = 已删除 core.hooksPath 配置
+hook-status = Git Hook 状态:
+hook-local = 本地 hook
+hook-global = 全局 hook
+hook-framework = pre-commit 框架
+hook-state-installed = 已安装 (gid)
+hook-state-foreign = 已存在 (非 gid)
+hook-state-not-installed = 未安装
+hook-state-framework = 已配置 gid-check
+hook-state-no-framework = .pre-commit-config.yaml 中没有 gid-check
+hook-state-no-repo = 不在 Git 仓库中
+hook-state-not-configured = 未配置
+
+## gid doctor
+
+doctor-header = 正在检查 Git 身份配置...
+doctor-not-a-repo = 当前目录不是 Git 仓库
+doctor-current-identity = 当前身份:
+doctor-identity = 身份:
+doctor-unknown-identity = 当前身份不在配置列表中
+doctor-no-user = 未配置 Git 用户信息
+doctor-project-config = 项目配置 (.gid):
+doctor-expected-identity = 期望身份: { $identity }
+doctor-project-mismatch = 当前身份与项目配置不匹配 (期望: [{ $id }])
+doctor-project-unknown = 项目配置的身份 '{ $id }' 不存在
+doctor-identity-matches = 身份一致
+doctor-remote = 远程 URL:
+doctor-matched-rules = 匹配的规则:
+doctor-rule-mismatch = 当前身份与规则不匹配 (期望: [{ $id }])
+doctor-signature = 最近提交的签名:
+doctor-signature-good = { $signer } 的有效签名
+doctor-signature-bad = 最近的提交签名无效
+doctor-no-issues = 没有发现问题
+doctor-issues = 发现 { $count } 个问题:
+doctor-fixing = 正在修复...
+doctor-suggestions = 建议操作:
+doctor-fix-hint = 使用 { $command } 自动修复
+doctor-restricted = 已将 { $path } 限制为仅所有者可访问
+doctor-not-owner-only = { $path } 不是仅所有者可访问 ({ $access }), ssh 会拒绝使用 (gid doctor --fix)
+doctor-submodules = 子模块:
+doctor-submodule-unresolved = 没有 .gid 或规则
+doctor-submodule-unknown = 子模块 { $label } 解析到未知身份 '{ $id }'
+doctor-submodule-switched = { $label } 已切换到 { $identity }
+doctor-submodule-expected = { $label } 期望 { $identity } ({ $source })
+doctor-submodule-mismatch = 子模块 { $label } 没有使用期望的身份 [{ $id }]
+
+## gid ruleset
+
+ruleset-name-invalid = 规则集名称只能包含字母, 数字, 下划线和连字符
+ruleset-exists = 已订阅规则集 '{ $name }'
+ruleset-subscribed = 已订阅 { $name }: { $count } 条规则
+ruleset-update-hint = 运行 { $command } 获取更新
+ruleset-not-subscribed = 没有订阅规则集 '{ $name }'
+ruleset-none = 没有订阅任何规则集
+ruleset-updated = { $name }: { $count } 条规则 ({ $added }, { $removed })
+ruleset-update-failed = { $count } 个规则集更新失败
+ruleset-unsubscribed = 已取消订阅 { $name } (删除了 { $count } 条规则)
+ruleset-subscribe-hint = 使用 { $command } 订阅
+ruleset-list-header = 已订阅的规则集:
+ruleset-rule-count = { $count } 条规则
+ruleset-updated-at = 更新时间: { $time }
+ruleset-downloading = 下载 { $url }
+ruleset-download-failed = 下载 { $url } 失败: { $error }
+ruleset-not-utf8 = 规则集不是有效的 UTF-8
+ruleset-read-failed = 无法读取规则集: { $path }
+ruleset-empty = { $url } 中没有规则
+ruleset-unknown-identities = 规则引用了本机未配置的身份: { $ids }
+
+## gid onboard
+
+onboard-not-interactive = gid onboard 是交互式命令, 使用 --yes 接受默认设置
+onboard-welcome = 欢迎使用 gid
+onboard-intro = 接下来分四步设置身份, 规则和自动切换
+onboard-step-identities = 身份
+onboard-no-identity = 没有找到 Git 身份, 请运行 `gid add` 创建
+onboard-step-ssh = SSH 密钥
+onboard-step-rules = 规则
+onboard-step-integration = 集成
+onboard-hook-confirm = 安装全局 pre-commit 身份检查?
+onboard-ready = gid 已就绪
+onboard-hint-list = { $command } 查看你的身份
+onboard-hint-doctor = { $command } 检查当前仓库
+onboard-hint-apply = { $command } 一次修复所有仓库
+onboard-all-keys = 每个身份都有 SSH 密钥
+onboard-missing-keys = { $count } 个身份没有 SSH 密钥 (用 `gid edit` 添加)
+onboard-generate-key = 为 [{ $id }] 生成 SSH 密钥?
+onboard-public-key = 将这个公钥添加到你的 Git 托管账户:
+onboard-all-rules = 每个身份都有路径规则
+onboard-missing-rules = 这些身份没有路径规则: { $ids } (用 `gid rule add` 添加)
+onboard-workspace = [{ $id }] 的工作目录 (例如 ~/work, 按回车跳过)
+onboard-workspace-missing = { $path } 不存在, 已跳过
+onboard-unknown-shell = 未知的 shell, 参考 { $command } 在 cd 时切换身份
+onboard-shell-present = { $path } 中已有 shell 集成
+onboard-shell-confirm = 在 cd 时自动切换身份 (会在 { $path } 中添加一行)?
+onboard-shell-added = 已将 shell 集成添加到 { $path }
+onboard-shell-activate = 打开新的 shell 后生效
+
+## gid current
+
+current-header = 当前 Git 身份:
+current-project = 项目:
+current-global = 全局:
+current-not-set = 未设置
+current-effective = 生效:
+current-identity-id = 身份 ID:
+current-possibly = 可能是:
+current-name-mismatch = (姓名不匹配)
+current-no-match = 没有匹配的已配置身份
+current-no-user = 没有有效的 Git 用户配置
+current-add-hint = 运行 { $command } 添加身份
+current-switch-hint = 运行 { $command } 切换身份
+current-remote = 远程:
+current-expects-unknown = { $source } 要求未知身份 { $identity }
+current-should-use = 这个仓库应该使用 [{ $id }] ({ $source })
+current-run = 运行: { $command }
+current-sources = 来源:
+current-overrides = 覆盖了来自 { $origin } 的 { $value }
+current-unknown-file = 未知文件
+current-origin-include = { $level } include: { $file }
+current-host-managed = Host { $patterns } (由 gid 管理)
+current-no-host-block = 没有 Host 配置块
+current-ssh-key = SSH 密钥:
+current-default-keys = ssh-agent / 默认密钥
+current-key-mismatch = 推送使用 { $identity } 的 SSH 密钥认证, 但提交作者是 { $email }
+
+## gid list
+
+list-empty = 没有配置任何身份
+list-add-hint = 运行 { $command } 添加新身份
+list-no-match = 没有身份匹配给定的过滤条件
+list-header = 已配置的身份:
+list-verified = 已验证
+list-total = 共 { $count } 个身份
+list-showing = 显示 { $count } / { $total } 个身份
+list-ssh-key-missing = 找不到 SSH 密钥 { $path }
+list-ssh-key-unreadable = SSH 密钥 { $path } 不可读
+list-gpg-unavailable = 无法检查 GPG 密钥 { $key }: 没有找到 gpg
+list-gpg-expired = GPG 密钥 { $key } 已过期
+list-gpg-revoked = GPG 密钥 { $key } 已被吊销
+list-gpg-missing = 密钥环中找不到 GPG 密钥 { $key }
+list-host-unresolved = 无法解析 SSH 主机 { $host }
+list-hosts-skipped-offline = 已跳过 SSH 主机解析 (离线)
+list-verify-failed = { $total } 个身份中有 { $failed } 个验证失败
+list-active = 当前使用
+list-expected-by = { $source } 期望的身份
+list-legend-active = 当前仓库正在使用
+list-column-id = ID
+list-column-name = 姓名
+list-column-email = 邮箱
+list-column-tags = 标签
+list-column-last-used = 最近使用
+list-column-uses = 次数
+list-column-status = 状态
+list-never = 从未
+
+## 相对时间
+
+time-just-now = 刚刚
+time-minutes-ago = { $count } 分钟前
+time-hours-ago = { $count } 小时前
+time-days-ago = { $count } 天前
+
+## gid audit
+
+audit-header = 正在审计 Git 提交历史...
+audit-target = 目标: { $path }
+audit-no-repo = 没有找到 Git 仓库
+audit-complete = 审计完成: { $repos } 个仓库, { $issues } 个问题
+audit-shallow-summary = { $count } 个浅克隆仓库只审计了部分历史 (参见 { $command })
+audit-fix-unsupported = 自动修复暂不支持修改提交历史
+audit-fix-rewrite = 修改提交历史需要 git rebase 或 git filter-branch
+audit-fix-tools = 建议手动处理或使用 git-filter-repo 等专用工具
+audit-total-commits = 提交总数: { $count }
+audit-shallow = 浅克隆: 统计只包含已获取的提交, 已跳过混合身份检查
+audit-unshallow = 获取完整历史: { $command }
+audit-usage = 身份使用统计
+audit-unknown = 未知
+audit-commits = { $count } 个提交
+audit-more = ... 还有 { $count } 个
+audit-issue-unknown-identity = 未知身份
+audit-issue-identity-mismatch = 身份不匹配
+audit-issue-mixed-identities = 混用身份
+audit-issue-bad-signature = 签名无效
+
+## gid show
+
+show-private = (私有)
+show-ssh-key = SSH 密钥
+show-fingerprint = 指纹
+show-unavailable = 不可用
+show-ssh-agent = SSH agent
+show-ssh-hosts = SSH 主机
+show-signing = 签名
+show-gh-account = gh 账户
+show-tags = 标签
+show-post-switch = 切换后命令
+show-commit-template = 提交模板
+show-last-used = 最近使用
+show-last-used-value = { $time } (切换 { $count } 次)
+show-git-config = Git 配置:
+show-rules = 规则:
+show-no-rules = 没有规则引用这个身份
+show-repos = 仓库:
+show-no-repos = 没有仓库使用这个身份
+show-signing-on = gpg { $key } (提交已签名)
+show-signing-off = gpg { $key } (签名已关闭)
+show-signing-none = 无
+
+## gid credential
+
+credential-prompt = { $username }@{ $host } 的令牌
+credential-stdin-failed = 无法从标准输入读取令牌
+credential-empty = 令牌为空
+credential-stored = 已保存 { $identity } 在 { $host } 上的 HTTPS 凭据
+credential-next-switch = 下次切换时生效: gid switch { $id }
+credential-none = 没有保存任何 HTTPS 凭据
+credential-add-hint = 添加方式: { $command }
+credential-header = HTTPS 凭据:
+credential-missing = 没有保存 [{ $id }] 在 { $host } 上的凭据
+credential-removed = 已删除 [{ $id }] 在 { $host } 上的 HTTPS 凭据
+credential-request-failed = 无法读取凭据请求
+
+## gid stats
+
+stats-identities = 身份:
+stats-column-repos = 仓库
+stats-column-switches = 切换
+stats-scanned = 扫描了 { $scanned } 个仓库, { $configured } 个使用已配置的身份
+stats-rule-hits = 规则命中:
+stats-unmatched = 未匹配的仓库 ({ $count }):
+stats-suggest-hint = 运行 { $command } 生成建议规则
+
+## gid whoami
+
+whoami-no-email = 这里没有配置 Git 身份 (user.email 未设置)
+whoami-not-gid = (不是 gid 身份)
+whoami-unknown-email = { $email } 不属于任何 gid 身份, 无法比较
+whoami-checking = 检查远程账户
+whoami-gh-missing = 没有安装 gh
+whoami-token = 令牌 ({ $host })
+whoami-emails = 账户邮箱
+whoami-match = 远程账户与 [{ $id }] 一致
+whoami-mismatch = 远程账户与 [{ $id }] 不一致
+whoami-account-unknown = 已认证, 账户未知
+whoami-via-ssh = SSH 密钥
+whoami-via-token = 令牌
+whoami-login-mismatch = { $how } 认证为 { $login }, 但 [{ $id }] 期望 { $expected }
+whoami-logins-differ = SSH 密钥认证为 { $ssh_login }, 但令牌认证为 { $api_login }
+whoami-email-not-on-account = { $email } 不是 { $account } 的邮箱, 提交不会归属到该账户
+whoami-the-account = 该账户
+
+## gid sync-gitconfig
+
+sync-no-rules = 没有需要同步的路径规则
+sync-header = { $path } 中的 includeIf 配置块 (后面的优先):
+sync-rule-removed = (规则已删除)
+sync-dry-run = 试运行: 没有修改 { $path }
+sync-done = 已从路径规则同步 { $count } 个 includeIf 配置块
+sync-automatic = 之后的规则修改会自动同步 (用 --remove 撤销)
+sync-in-sync = 已经是同步状态
+sync-resynced = 已同步全局配置中的 includeIf 配置块
+sync-resync-failed = 无法同步 includeIf 配置块: { $error }
+sync-none = 没有已同步的 includeIf 配置块
+sync-remove-dry-run = 试运行: 没有修改全局配置
+sync-removed = 已删除 { $count } 个 includeIf 配置块和 { $fragments } 个不再使用的配置片段
+
+## gid serve
+
+serve-already-listening = gid serve 已经在 { $path } 上监听
+serve-stale-socket = 无法删除遗留的 socket { $path }
+serve-listen-failed = 无法在 { $path } 上监听
+serve-listening = 正在监听 { $path }
+serve-connection-failed = 连接失败: { $error }
+serve-connection-closed = 连接已关闭: { $error }
+serve-no-unix-sockets = 这个平台不支持 Unix socket, 请使用 --stdio
+serve-read-failed = 无法读取请求
+serve-missing-method = 缺少 method
+serve-unknown-method = 未知的 method: { $method }
+serve-not-a-repo = { $path } 不在 Git 仓库中
+serve-no-resolution = 没有匹配的 .gid 文件或规则, 请指定身份
+serve-missing-path = 缺少 path 参数
+serve-path-relative = 路径必须是绝对路径: { $path }
+
+## gid apply
+
+apply-no-repos = 没有找到 Git 仓库
+apply-no-rule = 没有规则
+apply-would-set = 将设置 { $keys }
+apply-set = 已设置 { $keys }
+apply-would-configure-ssh = 将为 { $identity } 配置 SSH ({ $hosts })
+apply-default-hosts = 默认主机
+apply-wrote-host = 已写入 Host { $alias }
+apply-summary = { $total } 个仓库: { $updated } 个已更新, { $unchanged } 个未改变, { $unmatched } 个未匹配, { $failed } 个失败
+apply-summary-dry-run = { $total } 个仓库: { $updated } 个待更新, { $unchanged } 个未改变, { $unmatched } 个未匹配, { $failed } 个失败
+apply-failed = { $count } 个仓库应用失败
+
+## gid repos
+
+repos-no-roots = 没有要扫描的目录, 请传入 --scan <DIR> 或设置 settings.workspace_roots
+repos-column-path = 路径
+repos-column-current = 当前
+repos-column-expected = 期望
+repos-column-remote = 远程
+repos-not-set = 未设置
+repos-summary = { $total } 个仓库: { $ok } 个正常, { $drifted } 个偏离, { $unmatched } 个未匹配
+repos-fix-hint = 运行 { $command } 修复
+
+## gid init
+
+init-already-pinned = { $path } 已固定为 [{ $id }], 使用 --force 覆盖
+init-nothing-to-pin = 没有可固定的规则或当前身份, 请指定: gid init <identity>
+init-wrote = 已写入 { $path }, 固定为 { $id }
+init-gitignore-added = 已将 .gid 添加到 .gitignore
+init-committed = 已提交 .gid
+
+## gid edit
+
+edit-created = 已创建配置文件: { $path }
+edit-editing = 正在使用 { $editor } 编辑配置文件...
+edit-valid = 配置有效, 包含 { $identities } 个身份, { $rules } 条规则
+edit-invalid = 配置格式错误: { $error }
+edit-fix-hint = 请修复配置文件后重试
+edit-editor-failed = 无法启动编辑器: { $editor }
+edit-editor-exited = 编辑器异常退出
+
+## gid template
+
+template-editing = 正在使用 { $editor } 编辑 [{ $id }] 的提交模板...
+template-saved = 提交模板已保存, 下次切换时生效: { $command }
+template-none = [{ $id }] 没有提交模板
+template-none-hint = [{ $id }] 没有提交模板, 创建: gid template edit { $id }
+template-removed = 已移除 [{ $id }] 的提交模板 (仓库在下次切换时不再使用)
+
+## gid remove
+
+remove-about-to = 即将删除身份: { $id } { $name } <{ $email }>
+remove-done = 身份 '{ $id }' 已删除
+remove-prune-hint = 运行 { $command } 清理它的规则, SSH 别名和 includeIf 文件
+
+## gid verify
+
+verify-header = 正在验证 { $id } { $name } <{ $email }>
+verify-summary = { $passed } 项通过, { $warnings } 项警告, { $failed } 项失败
+verify-failed = [{ $id }] 验证失败
+verify-name = 姓名
+verify-name-empty = 为空
+verify-email = 邮箱
+verify-email-invalid = '{ $email }' 不是有效的邮箱地址
+verify-ssh-key = SSH 密钥
+verify-ssh-key-none = 未配置 SSH 密钥
+verify-not-found = 找不到 { $path }
+verify-permissions = 权限
+verify-owner-only = 仅所有者可访问
+verify-too-open = { $access } 权限过宽, ssh 会拒绝该密钥 (gid doctor --fix)
+verify-fingerprint = 指纹
+verify-agent-loaded = 密钥已加载
+verify-agent-none = 没有运行中的 agent
+verify-agent-not-loaded = 密钥未加载 (ssh-add { $path })
+verify-forge = 代码托管 { $host }
+verify-forge-skipped = 已跳过 (传入 --forge 检查)
+verify-gpg-key = GPG 密钥
+verify-gpg-key-unset = 已启用 gpg_sign 但未设置 gpg_key
+verify-gpg-key-none = 未配置签名密钥
+verify-gpg-missing = 找不到 gpg
+verify-gpg-expired = { $key } 已过期
+verify-gpg-revoked = { $key } 已被吊销
+verify-gpg-not-in-keyring = 密钥环中找不到 { $key }
+verify-gpg-uid-mismatch = 密钥属于 { $key_email }, 提交使用 { $email }
+verify-gpg-signing = GPG 签名
+verify-gpg-signing-ok = 已创建并验证测试签名
+
+## gid history
+
+history-empty = 没有切换记录
+history-empty-repo = { $path } 没有切换记录
+history-title = 切换历史 (最新在前):
+history-global = (全局)
+history-none = (无)
+
+## gid watch
+
+watch-no-paths = 没有要监视的路径, 请传入路径或设置 settings.workspace_roots
+watch-mode-check = 仅检查
+watch-mode-enforce = 强制应用
+watch-started = 正在监视 { $paths } (每 { $interval } 秒, { $mode })
+watch-config-invalid = { $error } (继续使用上一个有效配置)
+watch-unknown-identity = 规则引用了未知身份 '{ $id }'
+watch-drifted = 使用 { $current }, 期望 { $expected } ({ $source })
+
+## gid env 和 gid direnv
+
+env-no-identity = 无法为此目录确定身份, 请明确指定
+direnv-wrote = 已将 { $id } 配置段写入 { $path }
+direnv-allow-hint = 运行 { $command } 允许加载
+
+## gid check
+
+check-ok-unexpected = ok: 此处不要求身份
+check-ok = ok: { $id }
+check-mismatch = mismatch: 正在使用 { $email }, { $source } 期望 { $id }
+check-no-email = (无邮箱)
+
+## gid mailmap
+
+mailmap-wrote = 已写入 { $path }, 共 { $count } 条映射
+mailmap-unknown = { $count } 个作者不匹配任何身份, 已忽略:
+mailmap-commits = { $count } 次提交
+
+## gid clone
+
+clone-cloning-url = 克隆 { $url }
+clone-no-rule = 没有规则匹配 { $url }, 请传入 --identity <id> 或使用 `gid rule add` 添加规则
+clone-cloning-as = 以 { $id } 身份克隆 ({ $source })
+clone-failed = git clone 失败
+clone-wrote = 已写入 { $path }
+clone-enter-failed = 无法进入 { $path }
+
+## gid tui
+
+tui-needs-terminal = gid tui 需要交互式终端
+tui-not-a-repo = 未在 Git 仓库中启动
+tui-no-identity = 未选择身份
+tui-switched = 已切换到 [{ $id }]
+tui-no-expected = 没有 .gid 或规则匹配此仓库
+tui-rule-at-edge = 规则已在边界
+tui-rule-moved-up = 已将规则 { $id } 移到 { $other } 之前
+tui-rule-moved-down = 已将规则 { $id } 移到 { $other } 之后
+tui-reloaded = 已重新加载配置
+tui-help = Tab 切换面板  ↑↓ 选择  Enter 切换身份  Shift+↑↓ 移动规则  f 修复仓库  r 重新加载  q 退出
+tui-expected-unknown = 期望的身份 '{ $id }' 不存在
+tui-drifted = 当前身份与 { $source } 不匹配 (期望: [{ $id }]), 按 f 修复
+tui-identities = 身份
+tui-rules = 规则
+tui-repository = 仓库
+tui-outside-repo = 不在 Git 仓库中
+tui-path = 路径:
+tui-remote = 远程:
+tui-current = 当前:
+tui-expected = 期望:
+tui-no-match = 没有 .gid 或规则匹配
+
+## --format 模板
+
+format-unclosed = --format 中有未闭合的 '{"{"}': { $template }
+format-invalid-field = --format 中的字段 '{"{"}{ $name }{"}"}' 无效
+format-unmatched = --format 中有不匹配的 '{"}"}' (字面量花括号请写成 {"}}"})
+format-unknown-field = --format 中的字段 '{ $field }' 未知 (可用: { $available })
+format-not-an-object = --format 中的字段 '{ $field }' 没有 '{ $key }'
+
+## 插件 (PATH 中的 gid-<name>)
+
+plugin-missing = 缺少子命令
+plugin-unknown = 无法识别的子命令 '{ $name }' (PATH 中没有 gid-{ $name }), 运行 gid --help 查看帮助
+plugin-failed = 无法执行 { $program }
@@ -0,0 +1,273 @@
+//! Crash-safe replacement of configuration files
+//!
+//! New content goes to a temporary file next to the destination, is read back
+//! and checked, then renamed over the old file, so a crash leaves either the
+//! old or the new version and never a truncated one. A `<file>.lock` beside
+//! the destination serializes gid processes that modify the same file.
+
+use anyhow::{Context, Result};
+use std::fs::{self, File, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::i18n::tr;
+
+/// How long to wait for another gid process to release a lock
+const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+const LOCK_RETRY: Duration = Duration::from_millis(50);
+
+/// Locks older than this were left behind by a crashed process
+const STALE_AFTER: Duration = Duration::from_secs(60);
+
+/// Exclusive `<file>.lock`, removed on drop
+#[derive(Debug)]
+pub struct FileLock {
+    path: PathBuf,
+}
+
+impl FileLock {
+    /// Wait until no other gid process holds the lock for `target`
+    pub fn acquire(target: &Path) -> Result<Self> {
+        Self::acquire_within(target, LOCK_TIMEOUT)
+    }
+
+    fn acquire_within(target: &Path, timeout: Duration) -> Result<Self> {
+        let path = sibling(&resolve(target), "lock");
+        let start = std::time::Instant::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    let _ = writeln!(file, "{}", std::process::id());
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    if is_stale(&path) {
+                        log::debug!("removing stale lock {}", path.display());
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if start.elapsed() >= timeout {
+                        anyhow::bail!(tr!("file-locked", path = path.display()));
+                    }
+                    std::thread::sleep(LOCK_RETRY);
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| tr!("file-create-failed", path = path.display()))
+                }
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Replace `path` with `content` once `verify` accepts what was written
+///
+/// The existing file's permissions carry over, and a symlinked destination
+/// (e.g. a dotfiles checkout) is written through rather than replaced.
+pub fn write(path: &Path, content: &str, verify: impl FnOnce(&str) -> Result<()>) -> Result<()> {
+    write_with(path, content, verify, false)
+}
+
+/// Like [`write`], for secrets: the temporary file is owner-only before any
+/// content reaches it, and so is the result
+pub fn write_private(
+    path: &Path,
+    content: &str,
+    verify: impl FnOnce(&str) -> Result<()>,
+) -> Result<()> {
+    write_with(path, content, verify, true)
+}
+
+fn write_with(
+    path: &Path,
+    content: &str,
+    verify: impl FnOnce(&str) -> Result<()>,
+    private: bool,
+) -> Result<()> {
+    let path = resolve(path);
+    let tmp = sibling(&path, &format!("{}.tmp", std::process::id()));
+    // A temp file left by a crashed process with the same pid
+    let _ = fs::remove_file(&tmp);
+
+    let result = (|| {
+        let mut file = create_new(&tmp, private)
+            .with_context(|| tr!("file-create-failed", path = tmp.display()))?;
+        if private {
+            crate::permissions::restrict_to_owner(&tmp)?;
+        } else if let Ok(metadata) = fs::metadata(&path) {
+            fs::set_permissions(&tmp, metadata.permissions())?;
+        }
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+
+        let written = fs::read_to_string(&tmp)?;
+        if written != content {
+            anyhow::bail!(tr!("file-readback-mismatch", path = tmp.display()));
+        }
+        verify(&written)?;
+
+        fs::rename(&tmp, &path).with_context(|| tr!("file-replace-failed", path = path.display()))
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Create `path`, which must not exist yet; private files start out as 600
+fn create_new(path: &Path, private: bool) -> std::io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    options.open(path)
+}
+
+/// Create `dir` and missing parents, new directories owner-only (700 on Unix)
+pub fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(dir)
+    }
+    #[cfg(not(unix))]
+    {
+        let existed = dir.exists();
+        fs::create_dir_all(dir)?;
+        if !existed {
+            let _ = crate::permissions::restrict_to_owner(dir);
+        }
+        Ok(())
+    }
+}
+
+/// Follow a symlinked destination to the file it points at
+fn resolve(path: &Path) -> PathBuf {
+    match fs::symlink_metadata(path) {
+        Ok(m) if m.file_type().is_symlink() => {
+            fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+        }
+        _ => path.to_path_buf(),
+    }
+}
+
+/// `<dir>/.<name>.<suffix>`, hidden next to `path`
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{name}.{suffix}"))
+}
+
+fn is_stale(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age > STALE_AFTER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_replaces_after_verify() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "old").unwrap();
+
+        let err = write(&path, "broken", |_| anyhow::bail!("does not parse")).unwrap_err();
+        assert!(err.to_string().contains("does not parse"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        write(&path, "new", |s| {
+            assert_eq!(s, "new");
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_keeps_permissions_and_symlinks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("dotfiles-ssh-config");
+        fs::write(&target, "old").unwrap();
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o600)).unwrap();
+        let link = dir.path().join("config");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        write(&link, "new", |_| Ok(())).unwrap();
+        assert!(fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "new");
+        let mode = fs::metadata(&target).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_private_is_never_readable_by_others() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("credentials.toml");
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let tmp = sibling(&path, &format!("{}.tmp", std::process::id()));
+
+        write_private(&path, "token = \"secret\"", |_| {
+            // Checked while the temp file holds the content, before the rename
+            let mode = fs::metadata(&tmp).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+            Ok(())
+        })
+        .unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let data = dir.path().join("data/gid");
+        create_private_dir(&data).unwrap();
+        let mode = fs::metadata(&data).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+
+    #[test]
+    fn test_lock_is_exclusive() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("config.toml");
+
+        let lock = FileLock::acquire(&path).unwrap();
+        let err = FileLock::acquire_within(&path, Duration::from_millis(100)).unwrap_err();
+        assert!(err.to_string().contains("locked by another gid process"));
+
+        drop(lock);
+        assert!(FileLock::acquire_within(&path, Duration::from_millis(100)).is_ok());
+    }
+}
@@ -0,0 +1,307 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::config::Config;
+use crate::exit::Failure;
+use crate::git::{CommitInfo, GitConfigManager, SignatureStatus};
+use crate::i18n::tr;
+
+/// Most recent commits checked per repository
+const AUDIT_COMMIT_LIMIT: usize = 1000;
+
+/// Audit Result
+#[derive(Debug)]
+pub struct AuditResult {
+    pub repo_path: PathBuf,
+    pub total_commits: usize,
+    /// Shallow clone: counts only cover the fetched commits
+    pub shallow: bool,
+    pub issues: Vec<AuditIssue>,
+    pub identities_used: HashMap<String, IdentityUsage>,
+}
+
+/// Identity Usage Statistics
+#[derive(Debug, Default)]
+pub struct IdentityUsage {
+    pub name: String,
+    pub email: String,
+    pub commit_count: usize,
+    pub is_known: bool,
+    pub identity_id: Option<String>,
+}
+
+/// Audit Issue
+#[derive(Debug)]
+pub struct AuditIssue {
+    pub issue_type: IssueType,
+    pub commit_id: String,
+    pub message: String,
+    pub author_name: String,
+    pub author_email: String,
+}
+
+/// Issue Type
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IssueType {
+    /// Unknown identity used
+    UnknownIdentity,
+    /// Identity mismatch with rules
+    IdentityMismatch,
+    /// Mixed identities used
+    MixedIdentities,
+    /// Signature does not match the commit
+    BadSignature,
+}
+
+impl AuditIssue {
+    fn new(issue_type: IssueType, commit: &CommitInfo) -> Self {
+        Self {
+            issue_type,
+            commit_id: commit.id.clone(),
+            message: commit.message.clone(),
+            author_name: commit.author_name.clone(),
+            author_email: commit.author_email.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for IssueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            IssueType::UnknownIdentity => tr!("audit-issue-unknown-identity"),
+            IssueType::IdentityMismatch => tr!("audit-issue-identity-mismatch"),
+            IssueType::MixedIdentities => tr!("audit-issue-mixed-identities"),
+            IssueType::BadSignature => tr!("audit-issue-bad-signature"),
+        };
+        f.write_str(&name)
+    }
+}
+
+/// Auditor
+pub struct Auditor {
+    config: Config,
+    verify_signatures: bool,
+}
+
+impl Auditor {
+    /// Create new auditor
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            verify_signatures: false,
+        }
+    }
+
+    /// Also check the signature of every signed commit (runs gpg / ssh-keygen per commit)
+    pub fn with_signature_check(mut self, enabled: bool) -> Self {
+        self.verify_signatures = enabled;
+        self
+    }
+
+    /// Audit single repository
+    ///
+    /// Authors are canonicalized through the repository's mailmap first, so
+    /// commits made under an old, mapped email count as the identity.
+    pub fn audit_repo(&self, path: &Path) -> Result<AuditResult> {
+        let git = GitConfigManager::from_path(path)?;
+
+        if !git.is_in_repo() {
+            return Err(Failure::NotARepo.error(tr!("git-path-not-a-repo", path = path.display())));
+        }
+
+        let mut total_commits = 0;
+        let mut issues = Vec::new();
+        let mut identities_used: HashMap<String, IdentityUsage> = HashMap::new();
+        // Commits of each known identity, for the mixed-usage check once counts are final
+        let mut known_commits: HashMap<String, Vec<CommitInfo>> = HashMap::new();
+
+        // Check if specific identity should be used
+        let expected_identity = self.get_expected_identity(path, &git);
+
+        let mailmap = git.mailmap();
+
+        // Single pass over the most recent commits
+        for commit in git.commits()?.take(AUDIT_COMMIT_LIMIT) {
+            let mut commit = commit?;
+            if !mailmap.is_empty() {
+                (commit.author_name, commit.author_email) =
+                    mailmap.resolve(&commit.author_name, &commit.author_email);
+            }
+            total_commits += 1;
+            if self.verify_signatures
+                && matches!(
+                    git.verify_commit(&commit.oid),
+                    Ok(Some(SignatureStatus::Bad))
+                )
+            {
+                issues.push(AuditIssue::new(IssueType::BadSignature, &commit));
+            }
+            let key = format!("{} <{}>", commit.author_name, commit.author_email);
+
+            // Track identity usage
+            let usage = identities_used.entry(key.clone()).or_insert_with(|| {
+                let (is_known, identity_id) =
+                    self.find_matching_identity(&commit.author_name, &commit.author_email);
+
+                IdentityUsage {
+                    name: commit.author_name.clone(),
+                    email: commit.author_email.clone(),
+                    commit_count: 0,
+                    is_known,
+                    identity_id,
+                }
+            });
+            usage.commit_count += 1;
+
+            // Check for issues
+            if !usage.is_known {
+                issues.push(AuditIssue::new(IssueType::UnknownIdentity, &commit));
+                continue;
+            }
+            if let Some(ref expected) = expected_identity {
+                if usage.identity_id.as_ref() != Some(expected) {
+                    issues.push(AuditIssue::new(IssueType::IdentityMismatch, &commit));
+                }
+            }
+            known_commits.entry(key).or_default().push(commit);
+        }
+
+        // Check for mixed usage of multiple known identities; in a shallow clone
+        // the least used identity may just be the one whose commits were cut off
+        let shallow = git.is_shallow();
+        if known_commits.len() > 1 && !shallow {
+            // Flag commits of the least used identity
+            let least_used = known_commits
+                .keys()
+                .min_by_key(|key| identities_used[*key].commit_count)
+                .cloned();
+            if let Some(commits) = least_used.and_then(|key| known_commits.remove(&key)) {
+                issues.extend(
+                    commits
+                        .iter()
+                        .map(|commit| AuditIssue::new(IssueType::MixedIdentities, commit)),
+                );
+            }
+        }
+
+        Ok(AuditResult {
+            repo_path: path.to_path_buf(),
+            total_commits,
+            shallow,
+            issues,
+            identities_used,
+        })
+    }
+
+    /// Audit all repositories in directory
+    pub fn audit_directory(&self, path: &Path) -> Result<Vec<AuditResult>> {
+        let mut results = Vec::new();
+
+        // Check current directory first
+        if let Ok(result) = self.audit_repo(path) {
+            results.push(result);
+        }
+
+        // Walk subdirectories to find Git repositories, bare mirrors included
+        let mut walker = WalkDir::new(path).min_depth(1).max_depth(3).into_iter();
+        while let Some(entry) = walker.next() {
+            let Ok(entry) = entry else {
+                continue;
+            };
+            if !entry.file_type().is_dir() {
+                continue;
+            }
+            if entry.file_name() == ".git" {
+                if let Some(parent) = entry.path().parent() {
+                    if let Ok(result) = self.audit_repo(parent) {
+                        results.push(result);
+                    }
+                }
+                walker.skip_current_dir();
+            } else if crate::git::is_git_dir(entry.path()) {
+                if let Ok(result) = self.audit_repo(entry.path()) {
+                    results.push(result);
+                }
+                walker.skip_current_dir();
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Distinct commit authors across the whole history of a repository, most commits first
+    pub fn authors(&self, path: &Path) -> Result<Vec<IdentityUsage>> {
+        let git = GitConfigManager::from_path(path)?;
+
+        if !git.is_in_repo() {
+            return Err(Failure::NotARepo.error(tr!("git-path-not-a-repo", path = path.display())));
+        }
+
+        let mut authors: HashMap<(String, String), IdentityUsage> = HashMap::new();
+        for commit in git.commits()? {
+            let commit = commit?;
+            let key = (commit.author_name, commit.author_email);
+            if let Some(usage) = authors.get_mut(&key) {
+                usage.commit_count += 1;
+                continue;
+            }
+            let (is_known, identity_id) = self.find_matching_identity(&key.0, &key.1);
+            let usage = IdentityUsage {
+                name: key.0.clone(),
+                email: key.1.clone(),
+                commit_count: 1,
+                is_known,
+                identity_id,
+            };
+            authors.insert(key, usage);
+        }
+
+        let mut authors: Vec<IdentityUsage> = authors.into_values().collect();
+        authors.sort_by_key(|a| std::cmp::Reverse(a.commit_count));
+        Ok(authors)
+    }
+
+    /// Find matching identity
+    fn find_matching_identity(&self, name: &str, email: &str) -> (bool, Option<String>) {
+        for identity in &self.config.identities {
+            if identity.email == email && identity.name == name {
+                return (true, Some(identity.id.clone()));
+            }
+        }
+
+        // Match email only
+        for identity in &self.config.identities {
+            if identity.email == email {
+                return (true, Some(identity.id.clone()));
+            }
+        }
+
+        (false, None)
+    }
+
+    /// Get expected identity
+    fn get_expected_identity(&self, path: &Path, git: &GitConfigManager) -> Option<String> {
+        // Check .gid file
+        if let Ok(Some(project_config)) = crate::config::ProjectConfig::load_from_dir(path) {
+            return Some(project_config.identity);
+        }
+
+        // Check rule matching
+        let context = crate::rules::MatchContext::new().with_path(path.to_path_buf());
+
+        let context = if let Some(remote) = git.get_origin_url() {
+            context.with_remote(remote)
+        } else {
+            context
+        };
+        let context = match git.current_branch() {
+            Some(branch) => context.with_branch(branch),
+            None => context,
+        };
+
+        let engine = crate::rules::RuleEngine::from_config(&self.config);
+        engine.match_context(&context).map(|r| r.identity.clone())
+    }
+}
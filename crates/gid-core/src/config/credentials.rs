@@ -0,0 +1,124 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::Config;
+use crate::atomic::{self, FileLock};
+use crate::i18n::tr;
+
+/// HTTPS 凭据（与用户配置分开保存，不会被导出，也不记入操作日志）
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Credentials {
+    #[serde(default, rename = "credential", skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<Credential>,
+}
+
+/// 某个身份在某个主机上的用户名和令牌
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Credential {
+    /// 身份 ID
+    pub identity: String,
+
+    /// 主机名（与 git 凭据请求中的 host 一致，可带端口）
+    pub host: String,
+
+    /// HTTPS 用户名
+    pub username: String,
+
+    /// 访问令牌或密码
+    pub token: String,
+}
+
+impl Credentials {
+    /// 获取凭据文件路径
+    pub fn path() -> Result<PathBuf> {
+        Ok(Config::data_dir()?.join("credentials.toml"))
+    }
+
+    /// 加载凭据
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| tr!("credentials-read-failed", path = path.display()))?;
+
+        toml::from_str(&content).with_context(|| tr!("credentials-invalid"))
+    }
+
+    /// 保存凭据（仅当前用户可读写）
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        Self::ensure_dir(&path)?;
+
+        let _lock = FileLock::acquire(&path)?;
+        self.write_locked(&path)
+    }
+
+    /// 加锁后重新加载、修改并保存凭据，`change` 返回错误时不保存
+    pub fn update<T>(change: impl FnOnce(&mut Credentials) -> Result<T>) -> Result<T> {
+        let path = Self::path()?;
+        Self::ensure_dir(&path)?;
+
+        let _lock = FileLock::acquire(&path)?;
+        let mut credentials = Self::load()?;
+        let result = change(&mut credentials)?;
+        credentials.write_locked(&path)?;
+
+        Ok(result)
+    }
+
+    /// 确保数据目录存在（新建时仅当前用户可访问）
+    fn ensure_dir(path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            atomic::create_private_dir(parent)
+                .with_context(|| tr!("data-dir-create-failed", path = parent.display()))?;
+        }
+        Ok(())
+    }
+
+    /// 写入凭据文件（调用方需持有锁）
+    fn write_locked(&self, path: &Path) -> Result<()> {
+        let content =
+            toml::to_string_pretty(self).with_context(|| tr!("credentials-serialize-failed"))?;
+
+        atomic::write_private(path, &content, |written| {
+            toml::from_str::<Credentials>(written)
+                .map(|_| ())
+                .with_context(|| tr!("credentials-invalid"))
+        })
+        .with_context(|| tr!("credentials-write-failed", path = path.display()))?;
+
+        Ok(())
+    }
+
+    /// 查找凭据
+    pub fn find(&self, identity: &str, host: &str) -> Option<&Credential> {
+        self.entries
+            .iter()
+            .find(|c| c.identity == identity && c.host.eq_ignore_ascii_case(host))
+    }
+
+    /// 身份的所有凭据
+    pub fn for_identity<'a>(&'a self, identity: &'a str) -> impl Iterator<Item = &'a Credential> {
+        self.entries.iter().filter(move |c| c.identity == identity)
+    }
+
+    /// 添加或替换凭据
+    pub fn set(&mut self, credential: Credential) {
+        self.remove(&credential.identity, &credential.host);
+        self.entries.push(credential);
+    }
+
+    /// 删除凭据，返回是否存在
+    pub fn remove(&mut self, identity: &str, host: &str) -> bool {
+        let before = self.entries.len();
+        self.entries
+            .retain(|c| !(c.identity == identity && c.host.eq_ignore_ascii_case(host)));
+        self.entries.len() != before
+    }
+}
@@ -1,8 +1,11 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
+use crate::i18n::tr;
+
 /// Git 身份配置
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Identity {
     /// 身份 ID（唯一标识）
     pub id: String,
@@ -17,6 +20,10 @@ pub struct Identity {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    /// 描述为私人信息（脱敏导出时移除）
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub private: bool,
+
     /// SSH 私钥路径
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ssh_key: Option<PathBuf>,
@@ -28,6 +35,30 @@ pub struct Identity {
     /// 是否启用 GPG 签名
     #[serde(default)]
     pub gpg_sign: bool,
+
+    /// 标签（用于分组和筛选）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// 需要配置 SSH 的额外主机（除仓库 remote 之外）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ssh_hosts: Vec<String>,
+
+    /// GitHub CLI (gh) 账号，切换身份时同步切换
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gh_account: Option<String>,
+
+    /// 切换到此身份后执行的命令（覆盖全局设置）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_switch_command: Option<String>,
+
+    /// 此身份专用的 SSH agent socket（direnv 导出时覆盖 SSH_AUTH_SOCK）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ssh_auth_sock: Option<String>,
+
+    /// 提交信息模板文件（由 `gid template edit` 管理，切换时设置 commit.template）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_template: Option<PathBuf>,
 }
 
 impl Identity {
@@ -38,9 +69,16 @@ impl Identity {
             name,
             email,
             description: None,
+            private: false,
             ssh_key: None,
             gpg_key: None,
             gpg_sign: false,
+            tags: Vec::new(),
+            ssh_hosts: Vec::new(),
+            gh_account: None,
+            post_switch_command: None,
+            ssh_auth_sock: None,
+            commit_template: None,
         }
     }
 
@@ -65,10 +103,15 @@ impl Identity {
         self
     }
 
+    /// 展开后的 SSH 私钥路径
+    pub fn ssh_key_path(&self) -> Option<PathBuf> {
+        self.ssh_key.as_deref().map(expand_path)
+    }
+
     /// 验证身份配置
     pub fn validate(&self) -> Result<(), String> {
         if self.id.is_empty() {
-            return Err("身份 ID 不能为空".to_string());
+            return Err(tr!("identity-id-empty"));
         }
 
         if !self
@@ -76,27 +119,27 @@ impl Identity {
             .chars()
             .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
         {
-            return Err("身份 ID 只能包含字母、数字、下划线和连字符".to_string());
+            return Err(tr!("identity-id-invalid"));
         }
 
         if self.name.is_empty() {
-            return Err("姓名不能为空".to_string());
+            return Err(tr!("identity-name-empty"));
         }
 
         if self.email.is_empty() {
-            return Err("邮箱不能为空".to_string());
+            return Err(tr!("identity-email-empty"));
         }
 
         // 简单的邮箱格式验证
         if !self.email.contains('@') || !self.email.contains('.') {
-            return Err("邮箱格式不正确".to_string());
+            return Err(tr!("identity-email-invalid"));
         }
 
         // 验证 SSH 密钥文件是否存在
         if let Some(ref ssh_key) = self.ssh_key {
             let expanded = expand_path(ssh_key);
             if !expanded.exists() {
-                return Err(format!("SSH 密钥文件不存在: {}", expanded.display()));
+                return Err(tr!("identity-ssh-key-missing", path = expanded.display()));
             }
         }
 
@@ -105,7 +148,7 @@ impl Identity {
 }
 
 /// 展开路径中的 ~ 符号
-fn expand_path(path: &Path) -> PathBuf {
+pub fn expand_path(path: &Path) -> PathBuf {
     if let Some(path_str) = path.to_str() {
         if let Some(stripped) = path_str.strip_prefix("~/") {
             if let Some(home) = home::home_dir() {
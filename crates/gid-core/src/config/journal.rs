@@ -0,0 +1,294 @@
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::Config;
+use crate::atomic::{self, FileLock};
+use crate::i18n::tr;
+
+/// 保留的操作数量
+const MAX_ENTRIES: usize = 50;
+
+/// 暂停记录（撤销操作本身不写入日志）
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// 当前会话 ID，同一次命令（或 watch/serve 中的一次处理）的所有修改合并为一个操作
+    static SESSION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// 操作日志：每次修改文件前保存快照，供 `gid undo` 还原
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Journal {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<JournalEntry>,
+}
+
+/// 一次 gid 命令造成的修改
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// 执行时间（RFC 3339）
+    pub time: String,
+
+    /// 执行的命令
+    pub command: String,
+
+    /// 会话 ID
+    pub session: String,
+
+    /// 修改前的文件快照
+    #[serde(default)]
+    pub files: Vec<FileSnapshot>,
+}
+
+/// 文件修改前的内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileSnapshot {
+    pub path: PathBuf,
+
+    /// 原内容（None 表示文件原本不存在）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+
+    /// 是否可执行（如 hook 脚本）
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub executable: bool,
+
+    /// 命令结束时的内容摘要，撤销前据此发现之后的手动修改
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+impl FileSnapshot {
+    /// 读取文件当前状态
+    pub fn capture(path: &Path) -> Result<Self> {
+        let content = if path.exists() {
+            Some(
+                fs::read_to_string(path)
+                    .with_context(|| tr!("file-read-failed", path = path.display()))?,
+            )
+        } else {
+            None
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            content,
+            executable: is_executable(path),
+            after: None,
+        })
+    }
+
+    /// 文件内容是否与快照一致
+    pub fn is_current(&self) -> bool {
+        let current = fs::read_to_string(&self.path).ok();
+        current == self.content
+    }
+
+    /// 命令结束后文件是否又被修改过（旧版本的记录没有摘要，视为未修改）
+    pub fn changed_since(&self) -> bool {
+        self.after
+            .as_ref()
+            .is_some_and(|after| *after != digest(&self.path))
+    }
+
+    /// 将文件还原为快照内容
+    pub fn restore(&self) -> Result<()> {
+        let Some(content) = &self.content else {
+            if self.path.exists() {
+                fs::remove_file(&self.path)
+                    .with_context(|| tr!("file-delete-failed", path = self.path.display()))?;
+            }
+            return Ok(());
+        };
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| tr!("dir-create-failed", path = parent.display()))?;
+        }
+        atomic::write(&self.path, content, |_| Ok(()))
+            .with_context(|| tr!("file-write-failed", path = self.path.display()))?;
+
+        #[cfg(unix)]
+        if self.executable {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&self.path, fs::Permissions::from_mode(0o755))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Journal {
+    /// 获取日志文件路径
+    pub fn path() -> Result<PathBuf> {
+        Ok(Config::data_dir()?.join("journal.toml"))
+    }
+
+    /// 加载日志
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| tr!("journal-read-failed", path = path.display()))?;
+
+        toml::from_str(&content).with_context(|| tr!("journal-invalid"))
+    }
+
+    /// 保存日志
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        Self::ensure_dir(&path)?;
+
+        let _lock = FileLock::acquire(&path)?;
+        self.write_locked(&path)
+    }
+
+    /// 加锁后重新加载、修改并保存日志，其他 gid 进程同时记录的操作不会丢失
+    ///
+    /// `change` 返回错误时不保存
+    pub fn update<T>(change: impl FnOnce(&mut Journal) -> Result<T>) -> Result<T> {
+        let path = Self::path()?;
+        Self::ensure_dir(&path)?;
+
+        let _lock = FileLock::acquire(&path)?;
+        let mut journal = Self::load()?;
+        let result = change(&mut journal)?;
+        journal.write_locked(&path)?;
+
+        Ok(result)
+    }
+
+    /// 确保数据目录存在（新建时仅当前用户可访问）
+    fn ensure_dir(path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            atomic::create_private_dir(parent)
+                .with_context(|| tr!("data-dir-create-failed", path = parent.display()))?;
+        }
+        Ok(())
+    }
+
+    /// 写入日志文件（快照里有 ~/.gitconfig、~/.ssh/config 等内容，只允许本人读取；调用方需持有锁）
+    fn write_locked(&self, path: &Path) -> Result<()> {
+        let content =
+            toml::to_string_pretty(self).with_context(|| tr!("journal-serialize-failed"))?;
+
+        atomic::write_private(path, &content, |written| {
+            toml::from_str::<Journal>(written)
+                .map(|_| ())
+                .with_context(|| tr!("journal-invalid"))
+        })
+        .with_context(|| tr!("journal-write-failed", path = path.display()))?;
+
+        Ok(())
+    }
+}
+
+/// 在修改文件前记录其快照（失败不影响原操作）
+pub fn record(path: &Path) {
+    if SUSPENDED.load(Ordering::Relaxed) {
+        return;
+    }
+    log::info!("modifying {}", path.display());
+    if let Err(e) = try_record(path) {
+        log::debug!("journal not updated: {e:#}");
+    }
+}
+
+/// 结束当前会话：记下本次修改过的文件的最终内容摘要，之后的修改记为新的操作
+///
+/// 命令结束时调用；watch、serve 等常驻命令每处理完一个仓库或请求调用一次
+pub fn seal() {
+    let Some(session) = SESSION.with_borrow_mut(Option::take) else {
+        return;
+    };
+    if let Err(e) = try_seal(&session) {
+        log::debug!("journal not sealed: {e:#}");
+    }
+}
+
+fn try_seal(session: &str) -> Result<()> {
+    Journal::update(|journal| {
+        if let Some(entry) = journal.entries.iter_mut().rfind(|e| e.session == session) {
+            for file in &mut entry.files {
+                file.after = Some(digest(&file.path));
+            }
+        }
+        Ok(())
+    })
+}
+
+/// 文件内容的摘要（FNV-1a），文件不存在时为 "absent"
+fn digest(path: &Path) -> String {
+    let Ok(content) = fs::read(path) else {
+        return "absent".to_string();
+    };
+    let hash = content.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
+
+/// 暂停本进程的日志记录
+pub fn suspend() {
+    SUSPENDED.store(true, Ordering::Relaxed);
+}
+
+fn try_record(path: &Path) -> Result<()> {
+    let session = SESSION.with_borrow_mut(|session| {
+        session
+            .get_or_insert_with(|| {
+                format!(
+                    "{}-{}",
+                    std::process::id(),
+                    Local::now().timestamp_nanos_opt().unwrap_or_default()
+                )
+            })
+            .clone()
+    });
+
+    Journal::update(|journal| {
+        // serve 的多个连接各有会话，本会话的操作不一定是最后一条
+        if !journal.entries.iter().any(|e| e.session == session) {
+            let args: Vec<String> = std::env::args().skip(1).collect();
+            journal.entries.push(JournalEntry {
+                time: Local::now().to_rfc3339(),
+                command: format!("gid {}", args.join(" ")),
+                session: session.clone(),
+                files: Vec::new(),
+            });
+        }
+
+        let Some(entry) = journal.entries.iter_mut().rfind(|e| e.session == session) else {
+            return Ok(());
+        };
+        // 只保留命令执行前的第一份快照
+        if entry.files.iter().any(|f| f.path == path) {
+            return Ok(());
+        }
+        entry.files.push(FileSnapshot::capture(path)?);
+
+        let excess = journal.entries.len().saturating_sub(MAX_ENTRIES);
+        journal.entries.drain(..excess);
+        Ok(())
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    false
+}
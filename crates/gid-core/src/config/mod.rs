@@ -0,0 +1,288 @@
+pub mod credentials;
+pub mod identity;
+pub mod journal;
+pub mod project;
+pub mod ruleset;
+pub mod settings;
+pub mod state;
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+pub use credentials::{Credential, Credentials};
+pub use identity::Identity;
+pub use project::ProjectConfig;
+pub use ruleset::RulesetSubscription;
+pub use settings::Settings;
+pub use state::State;
+
+use crate::atomic::{self, FileLock};
+use crate::exit::Failure;
+use crate::i18n::tr;
+use crate::rules::Rule;
+
+/// --config 指定的配置文件，只对当前进程生效
+static CONFIG_FILE: OnceLock<PathBuf> = OnceLock::new();
+
+/// 本次运行改用指定的配置文件（相对路径按当前目录解析）
+pub fn use_config_file(path: &std::path::Path) -> Result<()> {
+    let path = std::path::absolute(path).with_context(|| {
+        Failure::Config.context(tr!("config-path-invalid", path = path.display()))
+    })?;
+    let _ = CONFIG_FILE.set(path);
+    Ok(())
+}
+
+/// TOML 没有 null：可选字段只能省略，不能写成 null
+fn drop_null_type(schema: &mut schemars::Schema) {
+    if let Some(types) = schema.get_mut("type").and_then(|t| t.as_array_mut()) {
+        types.retain(|t| t != "null");
+        if types.len() == 1 {
+            let single = types.remove(0);
+            schema.insert("type".to_string(), single);
+        }
+    }
+    schemars::transform::transform_subschemas(&mut drop_null_type, schema);
+}
+
+/// 主配置结构
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+pub struct Config {
+    /// 身份列表
+    #[serde(default)]
+    pub identities: Vec<Identity>,
+
+    /// 规则列表
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+
+    /// 订阅的规则集
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rulesets: Vec<RulesetSubscription>,
+
+    /// 设置
+    #[serde(default)]
+    pub settings: Settings,
+}
+
+impl Config {
+    /// 获取配置文件路径（--config 优先，其次 GID_CONFIG_DIR）
+    pub fn config_path() -> Result<PathBuf> {
+        if let Some(path) = CONFIG_FILE.get() {
+            return Ok(path.clone());
+        }
+
+        let config_dir = if let Ok(path) = std::env::var("GID_CONFIG_DIR") {
+            PathBuf::from(path)
+        } else if let Some(config_dir) = directories::ProjectDirs::from("com", "gid", "gid") {
+            config_dir.config_dir().to_path_buf()
+        } else {
+            let home = home::home_dir().with_context(|| tr!("home-dir-unknown"))?;
+            home.join(".config").join("gid")
+        };
+
+        Ok(config_dir.join("config.toml"))
+    }
+
+    /// 获取缓存目录（设置 GID_CONFIG_DIR 时位于其下的 cache 目录）
+    pub fn cache_dir() -> Result<PathBuf> {
+        if let Ok(path) = std::env::var("GID_CONFIG_DIR") {
+            return Ok(PathBuf::from(path).join("cache"));
+        }
+
+        if let Some(dirs) = directories::ProjectDirs::from("com", "gid", "gid") {
+            return Ok(dirs.cache_dir().to_path_buf());
+        }
+
+        let home = home::home_dir().with_context(|| tr!("home-dir-unknown"))?;
+        Ok(home.join(".cache").join("gid"))
+    }
+
+    /// 获取数据目录（运行状态等，设置 GID_CONFIG_DIR 时与配置目录相同）
+    pub fn data_dir() -> Result<PathBuf> {
+        if let Ok(path) = std::env::var("GID_CONFIG_DIR") {
+            return Ok(PathBuf::from(path));
+        }
+
+        if let Some(dirs) = directories::ProjectDirs::from("com", "gid", "gid") {
+            return Ok(dirs.data_dir().to_path_buf());
+        }
+
+        let home = home::home_dir().with_context(|| tr!("home-dir-unknown"))?;
+        Ok(home.join(".local").join("share").join("gid"))
+    }
+
+    /// 获取身份 Git 配置片段路径（供 includeIf 引用）
+    pub fn fragment_path(identity_id: &str) -> Result<PathBuf> {
+        let config_path = Self::config_path()?;
+        let config_dir = config_path
+            .parent()
+            .ok_or_else(|| anyhow::anyhow!(tr!("config-dir-unknown")))?;
+        Ok(config_dir
+            .join("identities")
+            .join(format!("{identity_id}.gitconfig")))
+    }
+
+    /// 获取身份提交信息模板路径（由 gid template 管理）
+    pub fn template_path(identity_id: &str) -> Result<PathBuf> {
+        let config_path = Self::config_path()?;
+        let config_dir = config_path
+            .parent()
+            .ok_or_else(|| anyhow::anyhow!(tr!("config-dir-unknown")))?;
+        Ok(config_dir
+            .join("templates")
+            .join(format!("{identity_id}.txt")))
+    }
+
+    /// 加载配置
+    pub fn load() -> Result<Self> {
+        let config_path = Self::config_path()?;
+
+        if !config_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&config_path).with_context(|| {
+            Failure::Config.context(tr!("config-read-failed", path = config_path.display()))
+        })?;
+
+        let mut config: Config = toml::from_str(&content)
+            .with_context(|| Failure::Config.context(tr!("config-invalid")))?;
+        crate::ssh::validate_alias_template(&config.settings.ssh_alias_template)
+            .with_context(|| Failure::Config.context(tr!("config-invalid")))?;
+        config.assign_rule_ids();
+
+        Ok(config)
+    }
+
+    /// config.toml 的 JSON Schema（draft-07，taplo 等编辑器插件支持），由上面的类型生成
+    pub fn json_schema() -> schemars::Schema {
+        let mut settings = schemars::generate::SchemaSettings::draft07();
+        settings.transforms.push(Box::new(drop_null_type));
+        settings.into_generator().into_root_schema_for::<Config>()
+    }
+
+    /// 保存配置
+    pub fn save(&self) -> Result<()> {
+        let config_path = Self::config_path()?;
+        Self::ensure_dir(&config_path)?;
+
+        let _lock = FileLock::acquire(&config_path)?;
+        self.write_locked(&config_path)
+    }
+
+    /// 加锁后重新加载、修改并保存配置，其他 gid 进程在此期间的修改不会丢失
+    ///
+    /// `change` 返回错误时不保存
+    pub fn update<T>(change: impl FnOnce(&mut Config) -> Result<T>) -> Result<T> {
+        let config_path = Self::config_path()?;
+        Self::ensure_dir(&config_path)?;
+
+        let _lock = FileLock::acquire(&config_path)?;
+        let mut config = Self::load()?;
+        let result = change(&mut config)?;
+        config.write_locked(&config_path)?;
+
+        Ok(result)
+    }
+
+    /// 确保配置目录存在
+    fn ensure_dir(config_path: &std::path::Path) -> Result<()> {
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| tr!("config-dir-create-failed", path = parent.display()))?;
+        }
+        Ok(())
+    }
+
+    /// 写入临时文件，能重新解析才替换原文件（调用方需持有锁）
+    fn write_locked(&self, config_path: &std::path::Path) -> Result<()> {
+        let content =
+            toml::to_string_pretty(self).with_context(|| tr!("config-serialize-failed"))?;
+
+        journal::record(config_path);
+        atomic::write(config_path, &content, |written| {
+            toml::from_str::<Config>(written)
+                .map(|_| ())
+                .with_context(|| tr!("config-roundtrip-failed", path = config_path.display()))
+        })
+        .with_context(|| tr!("config-write-failed", path = config_path.display()))?;
+
+        Ok(())
+    }
+
+    /// 查找身份
+    pub fn find_identity(&self, id: &str) -> Option<&Identity> {
+        self.identities.iter().find(|i| i.id == id)
+    }
+
+    /// 添加身份
+    pub fn add_identity(&mut self, identity: Identity) -> Result<()> {
+        if self.find_identity(&identity.id).is_some() {
+            anyhow::bail!(tr!("identity-exists", id = identity.id));
+        }
+        self.identities.push(identity);
+        Ok(())
+    }
+
+    /// 删除身份
+    pub fn remove_identity(&mut self, id: &str) -> Result<Identity> {
+        let index = self
+            .identities
+            .iter()
+            .position(|i| i.id == id)
+            .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = id)))?;
+
+        Ok(self.identities.remove(index))
+    }
+
+    /// 添加规则
+    pub fn add_rule(&mut self, rule: Rule) {
+        self.rules.push(rule);
+        self.assign_rule_ids();
+        self.sort_rules();
+    }
+
+    /// 按优先级排序（稳定排序，优先级相同的保持原有顺序）
+    pub fn sort_rules(&mut self) {
+        self.rules.sort_by_key(|r| r.priority);
+    }
+
+    /// 按 ID 查找规则的位置
+    pub fn rule_index(&self, id: &str) -> Result<usize> {
+        self.rules
+            .iter()
+            .position(|r| r.id == id)
+            .ok_or_else(|| anyhow::anyhow!(tr!("rule-not-found", id = id)))
+    }
+
+    /// 为没有 ID 或 ID 重复的规则生成 ID
+    ///
+    /// 生成的 ID 只取决于规则内容，保存之前多次加载得到的 ID 也相同
+    fn assign_rule_ids(&mut self) {
+        let mut seen: Vec<String> = Vec::new();
+        for index in 0..self.rules.len() {
+            let rule = &self.rules[index];
+            if rule.id.is_empty() || seen.contains(&rule.id) {
+                let id = (0..)
+                    .map(|salt| rule.content_id(salt))
+                    .find(|id| !seen.contains(id) && !self.rules.iter().any(|r| &r.id == id))
+                    .unwrap_or_default();
+                self.rules[index].id = id;
+            }
+            seen.push(self.rules[index].id.clone());
+        }
+    }
+
+    /// 删除规则
+    pub fn remove_rule(&mut self, index: usize) -> Result<Rule> {
+        if index >= self.rules.len() {
+            anyhow::bail!(tr!("rule-index-out-of-range", index = index));
+        }
+        Ok(self.rules.remove(index))
+    }
+}
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::i18n::tr;
 use crate::rules::Rule;
 
 /// Project configuration (.gid file)
@@ -26,13 +27,12 @@ impl ProjectConfig {
         }
 
         let content = fs::read_to_string(&gid_path)
-            .with_context(|| format!("Could not read .gid file: {}", gid_path.display()))?;
+            .with_context(|| tr!("project-read-failed", path = gid_path.display()))?;
 
         Self::parse(&content)
     }
 
     /// Find .gid file in parents starting from current directory
-    #[allow(dead_code)]
     pub fn find_in_parents(start: &Path) -> Result<Option<(Self, PathBuf)>> {
         let mut current = start.to_path_buf();
 
@@ -63,7 +63,7 @@ impl ProjectConfig {
             match toml::from_str::<ProjectConfig>(trimmed) {
                 Ok(config) => return Ok(Some(config)),
                 Err(e) => {
-                    anyhow::bail!(".gid file format error: {e}");
+                    anyhow::bail!(tr!("project-invalid", error = e));
                 }
             }
         }
@@ -80,7 +80,7 @@ impl ProjectConfig {
             .chars()
             .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
         {
-            anyhow::bail!("Invalid identity ID format in .gid file: {identity}");
+            anyhow::bail!(tr!("project-identity-invalid", id = identity));
         }
 
         Ok(Some(ProjectConfig {
@@ -90,19 +90,25 @@ impl ProjectConfig {
     }
 
     /// Save to specified directory
-    #[allow(dead_code)]
     pub fn save_to_dir(&self, path: &Path) -> Result<()> {
+        self.write_to_dir(path, !self.rules.is_empty())
+    }
+
+    /// Save to specified directory, in TOML format even without rules if requested
+    pub fn write_to_dir(&self, path: &Path, as_toml: bool) -> Result<()> {
         let gid_path = path.join(".gid");
+        super::journal::record(&gid_path);
 
-        // If no rules, use simple format
-        if self.rules.is_empty() {
+        // Simple format: single line identity ID
+        if !as_toml {
             fs::write(&gid_path, format!("{}\n", self.identity))
-                .with_context(|| format!("Could not write .gid file: {}", gid_path.display()))?;
+                .with_context(|| tr!("project-write-failed", path = gid_path.display()))?;
         } else {
             // Use TOML format
-            let content = toml::to_string_pretty(self).context("Could not serialize config")?;
+            let content =
+                toml::to_string_pretty(self).with_context(|| tr!("config-serialize-failed"))?;
             fs::write(&gid_path, content)
-                .with_context(|| format!("Could not write .gid file: {}", gid_path.display()))?;
+                .with_context(|| tr!("project-write-failed", path = gid_path.display()))?;
         }
 
         Ok(())
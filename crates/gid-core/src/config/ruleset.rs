@@ -0,0 +1,16 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// 订阅的团队规则集
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct RulesetSubscription {
+    /// 规则集名称（规则的 source 字段）
+    pub name: String,
+
+    /// 规则集地址（http(s)、file:// 或本地路径）
+    pub url: String,
+
+    /// 最近一次更新时间（RFC 3339）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
@@ -0,0 +1,122 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::rules::MatchStrategy;
+
+/// 全局设置
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Settings {
+    /// 是否在切换时显示详细信息
+    #[serde(default = "default_true")]
+    pub verbose: bool,
+
+    /// 是否启用颜色输出
+    #[serde(default = "default_true")]
+    pub color: bool,
+
+    /// 只输出 ASCII 字符（不用 ✓、⚠、→、表情和制表符）
+    #[serde(default)]
+    pub ascii: bool,
+
+    /// 界面语言（en、zh），未设置时按 LC_ALL / LC_MESSAGES / LANG 选择
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// 自动模式：进入目录时是否自动切换身份
+    #[serde(default)]
+    pub auto_switch: bool,
+
+    /// 是否在提交前检查身份
+    #[serde(default = "default_true")]
+    pub pre_commit_check: bool,
+
+    /// 身份不匹配时是否阻止提交
+    #[serde(default)]
+    pub strict_mode: bool,
+
+    /// 默认使用的编辑器
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+
+    /// 全局 hooks 目录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks_path: Option<String>,
+
+    /// 全局切换前是否需要确认
+    #[serde(default = "default_true")]
+    pub confirm_global_switch: bool,
+
+    /// SSH Host 别名模板，支持 {host}、{host_slug}（点替换为连字符）和 {identity}；
+    /// 必须包含 {identity} 以及 {host} 或 {host_slug}，不能有空白、`*` 和 `?`
+    #[serde(default = "default_ssh_alias_template")]
+    pub ssh_alias_template: String,
+
+    /// 没有 .gid 和规则匹配时 auto 使用的默认身份
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_identity: Option<String>,
+
+    /// 工作区根目录（watch 等扫描命令的默认路径）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workspace_roots: Vec<String>,
+
+    /// 切换成功后执行的命令（可被身份配置覆盖）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_switch_command: Option<String>,
+
+    /// 离线模式：不访问网络（forge 检查、gh API、规则集下载、clone）
+    #[serde(default)]
+    pub offline: bool,
+
+    /// 多条规则匹配时的选择方式：priority（默认）或 most-specific
+    #[serde(default)]
+    pub match_strategy: MatchStrategy,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            verbose: true,
+            color: true,
+            ascii: false,
+            language: None,
+            auto_switch: false,
+            pre_commit_check: true,
+            strict_mode: false,
+            editor: None,
+            hooks_path: None,
+            confirm_global_switch: true,
+            ssh_alias_template: default_ssh_alias_template(),
+            default_identity: None,
+            workspace_roots: Vec::new(),
+            post_switch_command: None,
+            offline: false,
+            match_strategy: MatchStrategy::default(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Settings {
+    /// 展开后的工作区根目录
+    pub fn workspace_root_paths(&self) -> Vec<PathBuf> {
+        self.workspace_roots
+            .iter()
+            .map(|root| {
+                if let Some(stripped) = root.strip_prefix("~/") {
+                    if let Some(home) = home::home_dir() {
+                        return home.join(stripped);
+                    }
+                }
+                PathBuf::from(root)
+            })
+            .collect()
+    }
+}
+
+fn default_ssh_alias_template() -> String {
+    crate::ssh::DEFAULT_ALIAS_TEMPLATE.to_string()
+}
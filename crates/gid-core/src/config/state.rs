@@ -0,0 +1,214 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::Config;
+use crate::atomic::{self, FileLock};
+use crate::i18n::tr;
+
+/// 运行状态（与用户配置分开保存）
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct State {
+    /// 没有匹配任何规则的仓库（路径 -> 仓库信息）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub unmatched: BTreeMap<String, UnmatchedRepo>,
+
+    /// 身份使用记录（身份 ID -> 使用统计）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub usage: BTreeMap<String, Usage>,
+
+    /// 身份切换历史（按时间先后）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub history: Vec<SwitchRecord>,
+
+    /// sync-gitconfig 写入全局配置的 includeIf gitdir（按写入顺序）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub synced_gitdirs: Vec<String>,
+}
+
+/// 最多保留的切换历史条数
+const MAX_HISTORY: usize = 1000;
+
+/// 一次身份切换
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwitchRecord {
+    /// 切换时间（RFC 3339）
+    pub time: String,
+
+    /// 仓库路径（全局切换时为空）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+
+    /// 切换前的身份（无法对应到已配置身份时为邮箱）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+
+    /// 切换后的身份 ID
+    pub to: String,
+
+    /// 作用范围：project / global / include-if
+    pub scope: String,
+}
+
+impl SwitchRecord {
+    /// 解析切换时间
+    pub fn time_at(&self) -> Option<DateTime<FixedOffset>> {
+        DateTime::parse_from_rfc3339(&self.time).ok()
+    }
+}
+
+/// 身份使用统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Usage {
+    /// 最近一次切换时间（RFC 3339）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<String>,
+
+    /// 切换次数
+    #[serde(default)]
+    pub count: u64,
+}
+
+impl Usage {
+    /// 解析最近使用时间
+    pub fn last_used_at(&self) -> Option<DateTime<FixedOffset>> {
+        self.last_used
+            .as_deref()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+    }
+}
+
+/// 未匹配规则的仓库
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnmatchedRepo {
+    /// origin 地址
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<String>,
+
+    /// 仓库当前使用的身份 ID（能对应到已配置身份时）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<String>,
+}
+
+impl State {
+    /// 获取状态文件路径
+    pub fn path() -> Result<PathBuf> {
+        Ok(Config::data_dir()?.join("state.toml"))
+    }
+
+    /// 加载状态
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| tr!("state-read-failed", path = path.display()))?;
+
+        toml::from_str(&content).with_context(|| tr!("state-invalid"))
+    }
+
+    /// 保存状态
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        Self::ensure_dir(&path)?;
+
+        let _lock = FileLock::acquire(&path)?;
+        self.write_locked(&path)
+    }
+
+    /// 加锁后重新加载、修改并保存状态，并发的 shell 钩子、watch、serve 的记录不会丢失
+    ///
+    /// `change` 返回错误或没有改动时不写入
+    pub fn update<T>(change: impl FnOnce(&mut State) -> Result<T>) -> Result<T> {
+        let path = Self::path()?;
+        Self::ensure_dir(&path)?;
+
+        let _lock = FileLock::acquire(&path)?;
+        let mut state = Self::load()?;
+        let before =
+            toml::to_string_pretty(&state).with_context(|| tr!("state-serialize-failed"))?;
+        let result = change(&mut state)?;
+        let after =
+            toml::to_string_pretty(&state).with_context(|| tr!("state-serialize-failed"))?;
+        if after != before {
+            state.write_locked(&path)?;
+        }
+
+        Ok(result)
+    }
+
+    /// 确保数据目录存在
+    fn ensure_dir(path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| tr!("data-dir-create-failed", path = parent.display()))?;
+        }
+        Ok(())
+    }
+
+    /// 写入状态文件（调用方需持有锁）
+    fn write_locked(&self, path: &Path) -> Result<()> {
+        let content =
+            toml::to_string_pretty(self).with_context(|| tr!("state-serialize-failed"))?;
+
+        atomic::write(path, &content, |written| {
+            toml::from_str::<State>(written)
+                .map(|_| ())
+                .with_context(|| tr!("state-invalid"))
+        })
+        .with_context(|| tr!("state-write-failed", path = path.display()))?;
+
+        Ok(())
+    }
+
+    /// 记录未匹配的仓库，返回是否有变化
+    pub fn record_unmatched(&mut self, path: &Path, repo: UnmatchedRepo) -> bool {
+        let key = path.to_string_lossy().to_string();
+        if self.unmatched.get(&key) == Some(&repo) {
+            return false;
+        }
+        self.unmatched.insert(key, repo);
+        true
+    }
+
+    /// 记录一次身份切换
+    pub fn record_usage(&mut self, identity_id: &str) {
+        let usage = self.usage.entry(identity_id.to_string()).or_default();
+        usage.last_used = Some(Local::now().to_rfc3339());
+        usage.count += 1;
+    }
+
+    /// 追加一条切换历史
+    pub fn record_switch(
+        &mut self,
+        repo: Option<&Path>,
+        from: Option<String>,
+        to: &str,
+        scope: &str,
+    ) {
+        self.history.push(SwitchRecord {
+            time: Local::now().to_rfc3339(),
+            repo: repo.map(|p| p.to_string_lossy().to_string()),
+            from,
+            to: to.to_string(),
+            scope: scope.to_string(),
+        });
+        if self.history.len() > MAX_HISTORY {
+            let excess = self.history.len() - MAX_HISTORY;
+            self.history.drain(..excess);
+        }
+    }
+
+    /// 移除未匹配记录，返回是否有变化
+    pub fn forget_unmatched(&mut self, path: &Path) -> bool {
+        self.unmatched
+            .remove(path.to_string_lossy().as_ref())
+            .is_some()
+    }
+}
@@ -0,0 +1,91 @@
+//! Exit codes, one per class of failure
+//!
+//! Hooks and CI scripts branch on these, so the numbers are part of the
+//! interface; they are listed in the README.
+//!
+//! | Code | Failure                                              |
+//! |------|------------------------------------------------------|
+//! | 0    | Success                                              |
+//! | 1    | Anything not listed below                            |
+//! | 2    | The config file is unreadable or invalid             |
+//! | 3    | The effective identity differs from the expected one |
+//! | 4    | Not inside a Git repository                          |
+//! | 5    | A forge or ruleset URL is unreachable, or offline    |
+//! | 64   | Invalid command line                                 |
+
+use std::fmt;
+
+/// Class of failure, deciding the exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    General = 1,
+    Config = 2,
+    Mismatch = 3,
+    NotARepo = 4,
+    Network = 5,
+    Usage = 64,
+}
+
+impl Failure {
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// Error with `message` that exits with this code
+    pub fn error(self, message: impl fmt::Display) -> anyhow::Error {
+        anyhow::Error::new(self.context(message))
+    }
+
+    /// Context for `.with_context()` that makes the whole error exit with this code
+    pub fn context(self, message: impl fmt::Display) -> Classified {
+        Classified {
+            failure: self,
+            message: message.to_string(),
+        }
+    }
+
+    /// Class of an error: the outermost one tagged anywhere in its context chain
+    pub fn of(error: &anyhow::Error) -> Self {
+        error
+            .downcast_ref::<Classified>()
+            .map_or(Self::General, |c| c.failure)
+    }
+}
+
+/// Error message tagged with a failure class
+#[derive(Debug)]
+pub struct Classified {
+    failure: Failure,
+    message: String,
+}
+
+impl fmt::Display for Classified {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Classified {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_class_survives_context() {
+        let error = Failure::NotARepo.error("not a repo");
+        assert_eq!(Failure::of(&error), Failure::NotARepo);
+        let error = error.context("Could not switch");
+        assert_eq!(Failure::of(&error), Failure::NotARepo);
+        assert_eq!(format!("{error:#}"), "Could not switch: not a repo");
+
+        let error: anyhow::Result<()> = Err(anyhow::anyhow!("bad toml"));
+        let error = error
+            .with_context(|| Failure::Config.context("Invalid config"))
+            .unwrap_err();
+        assert_eq!(Failure::of(&error), Failure::Config);
+
+        assert_eq!(Failure::of(&anyhow::anyhow!("other")), Failure::General);
+    }
+}
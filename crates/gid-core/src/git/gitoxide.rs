@@ -0,0 +1,588 @@
+//! Repository access through gitoxide, the default backend
+//!
+//! gitoxide is pure Rust, so gid links no libgit2, OpenSSL or zlib from the
+//! system and builds fully static. Config files are parsed and edited with
+//! gix-config, which keeps comments and formatting of the lines it does not
+//! touch. Only the dirty check shells out to `git status`, as gix's status
+//! support pulls in more than gid needs.
+
+use anyhow::{Context, Result};
+use gix::bstr::{BStr, ByteSlice};
+use gix::config::{File as ConfigFile, Source};
+use gix::refs::transaction::{Change, LogChange, PreviousValue, RefEdit};
+use gix::revision::walk::Sorting;
+use gix::traverse::commit::simple::CommitTimeOrder;
+use std::path::{Path, PathBuf};
+
+use super::{CommitInfo, CommitParts, CommitSigner, ConfigEntry, ConfigLevel, Person, ValueOrigin};
+use crate::atomic::{self, FileLock};
+use crate::i18n::tr;
+
+/// An open repository
+pub struct Repo {
+    repo: gix::Repository,
+    /// Absolute, as the GIT_* variables and discovery may hand out relative paths
+    git_dir: PathBuf,
+    workdir: Option<PathBuf>,
+}
+
+impl Repo {
+    /// Repository named by `GIT_DIR`, otherwise the one containing the current directory
+    pub fn from_env() -> Option<Self> {
+        let dir = std::env::current_dir().ok()?;
+        gix::ThreadSafeRepository::discover_with_environment_overrides_opts(
+            dir,
+            discover_options(),
+            Default::default(),
+        )
+        .ok()
+        .map(|repo| Self::wrap(repo.into()))
+    }
+
+    /// Repository containing `path`, stopping at `GIT_CEILING_DIRECTORIES`
+    pub fn discover(path: &Path) -> Option<Self> {
+        gix::ThreadSafeRepository::discover_opts(
+            std::path::absolute(path).ok()?,
+            discover_options().apply_environment(),
+            Default::default(),
+        )
+        .ok()
+        .map(|repo| Self::wrap(repo.into()))
+    }
+
+    /// Repository at `path` (its working tree or Git directory), without searching upwards
+    pub fn open(path: &Path) -> Result<Self> {
+        Ok(Self::wrap(gix::open(std::path::absolute(path)?)?))
+    }
+
+    fn wrap(mut repo: gix::Repository) -> Self {
+        // Every commit a walk yields is decoded right after
+        repo.object_cache_size_if_unset(4 * 1024 * 1024);
+        let absolute = |path: &Path| std::path::absolute(path).unwrap_or_else(|_| path.into());
+        Self {
+            git_dir: absolute(repo.git_dir()),
+            workdir: repo.workdir().map(absolute),
+            repo,
+        }
+    }
+
+    pub fn git_dir(&self) -> &Path {
+        &self.git_dir
+    }
+
+    pub fn workdir(&self) -> Option<&Path> {
+        self.workdir.as_deref()
+    }
+
+    pub fn is_bare(&self) -> bool {
+        self.repo.is_bare()
+    }
+
+    pub fn is_shallow(&self) -> bool {
+        self.repo.is_shallow()
+    }
+
+    pub fn operation_in_progress(&self) -> Option<&'static str> {
+        use gix::state::InProgress as S;
+
+        match self.repo.state()? {
+            S::Rebase | S::RebaseInteractive => Some("rebase"),
+            S::Merge => Some("merge"),
+            S::CherryPick | S::CherryPickSequence => Some("cherry-pick"),
+            S::Revert | S::RevertSequence => Some("revert"),
+            S::ApplyMailbox | S::ApplyMailboxRebase => Some("am"),
+            S::Bisect => None,
+        }
+    }
+
+    /// Every entry of the system, XDG, global and repository config, includes followed
+    ///
+    /// Read from disk on each call, so values gid has just written are seen.
+    pub fn config_entries(
+        &self,
+        level_file: &dyn Fn(ConfigLevel) -> Option<PathBuf>,
+    ) -> Vec<ConfigEntry> {
+        let branch = self.repo.head_name().ok().flatten();
+        let conditional = gix::config::file::includes::conditional::Context {
+            git_dir: Some(self.git_dir()),
+            branch_name: branch.as_ref().map(|name| name.as_ref()),
+        };
+        config_entries(
+            &[
+                ConfigLevel::System,
+                ConfigLevel::Xdg,
+                ConfigLevel::Global,
+                ConfigLevel::Local,
+            ],
+            level_file,
+            conditional,
+        )
+    }
+
+    /// Full name of the ref HEAD points at, also before the first commit
+    pub fn head_ref(&self) -> Option<String> {
+        Some(self.repo.head_name().ok()??.as_bstr().to_string())
+    }
+
+    pub fn find_ref(&self, name: &str) -> Option<String> {
+        let id = self
+            .repo
+            .try_find_reference(name)
+            .ok()??
+            .peel_to_id()
+            .ok()?;
+        Some(id.object().ok()?.peel_to_commit().ok()?.id.to_string())
+    }
+
+    pub fn resolve_commit(&self, rev: &str) -> Result<String> {
+        let object = self.repo.rev_parse_single(rev)?.object()?;
+        Ok(object.peel_to_commit()?.id.to_string())
+    }
+
+    pub fn resolve_range(&self, range: &str) -> Result<Option<(String, String)>> {
+        use gix::revision::plumbing::Spec;
+
+        let (from, to) = match self.repo.rev_parse(range)?.detach() {
+            Spec::Range { from, to } => (from, to),
+            Spec::Merge { theirs, ours } => (theirs, ours),
+            _ => return Ok(None),
+        };
+        let peel = |id: gix::ObjectId| -> Result<String> {
+            Ok(self.repo.find_object(id)?.peel_to_commit()?.id.to_string())
+        };
+        Ok(Some((peel(from)?, peel(to)?)))
+    }
+
+    pub fn rev_list(&self, tip: &str, hide: Option<&str>) -> Result<Vec<String>> {
+        use gix::traverse::commit::topo;
+
+        let tip = gix::ObjectId::from_hex(tip.as_bytes())?;
+        let hide = hide
+            .map(|id| gix::ObjectId::from_hex(id.as_bytes()))
+            .transpose()?;
+        let walk = topo::Builder::from_iters(&self.repo.objects, [tip], hide.map(|id| [id]))
+            .sorting(topo::Sorting::TopoOrder)
+            .build()?;
+        walk.map(|info| Ok(info?.id.to_string())).collect()
+    }
+
+    pub fn read_commit(&self, id: &str) -> Result<CommitParts> {
+        let commit = self
+            .repo
+            .find_commit(gix::ObjectId::from_hex(id.as_bytes())?)?;
+        let commit = commit.decode()?;
+        Ok(CommitParts {
+            tree: commit.tree().to_string(),
+            parents: commit.parents().map(|id| id.to_string()).collect(),
+            author: person(commit.author())?,
+            committer: person(commit.committer())?,
+            message: commit.message.to_str_lossy().into_owned(),
+        })
+    }
+
+    pub fn write_commit(
+        &self,
+        commit: &CommitParts,
+        sign: Option<&CommitSigner>,
+    ) -> Result<String> {
+        let mut object = gix::objs::Commit {
+            tree: gix::ObjectId::from_hex(commit.tree.as_bytes())?,
+            parents: commit
+                .parents
+                .iter()
+                .map(|id| gix::ObjectId::from_hex(id.as_bytes()))
+                .collect::<Result<_, _>>()?,
+            author: signature(&commit.author),
+            committer: signature(&commit.committer),
+            encoding: None,
+            message: commit.message.as_str().into(),
+            extra_headers: Vec::new(),
+        };
+
+        if let Some(sign) = sign {
+            let mut buffer = Vec::new();
+            gix::objs::WriteTo::write_to(&object, &mut buffer)?;
+            let signature = sign(&buffer)?;
+            object
+                .extra_headers
+                .push(("gpgsig".into(), signature.trim_end().into()));
+        }
+
+        Ok(self.repo.write_object(&object)?.to_string())
+    }
+
+    pub fn set_ref(&self, name: &str, id: &str, force: bool, message: &str) -> Result<()> {
+        let expected = if force {
+            PreviousValue::Any
+        } else {
+            PreviousValue::MustNotExist
+        };
+        self.edit_ref(name, id, expected, message)
+    }
+
+    pub fn detach_head(&self, id: &str, message: &str) -> Result<()> {
+        self.edit_ref("HEAD", id, PreviousValue::Any, message)
+    }
+
+    /// Point `name` itself (not what it refers to) at a commit, with a reflog entry
+    fn edit_ref(&self, name: &str, id: &str, expected: PreviousValue, message: &str) -> Result<()> {
+        let edit = RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    message: message.into(),
+                    ..Default::default()
+                },
+                expected,
+                new: gix::refs::Target::Object(gix::ObjectId::from_hex(id.as_bytes())?),
+            },
+            name: name.try_into()?,
+            deref: false,
+        };
+
+        // Git falls back to a placeholder when no identity is configured; so do we
+        let committer = match self.repo.committer() {
+            Some(committer) => committer?.to_owned()?,
+            None => gix::actor::Signature {
+                name: "gid".into(),
+                email: "gid@localhost".into(),
+                time: gix::date::Time::now_local_or_utc(),
+            },
+        };
+        let mut time = gix::date::parse::TimeBuf::default();
+        self.repo
+            .edit_references_as([edit], Some(committer.to_ref(&mut time)))
+            .with_context(|| tr!("git-ref-update-failed", name = name))?;
+        Ok(())
+    }
+
+    /// Whether tracked files differ from HEAD or untracked files exist
+    pub fn is_dirty(&self) -> Result<bool> {
+        let mut command = std::process::Command::new("git");
+        command.arg("--git-dir").arg(self.git_dir());
+        if let Some(workdir) = self.workdir() {
+            command.arg("--work-tree").arg(workdir).current_dir(workdir);
+        }
+        let output = command
+            .args(["status", "--porcelain"])
+            .output()
+            .with_context(|| tr!("git-failed"))?;
+        if !output.status.success() {
+            anyhow::bail!(tr!(
+                "git-command-failed",
+                command = "status",
+                error = String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(!output.stdout.is_empty())
+    }
+
+    /// Content of the blob a revision such as `HEAD:.mailmap` names
+    pub fn blob(&self, spec: &str) -> Option<Vec<u8>> {
+        let object = self.repo.rev_parse_single(spec).ok()?.object().ok()?;
+        Some(
+            object
+                .peel_to_kind(gix::object::Kind::Blob)
+                .ok()?
+                .detach()
+                .data,
+        )
+    }
+
+    /// Armored signature and the data it signs, `None` for unsigned commits
+    pub fn extract_signature(&self, rev: &str) -> Result<Option<(String, Vec<u8>)>> {
+        let commit = self
+            .repo
+            .rev_parse_single(rev)?
+            .object()?
+            .peel_to_commit()?;
+        let signature = gix::objs::CommitRefIter::signature(&commit.data)?;
+        Ok(signature.map(|(signature, signed_data)| {
+            (
+                signature.to_str_lossy().into_owned(),
+                signed_data.to_bstring().into(),
+            )
+        }))
+    }
+
+    /// Stream the history of HEAD, newest first
+    pub fn commits(&self) -> Result<impl Iterator<Item = Result<CommitInfo>> + '_> {
+        let head = self.repo.head_id()?.detach();
+        let walk = self
+            .repo
+            .rev_walk([head])
+            .sorting(Sorting::ByCommitTime(CommitTimeOrder::NewestFirst))
+            .all()?;
+
+        Ok(walk.map(|info| {
+            let commit = info?.object()?;
+            let author = commit.author()?;
+            Ok(CommitInfo {
+                id: commit.id.to_hex_with_len(7).to_string(),
+                oid: commit.id.to_string(),
+                message: commit.message()?.title.to_string(),
+                author_name: author.name.to_string(),
+                author_email: author.email.to_string(),
+            })
+        }))
+    }
+}
+
+/// Discovery as Git does it: ceilings that do not contain the start are ignored
+fn discover_options() -> gix::discover::upwards::Options<'static> {
+    gix::discover::upwards::Options {
+        match_ceiling_dir_or_error: false,
+        ..Default::default()
+    }
+}
+
+fn person(signature: gix::actor::SignatureRef<'_>) -> Result<Person> {
+    let time = signature.time()?;
+    Ok(Person {
+        name: signature.name.to_str_lossy().into_owned(),
+        email: signature.email.to_str_lossy().into_owned(),
+        time: time.seconds,
+        offset: time.offset / 60,
+    })
+}
+
+fn signature(person: &Person) -> gix::actor::Signature {
+    gix::actor::Signature {
+        name: person.name.as_str().into(),
+        email: person.email.as_str().into(),
+        time: gix::date::Time {
+            seconds: person.time,
+            offset: person.offset * 60,
+        },
+    }
+}
+
+/// Entries of the system, XDG and global config, as Git sees them outside a repository
+pub fn default_config_entries(
+    level_file: &dyn Fn(ConfigLevel) -> Option<PathBuf>,
+) -> Vec<ConfigEntry> {
+    config_entries(
+        &[ConfigLevel::System, ConfigLevel::Xdg, ConfigLevel::Global],
+        level_file,
+        Default::default(),
+    )
+}
+
+fn config_entries(
+    levels: &[ConfigLevel],
+    level_file: &dyn Fn(ConfigLevel) -> Option<PathBuf>,
+    conditional: gix::config::file::includes::conditional::Context<'_>,
+) -> Vec<ConfigEntry> {
+    let metas = levels.iter().filter_map(|&level| {
+        let path = level_file(level).filter(|path| path.is_file())?;
+        let source = match level {
+            ConfigLevel::System => Source::System,
+            ConfigLevel::Xdg => Source::Git,
+            ConfigLevel::Global => Source::User,
+            ConfigLevel::Local => Source::Local,
+        };
+        Some(gix::config::file::Metadata::from(source).at(path))
+    });
+
+    let home = gix::path::env::home_dir();
+    let interpolate = gix::config::path::interpolate::Context {
+        home_dir: home.as_deref(),
+        ..Default::default()
+    };
+    let options = gix::config::file::init::Options {
+        includes: gix::config::file::includes::Options::follow(interpolate, conditional),
+        ..Default::default()
+    };
+    let file = match ConfigFile::from_paths_metadata(metas, options) {
+        Ok(file) => file.unwrap_or_default(),
+        Err(e) => {
+            log::debug!("could not read git config: {e}");
+            return Vec::new();
+        }
+    };
+
+    let mut entries = Vec::new();
+    for section in file.sections() {
+        let meta = section.meta();
+        let level = match meta.source {
+            Source::System => ConfigLevel::System,
+            Source::Git => ConfigLevel::Xdg,
+            Source::User => ConfigLevel::Global,
+            _ => ConfigLevel::Local,
+        };
+        let prefix = section_prefix(section);
+        for (name, value) in section.body().clone() {
+            entries.push(ConfigEntry {
+                name: format!("{prefix}{}", name.to_string().to_ascii_lowercase()),
+                origin: ValueOrigin {
+                    value: value.to_str_lossy().into_owned(),
+                    level,
+                    file: meta.path.clone(),
+                    included: meta.level > 0,
+                },
+            });
+        }
+    }
+    entries
+}
+
+/// `section.` or `section.subsection.`, the section name lowercased
+fn section_prefix(section: &gix::config::file::Section<'_>) -> String {
+    let header = section.header();
+    let name = header.name().to_str_lossy().to_ascii_lowercase();
+    match header.subsection_name() {
+        Some(subsection) => format!("{name}.{subsection}."),
+        None => format!("{name}."),
+    }
+}
+
+/// `key = value` pairs of a single file (includes not followed), in file order
+pub fn file_entries(path: &Path) -> Vec<(String, String)> {
+    let Ok(file) = ConfigFile::from_path_no_includes(path.to_path_buf(), Source::Local) else {
+        return Vec::new();
+    };
+
+    let mut entries = Vec::new();
+    for section in file.sections() {
+        let prefix = section_prefix(section);
+        for (name, value) in section.body().clone() {
+            entries.push((
+                format!("{prefix}{}", name.to_string().to_ascii_lowercase()),
+                value.to_str_lossy().into_owned(),
+            ));
+        }
+    }
+    entries
+}
+
+/// Replace every value of a key in a config file with a single one
+pub fn set_value(path: &Path, key: &str, value: &str) -> Result<()> {
+    edit_file(path, |file| {
+        let (section, subsection, name) = split_key(key)?;
+        // Like `git config`, keep the position of an existing definition
+        remove_values(file, section, subsection, name, |count| count > 1);
+        file.set_raw_value_by(section, subsection, name.to_string(), value)?;
+        Ok(())
+    })
+}
+
+/// Add a value to a key, keeping the values it already has
+pub fn add_value(path: &Path, key: &str, value: &str) -> Result<()> {
+    edit_file(path, |file| {
+        let (section, subsection, name) = split_key(key)?;
+        file.section_mut_or_create_new(section, subsection)?
+            .push(name.to_string().try_into()?, Some(value.into()));
+        Ok(())
+    })
+}
+
+/// Remove every value of a key from a config file (nothing to do if absent)
+pub fn unset_all(path: &Path, key: &str) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    edit_file(path, |file| {
+        let (section, subsection, name) = split_key(key)?;
+        remove_values(file, section, subsection, name, |_| true);
+        Ok(())
+    })
+}
+
+/// Delete all values of a key when `when` accepts how many there are, then
+/// drop the sections this left empty
+fn remove_values(
+    file: &mut ConfigFile<'static>,
+    section: &str,
+    subsection: Option<&BStr>,
+    name: &str,
+    when: impl Fn(usize) -> bool,
+) {
+    let holding: Vec<_> = file
+        .sections_and_ids_by_name(section)
+        .into_iter()
+        .flatten()
+        .filter(|(s, _)| s.header().subsection_name() == subsection)
+        .filter(|(s, _)| !s.body().values(name).is_empty())
+        .map(|(_, id)| id)
+        .collect();
+
+    let Ok(mut values) = file.raw_values_mut_by(section, subsection, name) else {
+        return;
+    };
+    if !when(values.len()) {
+        return;
+    }
+    values.delete_all();
+
+    for id in holding {
+        let emptied = file
+            .sections_and_ids()
+            .any(|(s, other)| other == id && s.body().num_values() == 0);
+        if emptied {
+            file.remove_section_by_id(id);
+        }
+    }
+}
+
+/// Split `section.subsection.key`; the subsection may contain dots
+fn split_key(key: &str) -> Result<(&str, Option<&BStr>, &str)> {
+    let (section, rest) = key
+        .split_once('.')
+        .ok_or_else(|| anyhow::anyhow!(tr!("git-key-invalid", key = key)))?;
+    Ok(match rest.rsplit_once('.') {
+        Some((subsection, name)) => (section, Some(subsection.into()), name),
+        None => (section, None, rest),
+    })
+}
+
+/// Load a config file (empty if missing), apply `edit` and write it back atomically
+fn edit_file(path: &Path, edit: impl FnOnce(&mut ConfigFile<'static>) -> Result<()>) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| tr!("dir-create-failed", path = parent.display()))?;
+    }
+    let _lock = FileLock::acquire(path)?;
+
+    let mut file = if path.exists() {
+        ConfigFile::from_path_no_includes(path.to_path_buf(), Source::Local)
+            .with_context(|| tr!("file-read-failed", path = path.display()))?
+    } else {
+        ConfigFile::new(gix::config::file::Metadata::from(Source::Local))
+    };
+    edit(&mut file)?;
+
+    let content = String::from_utf8(file.to_bstring().into())
+        .with_context(|| tr!("file-not-utf8", path = path.display()))?;
+    atomic::write(path, &content, |written| {
+        ConfigFile::from_bytes_no_includes(
+            written.as_bytes(),
+            gix::config::file::Metadata::from(Source::Local),
+            Default::default(),
+        )?;
+        Ok(())
+    })
+}
+
+/// `~/.gitconfig`, or the file named by `GIT_CONFIG_GLOBAL`
+pub fn find_global() -> Option<PathBuf> {
+    Source::User
+        .storage_location(&mut gix::path::env::var)
+        .map(|path| path.into_owned())
+}
+
+/// `$XDG_CONFIG_HOME/git/config`, if it exists
+pub fn find_xdg() -> Option<PathBuf> {
+    gix::path::env::xdg_config("config", &mut gix::path::env::var).filter(|path| path.is_file())
+}
+
+/// The system-wide config file, if it exists and `GIT_CONFIG_NOSYSTEM` is not set
+pub fn find_system() -> Option<PathBuf> {
+    Source::System
+        .storage_location(&mut gix::path::env::var)
+        .map(|path| path.into_owned())
+        .filter(|path| path.is_file())
+}
+
+/// Whether `path` is a Git directory (bare repository or `.git`)
+pub fn is_git_dir(path: &Path) -> bool {
+    gix::discover::is_git(path).is_ok()
+}
@@ -0,0 +1,381 @@
+//! Repository access through libgit2 (enabled with the `git2` feature)
+//!
+//! The backend gid used before gitoxide, kept during the transition. It
+//! exposes the same functions as the gitoxide backend, so either one can sit
+//! behind [`GitConfigManager`](super::GitConfigManager).
+
+use anyhow::Result;
+use git2::{Config as GitConfig, Repository};
+use std::path::{Path, PathBuf};
+
+use super::{
+    is_include_key, resolve_include_path, CommitInfo, CommitParts, CommitSigner, ConfigEntry,
+    ConfigLevel, Person, ValueOrigin,
+};
+use crate::i18n::tr;
+
+/// An open repository
+pub struct Repo {
+    repo: Repository,
+}
+
+impl Repo {
+    /// Repository named by `GIT_DIR`, otherwise the one containing the current directory
+    pub fn from_env() -> Option<Self> {
+        Repository::open_from_env().ok().map(|repo| Self { repo })
+    }
+
+    /// Repository containing `path`, stopping at `GIT_CEILING_DIRECTORIES`
+    pub fn discover(path: &Path) -> Option<Self> {
+        let ceilings: Vec<PathBuf> = std::env::var_os("GIT_CEILING_DIRECTORIES")
+            .map(|dirs| std::env::split_paths(&dirs).collect())
+            .unwrap_or_default();
+        Repository::open_ext(path, git2::RepositoryOpenFlags::CROSS_FS, ceilings)
+            .ok()
+            .map(|repo| Self { repo })
+    }
+
+    /// Repository at `path` (its working tree or Git directory), without searching upwards
+    pub fn open(path: &Path) -> Result<Self> {
+        Ok(Self {
+            repo: Repository::open(path)?,
+        })
+    }
+
+    pub fn git_dir(&self) -> &Path {
+        self.repo.path()
+    }
+
+    pub fn workdir(&self) -> Option<&Path> {
+        self.repo.workdir()
+    }
+
+    pub fn is_bare(&self) -> bool {
+        self.repo.is_bare()
+    }
+
+    pub fn is_shallow(&self) -> bool {
+        self.repo.is_shallow()
+    }
+
+    pub fn operation_in_progress(&self) -> Option<&'static str> {
+        use git2::RepositoryState as S;
+
+        match self.repo.state() {
+            S::Rebase | S::RebaseInteractive | S::RebaseMerge => Some("rebase"),
+            S::Merge => Some("merge"),
+            S::CherryPick | S::CherryPickSequence => Some("cherry-pick"),
+            S::Revert | S::RevertSequence => Some("revert"),
+            S::ApplyMailbox | S::ApplyMailboxOrRebase => Some("am"),
+            S::Clean | S::Bisect => None,
+        }
+    }
+
+    /// Every entry of the system, XDG, global and repository config, includes followed
+    pub fn config_entries(
+        &self,
+        level_file: &dyn Fn(ConfigLevel) -> Option<PathBuf>,
+    ) -> Vec<ConfigEntry> {
+        match self.repo.config().and_then(|mut c| c.snapshot()) {
+            Ok(config) => config_entries(&config, level_file),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Full name of the ref HEAD points at, also before the first commit
+    pub fn head_ref(&self) -> Option<String> {
+        let head = self.repo.find_reference("HEAD").ok()?;
+        head.symbolic_target().map(str::to_string)
+    }
+
+    pub fn find_ref(&self, name: &str) -> Option<String> {
+        let reference = self.repo.find_reference(name).ok()?;
+        Some(reference.peel_to_commit().ok()?.id().to_string())
+    }
+
+    pub fn resolve_commit(&self, rev: &str) -> Result<String> {
+        Ok(self
+            .repo
+            .revparse_single(rev)?
+            .peel_to_commit()?
+            .id()
+            .to_string())
+    }
+
+    pub fn resolve_range(&self, range: &str) -> Result<Option<(String, String)>> {
+        let revspec = self.repo.revparse(range)?;
+        if revspec.mode().contains(git2::RevparseMode::SINGLE) {
+            return Ok(None);
+        }
+        let peel = |object: Option<&git2::Object>| -> Result<String> {
+            let object = object.ok_or_else(|| anyhow::anyhow!(tr!("git-range-invalid")))?;
+            Ok(object.peel_to_commit()?.id().to_string())
+        };
+        Ok(Some((peel(revspec.from())?, peel(revspec.to())?)))
+    }
+
+    pub fn rev_list(&self, tip: &str, hide: Option<&str>) -> Result<Vec<String>> {
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL)?;
+        revwalk.push(git2::Oid::from_str(tip)?)?;
+        if let Some(hide) = hide {
+            revwalk.hide(git2::Oid::from_str(hide)?)?;
+        }
+        revwalk.map(|oid| Ok(oid?.to_string())).collect()
+    }
+
+    pub fn read_commit(&self, id: &str) -> Result<CommitParts> {
+        let commit = self.repo.find_commit(git2::Oid::from_str(id)?)?;
+        let parts = CommitParts {
+            tree: commit.tree_id().to_string(),
+            parents: commit.parent_ids().map(|id| id.to_string()).collect(),
+            author: person(&commit.author()),
+            committer: person(&commit.committer()),
+            message: String::from_utf8_lossy(commit.message_bytes()).into_owned(),
+        };
+        Ok(parts)
+    }
+
+    pub fn write_commit(
+        &self,
+        commit: &CommitParts,
+        sign: Option<&CommitSigner>,
+    ) -> Result<String> {
+        let tree = self.repo.find_tree(git2::Oid::from_str(&commit.tree)?)?;
+        let parents = commit
+            .parents
+            .iter()
+            .map(|id| Ok(self.repo.find_commit(git2::Oid::from_str(id)?)?))
+            .collect::<Result<Vec<_>>>()?;
+        let parents: Vec<&git2::Commit> = parents.iter().collect();
+        let author = signature(&commit.author)?;
+        let committer = signature(&commit.committer)?;
+
+        let Some(sign) = sign else {
+            let oid =
+                self.repo
+                    .commit(None, &author, &committer, &commit.message, &tree, &parents)?;
+            return Ok(oid.to_string());
+        };
+
+        let buffer = self.repo.commit_create_buffer(
+            &author,
+            &committer,
+            &commit.message,
+            &tree,
+            &parents,
+        )?;
+        let content = buffer
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!(tr!("git-commit-not-utf8")))?;
+        let signature = sign(content.as_bytes())?;
+        Ok(self
+            .repo
+            .commit_signed(content, &signature, None)?
+            .to_string())
+    }
+
+    pub fn set_ref(&self, name: &str, id: &str, force: bool, message: &str) -> Result<()> {
+        self.repo
+            .reference(name, git2::Oid::from_str(id)?, force, message)?;
+        Ok(())
+    }
+
+    pub fn detach_head(&self, id: &str, _message: &str) -> Result<()> {
+        self.repo.set_head_detached(git2::Oid::from_str(id)?)?;
+        Ok(())
+    }
+
+    /// Whether tracked files differ from HEAD or untracked files exist
+    pub fn is_dirty(&self) -> Result<bool> {
+        Ok(!self.repo.statuses(None)?.is_empty())
+    }
+
+    /// Content of the blob a revision such as `HEAD:.mailmap` names
+    pub fn blob(&self, spec: &str) -> Option<Vec<u8>> {
+        let blob = self.repo.revparse_single(spec).ok()?.peel_to_blob().ok()?;
+        Some(blob.content().to_vec())
+    }
+
+    /// Armored signature and the data it signs, `None` for unsigned commits
+    pub fn extract_signature(&self, rev: &str) -> Result<Option<(String, Vec<u8>)>> {
+        let oid = self.repo.revparse_single(rev)?.peel_to_commit()?.id();
+
+        match self.repo.extract_signature(&oid, None) {
+            Ok((signature, signed_data)) => Ok(Some((
+                String::from_utf8_lossy(&signature).into_owned(),
+                signed_data.to_vec(),
+            ))),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Stream the history of HEAD, newest first
+    pub fn commits(&self) -> Result<impl Iterator<Item = Result<CommitInfo>> + '_> {
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push_head()?;
+
+        Ok(revwalk.map(move |oid| {
+            let id = oid?.to_string();
+            Ok(CommitInfo::new(&id, &self.read_commit(&id)?))
+        }))
+    }
+}
+
+fn person(signature: &git2::Signature) -> Person {
+    let when = signature.when();
+    Person {
+        name: String::from_utf8_lossy(signature.name_bytes()).into_owned(),
+        email: String::from_utf8_lossy(signature.email_bytes()).into_owned(),
+        time: when.seconds(),
+        offset: when.offset_minutes(),
+    }
+}
+
+fn signature(person: &Person) -> Result<git2::Signature<'static>> {
+    let time = git2::Time::new(person.time, person.offset);
+    Ok(git2::Signature::new(&person.name, &person.email, &time)?)
+}
+
+/// Entries of the system, XDG and global config, as Git sees them outside a repository
+pub fn default_config_entries(
+    level_file: &dyn Fn(ConfigLevel) -> Option<PathBuf>,
+) -> Vec<ConfigEntry> {
+    match GitConfig::open_default().and_then(|mut c| c.snapshot()) {
+        Ok(config) => config_entries(&config, level_file),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Entries of a config in resolution order, attributed to the file defining them
+///
+/// libgit2 lists the entries of an included file right after the include
+/// directive, one depth deeper, so tracking the latest include target per
+/// depth tells which file each entry comes from.
+fn config_entries(
+    config: &GitConfig,
+    level_file: &dyn Fn(ConfigLevel) -> Option<PathBuf>,
+) -> Vec<ConfigEntry> {
+    let mut found = Vec::new();
+    let Ok(mut entries) = config.entries(None) else {
+        return found;
+    };
+
+    let mut current = None;
+    // Files by include depth within the current level
+    let mut files: Vec<Option<PathBuf>> = Vec::new();
+    while let Some(Ok(entry)) = entries.next() {
+        let Some(level) = config_level(entry.level()) else {
+            continue;
+        };
+        if current != Some(level) {
+            current = Some(level);
+            files = vec![level_file(level)];
+        }
+        let depth = entry.include_depth() as usize;
+        files.truncate(depth + 1);
+        let file = files.get(depth).cloned().flatten();
+
+        let Some(name) = entry.name() else {
+            continue;
+        };
+        let value = entry.value().unwrap_or_default();
+        if is_include_key(name) {
+            files.resize(depth + 1, None);
+            files.push(Some(resolve_include_path(value, file.as_deref())));
+        }
+        found.push(ConfigEntry {
+            name: name.to_string(),
+            origin: ValueOrigin {
+                value: value.to_string(),
+                level,
+                file,
+                included: depth > 0,
+            },
+        });
+    }
+
+    found
+}
+
+fn config_level(level: git2::ConfigLevel) -> Option<ConfigLevel> {
+    match level {
+        git2::ConfigLevel::System => Some(ConfigLevel::System),
+        git2::ConfigLevel::XDG => Some(ConfigLevel::Xdg),
+        git2::ConfigLevel::Global => Some(ConfigLevel::Global),
+        git2::ConfigLevel::Local => Some(ConfigLevel::Local),
+        _ => None,
+    }
+}
+
+/// `key = value` pairs of a single file (includes not followed), in file order
+pub fn file_entries(path: &Path) -> Vec<(String, String)> {
+    if !path.is_file() {
+        return Vec::new();
+    }
+    let Ok(config) = GitConfig::open(path) else {
+        return Vec::new();
+    };
+    let Ok(mut entries) = config.entries(None) else {
+        return Vec::new();
+    };
+
+    let mut found = Vec::new();
+    while let Some(Ok(entry)) = entries.next() {
+        if entry.include_depth() > 0 {
+            continue;
+        }
+        if let Some(name) = entry.name() {
+            found.push((
+                name.to_string(),
+                entry.value().unwrap_or_default().to_string(),
+            ));
+        }
+    }
+    found
+}
+
+/// Replace every value of a key in a config file with a single one
+pub fn set_value(path: &Path, key: &str, value: &str) -> Result<()> {
+    GitConfig::open(path)?.set_str(key, value)?;
+    Ok(())
+}
+
+/// Add a value to a key, keeping the values it already has
+pub fn add_value(path: &Path, key: &str, value: &str) -> Result<()> {
+    // A regex matching no existing value appends
+    GitConfig::open(path)?.set_multivar(key, "^$a", value)?;
+    Ok(())
+}
+
+/// Remove every value of a key from a config file (nothing to do if absent)
+pub fn unset_all(path: &Path, key: &str) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    match GitConfig::open(path)?.remove_multivar(key, ".*") {
+        Err(e) if e.code() != git2::ErrorCode::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// `~/.gitconfig`, if it exists
+pub fn find_global() -> Option<PathBuf> {
+    GitConfig::find_global().ok()
+}
+
+/// `$XDG_CONFIG_HOME/git/config`, if it exists
+pub fn find_xdg() -> Option<PathBuf> {
+    GitConfig::find_xdg().ok()
+}
+
+/// The system-wide config file, if it exists
+pub fn find_system() -> Option<PathBuf> {
+    GitConfig::find_system().ok()
+}
+
+/// Whether `path` is a Git directory (bare repository or `.git`)
+pub fn is_git_dir(path: &Path) -> bool {
+    Repository::open_bare(path).is_ok()
+}
@@ -0,0 +1,135 @@
+//! Reader for Git's `.mailmap` format
+
+use std::collections::HashMap;
+
+/// Canonical author names and emails, resolved the way `git log --use-mailmap` does
+#[derive(Debug, Default)]
+pub struct Mailmap {
+    /// Keyed by the lowercased commit email
+    entries: HashMap<String, MailmapEntry>,
+}
+
+#[derive(Debug, Default)]
+struct MailmapEntry {
+    name: Option<String>,
+    email: Option<String>,
+    /// Replacements that only apply to one commit name (lowercased)
+    by_name: HashMap<String, (Option<String>, Option<String>)>,
+}
+
+impl Mailmap {
+    /// Parse the content of one mailmap file
+    pub fn parse(content: &str) -> Self {
+        let mut mailmap = Self::default();
+        mailmap.extend(content);
+        mailmap
+    }
+
+    /// Add the entries of another mailmap file; later entries win
+    pub fn extend(&mut self, content: &str) {
+        for line in content.lines() {
+            if line.starts_with('#') {
+                continue;
+            }
+            let Some((name1, email1, rest)) = name_and_email(line) else {
+                continue;
+            };
+
+            let (proper_name, proper_email, commit_name, commit_email) = match name_and_email(rest)
+            {
+                Some((name2, email2, _)) => (name1, Some(email1), name2, email2),
+                None => (name1, None, None, email1),
+            };
+
+            let entry = self.entries.entry(commit_email.to_lowercase()).or_default();
+            match commit_name {
+                Some(commit_name) => {
+                    entry
+                        .by_name
+                        .insert(commit_name.to_lowercase(), (proper_name, proper_email));
+                }
+                None => {
+                    if proper_name.is_some() {
+                        entry.name = proper_name;
+                    }
+                    if proper_email.is_some() {
+                        entry.email = proper_email;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Whether no entries were read
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Canonical name and email for a commit author (unchanged if not mapped)
+    pub fn resolve(&self, name: &str, email: &str) -> (String, String) {
+        let Some(entry) = self.entries.get(&email.to_lowercase()) else {
+            return (name.to_string(), email.to_string());
+        };
+        let (proper_name, proper_email) = entry
+            .by_name
+            .get(&name.to_lowercase())
+            .map_or((&entry.name, &entry.email), |(n, e)| (n, e));
+
+        (
+            proper_name.clone().unwrap_or_else(|| name.to_string()),
+            proper_email.clone().unwrap_or_else(|| email.to_string()),
+        )
+    }
+}
+
+/// Split `Name <email>` off the front of a mailmap line, returning the rest
+fn name_and_email(line: &str) -> Option<(Option<String>, String, &str)> {
+    let open = line.find('<')?;
+    let close = open + line[open..].find('>')?;
+
+    let name = line[..open].trim();
+    let email = line[open + 1..close].trim();
+    let name = (!name.is_empty()).then(|| name.to_string());
+    Some((name, email.to_string(), &line[close + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_forms() {
+        let mailmap = Mailmap::parse(
+            "# comment\n\
+             Work User <work@example.com>\n\
+             <work@example.com> <old@example.com>\n\
+             Work User <work@example.com> wu <shared@example.com>\n",
+        );
+
+        assert_eq!(
+            mailmap.resolve("w", "WORK@example.com"),
+            ("Work User".to_string(), "WORK@example.com".to_string())
+        );
+        assert_eq!(
+            mailmap.resolve("Old Name", "old@example.com"),
+            ("Old Name".to_string(), "work@example.com".to_string())
+        );
+        assert_eq!(
+            mailmap.resolve("WU", "shared@example.com"),
+            ("Work User".to_string(), "work@example.com".to_string())
+        );
+        // The name-specific entry does not apply to other names
+        assert_eq!(
+            mailmap.resolve("Someone", "shared@example.com"),
+            ("Someone".to_string(), "shared@example.com".to_string())
+        );
+    }
+
+    #[test]
+    fn test_later_entries_win() {
+        let mut mailmap = Mailmap::parse("First <a@example.com>\n");
+        mailmap.extend("Second <a@example.com>\nnot a mapping\n");
+        assert_eq!(mailmap.resolve("x", "a@example.com").0, "Second");
+        assert!(Mailmap::parse("").is_empty());
+    }
+}
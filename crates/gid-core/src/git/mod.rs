@@ -0,0 +1,1496 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+use crate::exit::Failure;
+use crate::i18n::tr;
+
+#[cfg(not(feature = "git2"))]
+mod gitoxide;
+#[cfg(feature = "git2")]
+mod libgit2;
+mod mailmap;
+mod repo_index;
+mod signature;
+mod url;
+
+#[cfg(not(feature = "git2"))]
+use gitoxide as backend;
+#[cfg(feature = "git2")]
+use libgit2 as backend;
+
+pub use mailmap::Mailmap;
+pub use signature::{CommitSignature, SignatureStatus, Verifier};
+pub use url::GitUrl;
+
+/// Helper command gid writes into `credential.<url>.helper`, followed by the identity id
+pub const CREDENTIAL_HELPER_PREFIX: &str = "!gid credential helper ";
+
+/// Git Configuration Manager
+///
+/// Repositories are read and written through gitoxide, or through libgit2
+/// when gid is built with the `git2` feature.
+pub struct GitConfigManager {
+    repo: Option<backend::Repo>,
+    /// Opened through the GIT_* environment variables rather than a path
+    from_env: bool,
+}
+
+impl GitConfigManager {
+    /// Create new configuration manager
+    ///
+    /// Honors `GIT_DIR`, `GIT_WORK_TREE` and `GIT_COMMON_DIR` (set by
+    /// `--git-dir`, by scripts and by Git when running hooks), otherwise
+    /// discovers the repository from the current directory, stopping at
+    /// `GIT_CEILING_DIRECTORIES`.
+    pub fn new() -> Result<Self> {
+        Ok(Self::with_repo(backend::Repo::from_env(), true))
+    }
+
+    /// Create from specified path
+    ///
+    /// Used for scans, so `GIT_DIR` and friends (which name one repository)
+    /// are ignored; `GIT_CEILING_DIRECTORIES` still bounds the search.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        Ok(Self::with_repo(backend::Repo::discover(path), false))
+    }
+
+    fn with_repo(repo: Option<backend::Repo>, from_env: bool) -> Self {
+        Self { repo, from_env }
+    }
+
+    /// Check if in Git repository
+    pub fn is_in_repo(&self) -> bool {
+        self.repo.is_some()
+    }
+
+    /// Get repository path
+    pub fn repo_path(&self) -> Option<&Path> {
+        self.repo.as_ref().map(|r| r.git_dir())
+    }
+
+    /// Get working directory of the repository
+    pub fn workdir(&self) -> Option<&Path> {
+        self.repo.as_ref().and_then(|r| r.workdir())
+    }
+
+    /// Whether the repository has no working tree (e.g. a server-side mirror)
+    pub fn is_bare(&self) -> bool {
+        self.repo.as_ref().is_some_and(|r| r.is_bare())
+    }
+
+    /// Whether the repository is a shallow clone with truncated history
+    pub fn is_shallow(&self) -> bool {
+        self.repo.as_ref().is_some_and(|r| r.is_shallow())
+    }
+
+    /// Working directories of initialized submodules, nested ones included
+    pub fn submodule_workdirs(&self) -> Vec<PathBuf> {
+        let Some(root) = self.workdir() else {
+            return Vec::new();
+        };
+
+        let mut dirs = Vec::new();
+        for (name, path) in backend::file_entries(&root.join(".gitmodules")) {
+            if !(name.starts_with("submodule.") && name.ends_with(".path")) {
+                continue;
+            }
+            // Submodules that are not initialized have no repository to open
+            let Ok(repo) = backend::Repo::open(&root.join(path)) else {
+                continue;
+            };
+            let Some(workdir) = repo.workdir().map(Path::to_path_buf) else {
+                continue;
+            };
+            dirs.push(workdir);
+            dirs.extend(Self::with_repo(Some(repo), false).submodule_workdirs());
+        }
+        dirs
+    }
+
+    /// Git command whose operation is unfinished in this repository (rebase, merge, ...)
+    pub fn operation_in_progress(&self) -> Option<&'static str> {
+        self.repo.as_ref()?.operation_in_progress()
+    }
+
+    /// Refuse to change the repository while a rebase, merge or cherry-pick is unfinished
+    ///
+    /// Rewriting HEAD or the identity mid-operation corrupts the branch being replayed.
+    pub fn ensure_no_operation_in_progress(&self) -> Result<()> {
+        if let Some(operation) = self.operation_in_progress() {
+            anyhow::bail!(tr!("git-operation-in-progress", operation = operation));
+        }
+        Ok(())
+    }
+
+    /// Directory rules and .gid files are resolved against
+    ///
+    /// The current directory when inside the working tree, otherwise the
+    /// working tree root, or the repository itself when it is bare.
+    pub fn context_dir(&self) -> Option<PathBuf> {
+        let repo = self.repo.as_ref()?;
+        let Some(workdir) = repo.workdir() else {
+            return Some(repo.git_dir().to_path_buf());
+        };
+        match std::env::current_dir() {
+            Ok(dir) if dir.starts_with(workdir) => Some(dir),
+            _ => Some(workdir.to_path_buf()),
+        }
+    }
+
+    /// Repository config file; linked worktrees share the main repository's
+    fn local_config_path(&self) -> Option<PathBuf> {
+        let git_dir = self.repo.as_ref()?.git_dir();
+        let common_dir = match std::env::var_os("GIT_COMMON_DIR") {
+            Some(dir) if self.from_env => PathBuf::from(dir),
+            _ => match std::fs::read_to_string(git_dir.join("commondir")) {
+                Ok(common) => git_dir.join(common.trim()),
+                Err(_) => git_dir.to_path_buf(),
+            },
+        };
+        Some(common_dir.join("config"))
+    }
+
+    /// Snapshot the config file about to change so `gid undo` can restore it
+    fn record_config(&self, global: bool) {
+        if let Ok(path) = self.scoped_file(global) {
+            crate::config::journal::record(&path);
+        }
+    }
+
+    /// Config file of the level gid writes to (repository-local or global)
+    fn scoped_file(&self, global: bool) -> Result<PathBuf> {
+        if global {
+            global_config_path()
+        } else {
+            self.local_config_path().ok_or_else(not_a_repo)
+        }
+    }
+
+    /// Set user name
+    pub fn set_user_name(&self, name: &str, global: bool) -> Result<()> {
+        self.set_scoped_value("user.name", name, global)
+    }
+
+    /// Set user email
+    pub fn set_user_email(&self, email: &str, global: bool) -> Result<()> {
+        self.set_scoped_value("user.email", email, global)
+    }
+
+    /// Set GPG signing key
+    pub fn set_signing_key(&self, key: &str, global: bool) -> Result<()> {
+        self.set_scoped_value("user.signingkey", key, global)
+    }
+
+    /// Enable/Disable GPG signing
+    pub fn set_gpg_sign(&self, enabled: bool, global: bool) -> Result<()> {
+        self.set_scoped_value("commit.gpgsign", &enabled.to_string(), global)
+    }
+
+    /// Set commit message template
+    pub fn set_commit_template(&self, path: &Path, global: bool) -> Result<()> {
+        self.set_scoped_value("commit.template", &path.to_string_lossy(), global)
+    }
+
+    /// Set a value at the level gid writes to (repository-local or global)
+    fn set_scoped_value(&self, key: &str, value: &str, global: bool) -> Result<()> {
+        self.record_config(global);
+        backend::set_value(&self.scoped_file(global)?, key, value)
+            .with_context(|| tr!("git-set-failed", key = key))?;
+        log_set(key, value, global);
+        Ok(())
+    }
+
+    /// Remove a value from repository-local or global config
+    pub fn unset_value(&self, key: &str, global: bool) -> Result<()> {
+        self.record_config(global);
+        backend::unset_all(&self.scoped_file(global)?, key)
+            .with_context(|| tr!("git-unset-failed", key = key))?;
+        log::info!("unset {key} in {} config", scope_name(global));
+        Ok(())
+    }
+
+    /// Make `gid credential helper <identity>` the only credential helper for a host
+    ///
+    /// An empty helper value first resets helpers inherited from other config
+    /// levels, so a system-wide store cannot answer for the wrong account.
+    pub fn set_credential_helper(
+        &self,
+        host: &str,
+        identity_id: &str,
+        username: &str,
+        global: bool,
+    ) -> Result<()> {
+        self.record_config(global);
+        let file = self.scoped_file(global)?;
+        let key = format!("credential.https://{host}.helper");
+        let helper = format!("{CREDENTIAL_HELPER_PREFIX}{identity_id}");
+
+        backend::unset_all(&file, &key).with_context(|| tr!("git-unset-failed", key = key))?;
+        for value in ["", &helper] {
+            backend::add_value(&file, &key, value)
+                .with_context(|| tr!("git-set-failed", key = key))?;
+        }
+        log_set(&key, &helper, global);
+
+        let key = format!("credential.https://{host}.username");
+        backend::set_value(&file, &key, username)
+            .with_context(|| tr!("git-set-failed", key = key))?;
+        log_set(&key, username, global);
+
+        Ok(())
+    }
+
+    /// Hosts with a gid credential helper at one level, as (host, identity)
+    pub fn credential_helpers(&self, global: bool) -> Vec<(String, String)> {
+        self.scoped_entries(global)
+            .into_iter()
+            .filter_map(|entry| {
+                let host = entry
+                    .name
+                    .strip_prefix("credential.https://")?
+                    .strip_suffix(".helper")?;
+                let identity = entry.origin.value.strip_prefix(CREDENTIAL_HELPER_PREFIX)?;
+                Some((host.to_string(), identity.to_string()))
+            })
+            .collect()
+    }
+
+    /// Remove the credential helper and username gid set for a host
+    pub fn unset_credential_helper(&self, host: &str, global: bool) -> Result<()> {
+        self.record_config(global);
+        let file = self.scoped_file(global)?;
+
+        for key in [
+            format!("credential.https://{host}.helper"),
+            format!("credential.https://{host}.username"),
+        ] {
+            backend::unset_all(&file, &key).with_context(|| tr!("git-credential-unset-failed"))?;
+        }
+
+        Ok(())
+    }
+
+    /// Point a gitdir-scoped includeIf block in the global config at a fragment file
+    ///
+    /// Only that block's `path` is touched; the rest of the file is kept as is.
+    pub fn set_include_if(&self, gitdir: &str, fragment: &Path) -> Result<()> {
+        self.record_config(true);
+        set_include_if_in(&global_config_path()?, gitdir, fragment)
+    }
+
+    /// Write an identity's fragment file and point an includeIf block at it
+    ///
+    /// The fragment is written first, so Git never sees a block whose file is missing.
+    pub fn write_include_if(
+        &self,
+        gitdir: &str,
+        fragment: &Path,
+        values: &[(&str, String)],
+    ) -> Result<()> {
+        write_config_fragment(fragment, values)?;
+        self.set_include_if(gitdir, fragment)
+    }
+
+    /// Get the fragment file an includeIf block points at
+    pub fn get_include_if(&self, gitdir: &str) -> Option<String> {
+        let key = format!("includeif.gitdir:{gitdir}.path");
+        backend::file_entries(&global_config_path().ok()?)
+            .into_iter()
+            .rev()
+            .find(|(name, _)| *name == key)
+            .map(|(_, value)| value)
+    }
+
+    /// All gitdir-scoped includeIf blocks in the global config as (gitdir, path)
+    pub fn include_if_entries(&self) -> Vec<(String, String)> {
+        let Ok(path) = global_config_path() else {
+            return Vec::new();
+        };
+
+        backend::file_entries(&path)
+            .into_iter()
+            .filter_map(|(name, value)| {
+                let gitdir = name
+                    .strip_prefix("includeif.gitdir:")?
+                    .strip_suffix(".path")?;
+                Some((gitdir.to_string(), value))
+            })
+            .collect()
+    }
+
+    /// Remove a gitdir-scoped includeIf block from the global config
+    pub fn remove_include_if(&self, gitdir: &str) -> Result<()> {
+        self.record_config(true);
+        remove_include_if_in(&global_config_path()?, gitdir)
+    }
+
+    /// Remove an includeIf block and, when nothing else includes it, the gid
+    /// fragment it pointed at. Returns the deleted fragment
+    ///
+    /// Files outside `fragment_dir` are never deleted; they belong to the user.
+    pub fn remove_include_if_with_fragment(
+        &self,
+        gitdir: &str,
+        fragment_dir: &Path,
+    ) -> Result<Option<PathBuf>> {
+        let Some(path) = self.get_include_if(gitdir) else {
+            return Ok(None);
+        };
+        self.remove_include_if(gitdir)?;
+
+        let fragment = crate::config::identity::expand_path(Path::new(&path));
+        let still_used = self
+            .include_if_entries()
+            .iter()
+            .any(|(_, other)| crate::config::identity::expand_path(Path::new(other)) == fragment);
+        if still_used || fragment.parent() != Some(fragment_dir) || !fragment.exists() {
+            return Ok(None);
+        }
+
+        crate::config::journal::record(&fragment);
+        std::fs::remove_file(&fragment)
+            .with_context(|| tr!("file-delete-failed", path = fragment.display()))?;
+        Ok(Some(fragment))
+    }
+
+    /// Replace gitdir-scoped includeIf blocks in the global config
+    ///
+    /// The last matching block wins in Git, so `remove` sections are deleted and
+    /// `add` blocks appended in the given order.
+    pub fn rewrite_include_ifs(&self, remove: &[String], add: &[(String, String)]) -> Result<()> {
+        self.record_config(true);
+        let path = global_config_path()?;
+
+        for gitdir in remove {
+            remove_include_if_in(&path, gitdir)?;
+        }
+
+        for (gitdir, fragment) in add {
+            git_config_file(
+                &path,
+                &[
+                    "--add",
+                    &format!("includeIf.gitdir:{gitdir}.path"),
+                    fragment,
+                ],
+            )
+            .with_context(|| tr!("git-include-add-failed", gitdir = gitdir))?;
+        }
+
+        Ok(())
+    }
+
+    /// Get current user name
+    pub fn get_user_name(&self, global: bool) -> Option<String> {
+        self.winning_value("user.name", global)
+    }
+
+    /// Get current user email
+    pub fn get_user_email(&self, global: bool) -> Option<String> {
+        self.winning_value("user.email", global)
+    }
+
+    /// Value Git uses for a key: the last definition in resolution order
+    ///
+    /// `global` leaves the repository out, as Git does outside one.
+    fn winning_value(&self, key: &str, global: bool) -> Option<String> {
+        let entries = if global {
+            backend::default_config_entries(&|level| self.level_file(level))
+        } else {
+            self.repo
+                .as_ref()?
+                .config_entries(&|level| self.level_file(level))
+        };
+        last_value(entries, key)
+    }
+
+    /// Value Git uses for a key in this repository (outside one, the user's)
+    fn config_value(&self, key: &str) -> Option<String> {
+        last_value(self.config_entries(), key)
+    }
+
+    /// Every config entry that applies here, lowest precedence first
+    fn config_entries(&self) -> Vec<ConfigEntry> {
+        let level_file = |level| self.level_file(level);
+        match self.repo.as_ref() {
+            Some(repo) => repo.config_entries(&level_file),
+            None => backend::default_config_entries(&level_file),
+        }
+    }
+
+    /// Entries of the level gid writes to (repository-local or global), includes followed
+    fn scoped_entries(&self, global: bool) -> Vec<ConfigEntry> {
+        let level_file = |level| self.level_file(level);
+        let (entries, level) = match (global, self.repo.as_ref()) {
+            (true, _) => (
+                backend::default_config_entries(&level_file),
+                ConfigLevel::Global,
+            ),
+            (false, Some(repo)) => (repo.config_entries(&level_file), ConfigLevel::Local),
+            (false, None) => return Vec::new(),
+        };
+        entries
+            .into_iter()
+            .filter(|entry| entry.origin.level == level)
+            .collect()
+    }
+
+    /// All definitions of a key across config levels, lowest precedence first
+    ///
+    /// Every value of a multivar is kept, and values pulled in by include or
+    /// includeIf (only those whose condition holds) are attributed to the file
+    /// defining them. The last entry is the effective value.
+    pub fn value_origins(&self, key: &str) -> Vec<ValueOrigin> {
+        self.config_entries()
+            .into_iter()
+            .filter(|entry| entry.name.eq_ignore_ascii_case(key))
+            .map(|entry| entry.origin)
+            .collect()
+    }
+
+    /// The definition of a key that wins, with the file it comes from
+    pub fn effective_origin(&self, key: &str) -> Option<ValueOrigin> {
+        self.value_origins(key).pop()
+    }
+
+    /// Config file backing a level
+    fn level_file(&self, level: ConfigLevel) -> Option<PathBuf> {
+        match level {
+            ConfigLevel::Local => self.local_config_path(),
+            ConfigLevel::Global => global_config_path().ok(),
+            ConfigLevel::Xdg => backend::find_xdg(),
+            ConfigLevel::System => backend::find_system(),
+        }
+    }
+
+    /// Get a value from the level gid writes to (repository-local or global)
+    pub fn get_scoped_value(&self, key: &str, global: bool) -> Option<String> {
+        last_value(self.scoped_entries(global), key)
+    }
+
+    /// Get effective user name (local first, then global)
+    pub fn get_effective_user_name(&self) -> Option<String> {
+        self.get_user_name(false)
+            .or_else(|| self.get_user_name(true))
+    }
+
+    /// Get effective user email (local first, then global)
+    pub fn get_effective_user_email(&self) -> Option<String> {
+        self.get_user_email(false)
+            .or_else(|| self.get_user_email(true))
+    }
+
+    /// Get origin remote URL
+    pub fn get_origin_url(&self) -> Option<String> {
+        self.remote_urls()
+            .into_iter()
+            .find(|(name, _)| name == "origin")
+            .map(|(_, url)| url)
+    }
+
+    /// Configured remotes with their (first) URL, sorted by name
+    fn remote_urls(&self) -> Vec<(String, String)> {
+        if self.repo.is_none() {
+            return Vec::new();
+        }
+
+        let mut urls: Vec<(String, String)> = Vec::new();
+        for entry in self.config_entries() {
+            let Some(name) = entry
+                .name
+                .strip_prefix("remote.")
+                .and_then(|rest| rest.strip_suffix(".url"))
+            else {
+                continue;
+            };
+            if !urls.iter().any(|(known, _)| known == name) {
+                urls.push((name.to_string(), entry.origin.value));
+            }
+        }
+        urls.sort_by(|a, b| a.0.cmp(&b.0));
+        urls
+    }
+
+    /// Name of the checked-out branch (also before its first commit)
+    ///
+    /// None when HEAD is detached or outside a repository.
+    pub fn current_branch(&self) -> Option<String> {
+        self.head_branch()?
+            .strip_prefix("refs/heads/")
+            .map(str::to_string)
+    }
+
+    /// Full ref name of the checked-out branch, `None` when HEAD is detached
+    pub fn head_branch(&self) -> Option<String> {
+        self.repo
+            .as_ref()?
+            .head_ref()
+            .filter(|name| name.starts_with("refs/heads/"))
+    }
+
+    /// Get URLs of all configured remotes
+    pub fn get_remote_urls(&self) -> Vec<String> {
+        self.remote_urls().into_iter().map(|(_, url)| url).collect()
+    }
+
+    /// Get commits on the current branch that are not yet on its upstream
+    ///
+    /// Returns an empty list when HEAD is detached or has no upstream.
+    pub fn get_unpushed_commits(&self) -> Result<Vec<CommitInfo>> {
+        let repo = self.repo.as_ref().ok_or_else(not_a_repo)?;
+
+        let Some(branch) = self.current_branch() else {
+            return Ok(Vec::new());
+        };
+        let Some(upstream) = self.upstream_ref(&branch) else {
+            return Ok(Vec::new());
+        };
+        let (Some(local), Some(upstream)) = (
+            repo.find_ref(&format!("refs/heads/{branch}")),
+            repo.find_ref(&upstream),
+        ) else {
+            return Ok(Vec::new());
+        };
+
+        repo.rev_list(&local, Some(&upstream))?
+            .iter()
+            .map(|id| Ok(CommitInfo::new(id, &repo.read_commit(id)?)))
+            .collect()
+    }
+
+    /// Remote-tracking ref a branch pulls from (`branch.<name>.remote` and `.merge`)
+    fn upstream_ref(&self, branch: &str) -> Option<String> {
+        let remote = self.config_value(&format!("branch.{branch}.remote"))?;
+        let merge = self.config_value(&format!("branch.{branch}.merge"))?;
+        if remote == "." {
+            return Some(merge);
+        }
+        let merged = merge.strip_prefix("refs/heads/").unwrap_or(&merge);
+        Some(format!("refs/remotes/{remote}/{merged}"))
+    }
+
+    /// The repository's mailmap, read in Git's order with later entries winning
+    ///
+    /// `.mailmap` in the working tree, then `mailmap.blob` (`HEAD:.mailmap` in
+    /// bare repositories), then the file named by `mailmap.file`.
+    pub fn mailmap(&self) -> Mailmap {
+        let mut mailmap = Mailmap::default();
+        let Some(repo) = self.repo.as_ref() else {
+            return mailmap;
+        };
+
+        if let Some(workdir) = repo.workdir() {
+            if let Ok(content) = std::fs::read_to_string(workdir.join(".mailmap")) {
+                mailmap.extend(&content);
+            }
+        }
+
+        let blob = self
+            .config_value("mailmap.blob")
+            .or_else(|| repo.is_bare().then(|| "HEAD:.mailmap".to_string()));
+        if let Some(content) = blob.and_then(|spec| repo.blob(&spec)) {
+            mailmap.extend(&String::from_utf8_lossy(&content));
+        }
+
+        if let Some(file) = self.config_value("mailmap.file") {
+            let path = crate::config::identity::expand_path(Path::new(&file));
+            if let Ok(content) = std::fs::read_to_string(path) {
+                mailmap.extend(&content);
+            }
+        }
+
+        mailmap
+    }
+
+    /// Signature embedded in a commit, or `None` if it is not signed
+    pub fn extract_signature(&self, rev: &str) -> Result<Option<CommitSignature>> {
+        let repo = self.repo.as_ref().ok_or_else(not_a_repo)?;
+        Ok(repo
+            .extract_signature(rev)?
+            .map(|(signature, signed_data)| CommitSignature::new(signature, signed_data)))
+    }
+
+    /// Check a signature with the programs Git is configured to use
+    pub fn verify_signature(&self, signature: &CommitSignature) -> SignatureStatus {
+        self.verifier().verify(signature)
+    }
+
+    /// Extract and check a commit's signature, `None` if it is not signed
+    pub fn verify_commit(&self, rev: &str) -> Result<Option<SignatureStatus>> {
+        Ok(self
+            .extract_signature(rev)?
+            .map(|signature| self.verify_signature(&signature)))
+    }
+
+    /// `gpg.program`, `gpg.ssh.program` and `gpg.ssh.allowedSignersFile`, as `git verify-commit` reads them
+    fn verifier(&self) -> Verifier {
+        let entries = self.config_entries();
+        let value = |key: &str| last_value(entries.iter().cloned(), key);
+
+        let default = Verifier::default();
+        Verifier {
+            gpg_program: value("gpg.openpgp.program")
+                .or_else(|| value("gpg.program"))
+                .unwrap_or(default.gpg_program),
+            ssh_program: value("gpg.ssh.program").unwrap_or(default.ssh_program),
+            allowed_signers: value("gpg.ssh.allowedSignersFile")
+                .map(|file| crate::config::identity::expand_path(Path::new(&file))),
+        }
+    }
+
+    /// Stream the history of HEAD, newest first
+    ///
+    /// Commits are read one at a time, so callers can stop early or fold
+    /// large histories without holding them in memory.
+    pub fn commits(&self) -> Result<impl Iterator<Item = Result<CommitInfo>> + '_> {
+        self.repo.as_ref().ok_or_else(not_a_repo)?.commits()
+    }
+
+    /// Full id of the commit a revision names
+    pub fn resolve_commit(&self, rev: &str) -> Result<String> {
+        self.repo
+            .as_ref()
+            .ok_or_else(not_a_repo)?
+            .resolve_commit(rev)
+    }
+
+    /// Both ends of an `a..b` range, `None` when `range` names a single commit
+    pub fn resolve_range(&self, range: &str) -> Result<Option<(String, String)>> {
+        self.repo
+            .as_ref()
+            .ok_or_else(not_a_repo)?
+            .resolve_range(range)
+    }
+
+    /// Commits reachable from `tip` but not from `hide`, children before parents
+    pub fn rev_list(&self, tip: &str, hide: Option<&str>) -> Result<Vec<String>> {
+        self.repo
+            .as_ref()
+            .ok_or_else(not_a_repo)?
+            .rev_list(tip, hide)
+    }
+
+    /// Tree, parents, people and message of a commit
+    pub fn read_commit(&self, id: &str) -> Result<CommitParts> {
+        self.repo.as_ref().ok_or_else(not_a_repo)?.read_commit(id)
+    }
+
+    /// Write a commit object without moving any ref, returning its id
+    ///
+    /// `sign` receives the commit as it is hashed without a signature and
+    /// returns the armored signature to embed.
+    pub fn write_commit(
+        &self,
+        commit: &CommitParts,
+        sign: Option<&CommitSigner>,
+    ) -> Result<String> {
+        self.repo
+            .as_ref()
+            .ok_or_else(not_a_repo)?
+            .write_commit(commit, sign)
+    }
+
+    /// Commit a ref points at, `None` if it does not exist
+    pub fn find_ref(&self, name: &str) -> Option<String> {
+        self.repo.as_ref()?.find_ref(name)
+    }
+
+    /// Point a ref at a commit; without `force` the ref must not exist yet
+    pub fn set_ref(&self, name: &str, id: &str, force: bool, message: &str) -> Result<()> {
+        self.repo
+            .as_ref()
+            .ok_or_else(not_a_repo)?
+            .set_ref(name, id, force, message)
+    }
+
+    /// Detach HEAD at a commit
+    pub fn detach_head(&self, id: &str, message: &str) -> Result<()> {
+        self.repo
+            .as_ref()
+            .ok_or_else(not_a_repo)?
+            .detach_head(id, message)
+    }
+
+    /// Whether the working tree or index differs from HEAD (untracked files count)
+    pub fn has_uncommitted_changes(&self) -> Result<bool> {
+        self.repo.as_ref().ok_or_else(not_a_repo)?.is_dirty()
+    }
+}
+
+/// Commit Information
+#[derive(Debug)]
+pub struct CommitInfo {
+    /// Abbreviated id for display
+    pub id: String,
+    /// Full object id
+    pub oid: String,
+    pub message: String,
+    pub author_name: String,
+    pub author_email: String,
+}
+
+impl CommitInfo {
+    fn new(id: &str, commit: &CommitParts) -> Self {
+        Self {
+            id: id[..7].to_string(),
+            oid: id.to_string(),
+            message: commit.message.lines().next().unwrap_or("").to_string(),
+            author_name: commit.author.name.clone(),
+            author_email: commit.author.email.clone(),
+        }
+    }
+}
+
+/// Signs a commit as it is hashed without a signature, returning the armored signature
+pub type CommitSigner<'a> = dyn Fn(&[u8]) -> Result<String> + 'a;
+
+/// What a commit object is written from
+#[derive(Debug, Clone)]
+pub struct CommitParts {
+    pub tree: String,
+    pub parents: Vec<String>,
+    pub author: Person,
+    pub committer: Person,
+    pub message: String,
+}
+
+/// Author or committer of a commit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Person {
+    pub name: String,
+    pub email: String,
+    /// Seconds since the epoch
+    pub time: i64,
+    /// Offset from UTC in minutes
+    pub offset: i32,
+}
+
+impl Person {
+    /// Someone acting now, in the local time zone
+    pub fn now(name: &str, email: &str) -> Self {
+        let now = chrono::Local::now();
+        Self {
+            name: name.to_string(),
+            email: email.to_string(),
+            time: now.timestamp(),
+            offset: now.offset().local_minus_utc() / 60,
+        }
+    }
+}
+
+/// Default directory depth for repository scans
+pub const DEFAULT_SCAN_DEPTH: usize = 5;
+
+fn scope_name(global: bool) -> &'static str {
+    if global {
+        "global"
+    } else {
+        "local"
+    }
+}
+
+fn log_set(key: &str, value: impl std::fmt::Display, global: bool) {
+    log::info!("set {key} = {value} in {} config", scope_name(global));
+}
+
+/// Last value of a key among config entries
+fn last_value(entries: impl IntoIterator<Item = ConfigEntry>, key: &str) -> Option<String> {
+    entries
+        .into_iter()
+        .filter(|entry| entry.name.eq_ignore_ascii_case(key))
+        .last()
+        .map(|entry| entry.origin.value)
+}
+
+/// Git's reading of a boolean config value
+fn parse_bool(value: &str) -> bool {
+    ["true", "yes", "on", "1"]
+        .iter()
+        .any(|truthy| value.eq_ignore_ascii_case(truthy))
+}
+
+/// Path of the global Git config file (~/.gitconfig unless another one exists)
+pub fn global_config_path() -> Result<PathBuf> {
+    if let Some(path) = backend::find_global() {
+        return Ok(path);
+    }
+
+    let home = home::home_dir().ok_or_else(|| anyhow::anyhow!(tr!("home-dir-unknown")))?;
+    Ok(home.join(".gitconfig"))
+}
+
+/// Write a standalone Git config fragment, replacing any previous content
+///
+/// The new content is built next to the fragment and renamed over it, so a
+/// repository including the fragment never reads a half-written file.
+pub fn write_config_fragment(path: &Path, values: &[(&str, String)]) -> Result<()> {
+    crate::config::journal::record(path);
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| tr!("dir-create-failed", path = parent.display()))?;
+    }
+
+    let staging = path.with_extension("gitconfig.tmp");
+    if staging.exists() {
+        std::fs::remove_file(&staging)
+            .with_context(|| tr!("file-replace-failed", path = staging.display()))?;
+    }
+
+    for (key, value) in values {
+        backend::set_value(&staging, key, value)
+            .with_context(|| tr!("git-set-failed", key = key))?;
+    }
+
+    std::fs::rename(&staging, path)
+        .with_context(|| tr!("file-replace-failed", path = path.display()))?;
+    log::info!("wrote config fragment {}", path.display());
+
+    Ok(())
+}
+
+/// Whether a fragment file holds exactly these values and nothing else
+pub fn config_fragment_matches(path: &Path, values: &[(&str, String)]) -> bool {
+    if !path.exists() {
+        return false;
+    }
+    let entries = backend::file_entries(path);
+
+    entries.len() == values.len()
+        && values.iter().all(|(key, value)| {
+            entries
+                .iter()
+                .rev()
+                .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                .is_some_and(|(_, found)| found == value)
+        })
+}
+
+/// Point `includeIf.gitdir:<gitdir>.path` in a config file at a fragment
+///
+/// Uses the git CLI, which keeps comments and formatting and drops emptied
+/// sections; libgit2 leaves empty headers behind and would reuse their position.
+pub fn set_include_if_in(file: &Path, gitdir: &str, fragment: &Path) -> Result<()> {
+    git_config_file(
+        file,
+        &[
+            "--replace-all",
+            &format!("includeIf.gitdir:{gitdir}.path"),
+            &fragment.to_string_lossy(),
+        ],
+    )
+    .with_context(|| tr!("git-include-add-failed", gitdir = gitdir))
+}
+
+/// Remove `includeIf.gitdir:<gitdir>.path` from a config file (nothing to do if absent)
+pub fn remove_include_if_in(file: &Path, gitdir: &str) -> Result<()> {
+    if !file.exists() {
+        return Ok(());
+    }
+    match git_config_file(
+        file,
+        &["--unset-all", &format!("includeIf.gitdir:{gitdir}.path")],
+    ) {
+        Ok(()) => Ok(()),
+        // Exit code 5: no such key
+        Err(e)
+            if e.downcast_ref::<GitConfigExit>()
+                .is_some_and(|c| c.0 == Some(5)) =>
+        {
+            Ok(())
+        }
+        Err(e) => Err(e).with_context(|| tr!("git-include-remove-failed", gitdir = gitdir)),
+    }
+}
+
+/// Error for commands that need a repository and were run outside one
+pub fn not_a_repo() -> anyhow::Error {
+    Failure::NotARepo.error(tr!("git-not-a-repo"))
+}
+
+/// Exit status of a failed `git config` call
+#[derive(Debug)]
+struct GitConfigExit(Option<i32>, String);
+
+impl std::fmt::Display for GitConfigExit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.1)
+    }
+}
+
+impl std::error::Error for GitConfigExit {}
+
+/// Run `git config --file <file> <args>`
+fn git_config_file(file: &Path, args: &[&str]) -> Result<()> {
+    log::debug!(
+        "running git config --file {} {}",
+        file.display(),
+        args.join(" ")
+    );
+    let output = std::process::Command::new("git")
+        .args(["config", "--file"])
+        .arg(file)
+        .args(args)
+        .output()
+        .with_context(|| tr!("git-failed"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(GitConfigExit(output.status.code(), stderr).into());
+    }
+    Ok(())
+}
+
+/// Extract the host name from a Git remote URL
+pub fn url_host(url: &str) -> Option<String> {
+    GitUrl::parse(url)?.host
+}
+
+/// Config file levels, lowest precedence first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigLevel {
+    System,
+    /// `$XDG_CONFIG_HOME/git/config`
+    Xdg,
+    Global,
+    Local,
+}
+
+/// Where a config value is defined
+#[derive(Debug, Clone)]
+pub struct ValueOrigin {
+    pub value: String,
+    pub level: ConfigLevel,
+    /// Config file, when it can be determined
+    pub file: Option<PathBuf>,
+    /// Defined in a file pulled in through include/includeIf
+    pub included: bool,
+}
+
+impl ValueOrigin {
+    /// Short name of the config level
+    pub fn level_name(&self) -> &'static str {
+        match self.level {
+            ConfigLevel::System => "system",
+            ConfigLevel::Xdg => "xdg",
+            ConfigLevel::Global => "global",
+            ConfigLevel::Local => "local",
+        }
+    }
+}
+
+/// One `key = value` of the resolved config, as the backends list them
+#[derive(Debug, Clone)]
+struct ConfigEntry {
+    /// Section and key lowercased, subsection as written (`credential.https://Host.helper`)
+    name: String,
+    origin: ValueOrigin,
+}
+
+/// User identity settings found in a Git config file
+#[derive(Debug, Clone)]
+pub struct ConfigIdentity {
+    pub name: String,
+    pub email: String,
+    pub signing_key: Option<String>,
+    pub gpg_sign: bool,
+    /// Config file defining the identity
+    pub file: PathBuf,
+    /// `gitdir:` pattern of the includeIf that pulls the file in
+    pub gitdir: Option<String>,
+}
+
+/// Maximum include nesting followed when scanning config files
+const MAX_INCLUDE_DEPTH: usize = 10;
+
+/// Collect distinct user.name/user.email/user.signingkey combinations from the
+/// system, XDG and global config files and every file they include
+pub fn scan_config_identities() -> Vec<ConfigIdentity> {
+    let roots = [
+        backend::find_system(),
+        backend::find_xdg(),
+        global_config_path().ok(),
+    ];
+
+    let mut visited = Vec::new();
+    let mut found: Vec<ConfigIdentity> = Vec::new();
+    for root in roots.into_iter().flatten() {
+        scan_config_file(&root, None, 0, &mut visited, &mut found);
+    }
+    found
+}
+
+fn scan_config_file(
+    path: &Path,
+    gitdir: Option<String>,
+    depth: usize,
+    visited: &mut Vec<PathBuf>,
+    found: &mut Vec<ConfigIdentity>,
+) {
+    if depth > MAX_INCLUDE_DEPTH || !path.is_file() || visited.iter().any(|p| p == path) {
+        return;
+    }
+    visited.push(path.to_path_buf());
+
+    let entries = backend::file_entries(path);
+    let value = |key: &str| {
+        entries
+            .iter()
+            .rev()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value.clone())
+    };
+
+    if let (Some(name), Some(email)) = (value("user.name"), value("user.email")) {
+        let signing_key = value("user.signingkey");
+        let duplicate = found
+            .iter()
+            .any(|f| f.name == name && f.email == email && f.signing_key == signing_key);
+        if !duplicate {
+            found.push(ConfigIdentity {
+                name,
+                email,
+                signing_key,
+                gpg_sign: value("commit.gpgsign").is_some_and(|v| parse_bool(&v)),
+                file: path.to_path_buf(),
+                gitdir: gitdir.clone(),
+            });
+        }
+    }
+
+    // include.path / includeIf.<condition>.path
+    let includes: Vec<(PathBuf, Option<String>)> = entries
+        .iter()
+        .filter(|(name, _)| is_include_key(name))
+        .map(|(name, value)| {
+            let condition = name
+                .strip_prefix("includeif.")
+                .and_then(|rest| rest.strip_suffix(".path"))
+                .and_then(|condition| {
+                    condition
+                        .strip_prefix("gitdir:")
+                        .or_else(|| condition.strip_prefix("gitdir/i:"))
+                })
+                .map(str::to_string);
+            (resolve_include_path(value, Some(path)), condition)
+        })
+        .collect();
+
+    for (include, condition) in includes {
+        scan_config_file(
+            &include,
+            condition.or_else(|| gitdir.clone()),
+            depth + 1,
+            visited,
+            found,
+        );
+    }
+}
+
+/// Whether a config key is an include directive (`include.path` or `includeIf.<condition>.path`)
+fn is_include_key(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    lower == "include.path" || (lower.starts_with("includeif.") && lower.ends_with(".path"))
+}
+
+/// Resolve an include path relative to the including file
+fn resolve_include_path(path: &str, including_file: Option<&Path>) -> PathBuf {
+    if let Some(stripped) = path.strip_prefix("~/") {
+        if let Some(home) = home::home_dir() {
+            return home.join(stripped);
+        }
+    }
+
+    let path = PathBuf::from(path);
+    if path.is_relative() {
+        if let Some(dir) = including_file.and_then(|f| f.parent()) {
+            return dir.join(path);
+        }
+    }
+    path
+}
+
+/// Whether a remote URL is fetched over SSH (scp-like or ssh:// URLs)
+pub fn is_ssh_url(url: &str) -> bool {
+    let url = url.trim();
+    match url.split_once("://") {
+        Some((scheme, _)) => scheme == "ssh" || scheme == "git+ssh",
+        None => url.contains(':') && !url.starts_with('/'),
+    }
+}
+
+/// Open the repository named by `--git-dir` for every later lookup
+///
+/// Sets `GIT_DIR`, so the git processes gid starts use it as well.
+pub fn use_git_dir(dir: &Path) -> Result<()> {
+    let repo = backend::Repo::open(dir).with_context(|| {
+        Failure::NotARepo.context(tr!("git-path-not-a-repo", path = dir.display()))
+    })?;
+    std::env::set_var("GIT_DIR", repo.git_dir());
+    Ok(())
+}
+
+/// Whether a directory is a Git directory itself (a bare repository or a `.git` directory)
+pub fn is_git_dir(path: &Path) -> bool {
+    path.join("HEAD").is_file() && backend::is_git_dir(path)
+}
+
+/// Find all Git repositories under a directory (including the directory itself)
+///
+/// Bare repositories are included; their contents are not searched. Directory
+/// listings are cached and only read again once a directory changes; the
+/// repositories themselves are not, so callers open each one they inspect.
+pub fn discover_repos(root: &Path, max_depth: usize) -> Vec<PathBuf> {
+    repo_index::discover(root, max_depth)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Repository;
+
+    /// Commits with increasing timestamps, the last one merging a side branch
+    fn history(dir: &Path) -> Repository {
+        let repo = Repository::init(dir).unwrap();
+        write_history(&repo);
+        repo
+    }
+
+    fn write_history(repo: &Repository) {
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let commit = |time: i64, message: &str, email: &str, parents: &[&git2::Commit]| {
+            let sig = git2::Signature::new(
+                "Test User",
+                email,
+                &git2::Time::new(1_700_000_000 + time, 0),
+            )
+            .unwrap();
+            let oid = repo
+                .commit(None, &sig, &sig, message, &tree, parents)
+                .unwrap();
+            repo.find_commit(oid).unwrap()
+        };
+        let root = commit(0, "Initial commit", "a@example.com", &[]);
+        let side = commit(1, "Side work", "b@example.com", &[&root]);
+        let main = commit(2, "Main work\n\nWith a body", "a@example.com", &[&root]);
+        let merge = commit(3, "Merge side", "a@example.com", &[&main, &side]);
+        repo.reference("refs/heads/main", merge.id(), true, "test")
+            .unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+    }
+
+    #[test]
+    fn test_commits_newest_first() {
+        // Runs against whichever backend is compiled in (cargo test --features git2)
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = history(dir.path());
+        let git = GitConfigManager::from_path(dir.path()).unwrap();
+
+        let commits: Vec<CommitInfo> = git.commits().unwrap().map(Result::unwrap).collect();
+        let messages: Vec<&str> = commits.iter().map(|c| c.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            ["Merge side", "Main work", "Side work", "Initial commit"]
+        );
+        let head = repo.head().unwrap().target().unwrap().to_string();
+        assert_eq!(commits[0].oid, head);
+        assert_eq!(commits[0].id, head[..7]);
+        assert_eq!(commits[2].author_name, "Test User");
+        assert_eq!(commits[2].author_email, "b@example.com");
+
+        // Stopping early does not read the rest
+        assert_eq!(git.commits().unwrap().take(1).count(), 1);
+    }
+
+    #[cfg(not(feature = "git2"))]
+    #[test]
+    fn test_gix_history_matches_libgit2() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = history(dir.path());
+
+        let mut revwalk = repo.revwalk().unwrap();
+        revwalk.set_sorting(git2::Sort::TIME).unwrap();
+        revwalk.push_head().unwrap();
+        let expected: Vec<String> = revwalk
+            .map(|oid| {
+                let commit = repo.find_commit(oid.unwrap()).unwrap();
+                let id = commit.id().to_string();
+                format!(
+                    "{:?}",
+                    CommitInfo {
+                        id: id[..7].to_string(),
+                        oid: id,
+                        message: commit.summary().unwrap().to_string(),
+                        author_name: commit.author().name().unwrap().to_string(),
+                        author_email: commit.author().email().unwrap().to_string(),
+                    }
+                )
+            })
+            .collect();
+
+        let git = GitConfigManager::from_path(dir.path()).unwrap();
+        let actual: Vec<String> = git
+            .commits()
+            .unwrap()
+            .map(|commit| format!("{:?}", commit.unwrap()))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_rewrite_primitives_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = history(dir.path());
+        let git = GitConfigManager::from_path(dir.path()).unwrap();
+
+        let head = git.resolve_commit("HEAD").unwrap();
+        let root = git.resolve_commit("HEAD~1~1").unwrap();
+        assert_eq!(head, repo.head().unwrap().target().unwrap().to_string());
+        assert_eq!(git.head_branch().as_deref(), Some("refs/heads/main"));
+        assert_eq!(git.find_ref("refs/heads/main").as_ref(), Some(&head));
+        assert!(git.resolve_range("HEAD").unwrap().is_none());
+        assert_eq!(
+            git.resolve_range("HEAD~1~1..main").unwrap(),
+            Some((root.clone(), head.clone()))
+        );
+
+        // Children come before their parents
+        let listed = git.rev_list(&head, Some(&root)).unwrap();
+        assert_eq!(listed.len(), 3);
+        assert_eq!(listed[0], head);
+        let listed = git.rev_list(&head, None).unwrap();
+        assert_eq!(listed.last(), Some(&root));
+
+        let mut commit = git.read_commit(&head).unwrap();
+        assert_eq!(commit.message, "Merge side");
+        assert_eq!(commit.parents.len(), 2);
+        assert_eq!(commit.author.time, 1_700_000_003);
+        commit.author.email = "new@example.com".to_string();
+        let rewritten = git.write_commit(&commit, None).unwrap();
+        assert_ne!(rewritten, head);
+        assert!(git.extract_signature(&rewritten).unwrap().is_none());
+
+        git.set_ref("refs/gid/test", &rewritten, false, "test")
+            .unwrap();
+        assert!(git.set_ref("refs/gid/test", &head, false, "test").is_err());
+        git.detach_head(&rewritten, "test").unwrap();
+        let git = GitConfigManager::from_path(dir.path()).unwrap();
+        assert_eq!(git.head_branch(), None);
+        assert_eq!(git.resolve_commit("HEAD").unwrap(), rewritten);
+        let written = repo
+            .find_commit(git2::Oid::from_str(&rewritten).unwrap())
+            .unwrap();
+        assert_eq!(written.author().email(), Some("new@example.com"));
+        assert_eq!(written.author().when().seconds(), 1_700_000_003);
+        assert_eq!(written.parent_count(), 2);
+    }
+
+    #[test]
+    fn test_verify_ssh_signed_commit() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let key = dir.path().join("key");
+        let status = std::process::Command::new("ssh-keygen")
+            .args([
+                "-q",
+                "-t",
+                "ed25519",
+                "-N",
+                "",
+                "-C",
+                "test@example.com",
+                "-f",
+            ])
+            .arg(&key)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let unsigned = repo
+            .commit(None, &sig, &sig, "Unsigned", &tree, &[])
+            .unwrap();
+        let buffer = repo
+            .commit_create_buffer(&sig, &sig, "Signed", &tree, &[])
+            .unwrap();
+        let output = std::process::Command::new("ssh-keygen")
+            .args(["-Y", "sign", "-n", "git", "-f"])
+            .arg(&key)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .and_then(|mut child| {
+                use std::io::Write;
+                child.stdin.take().unwrap().write_all(&buffer)?;
+                child.wait_with_output()
+            })
+            .unwrap();
+        let signature = String::from_utf8(output.stdout).unwrap();
+        let signed = repo
+            .commit_signed(buffer.as_str().unwrap(), &signature, None)
+            .unwrap();
+
+        let git = GitConfigManager::from_path(dir.path()).unwrap();
+        assert!(git
+            .extract_signature(&unsigned.to_string())
+            .unwrap()
+            .is_none());
+        assert!(matches!(
+            git.verify_commit(&signed.to_string()).unwrap(),
+            Some(SignatureStatus::UnknownKey(_))
+        ));
+
+        let public_key = std::fs::read_to_string(key.with_extension("pub")).unwrap();
+        let allowed = dir.path().join("allowed_signers");
+        std::fs::write(&allowed, format!("test@example.com {public_key}")).unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("gpg.ssh.allowedSignersFile", allowed.to_str().unwrap())
+            .unwrap();
+        let git = GitConfigManager::from_path(dir.path()).unwrap();
+        assert_eq!(
+            git.verify_commit(&signed.to_string()).unwrap(),
+            Some(SignatureStatus::Good("test@example.com".to_string()))
+        );
+
+        let mut tampered = git.extract_signature(&signed.to_string()).unwrap().unwrap();
+        tampered.signed_data.extend_from_slice(b"tampered");
+        assert_eq!(git.verify_signature(&tampered), SignatureStatus::Bad);
+    }
+
+    #[test]
+    fn test_include_if_in_preserves_other_content() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join(".gitconfig");
+        let original = "# my settings\n[core]\n\tautocrlf = false\n";
+        std::fs::write(&file, original).unwrap();
+
+        set_include_if_in(&file, "~/work/", Path::new("/tmp/a.gitconfig")).unwrap();
+        set_include_if_in(&file, "~/work/", Path::new("/tmp/b.gitconfig")).unwrap();
+        let content = std::fs::read_to_string(&file).unwrap();
+        assert!(content.starts_with(original));
+        assert_eq!(content.matches("includeIf").count(), 1);
+        assert!(content.contains("/tmp/b.gitconfig"));
+
+        remove_include_if_in(&file, "~/work/").unwrap();
+        remove_include_if_in(&file, "~/work/").unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), original);
+    }
+
+    #[test]
+    fn test_config_writes_keep_other_content() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("config");
+        let original = "# my settings\n[core]\n\tautocrlf = false\n";
+        std::fs::write(&file, original).unwrap();
+
+        backend::set_value(&file, "user.email", "a@example.com").unwrap();
+        backend::set_value(&file, "user.email", "b@example.com").unwrap();
+        let key = "credential.https://Example.com.helper";
+        backend::add_value(&file, key, "").unwrap();
+        backend::add_value(&file, key, "!gid credential helper work").unwrap();
+        let content = std::fs::read_to_string(&file).unwrap();
+        assert!(content.starts_with(original));
+        assert_eq!(content.matches("email").count(), 1);
+
+        let entries = backend::file_entries(&file);
+        let values = |key: &str| -> Vec<&str> {
+            entries
+                .iter()
+                .filter(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str())
+                .collect()
+        };
+        assert_eq!(values("user.email"), ["b@example.com"]);
+        assert_eq!(values(key), ["", "!gid credential helper work"]);
+
+        backend::unset_all(&file, key).unwrap();
+        backend::unset_all(&file, key).unwrap();
+        backend::unset_all(&file, "user.email").unwrap();
+        assert_eq!(
+            backend::file_entries(&file),
+            [("core.autocrlf".to_string(), "false".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_config_origins_follow_includes() {
+        let dir = tempfile::TempDir::new().unwrap();
+        Repository::init(dir.path()).unwrap();
+        let git_dir = dir.path().join(".git");
+        let local = git_dir.join("config");
+        let mut content = std::fs::read_to_string(&local).unwrap();
+        content.push_str(
+            "[user]\n\temail = first@example.com\n\temail = second@example.com\n\
+             [includeIf \"gitdir:/nowhere/\"]\n\tpath = skipped.gitconfig\n\
+             [include]\n\tpath = work.gitconfig\n",
+        );
+        std::fs::write(&local, content).unwrap();
+        std::fs::write(
+            git_dir.join("skipped.gitconfig"),
+            "[user]\n\temail = skipped@example.com\n",
+        )
+        .unwrap();
+        std::fs::write(
+            git_dir.join("work.gitconfig"),
+            "[user]\n\temail = work@example.com\n",
+        )
+        .unwrap();
+
+        let git = GitConfigManager::from_path(dir.path()).unwrap();
+        let origins: Vec<ValueOrigin> = git
+            .value_origins("user.email")
+            .into_iter()
+            .filter(|o| o.level == ConfigLevel::Local)
+            .collect();
+
+        let values: Vec<&str> = origins.iter().map(|o| o.value.as_str()).collect();
+        assert_eq!(
+            values,
+            [
+                "first@example.com",
+                "second@example.com",
+                "work@example.com"
+            ]
+        );
+        let same_file = |found: Option<&Path>, expected: &Path| {
+            found.map(|f| f.canonicalize().unwrap()) == Some(expected.canonicalize().unwrap())
+        };
+        assert!(same_file(origins[1].file.as_deref(), &local));
+        assert!(!origins[1].included);
+        let winner = origins.last().unwrap();
+        assert!(winner.included);
+        assert!(same_file(
+            winner.file.as_deref(),
+            &git_dir.join("work.gitconfig")
+        ));
+        assert_eq!(
+            git.get_scoped_value("user.email", false).as_deref(),
+            Some("work@example.com")
+        );
+    }
+
+    #[test]
+    fn test_config_fragment_matches_exactly() {
+        crate::config::journal::suspend();
+        let dir = tempfile::TempDir::new().unwrap();
+        let fragment = dir.path().join("work.gitconfig");
+        let values = [("user.email", "work@example.com".to_string())];
+
+        assert!(!config_fragment_matches(&fragment, &values));
+        write_config_fragment(&fragment, &values).unwrap();
+        assert!(config_fragment_matches(&fragment, &values));
+
+        let extra = [
+            ("user.email", "work@example.com".to_string()),
+            ("user.name", "Work User".to_string()),
+        ];
+        write_config_fragment(&fragment, &extra).unwrap();
+        assert!(!config_fragment_matches(&fragment, &values));
+        assert!(!dir.path().join("work.gitconfig.tmp").exists());
+    }
+}
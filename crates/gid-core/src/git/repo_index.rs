@@ -0,0 +1,250 @@
+//! Cached index of the directories repository scans walk
+//!
+//! Each directory's subdirectories and whether it is a repository are kept
+//! with its modification time. Adding, removing or renaming an entry changes
+//! a directory's mtime, so unchanged directories are not listed again and a
+//! scan over thousands of them costs one stat call each.
+//!
+//! Only the walk is cached. Repository contents (remotes, identity settings)
+//! change without touching these directories, so callers still open each
+//! repository the scan returns.
+//!
+//! Entries are keyed by canonical path, so `.`, `./src` and `~/src` given as
+//! roots share them and never drop each other's entries.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::atomic;
+use crate::config::Config;
+
+const INDEX_FILE: &str = "repos.toml";
+
+/// Listings read this soon after the directory changed are not trusted: a
+/// second change within the filesystem's timestamp granularity would go unseen
+const RACY_NANOS: u64 = 2_000_000_000;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RepoIndex {
+    #[serde(default)]
+    dirs: BTreeMap<String, DirEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DirEntry {
+    /// Modification time, nanoseconds since the epoch
+    mtime: u64,
+    /// When the directory was listed, nanoseconds since the epoch
+    read_at: u64,
+    #[serde(default)]
+    repo: bool,
+    #[serde(default)]
+    bare: bool,
+    /// Subdirectories, unless the directory was only checked at the depth limit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    subdirs: Option<Vec<String>>,
+}
+
+impl DirEntry {
+    fn read(dir: &Path, mtime: u64, list: bool) -> Self {
+        let repo = dir.join(".git").exists();
+        let bare = !repo && dir.file_name().is_some_and(|n| n != ".git") && super::is_git_dir(dir);
+        let subdirs = (list && !bare).then(|| {
+            let mut subdirs: Vec<String> = fs::read_dir(dir)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()) && e.file_name() != ".git")
+                .map(|e| e.file_name().to_string_lossy().into_owned())
+                .collect();
+            subdirs.sort();
+            subdirs
+        });
+
+        Self {
+            mtime,
+            read_at: now_nanos(),
+            repo: repo || bare,
+            bare,
+            subdirs,
+        }
+    }
+
+    fn is_current(&self, mtime: u64, list: bool) -> bool {
+        self.mtime == mtime
+            && self.read_at >= mtime.saturating_add(RACY_NANOS)
+            && (!list || self.bare || self.subdirs.is_some())
+    }
+}
+
+/// Find all Git repositories under `root`, reusing and refreshing the index
+pub fn discover(root: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let Ok(canonical) = fs::canonicalize(root) else {
+        return Vec::new();
+    };
+
+    let mut index = load().unwrap_or_default();
+    let mut visited = BTreeMap::new();
+    let mut repos = Vec::new();
+    visit(&index, &mut visited, &canonical, 0, max_depth, &mut repos);
+
+    // Directories under the root that were not reached are gone
+    index
+        .dirs
+        .retain(|path, _| !Path::new(path).starts_with(&canonical));
+    index.dirs.extend(visited);
+    let _ = save(&index);
+
+    repos
+        .iter()
+        .map(|repo| relocate(repo, &canonical, root))
+        .collect()
+}
+
+/// Spell a path found under the canonical root the way the caller gave the root
+fn relocate(path: &Path, canonical: &Path, root: &Path) -> PathBuf {
+    match path.strip_prefix(canonical) {
+        Ok(rest) if rest.as_os_str().is_empty() => root.to_path_buf(),
+        Ok(rest) => root.join(rest),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+fn visit(
+    index: &RepoIndex,
+    visited: &mut BTreeMap<String, DirEntry>,
+    dir: &Path,
+    depth: usize,
+    max_depth: usize,
+    repos: &mut Vec<PathBuf>,
+) {
+    let Some(mtime) = mtime_nanos(dir) else {
+        return;
+    };
+    let key = dir.to_string_lossy().into_owned();
+    let list = depth < max_depth;
+
+    let entry = match index.dirs.get(&key) {
+        Some(entry) if entry.is_current(mtime, list) => entry.clone(),
+        _ => DirEntry::read(dir, mtime, list),
+    };
+    if entry.repo {
+        repos.push(dir.to_path_buf());
+    }
+    if list && !entry.bare {
+        for name in entry.subdirs.iter().flatten() {
+            visit(index, visited, &dir.join(name), depth + 1, max_depth, repos);
+        }
+    }
+    visited.insert(key, entry);
+}
+
+fn mtime_nanos(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_nanos() as u64)
+}
+
+fn now_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
+}
+
+fn index_path() -> Result<PathBuf> {
+    Ok(Config::cache_dir()?.join(INDEX_FILE))
+}
+
+fn load() -> Option<RepoIndex> {
+    let content = fs::read_to_string(index_path().ok()?).ok()?;
+    toml::from_str(&content).ok()
+}
+
+fn save(index: &RepoIndex) -> Result<()> {
+    let path = index_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = toml::to_string(index)?;
+    atomic::write(&path, &content, |written| {
+        toml::from_str::<RepoIndex>(written)?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(index: &RepoIndex, root: &Path, max_depth: usize) -> Vec<PathBuf> {
+        let mut repos = Vec::new();
+        visit(index, &mut BTreeMap::new(), root, 0, max_depth, &mut repos);
+        repos
+    }
+
+    #[test]
+    fn test_visit_finds_repos_within_depth() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        git2::Repository::init(root.join("a")).unwrap();
+        git2::Repository::init(root.join("a/nested")).unwrap();
+        git2::Repository::init_bare(root.join("mirror.git")).unwrap();
+        git2::Repository::init(root.join("x/y/deep")).unwrap();
+
+        let index = RepoIndex::default();
+        assert_eq!(
+            scan(&index, root, 5),
+            [
+                root.join("a"),
+                root.join("a/nested"),
+                root.join("mirror.git"),
+                root.join("x/y/deep")
+            ]
+        );
+        assert_eq!(
+            scan(&index, root, 1),
+            [root.join("a"), root.join("mirror.git")]
+        );
+    }
+
+    #[test]
+    fn test_relocate_keeps_the_given_root() {
+        let canonical = Path::new("/home/me/src");
+        assert_eq!(
+            relocate(Path::new("/home/me/src/api"), canonical, Path::new(".")),
+            Path::new("./api")
+        );
+        assert_eq!(
+            relocate(canonical, canonical, Path::new(".")),
+            Path::new(".")
+        );
+    }
+
+    #[test]
+    fn test_unchanged_directories_are_not_listed_again() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        let mtime = mtime_nanos(root).unwrap();
+
+        // A listing recorded well after the last change is trusted as is
+        let mut index = RepoIndex::default();
+        index.dirs.insert(
+            root.to_string_lossy().into_owned(),
+            DirEntry {
+                mtime,
+                read_at: mtime + RACY_NANOS,
+                repo: true,
+                bare: false,
+                subdirs: Some(Vec::new()),
+            },
+        );
+        assert_eq!(scan(&index, root, 5), [root.to_path_buf()]);
+
+        // Once the directory changes it is read again
+        fs::create_dir(root.join("sub")).unwrap();
+        assert!(scan(&index, root, 5).is_empty());
+    }
+}
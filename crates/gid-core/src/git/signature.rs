@@ -0,0 +1,276 @@
+//! Commit signature verification through gpg and ssh-keygen
+//!
+//! Mirrors what `git verify-commit` runs, so audit, doctor and the signing
+//! checks of `gid verify` agree with Git about which signatures are good.
+
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::i18n::tr;
+
+/// Kind of signature, told apart by its armor header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureFormat {
+    Gpg,
+    Ssh,
+    X509,
+}
+
+impl SignatureFormat {
+    fn detect(signature: &str) -> Self {
+        if signature.starts_with("-----BEGIN SSH SIGNATURE-----") {
+            Self::Ssh
+        } else if signature.starts_with("-----BEGIN SIGNED MESSAGE-----") {
+            Self::X509
+        } else {
+            Self::Gpg
+        }
+    }
+}
+
+/// A detached signature and the content it covers
+#[derive(Debug, Clone)]
+pub struct CommitSignature {
+    pub format: SignatureFormat,
+    pub signature: String,
+    pub signed_data: Vec<u8>,
+}
+
+impl CommitSignature {
+    pub fn new(signature: String, signed_data: Vec<u8>) -> Self {
+        Self {
+            format: SignatureFormat::detect(&signature),
+            signature,
+            signed_data,
+        }
+    }
+}
+
+/// Result of checking a signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// Valid signature; the signer's user id or SSH principal
+    Good(String),
+    /// Valid signature made with a key that has since expired
+    Expired(String),
+    /// The signature does not match the content, or the key was revoked
+    Bad,
+    /// The key is not in the keyring or the allowed signers file
+    UnknownKey(String),
+    /// The signature could not be checked at all
+    Error(String),
+}
+
+impl std::fmt::Display for SignatureStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Good(signer) => write!(f, "{}", tr!("signature-good", signer = signer)),
+            Self::Expired(signer) => write!(f, "{}", tr!("signature-expired", signer = signer)),
+            Self::Bad => write!(f, "{}", tr!("signature-bad")),
+            Self::UnknownKey(key) => write!(f, "{}", tr!("signature-unknown-key", key = key)),
+            Self::Error(e) => write!(f, "{}", tr!("signature-error", error = e)),
+        }
+    }
+}
+
+/// Programs and files Git is configured to verify signatures with
+#[derive(Debug, Clone)]
+pub struct Verifier {
+    /// `gpg.openpgp.program` / `gpg.program`
+    pub gpg_program: String,
+    /// `gpg.ssh.program`
+    pub ssh_program: String,
+    /// `gpg.ssh.allowedSignersFile`
+    pub allowed_signers: Option<PathBuf>,
+}
+
+impl Default for Verifier {
+    fn default() -> Self {
+        Self {
+            gpg_program: "gpg".to_string(),
+            ssh_program: "ssh-keygen".to_string(),
+            allowed_signers: None,
+        }
+    }
+}
+
+impl Verifier {
+    /// Check a signature against the content it covers
+    pub fn verify(&self, signature: &CommitSignature) -> SignatureStatus {
+        let result = match signature.format {
+            SignatureFormat::Gpg => self.verify_gpg(signature),
+            SignatureFormat::Ssh => self.verify_ssh(signature),
+            SignatureFormat::X509 => {
+                return SignatureStatus::Error(tr!("signature-x509-unsupported"))
+            }
+        };
+        result.unwrap_or_else(|e| SignatureStatus::Error(e.to_string()))
+    }
+
+    fn verify_gpg(&self, signature: &CommitSignature) -> Result<SignatureStatus> {
+        let file = TempFile::write(signature.signature.as_bytes())?;
+        let output = run(
+            Command::new(&self.gpg_program)
+                .args(["--status-fd=1", "--verify"])
+                .arg(file.path())
+                .arg("-"),
+            &signature.signed_data,
+        )?;
+        Ok(parse_gpg_status(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    fn verify_ssh(&self, signature: &CommitSignature) -> Result<SignatureStatus> {
+        let file = TempFile::write(signature.signature.as_bytes())?;
+
+        let Some(allowed_signers) = &self.allowed_signers else {
+            // Without allowed signers only the signature itself can be checked
+            let output = run(
+                Command::new(&self.ssh_program)
+                    .args(["-Y", "check-novalidate", "-n", "git", "-s"])
+                    .arg(file.path()),
+                &signature.signed_data,
+            )?;
+            return Ok(if output.status.success() {
+                SignatureStatus::UnknownKey(tr!("signature-no-allowed-signers"))
+            } else {
+                SignatureStatus::Bad
+            });
+        };
+
+        let mut find_principals = Command::new(&self.ssh_program);
+        find_principals
+            .args(["-Y", "find-principals", "-f"])
+            .arg(allowed_signers)
+            .arg("-s")
+            .arg(file.path());
+        log::debug!("running {find_principals:?}");
+        let output = find_principals
+            .output()
+            .with_context(|| tr!("program-failed", program = self.ssh_program))?;
+        let principals = String::from_utf8_lossy(&output.stdout);
+        let principal = principals.lines().next().unwrap_or("").trim();
+        if !output.status.success() || principal.is_empty() {
+            return Ok(SignatureStatus::UnknownKey(tr!("signature-not-allowed")));
+        }
+
+        let output = run(
+            Command::new(&self.ssh_program)
+                .args(["-Y", "verify", "-n", "git", "-f"])
+                .arg(allowed_signers)
+                .arg("-I")
+                .arg(principal)
+                .arg("-s")
+                .arg(file.path()),
+            &signature.signed_data,
+        )?;
+        Ok(if output.status.success() {
+            SignatureStatus::Good(principal.to_string())
+        } else {
+            SignatureStatus::Bad
+        })
+    }
+}
+
+/// Run a command with `input` on stdin
+fn run(command: &mut Command, input: &[u8]) -> Result<Output> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    log::debug!("running {command:?}");
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| tr!("program-failed", program = program))?;
+    child
+        .stdin
+        .take()
+        .with_context(|| tr!("program-write-failed", program = program))?
+        .write_all(input)?;
+    Ok(child.wait_with_output()?)
+}
+
+/// Outcome reported on gpg's `--status-fd` channel
+fn parse_gpg_status(status: &str) -> SignatureStatus {
+    for line in status.lines() {
+        let Some(line) = line.strip_prefix("[GNUPG:] ") else {
+            continue;
+        };
+        let (keyword, args) = line.split_once(' ').unwrap_or((line, ""));
+        let (key_id, user) = args.split_once(' ').unwrap_or((args, ""));
+        match keyword {
+            "GOODSIG" => return SignatureStatus::Good(user.to_string()),
+            "EXPKEYSIG" => return SignatureStatus::Expired(user.to_string()),
+            "BADSIG" | "REVKEYSIG" => return SignatureStatus::Bad,
+            "NO_PUBKEY" => return SignatureStatus::UnknownKey(key_id.to_string()),
+            _ => {}
+        }
+    }
+    SignatureStatus::Error(tr!("signature-gpg-none"))
+}
+
+/// Signature file handed to the verifier, removed once dropped
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn write(content: &[u8]) -> Result<Self> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            ".gid-signature-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::write(&path, content)
+            .with_context(|| tr!("file-write-failed", path = path.display()))?;
+        Ok(Self(path))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gpg_status() {
+        let good = "[GNUPG:] NEWSIG\n\
+                    [GNUPG:] GOODSIG ABCD1234EF567890 Work User <work@example.com>\n\
+                    [GNUPG:] VALIDSIG 0123\n";
+        assert_eq!(
+            parse_gpg_status(good),
+            SignatureStatus::Good("Work User <work@example.com>".to_string())
+        );
+
+        let missing = "[GNUPG:] ERRSIG ABCD1234EF567890 1 10 00 1700000000 9 -\n\
+                       [GNUPG:] NO_PUBKEY ABCD1234EF567890\n";
+        assert_eq!(
+            parse_gpg_status(missing),
+            SignatureStatus::UnknownKey("ABCD1234EF567890".to_string())
+        );
+
+        assert_eq!(
+            parse_gpg_status("[GNUPG:] BADSIG ABCD1234EF567890 Work User\n"),
+            SignatureStatus::Bad
+        );
+        assert!(matches!(parse_gpg_status(""), SignatureStatus::Error(_)));
+    }
+
+    #[test]
+    fn test_detect_format() {
+        let ssh = CommitSignature::new("-----BEGIN SSH SIGNATURE-----\n".to_string(), Vec::new());
+        assert_eq!(ssh.format, SignatureFormat::Ssh);
+        let gpg = CommitSignature::new("-----BEGIN PGP SIGNATURE-----\n".to_string(), Vec::new());
+        assert_eq!(gpg.format, SignatureFormat::Gpg);
+    }
+}
@@ -0,0 +1,196 @@
+//! Parser for the remote URL forms Git accepts
+//!
+//! `scheme://[user@]host[:port]/path` (ssh, git, http(s), file), scp-like
+//! `[user@]host:path` and plain local paths.
+
+/// Components of a Git remote URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitUrl {
+    /// `ssh` for scp-like URLs, `file` for local paths
+    pub scheme: String,
+    pub user: Option<String>,
+    /// Lowercased; `None` for local repositories
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    /// Repository path without trailing slashes or `.git`, percent-decoded;
+    /// relative to the host when there is one
+    pub path: String,
+}
+
+impl GitUrl {
+    /// Parse a remote URL; `None` if it is empty or malformed
+    pub fn parse(url: &str) -> Option<Self> {
+        let url = url.trim();
+        if url.is_empty() {
+            return None;
+        }
+
+        if let Some((scheme, rest)) = url.split_once("://") {
+            if scheme.is_empty()
+                || !scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+            {
+                return None;
+            }
+            let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+            let (user, host, port) = split_authority(authority)?;
+            let has_host = host.is_some();
+            return Some(Self {
+                scheme: scheme.to_lowercase(),
+                user: user.map(percent_decode),
+                host,
+                port,
+                path: clean_path(&percent_decode(path), has_host),
+            });
+        }
+
+        // scp-like syntax, when a colon comes before the first slash (C:\ is a drive)
+        if let Some(colon) = url.find(':') {
+            let is_drive = colon == 1 && url.as_bytes()[0].is_ascii_alphabetic();
+            if !url[..colon].contains('/') && !is_drive {
+                let (user, host) = match url[..colon].rsplit_once('@') {
+                    Some((user, host)) => (Some(user.to_string()), host),
+                    None => (None, &url[..colon]),
+                };
+                let host = host.trim_start_matches('[').trim_end_matches(']');
+                if host.is_empty() {
+                    return None;
+                }
+                return Some(Self {
+                    scheme: "ssh".to_string(),
+                    user,
+                    host: Some(host.to_lowercase()),
+                    port: None,
+                    path: clean_path(&url[colon + 1..], true),
+                });
+            }
+        }
+
+        Some(Self {
+            scheme: "file".to_string(),
+            user: None,
+            host: None,
+            port: None,
+            path: clean_path(url, false),
+        })
+    }
+
+    /// Owner part of the path (`org`, or `group/subgroup` on GitLab)
+    pub fn owner(&self) -> Option<&str> {
+        self.path.rsplit_once('/').map(|(owner, _)| owner)
+    }
+
+    /// Repository name, the last path segment
+    pub fn repo(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+
+    /// `host/path` form that rules match against (scheme, user and port dropped)
+    pub fn normalized(&self) -> String {
+        match &self.host {
+            Some(host) => format!("{host}/{}", self.path),
+            None => self.path.clone(),
+        }
+    }
+}
+
+/// Split `[user@]host[:port]`, with brackets around IPv6 hosts
+fn split_authority(authority: &str) -> Option<(Option<&str>, Option<String>, Option<u16>)> {
+    let (user, host_port) = match authority.rsplit_once('@') {
+        Some((user, host_port)) => (Some(user), host_port),
+        None => (None, authority),
+    };
+
+    let (host, port) = if let Some(rest) = host_port.strip_prefix('[') {
+        let (host, after) = rest.split_once(']')?;
+        (host, after.strip_prefix(':'))
+    } else {
+        match host_port.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (host_port, None),
+        }
+    };
+
+    let port = match port {
+        Some("") | None => None,
+        Some(port) => Some(port.parse().ok()?),
+    };
+    let host = (!host.is_empty()).then(|| host.to_lowercase());
+    Some((user, host, port))
+}
+
+/// Drop trailing slashes and `.git`; the leading slash too when there is a host
+fn clean_path(path: &str, has_host: bool) -> String {
+    let path = path.trim_end_matches('/');
+    let path = path
+        .strip_suffix(".git")
+        .unwrap_or(path)
+        .trim_end_matches('/');
+    if has_host {
+        path.trim_start_matches('/').to_string()
+    } else {
+        path.to_string()
+    }
+}
+
+/// Decode `%xx` escapes; invalid escapes are kept as they are
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            if let Some(byte) = hex {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_forms() {
+        let ssh = GitUrl::parse("ssh://git@Git.Company.com:2222/team/api.git/").unwrap();
+        assert_eq!(ssh.scheme, "ssh");
+        assert_eq!(ssh.user.as_deref(), Some("git"));
+        assert_eq!(ssh.host.as_deref(), Some("git.company.com"));
+        assert_eq!(ssh.port, Some(2222));
+        assert_eq!(ssh.normalized(), "git.company.com/team/api");
+
+        let scp = GitUrl::parse("git@github.com:acme/tools.git").unwrap();
+        assert_eq!(scp.scheme, "ssh");
+        assert_eq!(scp.owner(), Some("acme"));
+        assert_eq!(scp.repo(), "tools");
+
+        let https = GitUrl::parse("https://gitlab.com/group/sub%20group/repo/").unwrap();
+        assert_eq!(https.owner(), Some("group/sub group"));
+        assert_eq!(https.normalized(), "gitlab.com/group/sub group/repo");
+
+        let git = GitUrl::parse("git://[::1]:9418/repo.git").unwrap();
+        assert_eq!(git.host.as_deref(), Some("::1"));
+        assert_eq!(git.port, Some(9418));
+        assert_eq!(git.owner(), None);
+
+        let file = GitUrl::parse("file:///srv/git/api.git").unwrap();
+        assert_eq!(file.host, None);
+        assert_eq!(file.normalized(), "/srv/git/api");
+        assert_eq!(GitUrl::parse("../lib").unwrap().scheme, "file");
+        assert_eq!(GitUrl::parse("C:/repos/api").unwrap().scheme, "file");
+
+        assert_eq!(GitUrl::parse("ssh://host:notaport/repo"), None);
+        assert_eq!(GitUrl::parse(""), None);
+    }
+}
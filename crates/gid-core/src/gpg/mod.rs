@@ -1,7 +1,11 @@
 use anyhow::{Context, Result};
-use std::process::Command;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::i18n::tr;
 
 /// GPG 管理器
+#[derive(Default)]
 pub struct GpgManager;
 
 impl GpgManager {
@@ -24,7 +28,7 @@ impl GpgManager {
         let output = Command::new("gpg")
             .args(["--list-secret-keys", "--keyid-format", "long"])
             .output()
-            .context("无法执行 gpg 命令")?;
+            .with_context(|| tr!("gpg-failed"))?;
 
         if !output.status.success() {
             return Ok(Vec::new());
@@ -114,10 +118,92 @@ impl GpgManager {
         let output = Command::new("gpg")
             .args(["--list-secret-keys", key_id])
             .output()
-            .context("无法执行 gpg 命令")?;
+            .with_context(|| tr!("gpg-failed"))?;
 
         Ok(output.status.success())
     }
+
+    /// 查询私钥状态（是否存在、过期或吊销）
+    pub fn key_status(&self, key_id: &str) -> Result<KeyStatus> {
+        let output = Command::new("gpg")
+            .args(["--list-secret-keys", "--with-colons", key_id])
+            .output()
+            .with_context(|| tr!("gpg-failed"))?;
+
+        if !output.status.success() {
+            return Ok(KeyStatus::Missing);
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Ok(parse_key_status(&stdout, chrono::Utc::now().timestamp()))
+    }
+
+    /// 生成 ASCII armor 格式的分离签名（用于提交签名）
+    pub fn sign(&self, key_id: &str, data: &[u8]) -> Result<String> {
+        let mut child = Command::new("gpg")
+            .args([
+                "--batch",
+                "--local-user",
+                key_id,
+                "--detach-sign",
+                "--armor",
+                "--output",
+                "-",
+            ])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| tr!("gpg-failed"))?;
+        child
+            .stdin
+            .take()
+            .with_context(|| tr!("gpg-write-failed"))?
+            .write_all(data)?;
+
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            match stderr.lines().last() {
+                Some(line) => anyhow::bail!("{}", line.trim()),
+                None => anyhow::bail!(tr!("gpg-sign-failed")),
+            }
+        }
+
+        String::from_utf8(output.stdout).with_context(|| tr!("gpg-signature-not-text"))
+    }
+}
+
+/// 私钥状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyStatus {
+    Valid,
+    Expired,
+    Revoked,
+    Missing,
+}
+
+/// 解析 `--with-colons` 输出中的 sec 行：第 2 列为有效性，第 7 列为过期时间
+fn parse_key_status(output: &str, now: i64) -> KeyStatus {
+    let Some(sec) = output.lines().find(|line| line.starts_with("sec:")) else {
+        return KeyStatus::Missing;
+    };
+    let fields: Vec<&str> = sec.split(':').collect();
+
+    match fields.get(1).copied() {
+        Some("r") => return KeyStatus::Revoked,
+        Some("e") => return KeyStatus::Expired,
+        _ => {}
+    }
+
+    let expires = fields
+        .get(6)
+        .and_then(|field| field.parse::<i64>().ok())
+        .filter(|&expires| expires > 0);
+    match expires {
+        Some(expires) if expires <= now => KeyStatus::Expired,
+        _ => KeyStatus::Valid,
+    }
 }
 
 /// GPG 密钥信息
@@ -133,3 +219,23 @@ impl std::fmt::Display for GpgKey {
         write!(f, "{} - {}", self.key_id, self.uid)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key_status() {
+        let valid = "sec:u:4096:1:ABCD1234EF567890:1672531200:::u:::scESC:::+:::23::0:\n";
+        assert_eq!(parse_key_status(valid, 1700000000), KeyStatus::Valid);
+
+        let expiring =
+            "sec:u:4096:1:ABCD1234EF567890:1672531200:1680000000::u:::scESC:::+:::23::0:\n";
+        assert_eq!(parse_key_status(expiring, 1700000000), KeyStatus::Expired);
+
+        let revoked = "sec:r:4096:1:ABCD1234EF567890:1672531200:::u:::sc:::+:::23::0:\n";
+        assert_eq!(parse_key_status(revoked, 1700000000), KeyStatus::Revoked);
+
+        assert_eq!(parse_key_status("", 1700000000), KeyStatus::Missing);
+    }
+}
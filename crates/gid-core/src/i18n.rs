@@ -0,0 +1,177 @@
+//! Localized user-facing messages
+//!
+//! Catalogs are `locales/<lang>.ftl` files in Fluent syntax, compiled into the
+//! binary. The language comes from `settings.language`, else LC_ALL,
+//! LC_MESSAGES or LANG; English is used for other languages and for messages a
+//! catalog does not have yet.
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use std::sync::OnceLock;
+use unic_langid::LanguageIdentifier;
+
+use crate::config::Config;
+
+/// Languages with a catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    En,
+    Zh,
+}
+
+impl Language {
+    fn catalog(self) -> &'static str {
+        match self {
+            Self::En => include_str!("../locales/en.ftl"),
+            Self::Zh => include_str!("../locales/zh.ftl"),
+        }
+    }
+
+    fn id(self) -> LanguageIdentifier {
+        match self {
+            Self::En => "en".parse().unwrap(),
+            Self::Zh => "zh".parse().unwrap(),
+        }
+    }
+
+    /// Match a locale such as `zh_CN.UTF-8`, `zh-Hans` or `en`
+    fn from_locale(locale: &str) -> Option<Self> {
+        let tag = locale.split(['.', '@']).next()?.replace('_', "-");
+        let id: LanguageIdentifier = tag.parse().ok()?;
+        match id.language.as_str() {
+            "zh" => Some(Self::Zh),
+            "en" => Some(Self::En),
+            _ => None,
+        }
+    }
+
+    fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|locale| Self::from_locale(&locale))
+            .unwrap_or(Self::En)
+    }
+}
+
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+/// Pick the language once per process, after `--config` has been applied
+pub fn init() {
+    // A broken config is reported by the command itself, in the environment's language
+    let setting = Config::load().ok().and_then(|c| c.settings.language);
+    let language = setting
+        .as_deref()
+        .and_then(Language::from_locale)
+        .unwrap_or_else(Language::from_env);
+    let _ = LANGUAGE.set(language);
+}
+
+pub fn language() -> Language {
+    *LANGUAGE.get_or_init(Language::from_env)
+}
+
+fn bundle(language: Language) -> &'static FluentBundle<FluentResource> {
+    static EN: OnceLock<FluentBundle<FluentResource>> = OnceLock::new();
+    static ZH: OnceLock<FluentBundle<FluentResource>> = OnceLock::new();
+
+    let cell = match language {
+        Language::En => &EN,
+        Language::Zh => &ZH,
+    };
+    cell.get_or_init(|| {
+        let resource = FluentResource::try_new(language.catalog().to_string())
+            .unwrap_or_else(|(resource, _)| resource);
+        let mut bundle = FluentBundle::new_concurrent(vec![language.id()]);
+        // Terminals render the bidi isolation marks around arguments as garbage
+        bundle.set_use_isolating(false);
+        let _ = bundle.add_resource(resource);
+        bundle
+    })
+}
+
+/// Message `id` in the current language, with `args` filled in
+///
+/// Falls back to English, then to the id itself.
+pub fn message(id: &str, args: &[(&str, String)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+
+    format(language(), id, &fluent_args)
+        .or_else(|| format(Language::En, id, &fluent_args))
+        .unwrap_or_else(|| id.to_string())
+}
+
+fn format(language: Language, id: &str, args: &FluentArgs) -> Option<String> {
+    let bundle = bundle(language);
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = Vec::new();
+    Some(
+        bundle
+            .format_pattern(pattern, Some(args), &mut errors)
+            .into_owned(),
+    )
+}
+
+/// `tr!("message-id")` or `tr!("message-id", path = path.display())`
+#[macro_export]
+macro_rules! tr {
+    ($id:literal) => {
+        $crate::i18n::message($id, &[])
+    };
+    ($id:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::message($id, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+pub use crate::tr;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(language: Language) -> Vec<&'static str> {
+        language
+            .catalog()
+            .lines()
+            .filter(|line| line.starts_with(|c: char| c.is_ascii_lowercase()))
+            .filter_map(|line| line.split_once(" = ").map(|(id, _)| id))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalogs_have_the_same_messages() {
+        assert_eq!(ids(Language::En), ids(Language::Zh));
+        for language in [Language::En, Language::Zh] {
+            for id in ids(language) {
+                assert!(bundle(language).has_message(id), "{language:?} {id}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_locale() {
+        assert_eq!(Language::from_locale("zh_CN.UTF-8"), Some(Language::Zh));
+        assert_eq!(Language::from_locale("zh-Hant-TW"), Some(Language::Zh));
+        assert_eq!(Language::from_locale("en_US"), Some(Language::En));
+        assert_eq!(Language::from_locale("C"), None);
+        assert_eq!(Language::from_locale("de_DE.UTF-8"), None);
+    }
+
+    #[test]
+    fn test_format_args() {
+        let mut args = FluentArgs::new();
+        args.set("id", "work");
+        assert_eq!(
+            format(Language::En, "identity-not-found", &args).as_deref(),
+            Some("Identity 'work' not found")
+        );
+        assert_eq!(
+            format(Language::Zh, "identity-not-found", &args).as_deref(),
+            Some("找不到身份 'work'")
+        );
+        assert_eq!(format(Language::En, "no-such-message", &args), None);
+    }
+}
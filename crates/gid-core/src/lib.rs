@@ -0,0 +1,47 @@
+//! Git identity management without the CLI
+//!
+//! The library behind `gid`: the identity configuration, the rules that pick
+//! an identity for a repository, and the Git, SSH and GPG configuration they
+//! translate into. Nothing here prints or prompts; functions return values
+//! and `anyhow` errors, and the `gid` binary is a thin layer that formats them.
+//!
+//! - [`config`]: `config.toml`, `.gid` project files, usage state and the undo journal
+//! - [`rules`]: matching paths, remotes and hosts to identities ([`rules::resolve`])
+//! - [`git`]: reading and writing Git configuration, history and signatures
+//! - [`ssh`] / [`gpg`]: keys, `~/.ssh/config` host aliases and signing
+//! - [`audit`]: checking commit history against the configured identities
+//!
+//! Checking which identity a repository should use, as `gid repos` does:
+//!
+//! ```no_run
+//! use gid_core::config::Config;
+//! use gid_core::rules::resolve::RepoStatus;
+//! use std::path::Path;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let config = Config::load()?;
+//! let status = RepoStatus::inspect(&config, Path::new("/src/api"))?;
+//! if let Some(expected) = &status.expected {
+//!     println!("{} from {}", expected.identity, expected.source);
+//! }
+//! if status.is_drifted(&config) {
+//!     println!("in use: {:?}", status.effective_email);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Errors carry an [`exit::Failure`] class where one applies, and user-facing
+//! messages go through the [`i18n`] catalogs.
+
+pub mod atomic;
+pub mod audit;
+pub mod config;
+pub mod exit;
+pub mod git;
+pub mod gpg;
+pub mod i18n;
+pub mod offline;
+pub mod permissions;
+pub mod rules;
+pub mod ssh;
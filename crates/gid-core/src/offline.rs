@@ -0,0 +1,40 @@
+//! Offline mode: no network access at all
+//!
+//! `--offline` or `settings.offline = true` (e.g. on air-gapped machines)
+//! turns every feature that would reach a forge, API or download URL into a
+//! skipped check or a [`Failure::Network`] error before anything is sent.
+
+use anyhow::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::config::Config;
+use crate::exit::Failure;
+use crate::i18n::tr;
+
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Decide once per process, after `--config` has been applied
+pub fn init(flag: bool) {
+    // A broken config is reported by the command itself
+    let offline = flag || Config::load().is_ok_and(|config| config.settings.offline);
+    set(offline);
+}
+
+/// Turn offline mode on or off for this process
+pub fn set(offline: bool) {
+    OFFLINE.store(offline, Ordering::Relaxed);
+}
+
+/// Whether network access is disabled
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::Relaxed)
+}
+
+/// Fail with a network error when offline; `action` says what needed the network
+pub fn ensure_online(action: &str) -> Result<()> {
+    if is_offline() {
+        log::debug!("offline: {action} skipped");
+        return Err(Failure::Network.error(tr!("offline-needs-network", action = action)));
+    }
+    Ok(())
+}
@@ -0,0 +1,152 @@
+//! Owner-only access for keys, the SSH config and stored credentials
+//!
+//! OpenSSH refuses private keys other users can read. On Unix that means mode
+//! 600 (700 for directories); on Windows the file's ACL may only grant access
+//! to the owner, SYSTEM and Administrators, which `icacls` sets and reports.
+
+use anyhow::Result;
+use std::path::Path;
+
+/// Restrict `path` to its owner: 600 / 700 on Unix, an owner-only ACL on Windows
+pub fn restrict_to_owner(path: &Path) -> Result<()> {
+    imp::restrict_to_owner(path)
+}
+
+/// Who else can access `path`, or `None` when only the owner can
+pub fn check_owner_only(path: &Path) -> Result<Option<String>> {
+    imp::check_owner_only(path)
+}
+
+#[cfg(unix)]
+mod imp {
+    use anyhow::{Context, Result};
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+
+    use crate::i18n::tr;
+
+    pub fn restrict_to_owner(path: &Path) -> Result<()> {
+        let mode = if path.is_dir() { 0o700 } else { 0o600 };
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))
+            .with_context(|| tr!("permissions-restrict-failed", path = path.display()))
+    }
+
+    pub fn check_owner_only(path: &Path) -> Result<Option<String>> {
+        let mode = fs::metadata(path)
+            .with_context(|| tr!("file-read-failed", path = path.display()))?
+            .permissions()
+            .mode()
+            & 0o777;
+        Ok((mode & 0o077 != 0).then(|| tr!("permissions-mode", mode = format!("{mode:o}"))))
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use anyhow::{Context, Result};
+    use std::path::Path;
+    use std::process::Command;
+
+    use crate::i18n::tr;
+
+    fn icacls(path: &Path, args: &[&str]) -> Result<String> {
+        let output = Command::new("icacls")
+            .arg(path)
+            .args(args)
+            .output()
+            .with_context(|| tr!("program-failed", program = "icacls"))?;
+        if !output.status.success() {
+            anyhow::bail!(tr!(
+                "permissions-icacls-failed",
+                path = path.display(),
+                error = String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    fn user() -> Result<String> {
+        std::env::var("USERNAME").with_context(|| tr!("permissions-no-username"))
+    }
+
+    pub fn restrict_to_owner(path: &Path) -> Result<()> {
+        let grant = if path.is_dir() {
+            format!("{}:(OI)(CI)F", user()?)
+        } else {
+            format!("{}:F", user()?)
+        };
+        // Drop inherited entries, then leave only the owner
+        icacls(path, &["/inheritance:r", "/grant:r", &grant])?;
+        Ok(())
+    }
+
+    pub fn check_owner_only(path: &Path) -> Result<Option<String>> {
+        let listing = icacls(path, &[])?;
+        let others = super::other_principals(&listing, &path.display().to_string(), &user()?);
+        Ok((!others.is_empty())
+            .then(|| tr!("permissions-accessible-to", principals = others.join(", "))))
+    }
+}
+
+/// Principals in an `icacls` listing besides `user`, SYSTEM and Administrators
+#[cfg_attr(not(windows), allow(dead_code))]
+fn other_principals(listing: &str, path: &str, user: &str) -> Vec<String> {
+    let trusted = |principal: &str| {
+        let account = principal.rsplit('\\').next().unwrap_or(principal);
+        account.eq_ignore_ascii_case(user)
+            || principal.eq_ignore_ascii_case("NT AUTHORITY\\SYSTEM")
+            || principal.eq_ignore_ascii_case("BUILTIN\\Administrators")
+    };
+
+    listing
+        .lines()
+        // The summary follows the first blank line
+        .take_while(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let entry = line.strip_prefix(path).unwrap_or(line).trim();
+            entry
+                .split_once(":(")
+                .map(|(principal, _)| principal.trim())
+        })
+        .filter(|principal| !trusted(principal))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_other_principals() {
+        let path = r"C:\Users\dev\.ssh\id_ed25519";
+        let listing = format!(
+            "{path} NT AUTHORITY\\SYSTEM:(F)\n\
+             {pad}BUILTIN\\Administrators:(F)\n\
+             {pad}DESKTOP-1\\dev:(F)\n\
+             {pad}BUILTIN\\Users:(RX)\n\
+             \n\
+             Successfully processed 1 files; Failed processing 0 files\n",
+            pad = " ".repeat(path.len() + 1)
+        );
+        assert_eq!(other_principals(&listing, path, "dev"), ["BUILTIN\\Users"]);
+
+        let owner_only = format!("{path} DESKTOP-1\\dev:(F)\n\nSuccessfully processed 1 files\n");
+        assert!(other_principals(&owner_only, path, "DEV").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_restrict_to_owner() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let key = dir.path().join("id_ed25519");
+        std::fs::write(&key, "key").unwrap();
+        restrict_to_owner(&key).unwrap();
+        assert_eq!(check_owner_only(&key).unwrap(), None);
+
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&key, std::fs::Permissions::from_mode(0o644)).unwrap();
+        assert_eq!(check_owner_only(&key).unwrap().as_deref(), Some("mode 644"));
+    }
+}
@@ -0,0 +1,134 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use super::resolve::{branch_for_rules, resolve_identity, Resolution};
+use crate::atomic;
+use crate::config::Config;
+
+const CACHE_FILE: &str = "resolve.toml";
+
+/// Entries kept before the cache is reset
+const MAX_ENTRIES: usize = 1000;
+
+/// Cached resolution results, keyed by directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ResolveCache {
+    /// Modification stamp of config.toml the entries were computed against
+    #[serde(default)]
+    config_stamp: String,
+
+    #[serde(default)]
+    entries: BTreeMap<String, CacheEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    remote: Option<String>,
+
+    /// Checked-out branch, when branch rules are configured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    branch: Option<String>,
+
+    /// Nearest .gid file and its modification stamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    resolution: Option<Resolution>,
+}
+
+/// Resolve the expected identity, reusing the cached result when possible
+///
+/// Entries are invalidated when config.toml changes, the origin URL or the
+/// checked-out branch differs, or the nearest .gid file is added, removed or
+/// edited.
+/// Cache read/write failures fall back to a normal resolution.
+pub fn resolve_cached(config: &Config, path: &Path, remote: Option<&str>) -> Option<Resolution> {
+    let stamp = config_stamp().unwrap_or_default();
+    let key = path.to_string_lossy().to_string();
+    let project = project_stamp(path);
+    let branch = branch_for_rules(config, path);
+
+    let mut cache = load()
+        .filter(|cache| !stamp.is_empty() && cache.config_stamp == stamp)
+        .unwrap_or_default();
+
+    if let Some(entry) = cache.entries.get(&key) {
+        if entry.remote.as_deref() == remote && entry.branch == branch && entry.project == project {
+            return entry.resolution.clone();
+        }
+    }
+
+    let resolution = resolve_identity(config, path, remote);
+
+    if cache.entries.len() >= MAX_ENTRIES {
+        cache.entries.clear();
+    }
+    cache.config_stamp = stamp;
+    cache.entries.insert(
+        key,
+        CacheEntry {
+            remote: remote.map(str::to_string),
+            branch,
+            project,
+            resolution: resolution.clone(),
+        },
+    );
+    let _ = save(&cache);
+
+    resolution
+}
+
+fn cache_path() -> Result<PathBuf> {
+    Ok(Config::cache_dir()?.join(CACHE_FILE))
+}
+
+fn config_stamp() -> Option<String> {
+    mtime_stamp(&Config::config_path().ok()?)
+}
+
+/// Stamp of the nearest .gid file (stat calls only, no parsing)
+fn project_stamp(path: &Path) -> Option<String> {
+    path.ancestors()
+        .map(|dir| dir.join(".gid"))
+        .find(|file| file.is_file())
+        .map(|file| {
+            format!(
+                "{}@{}",
+                file.display(),
+                mtime_stamp(&file).unwrap_or_default()
+            )
+        })
+}
+
+fn mtime_stamp(path: &Path) -> Option<String> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    let since_epoch = modified.duration_since(UNIX_EPOCH).ok()?;
+    Some(format!(
+        "{}.{:09}",
+        since_epoch.as_secs(),
+        since_epoch.subsec_nanos()
+    ))
+}
+
+fn load() -> Option<ResolveCache> {
+    let content = fs::read_to_string(cache_path().ok()?).ok()?;
+    toml::from_str(&content).ok()
+}
+
+fn save(cache: &ResolveCache) -> Result<()> {
+    let path = cache_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = toml::to_string(cache)?;
+    atomic::write(&path, &content, |written| {
+        toml::from_str::<ResolveCache>(written)?;
+        Ok(())
+    })
+}
@@ -0,0 +1,767 @@
+pub mod cache;
+pub mod resolve;
+pub mod suggest;
+
+use glob::Pattern;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::git::GitUrl;
+
+/// 规则类型
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum RuleType {
+    /// 路径匹配规则
+    Path { pattern: String },
+    /// Remote URL 匹配规则
+    ///
+    /// `pattern` 对原始 URL 做子串、正则或 glob 匹配；`host`、`org`、`repo`
+    /// 对解析后的 URL 逐项匹配，SSH 和 HTTPS 形式结果相同。都给出时都须匹配。
+    Remote {
+        #[serde(default, skip_serializing_if = "String::is_empty")]
+        pattern: String,
+        /// 主机名（如 github.com）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        host: Option<String>,
+        /// 组织或用户，GitLab 子组也算在内（如 mycompany）
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        org: Option<String>,
+        /// 仓库名
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        repo: Option<String>,
+    },
+    /// 当前分支匹配规则（如 release/*）
+    Branch { pattern: String },
+    /// 所有条件都匹配（如路径在 ~/clients/acme/** 下且 remote 在 acme.gitlab.com）
+    #[serde(rename = "all_of")]
+    AllOf { conditions: Vec<RuleType> },
+    /// 任一条件匹配
+    #[serde(rename = "any_of")]
+    AnyOf { conditions: Vec<RuleType> },
+}
+
+/// 多条规则匹配时的选择方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum MatchStrategy {
+    /// 取优先级最高的规则
+    #[default]
+    Priority,
+    /// 取模式最具体的规则（路径越深、通配越少越具体），一样具体时看优先级
+    MostSpecific,
+}
+
+/// 匹配规则
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Rule {
+    /// 稳定的规则 ID（不随优先级排序变化，缺省时按内容生成）
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+
+    /// 规则类型和模式
+    #[serde(flatten)]
+    pub rule_type: RuleType,
+
+    /// 匹配后使用的身份 ID
+    pub identity: String,
+
+    /// 规则优先级（数字越小优先级越高）
+    #[serde(default = "default_priority")]
+    pub priority: u32,
+
+    /// 规则描述
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// 是否启用
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// 所属的订阅规则集（订阅的规则在本地只读）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+fn default_priority() -> u32 {
+    100
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Rule {
+    /// 创建路径规则
+    pub fn path(pattern: String, identity: String) -> Self {
+        Self {
+            id: String::new(),
+            rule_type: RuleType::Path { pattern },
+            identity,
+            priority: default_priority(),
+            description: None,
+            enabled: true,
+            source: None,
+        }
+    }
+
+    /// 创建 remote URL 规则
+    pub fn remote(pattern: String, identity: String) -> Self {
+        Self {
+            id: String::new(),
+            rule_type: RuleType::Remote {
+                pattern,
+                host: None,
+                org: None,
+                repo: None,
+            },
+            identity,
+            priority: default_priority(),
+            description: None,
+            enabled: true,
+            source: None,
+        }
+    }
+
+    /// 创建分支规则
+    pub fn branch(pattern: String, identity: String) -> Self {
+        Self {
+            id: String::new(),
+            rule_type: RuleType::Branch { pattern },
+            identity,
+            priority: default_priority(),
+            description: None,
+            enabled: true,
+            source: None,
+        }
+    }
+
+    /// 创建组合规则：所有条件都匹配
+    pub fn all_of(conditions: Vec<RuleType>, identity: String) -> Self {
+        Self {
+            id: String::new(),
+            rule_type: RuleType::AllOf { conditions },
+            identity,
+            priority: default_priority(),
+            description: None,
+            enabled: true,
+            source: None,
+        }
+    }
+
+    /// 创建组合规则：任一条件匹配
+    pub fn any_of(conditions: Vec<RuleType>, identity: String) -> Self {
+        Self {
+            id: String::new(),
+            rule_type: RuleType::AnyOf { conditions },
+            identity,
+            priority: default_priority(),
+            description: None,
+            enabled: true,
+            source: None,
+        }
+    }
+
+    /// remote 规则再按主机、组织、仓库名匹配（每项都可为 glob，pattern 可为空）
+    pub fn with_remote_parts(
+        mut self,
+        host: Option<String>,
+        org: Option<String>,
+        repo: Option<String>,
+    ) -> Self {
+        if let RuleType::Remote {
+            host: h,
+            org: o,
+            repo: r,
+            ..
+        } = &mut self.rule_type
+        {
+            (*h, *o, *r) = (host, org, repo);
+        }
+        self
+    }
+
+    /// 设置优先级
+    pub fn with_priority(mut self, priority: u32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// 按类型、模式、身份和来源生成的短 ID（FNV-1a），`salt` 用于避开重复
+    pub fn content_id(&self, salt: u32) -> String {
+        let content = format!(
+            "{}\0{}\0{}\0{}\0{salt}",
+            self.type_name(),
+            self.pattern(),
+            self.identity,
+            self.source.as_deref().unwrap_or_default()
+        );
+        let hash = content.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        });
+        format!("{hash:016x}")[..6].to_string()
+    }
+
+    /// 检查是否匹配上下文
+    pub fn matches(&self, context: &MatchContext) -> bool {
+        self.enabled && self.rule_type.matches(context)
+    }
+
+    /// 获取规则类型名称
+    pub fn type_name(&self) -> &'static str {
+        self.rule_type.type_name()
+    }
+
+    /// 获取匹配模式
+    pub fn pattern(&self) -> Cow<'_, str> {
+        self.rule_type.pattern()
+    }
+}
+
+impl RuleType {
+    /// 检查是否匹配上下文（组合规则按 all_of / any_of 组合各条件）
+    pub fn matches(&self, context: &MatchContext) -> bool {
+        match self {
+            RuleType::Path { .. } => context
+                .path
+                .as_deref()
+                .is_some_and(|p| self.matches_path(p)),
+            RuleType::Remote { .. } => context
+                .remote_url
+                .as_deref()
+                .is_some_and(|r| self.matches_remote(r)),
+            RuleType::Branch { .. } => context
+                .branch
+                .as_deref()
+                .is_some_and(|b| self.matches_branch(b)),
+            // 空的 all_of 不匹配任何仓库
+            RuleType::AllOf { conditions } => {
+                !conditions.is_empty() && conditions.iter().all(|c| c.matches(context))
+            }
+            RuleType::AnyOf { conditions } => conditions.iter().any(|c| c.matches(context)),
+        }
+    }
+
+    /// 是否有条件用到当前分支
+    pub fn uses_branch(&self) -> bool {
+        match self {
+            RuleType::Branch { .. } => true,
+            RuleType::AllOf { conditions } | RuleType::AnyOf { conditions } => {
+                conditions.iter().any(RuleType::uses_branch)
+            }
+            _ => false,
+        }
+    }
+
+    /// 检查是否匹配路径
+    pub fn matches_path(&self, path: &Path) -> bool {
+        match self {
+            RuleType::Path { pattern } => {
+                let path_str = path.to_string_lossy();
+                let expanded_pattern = expand_home(pattern);
+
+                // 使用 glob 模式匹配
+                if let Ok(glob) = Pattern::new(&expanded_pattern) {
+                    if glob.matches(&path_str) {
+                        return true;
+                    }
+                }
+
+                // 检查路径是否在模式目录下
+                let trimmed = expanded_pattern
+                    .trim_end_matches("**")
+                    .trim_end_matches('/');
+                let pattern_path = Path::new(trimmed);
+                if path.starts_with(pattern_path) {
+                    return true;
+                }
+
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// 检查是否匹配 remote URL
+    pub fn matches_remote(&self, remote_url: &str) -> bool {
+        match self {
+            RuleType::Remote {
+                pattern,
+                host,
+                org,
+                repo,
+            } => {
+                if host.is_some() || org.is_some() || repo.is_some() {
+                    let Some(url) = GitUrl::parse(remote_url) else {
+                        return false;
+                    };
+                    let parts_match = host.as_deref().is_none_or(|host| {
+                        url.host.as_deref().is_some_and(|h| part_matches(host, h))
+                    }) && org.as_deref().is_none_or(|org| {
+                        url.owner().is_some_and(|owner| {
+                            // GitLab 子组：org = "group" 也匹配 group/sub
+                            part_matches(org, owner)
+                                || owner
+                                    .split_once('/')
+                                    .is_some_and(|(top, _)| part_matches(org, top))
+                        })
+                    }) && repo
+                        .as_deref()
+                        .is_none_or(|repo| part_matches(repo, url.repo()));
+                    if !parts_match {
+                        return false;
+                    }
+                    if pattern.is_empty() {
+                        return true;
+                    }
+                } else if pattern.is_empty() {
+                    return false;
+                }
+
+                // 首先尝试精确匹配
+                if remote_url.contains(pattern.as_str()) {
+                    return true;
+                }
+
+                // 尝试正则匹配
+                if let Ok(regex) = Regex::new(pattern) {
+                    if regex.is_match(remote_url) {
+                        return true;
+                    }
+                }
+
+                // 尝试 glob 模式匹配
+                if let Ok(glob) = Pattern::new(pattern) {
+                    // 标准化 URL 进行匹配
+                    let normalized = normalize_git_url(remote_url);
+                    if glob.matches(&normalized) {
+                        return true;
+                    }
+                }
+
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// 检查是否匹配当前分支（精确或 glob，如 release/*）
+    pub fn matches_branch(&self, branch: &str) -> bool {
+        match self {
+            RuleType::Branch { pattern } => {
+                pattern == branch || Pattern::new(pattern).is_ok_and(|glob| glob.matches(branch))
+            }
+            _ => false,
+        }
+    }
+
+    /// 模式的具体程度，most-specific 策略据此在匹配的规则中选择
+    ///
+    /// 组合规则：all_of 累加各条件，any_of 取匹配 `context` 的条件中最具体的
+    pub fn specificity(&self, context: &MatchContext) -> u32 {
+        match self {
+            RuleType::Path { pattern } => glob_specificity(&expand_home(pattern)),
+            RuleType::Branch { pattern } => glob_specificity(pattern),
+            RuleType::Remote {
+                pattern,
+                host,
+                org,
+                repo,
+            } => {
+                glob_specificity(pattern)
+                    + [host, org, repo]
+                        .into_iter()
+                        .flatten()
+                        .map(|part| glob_specificity(part))
+                        .sum::<u32>()
+            }
+            RuleType::AllOf { conditions } => conditions
+                .iter()
+                .map(|condition| condition.specificity(context))
+                .sum(),
+            RuleType::AnyOf { conditions } => conditions
+                .iter()
+                .filter(|condition| condition.matches(context))
+                .map(|condition| condition.specificity(context))
+                .max()
+                .unwrap_or_default(),
+        }
+    }
+
+    /// 获取规则类型名称
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            RuleType::Path { .. } => "path",
+            RuleType::Remote { .. } => "remote",
+            RuleType::Branch { .. } => "branch",
+            RuleType::AllOf { .. } => "all_of",
+            RuleType::AnyOf { .. } => "any_of",
+        }
+    }
+
+    /// 获取匹配模式（按主机、组织、仓库匹配的 remote 规则显示为 host=... org=...）
+    pub fn pattern(&self) -> Cow<'_, str> {
+        match self {
+            RuleType::Path { pattern } | RuleType::Branch { pattern } => Cow::Borrowed(pattern),
+            RuleType::Remote {
+                pattern,
+                host,
+                org,
+                repo,
+            } => {
+                let parts: Vec<String> = [("host", host), ("org", org), ("repo", repo)]
+                    .into_iter()
+                    .filter_map(|(key, value)| Some(format!("{key}={}", value.as_ref()?)))
+                    .collect();
+                match (parts.is_empty(), pattern.is_empty()) {
+                    (true, _) => Cow::Borrowed(pattern),
+                    (false, true) => Cow::Owned(parts.join(" ")),
+                    (false, false) => Cow::Owned(format!("{pattern} {}", parts.join(" "))),
+                }
+            }
+            RuleType::AllOf { conditions } => Cow::Owned(describe_conditions(conditions, " & ")),
+            RuleType::AnyOf { conditions } => Cow::Owned(describe_conditions(conditions, " | ")),
+        }
+    }
+}
+
+/// 规则引擎
+pub struct RuleEngine<'a> {
+    rules: &'a [Rule],
+    strategy: MatchStrategy,
+}
+
+impl<'a> RuleEngine<'a> {
+    pub fn new(rules: &'a [Rule]) -> Self {
+        Self {
+            rules,
+            strategy: MatchStrategy::default(),
+        }
+    }
+
+    /// 使用配置中的规则和 settings.match_strategy
+    pub fn from_config(config: &'a Config) -> Self {
+        Self::new(&config.rules).with_strategy(config.settings.match_strategy)
+    }
+
+    /// 设置匹配策略
+    pub fn with_strategy(mut self, strategy: MatchStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// 根据上下文匹配规则
+    pub fn match_context(&self, context: &MatchContext) -> Option<&'a Rule> {
+        // 规则已按优先级排序，最具体的有多条时取第一条
+        let mut matched = self.rules.iter().filter(|rule| rule.matches(context));
+        match self.strategy {
+            MatchStrategy::Priority => matched.next(),
+            MatchStrategy::MostSpecific => {
+                matched.min_by_key(|rule| std::cmp::Reverse(rule.rule_type.specificity(context)))
+            }
+        }
+    }
+
+    /// 获取所有匹配的规则（按策略排序，第一条即选中的规则）
+    pub fn match_all(&self, context: &MatchContext) -> Vec<&'a Rule> {
+        let mut matched: Vec<&'a Rule> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.matches(context))
+            .collect();
+        if self.strategy == MatchStrategy::MostSpecific {
+            matched.sort_by_key(|rule| std::cmp::Reverse(rule.rule_type.specificity(context)));
+        }
+        matched
+    }
+}
+
+/// 匹配上下文
+#[derive(Debug, Default)]
+pub struct MatchContext {
+    pub path: Option<std::path::PathBuf>,
+    pub remote_url: Option<String>,
+    pub branch: Option<String>,
+}
+
+impl MatchContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_path(mut self, path: std::path::PathBuf) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    pub fn with_remote(mut self, remote: String) -> Self {
+        self.remote_url = Some(remote);
+        self
+    }
+
+    pub fn with_branch(mut self, branch: String) -> Self {
+        self.branch = Some(branch);
+        self
+    }
+}
+
+/// 组合规则的条件，如 `path ~/clients/acme/** & remote host=acme.gitlab.com`
+fn describe_conditions(conditions: &[RuleType], separator: &str) -> String {
+    let described: Vec<String> = conditions
+        .iter()
+        .map(|condition| match condition {
+            RuleType::AllOf { .. } | RuleType::AnyOf { .. } => format!("({})", condition.pattern()),
+            _ => format!("{} {}", condition.type_name(), condition.pattern()),
+        })
+        .collect();
+    described.join(separator)
+}
+
+/// 解析后的 URL 的一部分是否匹配（不区分大小写，支持 glob）
+fn part_matches(pattern: &str, value: &str) -> bool {
+    let options = glob::MatchOptions {
+        case_sensitive: false,
+        ..Default::default()
+    };
+    pattern.eq_ignore_ascii_case(value)
+        || Pattern::new(pattern).is_ok_and(|glob| glob.matches_with(value, options))
+}
+
+/// 展开模式开头的 ~
+fn expand_home(pattern: &str) -> Cow<'_, str> {
+    if let Some(stripped) = pattern.strip_prefix("~/") {
+        if let Some(home) = home::home_dir() {
+            return Cow::Owned(format!("{}/{stripped}", home.display()));
+        }
+    }
+    Cow::Borrowed(pattern)
+}
+
+/// 字面量的段计 2 分，含通配符的段计 1 分，** 不计分
+///
+/// 段越多越具体，同样深度时字面量比 glob 具体
+fn glob_specificity(pattern: &str) -> u32 {
+    pattern
+        .split(['/', ':'])
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| match segment {
+            "**" => 0,
+            _ if segment.contains(['*', '?', '[']) => 1,
+            _ => 2,
+        })
+        .sum()
+}
+
+/// 标准化 Git URL
+///
+/// git@github.com:user/repo.git、ssh://git@github.com:22/user/repo.git 和
+/// https://github.com/user/repo.git 都得到 github.com/user/repo
+pub fn normalize_git_url(url: &str) -> String {
+    match GitUrl::parse(url) {
+        Some(parsed) => parsed.normalized(),
+        None => url.trim().to_string(),
+    }
+}
+
+impl std::fmt::Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}] {} -> {}",
+            self.type_name(),
+            self.pattern(),
+            self.identity
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_parts_match_all_url_forms() {
+        let rule = Rule::remote(String::new(), "work".to_string()).with_remote_parts(
+            Some("github.com".to_string()),
+            Some("MyCompany".to_string()),
+            None,
+        );
+        for url in [
+            "git@github.com:mycompany/api.git",
+            "https://github.com/mycompany/api",
+            "ssh://git@GitHub.com:22/mycompany/api.git",
+        ] {
+            assert!(rule.rule_type.matches_remote(url), "{url}");
+        }
+        for url in [
+            "git@github.com:mycompany-labs/api.git",
+            "https://gitlab.com/mycompany/api",
+            "https://github.com/other/mycompany",
+            "not a url",
+        ] {
+            assert!(!rule.rule_type.matches_remote(url), "{url}");
+        }
+        assert_eq!(rule.pattern(), "host=github.com org=MyCompany");
+
+        let rule = Rule::remote(String::new(), "work".to_string()).with_remote_parts(
+            None,
+            Some("group".to_string()),
+            Some("api-*".to_string()),
+        );
+        assert!(rule
+            .rule_type
+            .matches_remote("git@gitlab.com:group/sub/api-gateway.git"));
+        assert!(!rule
+            .rule_type
+            .matches_remote("git@gitlab.com:group/sub/web.git"));
+
+        // A remote rule with nothing to match never matches
+        assert!(!Rule::remote(String::new(), "work".to_string())
+            .rule_type
+            .matches_remote("x"));
+    }
+
+    #[test]
+    fn test_all_of_requires_every_condition() {
+        let config: crate::config::Config = toml::from_str(
+            r#"
+[[rules]]
+type = "all_of"
+identity = "acme"
+conditions = [
+    { type = "path", pattern = "/clients/acme/**" },
+    { type = "remote", host = "acme.gitlab.com" },
+]
+
+[[rules]]
+type = "any_of"
+identity = "oss"
+conditions = [
+    { type = "branch", pattern = "oss/*" },
+    { type = "remote", pattern = "github.com/oss/*" },
+]
+"#,
+        )
+        .unwrap();
+        let engine = RuleEngine::new(&config.rules);
+        let context = |path: &str, remote: &str| {
+            MatchContext::new()
+                .with_path(path.into())
+                .with_remote(remote.to_string())
+        };
+
+        let matched =
+            |context: &MatchContext| engine.match_context(context).map(|r| r.identity.as_str());
+        assert_eq!(
+            matched(&context(
+                "/clients/acme/api",
+                "git@acme.gitlab.com:team/api.git"
+            )),
+            Some("acme")
+        );
+        assert_eq!(
+            matched(&context("/clients/acme/api", "git@github.com:team/api.git")),
+            None
+        );
+        assert_eq!(
+            matched(&context("/src/api", "https://acme.gitlab.com/team/api")),
+            None
+        );
+        assert_eq!(
+            matched(&context("/src/tool", "x").with_branch("oss/fix".to_string())),
+            Some("oss")
+        );
+        assert_eq!(
+            config.rules[0].pattern(),
+            "path /clients/acme/** & remote host=acme.gitlab.com"
+        );
+        assert!(config.rules[1].rule_type.uses_branch());
+
+        // Nothing to require: never matches
+        assert!(!RuleType::AllOf {
+            conditions: Vec::new()
+        }
+        .matches(&context("/", "x")));
+    }
+
+    #[test]
+    fn test_most_specific_strategy() {
+        let rules = vec![
+            Rule::path("/work/**".to_string(), "work".to_string()).with_priority(10),
+            Rule::path("/work/oss/**".to_string(), "oss".to_string()).with_priority(50),
+            Rule::path("/work/*/gid".to_string(), "gid".to_string()).with_priority(50),
+        ];
+        let context = |path: &str| MatchContext::new().with_path(path.into());
+        let matched = |engine: &RuleEngine, path: &str| {
+            engine
+                .match_context(&context(path))
+                .map(|r| r.identity.clone())
+        };
+
+        let engine = RuleEngine::new(&rules);
+        assert_eq!(matched(&engine, "/work/oss/gid").as_deref(), Some("work"));
+
+        let engine = engine.with_strategy(MatchStrategy::MostSpecific);
+        // Deeper literal beats shallower glob
+        assert_eq!(matched(&engine, "/work/oss/tool").as_deref(), Some("oss"));
+        assert_eq!(matched(&engine, "/work/oss/gid").as_deref(), Some("gid"));
+        assert_eq!(matched(&engine, "/work/api").as_deref(), Some("work"));
+        let all: Vec<&str> = engine
+            .match_all(&context("/work/oss/gid"))
+            .iter()
+            .map(|r| r.identity.as_str())
+            .collect();
+        assert_eq!(all, ["gid", "oss", "work"]);
+
+        // Equally specific: priority decides
+        let rules = vec![
+            Rule::branch("release/*".to_string(), "a".to_string()).with_priority(1),
+            Rule::branch("*/1.0".to_string(), "b".to_string()).with_priority(2),
+        ];
+        let engine = RuleEngine::new(&rules).with_strategy(MatchStrategy::MostSpecific);
+        let context = MatchContext::new().with_branch("release/1.0".to_string());
+        assert_eq!(engine.match_context(&context).unwrap().identity, "a");
+
+        // any_of counts the condition that matched, not its vaguest one
+        let rules = vec![
+            Rule::path("/work/oss/**".to_string(), "oss".to_string()).with_priority(10),
+            Rule::any_of(
+                vec![
+                    RuleType::Path {
+                        pattern: "/work/oss/gid/**".to_string(),
+                    },
+                    RuleType::Branch {
+                        pattern: "*".to_string(),
+                    },
+                ],
+                "gid".to_string(),
+            )
+            .with_priority(20),
+        ];
+        let engine = RuleEngine::new(&rules).with_strategy(MatchStrategy::MostSpecific);
+        let context = |path: &str, branch: &str| {
+            MatchContext::new()
+                .with_path(path.into())
+                .with_branch(branch.to_string())
+        };
+        assert_eq!(
+            engine
+                .match_context(&context("/work/oss/gid/src", "main"))
+                .unwrap()
+                .identity,
+            "gid"
+        );
+        // Only the catch-all branch condition matches here
+        assert_eq!(
+            engine
+                .match_context(&context("/work/oss/tool", "main"))
+                .unwrap()
+                .identity,
+            "oss"
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use super::{MatchContext, Rule, RuleEngine};
+use crate::config::{Config, Identity, ProjectConfig};
+use crate::exit::Failure;
+use crate::git::GitConfigManager;
+use crate::i18n::tr;
+
+/// Where an expected identity was resolved from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Source {
+    /// Project config (.gid file)
+    Project(PathBuf),
+    /// Matched rule
+    Rule(Rule),
+    /// Fallback identity (--fallback or settings.default_identity)
+    Fallback,
+}
+
+/// Identity expected for a location
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Resolution {
+    pub identity: String,
+    pub source: Source,
+}
+
+impl Source {
+    /// Short machine-readable kind
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Source::Project(_) => "project",
+            Source::Rule(_) => "rule",
+            Source::Fallback => "fallback",
+        }
+    }
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            Source::Project(path) => tr!("source-project", path = path.display()),
+            Source::Rule(rule) => tr!("source-rule", rule = rule),
+            Source::Fallback => tr!("source-fallback"),
+        };
+        f.write_str(&text)
+    }
+}
+
+/// Resolve the identity expected for a path (.gid first, then rules)
+///
+/// A .gid file naming an unknown identity is ignored so rules still apply.
+pub fn resolve_identity(config: &Config, path: &Path, remote: Option<&str>) -> Option<Resolution> {
+    if let Ok(Some((project_config, gid_path))) = ProjectConfig::find_in_parents(path) {
+        if config.find_identity(&project_config.identity).is_some() {
+            return Some(Resolution {
+                identity: project_config.identity,
+                source: Source::Project(gid_path),
+            });
+        }
+    }
+
+    resolve_rules(config, path, remote)
+}
+
+/// Identity for a submodule: its own .gid, then rules for its path and remote
+///
+/// Unlike [`resolve_identity`], a .gid above the submodule (the superproject's)
+/// is not inherited; submodules often belong to another organization.
+pub fn resolve_submodule_identity(
+    config: &Config,
+    workdir: &Path,
+    remote: Option<&str>,
+) -> Option<Resolution> {
+    if let Ok(Some(project_config)) = ProjectConfig::load_from_dir(workdir) {
+        if config.find_identity(&project_config.identity).is_some() {
+            return Some(Resolution {
+                identity: project_config.identity,
+                source: Source::Project(workdir.join(".gid")),
+            });
+        }
+    }
+
+    resolve_rules(config, workdir, remote)
+}
+
+fn resolve_rules(config: &Config, path: &Path, remote: Option<&str>) -> Option<Resolution> {
+    let mut context = MatchContext::new().with_path(path.to_path_buf());
+    if let Some(remote) = remote {
+        context = context.with_remote(remote.to_string());
+    }
+    if let Some(branch) = branch_for_rules(config, path) {
+        context = context.with_branch(branch);
+    }
+
+    RuleEngine::from_config(config)
+        .match_context(&context)
+        .map(|rule| Resolution {
+            identity: rule.identity.clone(),
+            source: Source::Rule(rule.clone()),
+        })
+}
+
+/// Branch checked out at `path`, only looked up when a branch rule could use it
+pub fn branch_for_rules(config: &Config, path: &Path) -> Option<String> {
+    let has_branch_rules = config
+        .rules
+        .iter()
+        .any(|rule| rule.enabled && rule.rule_type.uses_branch());
+    if !has_branch_rules {
+        return None;
+    }
+    GitConfigManager::from_path(path).ok()?.current_branch()
+}
+
+/// Fallback used when neither .gid nor rules match (explicit id first, then settings)
+pub fn fallback_identity(config: &Config, explicit: Option<&str>) -> Option<Resolution> {
+    explicit
+        .map(str::to_string)
+        .or_else(|| config.settings.default_identity.clone())
+        .map(|identity| Resolution {
+            identity,
+            source: Source::Fallback,
+        })
+}
+
+/// Identity status of a single repository
+#[derive(Debug)]
+pub struct RepoStatus {
+    pub path: PathBuf,
+    pub remote: Option<String>,
+    pub effective_name: Option<String>,
+    pub effective_email: Option<String>,
+    pub expected: Option<Resolution>,
+}
+
+impl RepoStatus {
+    /// Inspect a repository: effective identity and the identity rules expect
+    pub fn inspect(config: &Config, path: &Path) -> anyhow::Result<Self> {
+        Self::inspect_with(config, path, resolve_identity)
+    }
+
+    /// Same as [`RepoStatus::inspect`], reusing the resolution cache
+    pub fn inspect_cached(config: &Config, path: &Path) -> anyhow::Result<Self> {
+        Self::inspect_with(config, path, super::cache::resolve_cached)
+    }
+
+    fn inspect_with(
+        config: &Config,
+        path: &Path,
+        resolve: fn(&Config, &Path, Option<&str>) -> Option<Resolution>,
+    ) -> anyhow::Result<Self> {
+        let git = GitConfigManager::from_path(path)?;
+
+        if !git.is_in_repo() {
+            return Err(Failure::NotARepo.error(tr!("git-path-not-a-repo", path = path.display())));
+        }
+
+        let remote = git.get_origin_url();
+        let expected = resolve(config, path, remote.as_deref());
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            remote,
+            effective_name: git.get_effective_user_name(),
+            effective_email: git.get_effective_user_email(),
+            expected,
+        })
+    }
+
+    /// Configured identity matching the effective user.name/user.email
+    pub fn effective_identity<'a>(&self, config: &'a Config) -> Option<&'a Identity> {
+        config.identities.iter().find(|i| {
+            Some(&i.name) == self.effective_name.as_ref()
+                && Some(&i.email) == self.effective_email.as_ref()
+        })
+    }
+
+    /// Whether the effective identity differs from the expected one
+    pub fn is_drifted(&self, config: &Config) -> bool {
+        match &self.expected {
+            Some(expected) => {
+                self.effective_identity(config).map(|i| i.id.as_str())
+                    != Some(expected.identity.as_str())
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::Rule;
+use crate::config::state::UnmatchedRepo;
+use crate::git::GitUrl;
+
+/// Placeholder used when no identity can be inferred for a cluster
+pub const IDENTITY_PLACEHOLDER: &str = "<identity>";
+
+/// A proposed rule and the repositories it would cover
+#[derive(Debug)]
+pub struct Suggestion {
+    pub rule: Rule,
+    pub repos: Vec<String>,
+}
+
+impl Suggestion {
+    /// Equivalent `gid rule add` command
+    pub fn command(&self) -> String {
+        format!(
+            "gid rule add -t {} -p \"{}\" -i {}",
+            self.rule.type_name(),
+            self.rule.pattern(),
+            self.rule.identity
+        )
+    }
+}
+
+/// Cluster unmatched repositories by remote org (or parent directory) into rules
+///
+/// Repositories with a remote are grouped by `host/org`, the rest by parent directory.
+/// Each cluster uses the identity most of its repositories currently use.
+pub fn suggest_rules(unmatched: &BTreeMap<String, UnmatchedRepo>) -> Vec<Suggestion> {
+    // (type, pattern) -> repositories
+    let mut clusters: BTreeMap<(&'static str, String), Vec<(&String, &UnmatchedRepo)>> =
+        BTreeMap::new();
+
+    for (path, repo) in unmatched {
+        let key = match repo.remote.as_deref().and_then(remote_prefix) {
+            Some(prefix) => ("remote", format!("{prefix}/*")),
+            None => match Path::new(path).parent() {
+                Some(parent) => ("path", format!("{}/**", contract_home(parent))),
+                None => continue,
+            },
+        };
+        clusters.entry(key).or_default().push((path, repo));
+    }
+
+    let mut suggestions: Vec<Suggestion> = clusters
+        .into_iter()
+        .map(|((kind, pattern), members)| {
+            let identity = majority_identity(&members)
+                .unwrap_or(IDENTITY_PLACEHOLDER)
+                .to_string();
+            let rule = if kind == "remote" {
+                Rule::remote(pattern, identity)
+            } else {
+                Rule::path(pattern, identity)
+            };
+            Suggestion {
+                rule,
+                repos: members.into_iter().map(|(path, _)| path.clone()).collect(),
+            }
+        })
+        .collect();
+
+    suggestions.sort_by_key(|s| std::cmp::Reverse(s.repos.len()));
+    suggestions
+}
+
+/// `host/owner` part of a remote URL
+fn remote_prefix(remote: &str) -> Option<String> {
+    let url = GitUrl::parse(remote)?;
+    Some(format!("{}/{}", url.host.as_deref()?, url.owner()?))
+}
+
+fn majority_identity<'a>(members: &[(&String, &'a UnmatchedRepo)]) -> Option<&'a str> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for (_, repo) in members {
+        if let Some(identity) = repo.identity.as_deref() {
+            *counts.entry(identity).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(identity, _)| identity)
+}
+
+fn contract_home(path: &Path) -> String {
+    if let Some(home) = home::home_dir() {
+        if let Ok(rest) = path.strip_prefix(&home) {
+            return format!("~/{}", rest.display());
+        }
+    }
+    path.display().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(remote: Option<&str>, identity: Option<&str>) -> UnmatchedRepo {
+        UnmatchedRepo {
+            remote: remote.map(str::to_string),
+            identity: identity.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_clusters_by_remote_org() {
+        let mut unmatched = BTreeMap::new();
+        unmatched.insert(
+            "/src/a".to_string(),
+            repo(Some("git@github.com:acme/a.git"), Some("work")),
+        );
+        unmatched.insert(
+            "/src/b".to_string(),
+            repo(Some("https://github.com/acme/b.git"), None),
+        );
+        unmatched.insert("/tmp/scratch/c".to_string(), repo(None, None));
+
+        let suggestions = suggest_rules(&unmatched);
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(
+            suggestions[0].command(),
+            "gid rule add -t remote -p \"github.com/acme/*\" -i work"
+        );
+        assert_eq!(suggestions[0].repos.len(), 2);
+        assert_eq!(
+            suggestions[1].command(),
+            "gid rule add -t path -p \"/tmp/scratch/**\" -i <identity>"
+        );
+    }
+}
@@ -0,0 +1,763 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::atomic::{self, FileLock};
+use crate::i18n::tr;
+use crate::permissions;
+
+/// Default Host alias template (e.g. github-com-work)
+pub const DEFAULT_ALIAS_TEMPLATE: &str = "{host_slug}-{identity}";
+
+/// Render a Host alias from a template
+pub fn host_alias(template: &str, hostname: &str, identity_id: &str) -> String {
+    template
+        .replace("{host_slug}", &hostname.replace('.', "-"))
+        .replace("{host}", hostname)
+        .replace("{identity}", identity_id)
+}
+
+/// Check that a Host alias template yields one distinct literal alias per host and identity
+pub fn validate_alias_template(template: &str) -> Result<()> {
+    if !template.contains("{identity}") {
+        anyhow::bail!(tr!("ssh-alias-template-no-identity", template = template));
+    }
+    if !template.contains("{host}") && !template.contains("{host_slug}") {
+        anyhow::bail!(tr!("ssh-alias-template-no-host", template = template));
+    }
+    if template
+        .chars()
+        .any(|c| c.is_whitespace() || c == '*' || c == '?')
+    {
+        anyhow::bail!(tr!("ssh-alias-template-invalid-char", template = template));
+    }
+    Ok(())
+}
+
+/// A `Host` block from the SSH config
+#[derive(Debug, Clone, PartialEq)]
+pub struct HostBlock {
+    pub patterns: Vec<String>,
+    pub hostname: Option<String>,
+    pub user: Option<String>,
+    pub identity_files: Vec<String>,
+    /// Written by gid (preceded by a `# gid managed` comment)
+    pub gid_managed: bool,
+}
+
+impl HostBlock {
+    /// Whether the block applies to a host (supports `*`, `?` and `!` negation)
+    pub fn matches(&self, host: &str) -> bool {
+        let mut matched = false;
+        for pattern in &self.patterns {
+            if let Some(negated) = pattern.strip_prefix('!') {
+                if glob_match(negated, host) {
+                    return false;
+                }
+            } else if glob_match(pattern, host) {
+                matched = true;
+            }
+        }
+        matched
+    }
+}
+
+/// Effective SSH settings used to connect to a host
+#[derive(Debug, Clone)]
+pub struct SshRoute {
+    pub host: String,
+    pub hostname: String,
+    pub user: Option<String>,
+    /// Identity files that exist on disk, in the order ssh tries them
+    pub identity_files: Vec<PathBuf>,
+    /// First Host block matching the host
+    pub block: Option<HostBlock>,
+}
+
+/// Parse `Host` blocks from SSH config content (`Match` blocks are skipped)
+pub fn parse_host_blocks(content: &str) -> Vec<HostBlock> {
+    let mut blocks: Vec<HostBlock> = Vec::new();
+    let mut in_match = false;
+    let mut previous_comment = String::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            previous_comment = comment.trim().to_string();
+            continue;
+        }
+
+        let (key, value) = match line.split_once(|c: char| c.is_whitespace() || c == '=') {
+            Some((key, value)) => (
+                key.to_lowercase(),
+                value.trim_start_matches([' ', '\t', '=']).trim(),
+            ),
+            None => (line.to_lowercase(), ""),
+        };
+
+        match key.as_str() {
+            "host" => {
+                in_match = false;
+                blocks.push(HostBlock {
+                    patterns: value.split_whitespace().map(str::to_string).collect(),
+                    hostname: None,
+                    user: None,
+                    identity_files: Vec::new(),
+                    gid_managed: previous_comment.starts_with("gid managed"),
+                });
+            }
+            "match" => in_match = true,
+            _ if in_match => {}
+            "hostname" => {
+                if let Some(block) = blocks.last_mut() {
+                    block.hostname.get_or_insert_with(|| value.to_string());
+                }
+            }
+            "user" => {
+                if let Some(block) = blocks.last_mut() {
+                    block.user.get_or_insert_with(|| value.to_string());
+                }
+            }
+            "identityfile" => {
+                if let Some(block) = blocks.last_mut() {
+                    block
+                        .identity_files
+                        .push(value.trim_matches('"').to_string());
+                }
+            }
+            _ => {}
+        }
+
+        previous_comment.clear();
+    }
+
+    blocks
+}
+
+/// Minimal ssh_config pattern matching (`*` and `?`)
+fn glob_match(pattern: &str, text: &str) -> bool {
+    glob::Pattern::new(&pattern.to_lowercase())
+        .map(|p| p.matches(&text.to_lowercase()))
+        .unwrap_or(false)
+}
+
+/// Extract the forge greeting from `ssh -T` output
+fn auth_greeting(output: &std::process::Output) -> Result<String> {
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let greeting = text
+        .lines()
+        .find(|line| {
+            let line = line.to_lowercase();
+            line.contains("authenticated") || line.contains("welcome to")
+        })
+        .map(|line| line.trim().to_string());
+
+    match greeting {
+        Some(greeting) => Ok(greeting),
+        None => match text.lines().last() {
+            Some(last) => anyhow::bail!("{}", last.trim()),
+            None => anyhow::bail!(tr!("ssh-no-response")),
+        },
+    }
+}
+
+/// Account name in a forge greeting (GitHub, GitLab, Gitea, Bitbucket)
+pub fn greeting_login(greeting: &str) -> Option<String> {
+    let login = if let Some(rest) = greeting.strip_prefix("Hi ") {
+        // Hi octocat! You've successfully authenticated...
+        rest.split('!').next()
+    } else if let Some((_, rest)) = greeting.split_once(", @") {
+        // Welcome to GitLab, @octocat!
+        rest.split('!').next()
+    } else if let Some((_, rest)) = greeting.split_once("logged in as ") {
+        // authenticated via ssh key. You are logged in as octocat.
+        rest.split_whitespace()
+            .next()
+            .map(|s| s.trim_end_matches('.'))
+    } else {
+        None
+    };
+
+    login
+        .map(str::trim)
+        .filter(|login| !login.is_empty())
+        .map(str::to_string)
+}
+
+/// SSH Configuration Manager
+pub struct SshManager {
+    ssh_dir: PathBuf,
+    config_path: PathBuf,
+}
+
+impl SshManager {
+    /// Create new SSH manager
+    pub fn new() -> Result<Self> {
+        let home = home::home_dir().ok_or_else(|| anyhow::anyhow!(tr!("home-dir-unknown")))?;
+        let ssh_dir = home.join(".ssh");
+        let config_path = ssh_dir.join("config");
+
+        Ok(Self {
+            ssh_dir,
+            config_path,
+        })
+    }
+
+    /// Path of the managed SSH config (`~/.ssh/config`)
+    pub fn config_path(&self) -> &Path {
+        &self.config_path
+    }
+
+    /// Ensure SSH directory exists
+    pub fn ensure_ssh_dir(&self) -> Result<()> {
+        if !self.ssh_dir.exists() {
+            fs::create_dir_all(&self.ssh_dir)
+                .with_context(|| tr!("dir-create-failed", path = self.ssh_dir.display()))?;
+
+            // Owner only (700 on Unix)
+            permissions::restrict_to_owner(&self.ssh_dir)?;
+        }
+        Ok(())
+    }
+
+    /// Check if key file exists
+    pub fn key_exists(&self, key_path: &Path) -> bool {
+        let expanded = self.expand_path(key_path);
+        expanded.exists()
+    }
+
+    /// Get public key path for private key
+    pub fn get_public_key_path(&self, private_key: &Path) -> PathBuf {
+        let mut pub_path = private_key.to_path_buf();
+        let file_name = pub_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| format!("{n}.pub"))
+            .unwrap_or_else(|| "id_rsa.pub".to_string());
+        pub_path.set_file_name(file_name);
+        pub_path
+    }
+
+    /// Read public key content
+    pub fn read_public_key(&self, private_key: &Path) -> Result<String> {
+        let pub_path = self.get_public_key_path(private_key);
+        let expanded = self.expand_path(&pub_path);
+
+        fs::read_to_string(&expanded)
+            .with_context(|| tr!("ssh-public-key-read-failed", path = expanded.display()))
+    }
+
+    /// Key fingerprint as printed by `ssh-keygen -l` (e.g. SHA256:...)
+    pub fn fingerprint(&self, key_path: &Path) -> Result<String> {
+        let output = std::process::Command::new("ssh-keygen")
+            .arg("-lf")
+            .arg(self.expand_path(key_path))
+            .output()
+            .with_context(|| tr!("program-failed", program = "ssh-keygen"))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("{}", stderr.trim());
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        stdout
+            .split_whitespace()
+            .nth(1)
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!(tr!("ssh-keygen-unexpected", output = stdout.trim())))
+    }
+
+    /// Authenticate against a forge with only this key, returning its greeting
+    ///
+    /// Forges close `ssh -T` sessions with a non-zero status even on success, so
+    /// the greeting decides the outcome.
+    pub fn test_auth(&self, host: &str, key_path: &Path) -> Result<String> {
+        crate::offline::ensure_online(&tr!("ssh-connecting", host = host))?;
+        let output = std::process::Command::new("ssh")
+            .args([
+                "-T",
+                "-o",
+                "BatchMode=yes",
+                "-o",
+                "IdentitiesOnly=yes",
+                "-o",
+                "StrictHostKeyChecking=accept-new",
+                "-o",
+                "ConnectTimeout=10",
+                "-i",
+            ])
+            .arg(self.expand_path(key_path))
+            .arg(format!("git@{host}"))
+            .output()
+            .with_context(|| tr!("program-failed", program = "ssh"))?;
+
+        auth_greeting(&output)
+    }
+
+    /// Ask a forge which account the SSH config's key for a host authenticates as
+    ///
+    /// Unlike `test_auth`, keys come from the SSH config and agent, the way
+    /// `git push` would pick them.
+    pub fn whoami(&self, host: &str) -> Result<String> {
+        crate::offline::ensure_online(&tr!("ssh-connecting", host = host))?;
+        let output = std::process::Command::new("ssh")
+            .args([
+                "-T",
+                "-o",
+                "BatchMode=yes",
+                "-o",
+                "StrictHostKeyChecking=accept-new",
+                "-o",
+                "ConnectTimeout=10",
+            ])
+            .arg(format!("git@{host}"))
+            .output()
+            .with_context(|| tr!("program-failed", program = "ssh"))?;
+
+        auth_greeting(&output)
+    }
+
+    /// Add SSH configuration entry
+    pub fn add_host_config(
+        &self,
+        host_alias: &str,
+        hostname: &str,
+        identity_file: &Path,
+        user: &str,
+    ) -> Result<()> {
+        self.ensure_ssh_dir()?;
+
+        let config_entry = format!(
+            r#"
+# gid managed - {}
+Host {}
+    HostName {}
+    User {}
+    IdentityFile {}
+    IdentitiesOnly yes
+"#,
+            host_alias,
+            host_alias,
+            hostname,
+            user,
+            identity_file.display()
+        );
+
+        // Read, modify and replace under one lock
+        let _lock = FileLock::acquire(&self.config_path)?;
+        let mut existing = if self.config_path.exists() {
+            fs::read_to_string(&self.config_path).with_context(|| tr!("ssh-config-read-failed"))?
+        } else {
+            String::new()
+        };
+
+        // Check if Host config already exists
+        if existing.contains(&format!("Host {host_alias}")) {
+            // Remove old configuration
+            existing = self.remove_host_from_config(&existing, host_alias);
+        }
+
+        // Add new configuration
+        existing.push_str(&config_entry);
+
+        crate::config::journal::record(&self.config_path);
+        self.replace_config(&existing, |blocks| {
+            blocks
+                .iter()
+                .any(|b| b.gid_managed && b.patterns.iter().any(|p| p == host_alias))
+        })?;
+
+        // Owner only (600 on Unix)
+        permissions::restrict_to_owner(&self.config_path)?;
+
+        Ok(())
+    }
+
+    /// Host blocks written by gid
+    pub fn managed_hosts(&self) -> Vec<HostBlock> {
+        let content = fs::read_to_string(&self.config_path).unwrap_or_default();
+        parse_host_blocks(&content)
+            .into_iter()
+            .filter(|b| b.gid_managed)
+            .collect()
+    }
+
+    /// Remove a gid managed Host block from the SSH config file
+    pub fn remove_host_config(&self, host_alias: &str) -> Result<()> {
+        let _lock = FileLock::acquire(&self.config_path)?;
+        let existing =
+            fs::read_to_string(&self.config_path).with_context(|| tr!("ssh-config-read-failed"))?;
+        let updated = self.remove_host_from_config(&existing, host_alias);
+
+        crate::config::journal::record(&self.config_path);
+        self.replace_config(&updated, |blocks| {
+            !blocks
+                .iter()
+                .any(|b| b.gid_managed && b.patterns.iter().any(|p| p == host_alias))
+        })
+    }
+
+    /// Atomically write the SSH config once its Host blocks parse as expected
+    fn replace_config(&self, content: &str, expected: impl Fn(&[HostBlock]) -> bool) -> Result<()> {
+        atomic::write(&self.config_path, content, |written| {
+            if expected(&parse_host_blocks(written)) {
+                Ok(())
+            } else {
+                anyhow::bail!(tr!("ssh-config-roundtrip-failed"))
+            }
+        })
+        .with_context(|| tr!("ssh-config-write-failed"))
+    }
+
+    /// Remove specified Host from configuration
+    fn remove_host_from_config(&self, config: &str, host_alias: &str) -> String {
+        let mut result = String::new();
+        let mut skip = false;
+        let mut skip_comment = false;
+
+        for line in config.lines() {
+            // Check if it is a gid managed comment
+            if line.contains("# gid managed") && line.contains(host_alias) {
+                skip_comment = true;
+                continue;
+            }
+
+            if skip_comment && line.starts_with("Host ") {
+                if line.contains(host_alias) {
+                    skip = true;
+                    skip_comment = false;
+                    continue;
+                }
+                skip_comment = false;
+            }
+
+            if skip {
+                if line.starts_with("Host ") {
+                    skip = false;
+                    result.push_str(line);
+                    result.push('\n');
+                }
+                continue;
+            }
+
+            if !skip_comment {
+                result.push_str(line);
+                result.push('\n');
+            }
+        }
+
+        result
+    }
+
+    /// Generate new SSH key pair
+    pub fn generate_key(&self, name: &str, email: &str) -> Result<PathBuf> {
+        self.ensure_ssh_dir()?;
+
+        let key_name = format!("id_ed25519_gid_{name}");
+        let key_path = self.ssh_dir.join(&key_name);
+
+        if key_path.exists() {
+            anyhow::bail!(tr!("ssh-key-exists", path = key_path.display()));
+        }
+
+        // Generate key using ssh-keygen
+        let output = std::process::Command::new("ssh-keygen")
+            .args([
+                "-t",
+                "ed25519",
+                "-C",
+                email,
+                "-f",
+                key_path.to_str().unwrap(),
+                "-N",
+                "", // Empty passphrase
+            ])
+            .output()
+            .with_context(|| tr!("program-failed", program = "ssh-keygen"))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!(tr!("ssh-keygen-failed", error = stderr));
+        }
+
+        // ssh-keygen leaves Windows keys with inherited ACLs
+        permissions::restrict_to_owner(&key_path)?;
+
+        Ok(key_path)
+    }
+
+    /// Expand ~ symbol in path
+    fn expand_path(&self, path: &Path) -> PathBuf {
+        if let Some(path_str) = path.to_str() {
+            if let Some(stripped) = path_str.strip_prefix("~/") {
+                if let Some(home) = home::home_dir() {
+                    return home.join(stripped);
+                }
+            }
+        }
+        path.to_path_buf()
+    }
+
+    /// Configure SSH for identity
+    pub fn configure_for_identity(
+        &self,
+        identity_id: &str,
+        hostname: &str,
+        key_path: &Path,
+        alias_template: &str,
+    ) -> Result<String> {
+        let host_alias = host_alias(alias_template, hostname, identity_id);
+        if host_alias == hostname {
+            anyhow::bail!(tr!("ssh-alias-is-hostname"));
+        }
+        self.add_host_config(&host_alias, hostname, key_path, "git")?;
+        Ok(host_alias)
+    }
+
+    /// Resolve which host, user and keys ssh will use for a host or alias
+    ///
+    /// Uses `ssh -G` for the effective values and falls back to parsing the
+    /// config file when ssh is unavailable.
+    pub fn resolve_route(&self, host: &str) -> SshRoute {
+        let content = fs::read_to_string(&self.config_path).unwrap_or_default();
+        let blocks = parse_host_blocks(&content);
+        let matching: Vec<&HostBlock> = blocks.iter().filter(|b| b.matches(host)).collect();
+
+        let (hostname, user, identity_files) = match self.effective_config(host) {
+            Some(effective) => effective,
+            None => (
+                matching
+                    .iter()
+                    .find_map(|b| b.hostname.clone())
+                    .unwrap_or_else(|| host.to_string()),
+                matching.iter().find_map(|b| b.user.clone()),
+                matching
+                    .iter()
+                    .flat_map(|b| b.identity_files.iter().cloned())
+                    .collect(),
+            ),
+        };
+
+        let identity_files = identity_files
+            .iter()
+            .map(|f| self.expand_path(Path::new(f)))
+            .filter(|f| f.exists())
+            .collect();
+
+        SshRoute {
+            host: host.to_string(),
+            hostname,
+            user,
+            identity_files,
+            block: matching.first().map(|b| (*b).clone()),
+        }
+    }
+
+    /// Run `ssh -G` and return (hostname, user, identity files)
+    fn effective_config(&self, host: &str) -> Option<(String, Option<String>, Vec<String>)> {
+        let mut cmd = std::process::Command::new("ssh");
+        if self.config_path.exists() {
+            cmd.arg("-F").arg(&self.config_path);
+        }
+        cmd.arg("-G").arg(host);
+        log::debug!("running {cmd:?}");
+        let output = cmd.output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut hostname = None;
+        let mut user = None;
+        let mut identity_files = Vec::new();
+
+        for line in stdout.lines() {
+            match line.split_once(' ') {
+                Some(("hostname", value)) => hostname = Some(value.to_string()),
+                Some(("user", value)) => user = Some(value.to_string()),
+                Some(("identityfile", value)) => identity_files.push(value.to_string()),
+                _ => {}
+            }
+        }
+
+        Some((hostname?, user, identity_files))
+    }
+
+    /// Check if ssh-agent is running
+    pub fn is_agent_running(&self) -> bool {
+        std::process::Command::new("ssh-add")
+            .arg("-l")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Add key to ssh-agent
+    pub fn add_to_agent(&self, key_path: &Path) -> Result<()> {
+        let expanded = self.expand_path(key_path);
+
+        if !expanded.exists() {
+            anyhow::bail!(tr!("identity-ssh-key-missing", path = expanded.display()));
+        }
+
+        log::info!("adding {} to ssh-agent", expanded.display());
+        let output = std::process::Command::new("ssh-add")
+            .arg(expanded.to_str().unwrap())
+            .output()
+            .with_context(|| tr!("ssh-add-failed"))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!(tr!("ssh-agent-add-failed", error = stderr));
+        }
+
+        Ok(())
+    }
+
+    /// Remove key from ssh-agent
+    #[allow(dead_code)]
+    pub fn remove_from_agent(&self, key_path: &Path) -> Result<()> {
+        let expanded = self.expand_path(key_path);
+
+        let output = std::process::Command::new("ssh-add")
+            .arg("-d")
+            .arg(expanded.to_str().unwrap())
+            .output()
+            .with_context(|| tr!("ssh-add-failed"))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // Ignore key not found error
+            if !stderr.contains("not found") {
+                anyhow::bail!(tr!("ssh-agent-remove-failed", error = stderr));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// List keys in ssh-agent
+    pub fn list_agent_keys(&self) -> Result<Vec<String>> {
+        let output = std::process::Command::new("ssh-add")
+            .arg("-l")
+            .output()
+            .with_context(|| tr!("ssh-add-failed"))?;
+
+        if !output.status.success() {
+            return Ok(Vec::new());
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let keys = stdout
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.to_string())
+            .collect();
+
+        Ok(keys)
+    }
+
+    /// Clear all keys from ssh-agent
+    #[allow(dead_code)]
+    pub fn clear_agent(&self) -> Result<()> {
+        let output = std::process::Command::new("ssh-add")
+            .arg("-D")
+            .output()
+            .with_context(|| tr!("ssh-add-failed"))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!(tr!("ssh-agent-clear-failed", error = stderr));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_host_blocks() {
+        let config = r#"
+Host *.internal !db.internal
+    User admin
+
+# gid managed - github-com-work
+Host github-com-work
+    HostName github.com
+    User git
+    IdentityFile ~/.ssh/id_work
+
+Match host foo
+    IdentityFile ~/.ssh/ignored
+"#;
+        let blocks = parse_host_blocks(config);
+        assert_eq!(blocks.len(), 2);
+
+        assert!(blocks[0].matches("app.internal"));
+        assert!(!blocks[0].matches("db.internal"));
+        assert!(!blocks[0].gid_managed);
+
+        assert!(blocks[1].matches("github-com-work"));
+        assert!(blocks[1].gid_managed);
+        assert_eq!(blocks[1].hostname.as_deref(), Some("github.com"));
+        assert_eq!(blocks[1].identity_files, vec!["~/.ssh/id_work"]);
+    }
+
+    #[test]
+    fn test_host_alias_default_template() {
+        assert_eq!(
+            host_alias(DEFAULT_ALIAS_TEMPLATE, "github.com", "work"),
+            "github-com-work"
+        );
+    }
+
+    #[test]
+    fn test_host_alias_custom_template() {
+        assert_eq!(
+            host_alias("{identity}.{host}", "gitlab.acme.com", "acme"),
+            "acme.gitlab.acme.com"
+        );
+    }
+
+    #[test]
+    fn test_validate_alias_template() {
+        assert!(validate_alias_template(DEFAULT_ALIAS_TEMPLATE).is_ok());
+        assert!(validate_alias_template("{identity}.{host}").is_ok());
+        assert!(validate_alias_template("{host_slug}").is_err());
+        assert!(validate_alias_template("{identity}").is_err());
+        assert!(validate_alias_template("{host} {identity}").is_err());
+        assert!(validate_alias_template("*-{host}-{identity}").is_err());
+        assert!(validate_alias_template("{host}-{identity}?").is_err());
+    }
+
+    #[test]
+    fn test_greeting_login() {
+        assert_eq!(
+            greeting_login(
+                "Hi octocat! You've successfully authenticated, but GitHub does not provide shell access."
+            )
+            .as_deref(),
+            Some("octocat")
+        );
+        assert_eq!(
+            greeting_login("Welcome to GitLab, @octocat!").as_deref(),
+            Some("octocat")
+        );
+        assert_eq!(
+            greeting_login("authenticated via ssh key. You are logged in as octocat.").as_deref(),
+            Some("octocat")
+        );
+        assert_eq!(greeting_login("Permission denied (publickey)."), None);
+    }
+}
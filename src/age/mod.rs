@@ -0,0 +1,99 @@
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::i18n::tr;
+
+/// First line of an ASCII-armored age file
+const ARMOR_HEADER: &[u8] = b"-----BEGIN AGE ENCRYPTED FILE-----";
+
+/// First line of a binary age file
+const BINARY_HEADER: &[u8] = b"age-encryption.org/";
+
+/// age encryption tool manager
+pub struct AgeManager;
+
+impl AgeManager {
+    /// Create new age manager
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check if age is available
+    pub fn is_available(&self) -> bool {
+        Command::new("age")
+            .arg("--version")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Encrypt to the given recipients, or with a passphrase age prompts for when there are none
+    pub fn encrypt(&self, plaintext: &[u8], recipients: &[String], output: &Path) -> Result<()> {
+        let mut command = Command::new("age");
+        command.arg("--armor").arg("--output").arg(output);
+        if recipients.is_empty() {
+            command.arg("--passphrase");
+        }
+        for recipient in recipients {
+            command.args(["--recipient", recipient]);
+        }
+
+        let mut child = command
+            .stdin(Stdio::piped())
+            .spawn()
+            .with_context(|| tr!("age-failed"))?;
+        child
+            .stdin
+            .take()
+            .with_context(|| tr!("age-stdin-failed"))?
+            .write_all(plaintext)?;
+
+        if !child.wait()?.success() {
+            anyhow::bail!(tr!("age-encrypt-failed"));
+        }
+
+        Ok(())
+    }
+
+    /// Decrypt a file with an identity file, or with a passphrase age prompts for
+    pub fn decrypt(&self, input: &Path, identity: Option<&Path>) -> Result<Vec<u8>> {
+        let mut command = Command::new("age");
+        command.arg("--decrypt");
+        if let Some(identity) = identity {
+            command.arg("--identity").arg(identity);
+        }
+
+        let output = command
+            .arg(input)
+            .stderr(Stdio::inherit())
+            .output()
+            .with_context(|| tr!("age-failed"))?;
+
+        if !output.status.success() {
+            anyhow::bail!(tr!("age-decrypt-failed"));
+        }
+
+        Ok(output.stdout)
+    }
+}
+
+/// Whether file content is age-encrypted (armored or binary)
+pub fn is_encrypted(content: &[u8]) -> bool {
+    content.starts_with(ARMOR_HEADER) || content.starts_with(BINARY_HEADER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_encrypted() {
+        assert!(is_encrypted(
+            b"-----BEGIN AGE ENCRYPTED FILE-----\nYWdlLWVuY3J5cHRpb24ub3JnL3YxCg==\n"
+        ));
+        assert!(is_encrypted(b"age-encryption.org/v1\n-> X25519 abc\n"));
+        assert!(!is_encrypted(b"[[identities]]\nid = \"work\"\n"));
+    }
+}
@@ -0,0 +1,120 @@
+//! `--annotate github` for audit and doctor
+//!
+//! Issues are additionally printed as GitHub Actions workflow commands
+//! (`::error file=.gid,title=...::message`), which the runner turns into
+//! annotations on the run summary and, when a file is given, inline on the
+//! pull request diff.
+
+use std::path::{Path, PathBuf};
+
+use crate::cli::Annotate;
+
+/// Annotation severity
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+/// One annotation
+#[derive(Debug)]
+pub struct Annotation {
+    pub level: Level,
+    pub title: String,
+    pub message: String,
+    pub file: Option<PathBuf>,
+}
+
+impl Annotation {
+    pub fn new(level: Level, title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            title: title.into(),
+            message: message.into(),
+            file: None,
+        }
+    }
+
+    /// Attach a file; only paths inside the workspace are kept, since GitHub
+    /// cannot place anything else
+    pub fn with_file(mut self, file: &Path) -> Self {
+        self.file = workspace_relative(file);
+        self
+    }
+
+    /// The workflow command line
+    pub fn github(&self) -> String {
+        let command = match self.level {
+            Level::Error => "error",
+            Level::Warning => "warning",
+        };
+        let mut properties = Vec::new();
+        if let Some(file) = &self.file {
+            properties.push(format!(
+                "file={}",
+                escape_property(&file.to_string_lossy().replace('\\', "/"))
+            ));
+        }
+        properties.push(format!("title={}", escape_property(&self.title)));
+        format!(
+            "::{command} {}::{}",
+            properties.join(","),
+            escape_data(&self.message)
+        )
+    }
+
+    /// Print in the requested format
+    pub fn emit(&self, annotate: Annotate) {
+        match annotate {
+            Annotate::Github => println!("{}", self.github()),
+        }
+    }
+}
+
+/// `file` relative to `$GITHUB_WORKSPACE` (the current directory outside Actions)
+fn workspace_relative(file: &Path) -> Option<PathBuf> {
+    let root = std::env::var_os("GITHUB_WORKSPACE")
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())?;
+    let root = root.canonicalize().unwrap_or(root);
+    let file = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+    file.strip_prefix(&root).ok().map(Path::to_path_buf)
+}
+
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_github_workflow_command() {
+        let annotation = Annotation::new(
+            Level::Error,
+            "Identity Mismatch: abc1234",
+            "Committed as Test <t@example.com>, 100% wrong\nexpected [work]",
+        );
+        assert_eq!(
+            annotation.github(),
+            "::error title=Identity Mismatch%3A abc1234::Committed as Test <t@example.com>, 100%25 wrong%0Aexpected [work]"
+        );
+
+        let annotation = Annotation {
+            file: Some(PathBuf::from("sub/.gid")),
+            ..Annotation::new(Level::Warning, "a,b", "m")
+        };
+        assert_eq!(
+            annotation.github(),
+            "::warning file=sub/.gid,title=a%2Cb::m"
+        );
+    }
+}
@@ -0,0 +1,103 @@
+//! ASCII-only output
+//!
+//! `--ascii`, GID_ASCII=1 or `settings.ascii = true` replace the symbols gid
+//! prints (✓, ⚠, →, 🔑, box drawing) with plain ASCII for terminals, CI log
+//! viewers and screen readers that render them badly.
+
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::config::Config;
+
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+/// Decide once per process, after `--config` has been applied
+pub fn init(flag: bool) {
+    let ascii = flag || Config::load().is_ok_and(|config| config.settings.ascii);
+    ASCII.store(ascii, Ordering::Relaxed);
+}
+
+/// Whether ASCII mode is on
+pub fn is_enabled() -> bool {
+    ASCII.load(Ordering::Relaxed)
+}
+
+/// A symbol, or its ASCII replacement in ASCII mode
+pub fn sym(symbol: &'static str) -> &'static str {
+    if !is_enabled() {
+        return symbol;
+    }
+    replacement(symbol).unwrap_or(symbol)
+}
+
+/// Text with every symbol replaced in ASCII mode
+pub fn text(text: &str) -> Cow<'_, str> {
+    if !is_enabled() {
+        return Cow::Borrowed(text);
+    }
+    transliterate(text)
+}
+
+fn replacement(symbol: &str) -> Option<&'static str> {
+    let ascii = match symbol {
+        "✓" => "+",
+        "✗" => "x",
+        "⚠" => "!",
+        "→" => "->",
+        "↑" => "^",
+        "↓" => "v",
+        "●" => "*",
+        "○" => "o",
+        "•" => "-",
+        "─" => "-",
+        "═" => "=",
+        "│" => "|",
+        "╭" | "╮" | "╰" | "╯" => "+",
+        "🔑" => "key",
+        "🔏" => "gpg",
+        "🐙" => "gh",
+        "📁" => "repo",
+        // Emoji presentation selector, meaningless without the emoji
+        "\u{fe0f}" => "",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+fn transliterate(text: &str) -> Cow<'_, str> {
+    if text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    let mut output = String::with_capacity(text.len());
+    let mut buf = [0; 4];
+    for c in text.chars() {
+        if c.is_ascii() {
+            output.push(c);
+        } else {
+            match replacement(c.encode_utf8(&mut buf)) {
+                Some(ascii) => output.push_str(ascii),
+                // Not one of gid's symbols (names, paths, translations)
+                None => output.push(c),
+            }
+        }
+    }
+    Cow::Owned(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transliterate() {
+        assert_eq!(transliterate("[work✓]"), "[work+]");
+        assert_eq!(transliterate("main → work"), "main -> work");
+        assert_eq!(transliterate("│  ⚠️  Failed │"), "|  !  Failed |");
+        // Names and translations stay as they are
+        assert_eq!(
+            transliterate("张三 <zs@example.com>"),
+            "张三 <zs@example.com>"
+        );
+        assert!(matches!(transliterate("plain"), Cow::Borrowed("plain")));
+    }
+}
@@ -1,4 +1,5 @@
 use clap::{Parser, Subcommand, ValueEnum};
+use std::ffi::OsString;
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -10,6 +11,38 @@ use std::path::PathBuf;
     after_help = "For more information: https://github.com/your-username/gid"
 )]
 pub struct Cli {
+    /// Run as if gid was started in this directory (like git -C)
+    #[arg(short = 'C', long = "chdir", global = true, value_name = "PATH")]
+    pub chdir: Option<PathBuf>,
+
+    /// Log what gid changes to stderr (-vv: also every command it runs)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    pub verbose: u8,
+
+    /// Only print errors and requested data, no status messages
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// When to color output (auto: only on a terminal, unless NO_COLOR or settings.color = false)
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    /// Print plain ASCII instead of symbols, emoji and box drawing
+    #[arg(long, global = true, env = "GID_ASCII", value_parser = clap::builder::BoolishValueParser::new())]
+    pub ascii: bool,
+
+    /// Never use the network; forge checks, gh API calls and downloads are skipped or fail
+    #[arg(long, global = true, env = "GID_OFFLINE", value_parser = clap::builder::BoolishValueParser::new())]
+    pub offline: bool,
+
+    /// Use this configuration file instead of the default config.toml
+    #[arg(long, global = true, value_name = "FILE", env = "GID_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Operate on this repository (e.g. a bare mirror) instead of the one containing the current directory
+    #[arg(long, global = true, value_name = "DIR")]
+    pub git_dir: Option<PathBuf>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -25,15 +58,147 @@ pub enum Commands {
         /// Global switch (affects all repositories)
         #[arg(short, long)]
         global: bool,
+
+        /// Apply the identity to every repository found under this directory
+        #[arg(short, long, value_name = "DIR", conflicts_with = "global")]
+        recursive: Option<PathBuf>,
+
+        /// Write a gitdir-scoped includeIf block to the global config instead of local config
+        #[arg(long, conflicts_with_all = ["global", "recursive"])]
+        include_if: bool,
+
+        /// Also apply each submodule's own .gid / rule identity (the switched identity otherwise)
+        #[arg(long, conflicts_with_all = ["global", "recursive", "include_if"])]
+        recurse_submodules: bool,
+
+        /// Skip confirmation prompts
+        #[arg(short = 'y', long)]
+        yes: bool,
     },
 
     /// List all identities
     #[command(visible_alias = "ls")]
-    List,
+    List {
+        /// Output as JSON
+        #[arg(long, conflicts_with = "table")]
+        json: bool,
+
+        /// Output as an aligned table
+        #[arg(short, long)]
+        table: bool,
+
+        /// One line per identity from a template, e.g. '{id}\t{email}\t{ssh_key}' (fields as in --json)
+        #[arg(long, value_name = "TEMPLATE", conflicts_with_all = ["json", "table", "verify"])]
+        format: Option<String>,
+
+        /// Only show identities whose id, name, email or description contains this text
+        #[arg(short, long, value_name = "TEXT")]
+        filter: Option<String>,
+
+        /// Only show identities with this tag
+        #[arg(long)]
+        tag: Option<String>,
+
+        /// Sort order (defaults to configuration order)
+        #[arg(short, long, value_enum)]
+        sort: Option<ListSort>,
+
+        /// Check that SSH keys, GPG keys and SSH hosts of each identity are usable
+        #[arg(long, conflicts_with = "json")]
+        verify: bool,
+    },
+
+    /// Show the full record of an identity
+    Show {
+        /// Identity ID
+        identity: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Show current identity
     #[command(visible_alias = "c")]
-    Current,
+    Current {
+        /// Output as JSON
+        #[arg(long, conflicts_with = "prompt")]
+        json: bool,
+
+        /// Print a line from a template, e.g. '{effective.identity} {expected.identity}' (fields as in --json)
+        #[arg(long, value_name = "TEMPLATE", conflicts_with_all = ["json", "prompt"])]
+        format: Option<String>,
+
+        /// Print only the identity ID (marked with ! on rule mismatch) for shell prompts
+        #[arg(long)]
+        prompt: bool,
+    },
+
+    /// Exit 3 if the effective identity differs from the expected one (fast, for prompts and hooks)
+    ///
+    /// With -v a one-line result is printed.
+    Check,
+
+    /// Show the active identity (--remote asks the forge which account is behind it)
+    Whoami {
+        /// Check the SSH key and gh token against the forge and compare accounts
+        #[arg(long)]
+        remote: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Create a .gid file pinning an identity in the repository root and apply it
+    Init {
+        /// Identity ID (defaults to the rule-matched or current identity)
+        identity: Option<String>,
+
+        /// Write the TOML form (identity = "...") instead of a bare id
+        #[arg(long)]
+        toml: bool,
+
+        /// Add .gid to .gitignore (keep the pin local)
+        #[arg(long, conflicts_with = "commit")]
+        gitignore: bool,
+
+        /// Commit the .gid file (share the pin with the team)
+        #[arg(long)]
+        commit: bool,
+
+        /// Overwrite an existing .gid
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Clone a repository with the identity its rules resolve to
+    Clone {
+        /// Repository URL
+        url: String,
+
+        /// Target directory (defaults to the repository name)
+        dir: Option<PathBuf>,
+
+        /// Identity to clone with instead of resolving from rules
+        #[arg(short, long)]
+        identity: Option<String>,
+
+        /// Write a .gid file pinning the identity into the clone
+        #[arg(long)]
+        write_gid: bool,
+
+        /// Extra arguments passed to git clone (after --)
+        #[arg(last = true)]
+        git_args: Vec<String>,
+    },
+
+    /// First-run setup: identities, keys, starter rules, hook and shell integration
+    Onboard {
+        /// Accept the defaults without prompting
+        #[arg(short, long)]
+        yes: bool,
+    },
 
     /// Add a new identity
     Add {
@@ -69,20 +234,122 @@ pub enum Commands {
         identity: String,
     },
 
+    /// Remove SSH aliases, rules and includeIf files left behind by deleted identities
+    Prune {
+        /// Only list what would be removed
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
+    },
+
     /// Edit configuration file
     Edit,
 
+    /// Inspect the configuration file format
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
     /// Export configuration
     Export {
         /// Export file path
         #[arg(default_value = "gid-config.toml")]
         file: PathBuf,
+
+        /// Only export this identity and its rules (repeatable)
+        #[arg(short, long = "identity", value_name = "ID")]
+        identities: Vec<String>,
+
+        /// Only export rules
+        #[arg(long, conflicts_with = "identities_only")]
+        rules_only: bool,
+
+        /// Only export identities
+        #[arg(long)]
+        identities_only: bool,
+
+        /// Only export rules whose pattern or identity matches this glob or text
+        /// (with the identities they use)
+        #[arg(long, value_name = "FILTER", conflicts_with = "identities_only")]
+        rules_matching: Option<String>,
+
+        /// Strip machine-specific data (key paths, GPG key ids, private descriptions)
+        #[arg(long)]
+        sanitized: bool,
+
+        /// Encrypt the export with age (passphrase unless --recipient is given)
+        #[arg(long)]
+        encrypt: bool,
+
+        /// age recipient (public key or SSH public key) to encrypt to (repeatable)
+        #[arg(short, long, value_name = "RECIPIENT", requires = "encrypt")]
+        recipient: Vec<String>,
+
+        /// Output format (defaults to the file extension: .json, .yaml/.yml, .sh, otherwise TOML)
+        #[arg(long, value_enum)]
+        format: Option<ExportFormat>,
     },
 
     /// Import configuration
     Import {
         /// File path to import
-        file: PathBuf,
+        #[arg(required_unless_present = "from_gitconfig")]
+        file: Option<PathBuf>,
+
+        /// age identity file used to decrypt an encrypted export
+        #[arg(long, value_name = "FILE")]
+        age_identity: Option<PathBuf>,
+
+        /// Show which identities and rules would be added, skipped or overwritten
+        #[arg(short = 'n', long, conflicts_with = "from_gitconfig")]
+        dry_run: bool,
+
+        /// File format (defaults to the file extension: .json, .yaml/.yml, otherwise TOML)
+        #[arg(long, value_enum)]
+        format: Option<ExportFormat>,
+
+        /// Merge without prompting, resolving identities that exist with different fields
+        #[arg(
+            long,
+            value_enum,
+            value_name = "STRATEGY",
+            conflicts_with = "from_gitconfig"
+        )]
+        on_conflict: Option<ConflictStrategy>,
+
+        /// Create identities and gitdir rules from existing Git config (global, system, includeIf files)
+        #[arg(long, conflicts_with_all = ["file", "age_identity"])]
+        from_gitconfig: bool,
+
+        /// Accept all discovered identities and rules, and imported post_switch_command values, without prompting
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Revert the most recent gid operation (switch, rule or identity change, hook install)
+    Undo {
+        /// List recorded operations instead of reverting one
+        #[arg(long)]
+        list: bool,
+
+        /// Revert even files that were edited after the operation
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Write includeIf blocks for path rules into the global Git config
+    SyncGitconfig {
+        /// Only show the blocks that would be written
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Remove the synced blocks and stop syncing on rule changes
+        #[arg(long)]
+        remove: bool,
     },
 
     /// Manage rules
@@ -91,15 +358,180 @@ pub enum Commands {
         action: RuleAction,
     },
 
+    /// Manage per-identity commit message templates (applied as commit.template on switch)
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+
+    /// Store per-identity HTTPS credentials used by `gid switch`
+    Credential {
+        #[command(subcommand)]
+        action: CredentialAction,
+    },
+
+    /// Subscribe to shared team rulesets
+    Ruleset {
+        #[command(subcommand)]
+        action: RulesetAction,
+    },
+
+    /// Validate an identity end to end (config, SSH key, agent, forge, signing)
+    Verify {
+        /// Identity ID
+        identity: String,
+
+        /// Also authenticate against each forge in ssh_hosts (github.com by default)
+        #[arg(long)]
+        forge: bool,
+    },
+
+    /// Interactive view of identities, rules and the current repository
+    Tui,
+
     /// Check identity configuration in current directory
     Doctor {
         /// Automatically fix issues
         #[arg(short, long)]
         fix: bool,
+
+        /// Also check that each submodule uses its own .gid / rule identity
+        #[arg(long)]
+        recurse_submodules: bool,
+
+        /// Also print issues as CI annotations (github: workflow commands)
+        #[arg(long, value_enum, value_name = "FORMAT")]
+        annotate: Option<Annotate>,
+    },
+
+    /// Apply the .gid / rule-resolved identity to every repository in a workspace
+    Apply {
+        /// Directories to scan (defaults to settings.workspace_roots)
+        #[arg(long, value_name = "DIR", num_args = 1..)]
+        scan: Vec<PathBuf>,
+
+        /// Show what would change without writing config
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+    },
+
+    /// List repositories with their current and rule-expected identity
+    Repos {
+        /// Directories to scan (defaults to settings.workspace_roots)
+        #[arg(long, value_name = "DIR", num_args = 1..)]
+        scan: Vec<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+
+        /// One line per repository from a template, e.g. '{path}\t{effective.identity}' (fields as in --json)
+        #[arg(long, value_name = "TEMPLATE", conflicts_with = "json")]
+        format: Option<String>,
+    },
+
+    /// Show usage statistics for identities, rules and repositories
+    Stats {
+        /// Directories to scan (defaults to settings.workspace_roots)
+        #[arg(long, value_name = "DIR", num_args = 1..)]
+        scan: Vec<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show past identity switches
+    History {
+        /// Only show switches in this repository
+        #[arg(long, value_name = "PATH")]
+        repo: Option<PathBuf>,
+
+        /// Show at most N entries
+        #[arg(short = 'n', long, value_name = "N")]
+        limit: Option<usize>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Automatically switch identity based on rules
-    Auto,
+    Auto {
+        /// Quiet mode used by `gid shell-init` snippets (honors settings.auto_switch)
+        #[arg(long, hide = true)]
+        shell_hook: bool,
+
+        /// Show which source and identity would apply without changing config
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+
+        /// Reuse the cached rule resolution for this directory
+        #[arg(long)]
+        cached: bool,
+
+        /// Identity to apply when no .gid or rule matches (overrides settings.default_identity)
+        #[arg(long, value_name = "ID")]
+        fallback: Option<String>,
+
+        /// Propose rules for repositories that previously matched nothing
+        #[arg(long)]
+        suggest_rules: bool,
+
+        /// Report whether the current identity matches the rules, without switching
+        #[arg(long)]
+        status: bool,
+
+        /// With --status: print a short prompt token such as [work✓] or [!mismatch]
+        #[arg(long, requires = "status")]
+        prompt: bool,
+
+        /// Apply the matched identity to the global config (works outside repositories)
+        #[arg(short, long, conflicts_with_all = ["status", "suggest_rules", "shell_hook"])]
+        global: bool,
+
+        /// Skip confirmation prompts
+        #[arg(short = 'y', long)]
+        yes: bool,
+
+        /// Resolve every repository under this directory and print a drift report
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["global", "status", "suggest_rules", "shell_hook"])]
+        scan: Option<PathBuf>,
+
+        /// With --scan: apply the expected identity to drifted repositories
+        #[arg(long, requires = "scan", conflicts_with = "check")]
+        apply: bool,
+
+        /// With --scan: only verify and exit non-zero on drift (default)
+        #[arg(long, requires = "scan")]
+        check: bool,
+
+        /// Also apply each submodule's own .gid / rule identity
+        #[arg(long, conflicts_with_all = ["global", "status", "suggest_rules", "shell_hook", "scan"])]
+        recurse_submodules: bool,
+    },
+
+    /// Print shell exports for an identity (eval "$(gid env work)")
+    Env {
+        /// Identity ID (defaults to the .gid / rule resolution)
+        identity: Option<String>,
+
+        /// Shell syntax of the export statements
+        #[arg(short, long, value_enum, default_value = "bash")]
+        shell: ShellType,
+    },
+
+    /// Generate direnv integration
+    Direnv {
+        #[command(subcommand)]
+        action: DirenvAction,
+    },
+
+    /// Generate a .mailmap mapping historical authors to configured identities
+    Mailmap {
+        #[command(subcommand)]
+        action: MailmapAction,
+    },
 
     /// Manage Git hooks
     Hook {
@@ -116,6 +548,23 @@ pub enum Commands {
         /// Attempt to fix issues
         #[arg(short, long)]
         fix: bool,
+
+        /// Print the command that fetches the full history of shallow clones
+        #[arg(long)]
+        unshallow_hint: bool,
+
+        /// Check the signature of every signed commit with gpg / ssh-keygen
+        #[arg(long)]
+        verify_signatures: bool,
+
+        /// One line per issue from a template, e.g. '{repo}\t{commit}\t{type}'
+        /// (fields: repo, type, commit, message, author_name, author_email)
+        #[arg(long, value_name = "TEMPLATE")]
+        format: Option<String>,
+
+        /// Also print issues as CI annotations (github: workflow commands)
+        #[arg(long, value_enum, value_name = "FORMAT", conflicts_with = "format")]
+        annotate: Option<Annotate>,
     },
 
     /// Fix identity information in commits
@@ -128,21 +577,112 @@ pub enum Commands {
         #[arg(short, long)]
         identity: Option<String>,
 
-        /// Batch fix commit range (e.g., HEAD~3..HEAD)
+        /// Batch fix commit range (e.g., HEAD~3..HEAD, or main~3..main to move a branch that is not checked out)
         #[arg(short, long)]
         range: Option<String>,
 
+        /// Fix every commit on the current branch authored with this email
+        #[arg(long, value_name = "EMAIL", conflicts_with = "range")]
+        all_by: Option<String>,
+
+        /// Also set the committer to the identity (default: keep the original committer)
+        #[arg(long)]
+        committer: bool,
+
+        /// Keep the original author and committer dates (default)
+        #[arg(long, conflicts_with = "reset_dates")]
+        keep_dates: bool,
+
+        /// Use the current time as author and committer date
+        #[arg(long)]
+        reset_dates: bool,
+
+        /// Sign the rewritten commits with the identity's GPG key
+        #[arg(long)]
+        resign: bool,
+
         /// Skip confirmation prompts
         #[arg(short = 'y', long)]
         yes: bool,
     },
 
+    /// Watch workspace directories and enforce rule-matched identities
+    Watch {
+        /// Directories to watch (defaults to settings.workspace_roots)
+        paths: Vec<PathBuf>,
+
+        /// Seconds between scans
+        #[arg(short, long, default_value = "10")]
+        interval: u64,
+
+        /// Only report violations, do not change any config
+        #[arg(short, long)]
+        check: bool,
+
+        /// Run a single scan and exit
+        #[arg(long)]
+        once: bool,
+    },
+
+    /// Print shell integration that auto-switches identity on directory change
+    ShellInit {
+        /// Shell type
+        #[arg(value_enum)]
+        shell: ShellType,
+    },
+
+    /// Serve identity queries and switches to editor plugins over JSON-RPC
+    Serve {
+        /// Unix socket to listen on (defaults to gid.sock in the data directory)
+        #[arg(long, value_name = "PATH", conflicts_with = "stdio")]
+        socket: Option<PathBuf>,
+
+        /// Talk over stdin/stdout instead of a socket
+        #[arg(long)]
+        stdio: bool,
+    },
+
     /// Generate shell completion scripts
     Completions {
         /// Shell type
         #[arg(value_enum)]
         shell: ShellType,
     },
+
+    /// Print completion candidates from the current config (used by completion scripts)
+    #[command(name = "__complete", hide = true)]
+    Complete {
+        #[arg(value_enum)]
+        kind: CompleteKind,
+    },
+
+    /// Any other name runs gid-<name> from PATH
+    #[command(external_subcommand)]
+    External(Vec<OsString>),
+}
+
+/// `--color` setting
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+/// `--annotate` target
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Annotate {
+    /// GitHub Actions `::error` / `::warning` workflow commands
+    Github,
+}
+
+/// Values completed dynamically by `gid __complete`
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CompleteKind {
+    /// Identity ids
+    Identities,
+    /// Rule ids
+    Rules,
 }
 
 #[derive(Subcommand, Clone)]
@@ -150,12 +690,35 @@ pub enum RuleAction {
     /// Add a rule
     Add {
         /// Rule type
-        #[arg(short, long, value_enum)]
+        #[arg(
+            short = 't',
+            long,
+            visible_alias = "type",
+            short_alias = 'r',
+            value_enum
+        )]
         rule_type: RuleType,
 
         /// Match pattern
-        #[arg(short, long)]
-        pattern: String,
+        #[arg(short, long, required_unless_present_any = ["host", "org", "repo", "when"])]
+        pattern: Option<String>,
+
+        /// Remote rules: host of the parsed URL, SSH and HTTPS alike (e.g. github.com)
+        #[arg(long)]
+        host: Option<String>,
+
+        /// Remote rules: organization or user of the parsed URL (GitLab subgroups included)
+        #[arg(long)]
+        org: Option<String>,
+
+        /// Remote rules: repository name of the parsed URL
+        #[arg(long)]
+        repo: Option<String>,
+
+        /// all-of / any-of rules: a condition such as path='~/clients/acme/**',
+        /// remote=…, branch=…, host=…, org=… or repo=… (repeatable)
+        #[arg(long, value_name = "KIND=PATTERN")]
+        when: Vec<String>,
 
         /// Identity to use when matched
         #[arg(short, long)]
@@ -169,10 +732,74 @@ pub enum RuleAction {
     /// List all rules
     List,
 
+    /// Change a rule in place, keeping its id (prompts for each field when no flag is given)
+    Edit {
+        /// Rule ID (shown by `gid rule list`)
+        id: String,
+
+        /// New rule type
+        #[arg(short = 't', long, visible_alias = "type", value_enum)]
+        rule_type: Option<RuleType>,
+
+        /// New match pattern
+        #[arg(short, long)]
+        pattern: Option<String>,
+
+        /// Remote rules: host of the parsed URL ('' clears it)
+        #[arg(long)]
+        host: Option<String>,
+
+        /// Remote rules: organization or user of the parsed URL ('' clears it)
+        #[arg(long)]
+        org: Option<String>,
+
+        /// Remote rules: repository name of the parsed URL ('' clears it)
+        #[arg(long)]
+        repo: Option<String>,
+
+        /// all-of / any-of rules: replace the conditions (repeatable, KIND=PATTERN)
+        #[arg(long, value_name = "KIND=PATTERN")]
+        when: Vec<String>,
+
+        /// Identity to use when matched
+        #[arg(short, long)]
+        identity: Option<String>,
+
+        /// Rule priority (lower number = higher priority)
+        #[arg(long)]
+        priority: Option<u32>,
+
+        /// Description ('' clears it)
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+
+    /// Enable a disabled rule
+    Enable {
+        /// Rule ID (shown by `gid rule list`)
+        id: String,
+    },
+
+    /// Disable a rule without removing it
+    Disable {
+        /// Rule ID (shown by `gid rule list`)
+        id: String,
+    },
+
+    /// Change a rule's priority, moving it up or down the list
+    Move {
+        /// Rule ID (shown by `gid rule list`)
+        id: String,
+
+        /// New priority (lower number = higher priority)
+        #[arg(long)]
+        priority: u32,
+    },
+
     /// Remove a rule
     Remove {
-        /// Rule index
-        index: usize,
+        /// Rule ID (shown by `gid rule list`)
+        id: String,
     },
 
     /// Test rule matching
@@ -184,6 +811,105 @@ pub enum RuleAction {
         /// Test remote URL
         #[arg(short, long)]
         remote: Option<String>,
+
+        /// Test branch name (defaults to the checked-out branch)
+        #[arg(short, long)]
+        branch: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum RulesetAction {
+    /// Subscribe to a ruleset (http(s) URL or local file with [[rules]])
+    Subscribe {
+        /// Ruleset URL
+        url: String,
+
+        /// Local name of the ruleset
+        #[arg(short, long)]
+        name: String,
+    },
+
+    /// Re-fetch subscribed rulesets and merge their changes
+    Update {
+        /// Only update this ruleset
+        name: Option<String>,
+    },
+
+    /// Remove a subscription and its rules
+    Unsubscribe {
+        /// Ruleset name
+        name: String,
+    },
+
+    /// List subscribed rulesets
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print the JSON Schema of config.toml, for editor validation and completion
+    Schema,
+}
+
+#[derive(Subcommand)]
+pub enum TemplateAction {
+    /// Create or edit the identity's commit template in $EDITOR
+    Edit {
+        /// Identity ID
+        identity: String,
+    },
+
+    /// Print the identity's commit template
+    Show {
+        /// Identity ID
+        identity: String,
+    },
+
+    /// Stop using a commit template for the identity
+    Remove {
+        /// Identity ID
+        identity: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CredentialAction {
+    /// Store a username and token (read from stdin when piped)
+    Set {
+        /// Identity ID
+        identity: String,
+
+        /// Host the credential is for
+        #[arg(long, default_value = crate::gh::DEFAULT_HOST)]
+        host: String,
+
+        /// HTTPS username (the account login on most forges)
+        #[arg(short, long)]
+        username: String,
+    },
+
+    /// List stored credentials (tokens masked)
+    List,
+
+    /// Remove a stored credential
+    Unset {
+        /// Identity ID
+        identity: String,
+
+        /// Host the credential is for
+        #[arg(long, default_value = crate::gh::DEFAULT_HOST)]
+        host: String,
+    },
+
+    /// Git credential helper entry point (configured by `gid switch`)
+    #[command(hide = true)]
+    Helper {
+        /// Identity ID
+        identity: String,
+
+        /// Operation requested by git (get, store or erase)
+        operation: String,
     },
 }
 
@@ -205,6 +931,77 @@ pub enum HookAction {
 
     /// Show hook status
     Status,
+
+    /// Identity check for the pre-commit framework (entry of the hooks in .pre-commit-hooks.yaml)
+    ///
+    /// Staged file names are accepted and ignored; fails with exit 3 when the
+    /// identity differs from the one .gid or rules expect. GID_SKIP=1 skips it.
+    PreCommitFramework {
+        /// Files pre-commit passes (unused, the identity applies to the whole commit)
+        files: Vec<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// gid configuration file (for gid import)
+    #[default]
+    Toml,
+    /// gid configuration as JSON
+    Json,
+    /// gid configuration as YAML
+    Yaml,
+    /// Standalone shell script of `git config` commands
+    Script,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ConflictStrategy {
+    /// Keep the existing identity
+    #[default]
+    Skip,
+    /// Replace the existing identity with the imported one
+    Overwrite,
+    /// Import under a new id with an "-imported" suffix
+    Rename,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ListSort {
+    /// Alphabetically by identity ID
+    Id,
+    /// Most recently switched to first
+    Recent,
+    /// Most frequently switched to first
+    Usage,
+}
+
+#[derive(Subcommand)]
+pub enum DirenvAction {
+    /// Print (or write) an .envrc stanza for the identity resolved for this repository
+    Export {
+        /// Identity ID (defaults to the .gid / rule resolution)
+        #[arg(short, long)]
+        identity: Option<String>,
+
+        /// Write the stanza into the repository's .envrc instead of printing it
+        #[arg(short, long)]
+        write: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum MailmapAction {
+    /// Write (or update gid's block in) the repository's .mailmap
+    Generate {
+        /// Mailmap file to write (defaults to .mailmap in the repository root)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Print the mappings instead of writing them
+        #[arg(short = 'n', long, conflicts_with = "output")]
+        dry_run: bool,
+    },
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
@@ -213,6 +1010,12 @@ pub enum RuleType {
     Path,
     /// Remote URL matching rule
     Remote,
+    /// Checked-out branch matching rule (e.g. release/*)
+    Branch,
+    /// Every --when condition must match
+    AllOf,
+    /// Any --when condition matches
+    AnyOf,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
@@ -220,6 +1023,7 @@ pub enum ShellType {
     Bash,
     Zsh,
     Fish,
+    #[value(name = "powershell")]
     PowerShell,
 }
 
@@ -0,0 +1,51 @@
+//! Whether output is colored
+//!
+//! `--color always|never` wins; otherwise NO_COLOR and `settings.color = false`
+//! turn colors off, and they are only used when stdout is a terminal. The
+//! decision applies to both `colored` and the `console` styling dialoguer uses.
+
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::cli::ColorChoice;
+use crate::config::Config;
+
+/// Colors are off only because stdout is not a terminal
+static OFF_FOR_PIPE: AtomicBool = AtomicBool::new(false);
+
+/// Decide once per process, after `--config` has been applied
+pub fn init(choice: ColorChoice) {
+    let enabled = match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto if !allowed_by_env_and_settings() => false,
+        ColorChoice::Auto => {
+            let terminal = std::io::stdout().is_terminal();
+            OFF_FOR_PIPE.store(!terminal, Ordering::Relaxed);
+            terminal
+        }
+    };
+    set_enabled(enabled);
+}
+
+/// Shell prompts capture stdout, so color it unless colors were turned off
+pub fn enable_for_prompt() {
+    if OFF_FOR_PIPE.load(Ordering::Relaxed) {
+        set_enabled(true);
+    }
+}
+
+fn allowed_by_env_and_settings() -> bool {
+    // https://no-color.org: set and not empty
+    if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        return false;
+    }
+    // A broken config is reported by the command itself
+    Config::load().map_or(true, |config| config.settings.color)
+}
+
+fn set_enabled(enabled: bool) {
+    colored::control::set_override(enabled);
+    console::set_colors_enabled(enabled);
+    console::set_colors_enabled_stderr(enabled);
+}
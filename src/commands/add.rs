@@ -3,8 +3,10 @@ use colored::Colorize;
 use dialoguer::{Confirm, Input};
 use std::path::PathBuf;
 
+use crate::ascii::sym;
 use crate::config::{Config, Identity};
 use crate::gpg::GpgManager;
+use crate::i18n::tr;
 use crate::ssh::SshManager;
 
 /// 添加新身份
@@ -16,9 +18,9 @@ pub fn execute(
     ssh_key: Option<PathBuf>,
     gpg_key: Option<String>,
 ) -> Result<()> {
-    let mut config = Config::load()?;
+    let config = Config::load()?;
 
-    println!("{}", "Add new Git identity".bold());
+    println!("{}", tr!("add-header").bold());
     println!();
 
     // 获取身份 ID
@@ -26,7 +28,7 @@ pub fn execute(
         id
     } else {
         Input::<String>::new()
-            .with_prompt("Identity ID (e.g., work, personal)")
+            .with_prompt(tr!("add-prompt-id"))
             .interact_text()?
     };
 
@@ -35,23 +37,25 @@ pub fn execute(
         .chars()
         .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
     {
-        anyhow::bail!("Identity ID can only contain letters, numbers, underscores, and hyphens");
+        anyhow::bail!(tr!("identity-id-invalid"));
     }
 
     // 检查 ID 是否已存在
     if config.find_identity(&id).is_some() {
-        anyhow::bail!("Identity '{id}' already exists");
+        anyhow::bail!(tr!("identity-exists", id = id));
     }
 
     // 获取姓名
     let name = if let Some(name) = name {
         name
     } else {
-        Input::<String>::new().with_prompt("Name").interact_text()?
+        Input::<String>::new()
+            .with_prompt(tr!("add-prompt-name"))
+            .interact_text()?
     };
 
     if name.is_empty() {
-        anyhow::bail!("Name cannot be empty");
+        anyhow::bail!(tr!("identity-name-empty"));
     }
 
     // 获取邮箱
@@ -59,12 +63,12 @@ pub fn execute(
         email
     } else {
         Input::<String>::new()
-            .with_prompt("Email")
+            .with_prompt(tr!("add-prompt-email"))
             .interact_text()?
     };
 
     if !email.contains('@') || !email.contains('.') {
-        anyhow::bail!("Invalid email format");
+        anyhow::bail!(tr!("identity-email-invalid"));
     }
 
     // 获取描述
@@ -72,7 +76,7 @@ pub fn execute(
         description
     } else {
         let desc: String = Input::new()
-            .with_prompt("Description (optional, press Enter to skip)")
+            .with_prompt(tr!("add-prompt-description"))
             .allow_empty(true)
             .interact_text()?;
         if desc.is_empty() {
@@ -87,7 +91,7 @@ pub fn execute(
         ssh_key
     } else {
         let configure_ssh = Confirm::new()
-            .with_prompt("Configure SSH key?")
+            .with_prompt(tr!("add-prompt-ssh"))
             .default(false)
             .interact()?;
 
@@ -103,7 +107,7 @@ pub fn execute(
         gpg_key
     } else {
         let configure_gpg = Confirm::new()
-            .with_prompt("Configure GPG signing key?")
+            .with_prompt(tr!("add-prompt-gpg"))
             .default(false)
             .interact()?;
 
@@ -122,35 +126,37 @@ pub fn execute(
 
     // 验证并保存
     identity.validate().map_err(|e| anyhow::anyhow!(e))?;
-    config.add_identity(identity)?;
-    config.save()?;
+    Config::update(|config| config.add_identity(identity))?;
 
     println!();
     println!(
-        "{} Identity added: {} {} <{}>",
-        "✓".green(),
-        format!("[{id}]").green().bold(),
-        name,
-        email.cyan()
+        "{} {}",
+        sym("✓").green(),
+        tr!(
+            "add-done",
+            identity = format!("[{id}]").green().bold(),
+            name = name,
+            email = email.cyan()
+        )
     );
 
     if ssh_key.is_some() {
-        println!("  {} SSH key configured", "🔑".dimmed());
+        println!("  {} {}", sym("🔑").dimmed(), tr!("switch-ssh-key"));
     }
     if gpg_key.is_some() {
-        println!("  {} GPG signing configured", "🔏".dimmed());
+        println!("  {} {}", sym("🔏").dimmed(), tr!("add-gpg-signing"));
     }
 
     // 询问是否立即切换
     println!();
     let switch_now = Confirm::new()
-        .with_prompt("Switch to this identity now?")
+        .with_prompt(tr!("add-prompt-switch"))
         .default(false)
         .interact()?;
 
     if switch_now {
         let global = Confirm::new()
-            .with_prompt("Switch to global configuration?")
+            .with_prompt(tr!("add-prompt-switch-global"))
             .default(false)
             .interact()?;
 
@@ -165,40 +171,44 @@ fn configure_ssh_key(identity_id: &str, email: &str) -> Result<Option<PathBuf>>
     let ssh = SshManager::new()?;
 
     println!();
-    println!("{}", "SSH Key Configuration:".cyan());
-    println!("  1. Use existing key");
-    println!("  2. Generate new key");
-    println!("  3. Skip");
+    println!("{}", tr!("add-ssh-header").cyan());
+    println!("  1. {}", tr!("add-ssh-existing"));
+    println!("  2. {}", tr!("add-ssh-generate"));
+    println!("  3. {}", tr!("add-skip"));
 
     let choice: String = Input::new()
-        .with_prompt("Select [1/2/3]")
+        .with_prompt(tr!("add-prompt-choice"))
         .default("3".to_string())
         .interact_text()?;
 
     match choice.trim() {
         "1" => {
             let key_path: String = Input::new()
-                .with_prompt("SSH private key path")
+                .with_prompt(tr!("add-prompt-ssh-path"))
                 .default("~/.ssh/id_ed25519".to_string())
                 .interact_text()?;
 
             let path = PathBuf::from(shellexpand::tilde(&key_path).to_string());
 
             if !ssh.key_exists(&path) {
-                anyhow::bail!("Key file does not exist: {}", path.display());
+                anyhow::bail!(tr!("identity-ssh-key-missing", path = path.display()));
             }
 
             Ok(Some(path))
         }
         "2" => {
-            println!("{} Generating new SSH key...", "→".blue());
+            println!("{} {}", sym("→").blue(), tr!("add-ssh-generating"));
             let key_path = ssh.generate_key(identity_id, email)?;
-            println!("{} Key generated: {}", "✓".green(), key_path.display());
+            println!(
+                "{} {}",
+                sym("✓").green(),
+                tr!("add-ssh-generated", path = key_path.display())
+            );
 
             // 显示公钥
             if let Ok(pub_key) = ssh.read_public_key(&key_path) {
                 println!();
-                println!("{}", "Public key content (add to GitHub/GitLab):".cyan());
+                println!("{}", tr!("add-ssh-public-key").cyan());
                 println!("{}", pub_key.trim().dimmed());
             }
 
@@ -213,18 +223,18 @@ fn configure_gpg_key(email: &str) -> Result<Option<String>> {
     let gpg = GpgManager::new();
 
     if !gpg.is_available() {
-        println!("{} GPG not installed, skipping configuration", "!".yellow());
+        println!("{} {}", "!".yellow(), tr!("add-gpg-missing"));
         return Ok(None);
     }
 
     println!();
-    println!("{}", "GPG Signing Configuration:".cyan());
+    println!("{}", tr!("add-gpg-header").cyan());
 
     // 查找现有密钥
     if let Ok(Some(key)) = gpg.find_key_by_email(email) {
-        println!("Found matching GPG key: {}", key.key_id);
+        println!("{}", tr!("add-gpg-found", key = key.key_id));
         let use_existing = Confirm::new()
-            .with_prompt("Use this key?")
+            .with_prompt(tr!("add-prompt-gpg-use"))
             .default(true)
             .interact()?;
 
@@ -233,29 +243,31 @@ fn configure_gpg_key(email: &str) -> Result<Option<String>> {
         }
     }
 
-    println!("  1. Enter existing key ID");
-    println!("  2. List all keys");
-    println!("  3. Skip");
+    println!("  1. {}", tr!("add-gpg-enter"));
+    println!("  2. {}", tr!("add-gpg-list"));
+    println!("  3. {}", tr!("add-skip"));
 
     let choice: String = Input::new()
-        .with_prompt("Select [1/2/3]")
+        .with_prompt(tr!("add-prompt-choice"))
         .default("3".to_string())
         .interact_text()?;
 
     match choice.trim() {
         "1" => {
-            let key_id: String = Input::new().with_prompt("GPG Key ID").interact_text()?;
+            let key_id: String = Input::new()
+                .with_prompt(tr!("add-prompt-gpg-id"))
+                .interact_text()?;
 
             if gpg.verify_key(&key_id)? {
                 Ok(Some(key_id))
             } else {
-                anyhow::bail!("Invalid GPG Key ID");
+                anyhow::bail!(tr!("add-gpg-invalid"));
             }
         }
         "2" => {
             let keys = gpg.list_keys()?;
             if keys.is_empty() {
-                println!("{} No GPG keys found", "!".yellow());
+                println!("{} {}", "!".yellow(), tr!("add-gpg-none"));
                 return Ok(None);
             }
 
@@ -266,7 +278,7 @@ fn configure_gpg_key(email: &str) -> Result<Option<String>> {
             println!();
 
             let index: String = Input::new()
-                .with_prompt("Select key index (press Enter to skip)")
+                .with_prompt(tr!("add-prompt-gpg-index"))
                 .allow_empty(true)
                 .interact_text()?;
 
@@ -276,9 +288,9 @@ fn configure_gpg_key(email: &str) -> Result<Option<String>> {
 
             let index: usize = index
                 .parse()
-                .map_err(|_| anyhow::anyhow!("Invalid index"))?;
+                .map_err(|_| anyhow::anyhow!(tr!("add-index-invalid")))?;
             if index == 0 || index > keys.len() {
-                anyhow::bail!("Index out of range");
+                anyhow::bail!(tr!("add-index-out-of-range"));
             }
 
             Ok(Some(keys[index - 1].key_id.clone()))
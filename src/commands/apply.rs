@@ -0,0 +1,169 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::ascii::sym;
+use crate::commands::export::contract_home;
+use crate::commands::repos::workspace_repos;
+use crate::commands::switch;
+use crate::config::Config;
+use crate::git::GitConfigManager;
+use crate::i18n::tr;
+use crate::rules::resolve::RepoStatus;
+
+/// Apply the .gid / rule-resolved identity to every repository under the scan roots
+pub fn execute(scan: Vec<PathBuf>, dry_run: bool) -> Result<()> {
+    let config = Config::load()?;
+
+    let repos = workspace_repos(&config, scan)?;
+
+    if repos.is_empty() {
+        println!("{} {}", "!".yellow(), tr!("apply-no-repos"));
+        return Ok(());
+    }
+
+    let (mut updated, mut unchanged, mut unmatched, mut failed) = (0, 0, 0, 0);
+    // Identity -> remote hosts of the repositories it was applied to
+    let mut ssh_hosts: BTreeMap<String, Vec<String>> = BTreeMap::new();
+
+    for repo in &repos {
+        let display = contract_home(&repo.display().to_string());
+
+        let result = RepoStatus::inspect(&config, repo).and_then(|status| {
+            let Some(expected) = status.expected else {
+                return Ok(None);
+            };
+            let identity = config.find_identity(&expected.identity).ok_or_else(|| {
+                anyhow::anyhow!(tr!("identity-not-found", id = expected.identity))
+            })?;
+
+            let git = GitConfigManager::from_path(repo)?;
+            let hosts = ssh_hosts.entry(identity.id.clone()).or_default();
+            for host in switch::remote_hosts(&git) {
+                if !hosts.contains(&host) {
+                    hosts.push(host);
+                }
+            }
+
+            let changes = if dry_run {
+                switch::pending_changes(&git, identity)
+            } else {
+                switch::apply_to_repo(&git, identity)?
+            };
+            Ok(Some((identity.id.clone(), changes)))
+        });
+
+        match result {
+            Ok(None) => {
+                unmatched += 1;
+                println!(
+                    "  {} {} {}",
+                    "?".dimmed(),
+                    display,
+                    tr!("apply-no-rule").dimmed()
+                );
+            }
+            Ok(Some((id, changes))) if changes.is_empty() => {
+                unchanged += 1;
+                println!(
+                    "  {} {} {}",
+                    "=".dimmed(),
+                    display,
+                    format!("[{id}]").dimmed()
+                );
+            }
+            Ok(Some((id, changes))) => {
+                updated += 1;
+                let (marker, message) = if dry_run {
+                    (
+                        "~".yellow(),
+                        tr!("apply-would-set", keys = changes.join(", ")),
+                    )
+                } else {
+                    (
+                        sym("✓").green(),
+                        tr!("apply-set", keys = changes.join(", ")),
+                    )
+                };
+                println!(
+                    "  {} {} {} {}",
+                    marker,
+                    display,
+                    format!("[{id}]").green(),
+                    message.dimmed()
+                );
+            }
+            Err(e) => {
+                failed += 1;
+                println!("  {} {}: {}", sym("✗").red(), display, e);
+            }
+        }
+    }
+
+    // SSH config is global, configure each applied identity once
+    for (id, hosts) in &ssh_hosts {
+        let Some(identity) = config.find_identity(id) else {
+            continue;
+        };
+        if identity.ssh_key.is_none() {
+            continue;
+        }
+        if dry_run {
+            println!(
+                "  {} {}",
+                sym("🔑").dimmed(),
+                tr!(
+                    "apply-would-configure-ssh",
+                    identity = format!("[{id}]").cyan(),
+                    hosts = if hosts.is_empty() {
+                        tr!("apply-default-hosts")
+                    } else {
+                        hosts.join(", ")
+                    }
+                )
+            );
+            continue;
+        }
+        for alias in switch::configure_ssh(&config, identity, hosts)? {
+            println!(
+                "  {} {}",
+                sym("🔑").dimmed(),
+                tr!("apply-wrote-host", alias = alias.cyan())
+            );
+        }
+    }
+
+    println!();
+    let failed_count = failed;
+    let (updated, failed) = (updated.to_string().green(), failed.to_string().red());
+    let summary = if dry_run {
+        tr!(
+            "apply-summary-dry-run",
+            total = repos.len(),
+            updated = updated,
+            unchanged = unchanged,
+            unmatched = unmatched,
+            failed = failed
+        )
+    } else {
+        tr!(
+            "apply-summary",
+            total = repos.len(),
+            updated = updated,
+            unchanged = unchanged,
+            unmatched = unmatched,
+            failed = failed
+        )
+    };
+    println!("{summary}");
+    if dry_run {
+        println!("{}", tr!("import-dry-run"));
+    }
+
+    if failed_count > 0 {
+        anyhow::bail!(tr!("apply-failed", count = failed_count));
+    }
+
+    Ok(())
+}
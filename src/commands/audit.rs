@@ -1,62 +1,247 @@
 use anyhow::Result;
 use colored::Colorize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::audit::Auditor;
+use crate::annotate::{Annotation, Level};
+use crate::ascii::sym;
+use crate::audit::{AuditIssue, AuditResult, Auditor, IssueType};
+use crate::cli::Annotate;
 use crate::config::Config;
+use crate::format::Template;
+use crate::i18n::tr;
 
 /// Audit commit history
-pub fn execute(path: Option<PathBuf>, fix: bool) -> Result<()> {
+pub fn execute(
+    path: Option<PathBuf>,
+    fix: bool,
+    unshallow_hint: bool,
+    verify_signatures: bool,
+    format: Option<&str>,
+    annotate: Option<Annotate>,
+) -> Result<()> {
+    let template = format.map(Template::parse).transpose()?;
     let config = Config::load()?;
-    let auditor = Auditor::new(config);
+    let auditor = Auditor::new(config).with_signature_check(verify_signatures);
 
-    let target_path = path.unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
+    // --git-dir selects the repository to audit when no path is given
+    let target_path = path
+        .or_else(|| std::env::var_os("GIT_DIR").map(PathBuf::from))
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
-    println!("{}", "Auditing Git commit history...".bold());
-    println!("  Target: {}", target_path.display().to_string().cyan());
-    println!();
+    if template.is_none() {
+        println!("{}", tr!("audit-header").bold());
+        println!(
+            "  {}",
+            tr!(
+                "audit-target",
+                path = target_path.display().to_string().cyan()
+            )
+        );
+        println!();
+    }
 
     // Check if single repo or directory
-    let results = if target_path.join(".git").exists() {
+    let results = if target_path.join(".git").exists() || crate::git::is_git_dir(&target_path) {
         vec![auditor.audit_repo(&target_path)?]
     } else {
         auditor.audit_directory(&target_path)?
     };
 
+    if let Some(template) = template {
+        for result in &results {
+            for issue in &result.issues {
+                template.print(&issue_json(result, issue))?;
+            }
+        }
+        return Ok(());
+    }
+
     if results.is_empty() {
-        println!("{} No Git repository found", "!".yellow());
+        println!("{} {}", "!".yellow(), tr!("audit-no-repo"));
         return Ok(());
     }
 
     // Show results
     let mut total_issues = 0;
     for result in &results {
-        result.print_report();
+        print_report(result, unshallow_hint);
         total_issues += result.issues.len();
     }
+    if let Some(annotate) = annotate {
+        for result in &results {
+            for issue in &result.issues {
+                issue_annotation(result, issue).emit(annotate);
+            }
+        }
+    }
+    let shallow = results.iter().filter(|r| r.shallow).count();
 
     // Summary
     println!();
-    println!("{}", "═".repeat(50));
+    println!("{}", sym("═").repeat(50));
     println!(
-        "Audit complete: {} repositories, {} issues",
-        results.len(),
-        if total_issues > 0 {
-            total_issues.to_string().yellow().to_string()
-        } else {
-            "0".green().to_string()
-        }
+        "{}",
+        tr!(
+            "audit-complete",
+            repos = results.len(),
+            issues = if total_issues > 0 {
+                total_issues.to_string().yellow().to_string()
+            } else {
+                "0".green().to_string()
+            }
+        )
     );
+    if shallow > 0 && !unshallow_hint {
+        println!(
+            "  {} {}",
+            "!".yellow(),
+            tr!(
+                "audit-shallow-summary",
+                count = shallow,
+                command = "gid audit --unshallow-hint".cyan()
+            )
+        );
+    }
 
     if total_issues > 0 && fix {
         println!();
+        println!("{} {}", "!".yellow(), tr!("audit-fix-unsupported"));
+        println!("  {}", tr!("audit-fix-rewrite"));
+        println!("  {}", tr!("audit-fix-tools"));
+    }
+
+    Ok(())
+}
+
+/// One issue as a `--format` record
+fn issue_json(result: &AuditResult, issue: &AuditIssue) -> Value {
+    let kind = match issue.issue_type {
+        IssueType::UnknownIdentity => "unknown_identity",
+        IssueType::IdentityMismatch => "identity_mismatch",
+        IssueType::MixedIdentities => "mixed_identities",
+        IssueType::BadSignature => "bad_signature",
+    };
+    json!({
+        "repo": result.repo_path,
+        "type": kind,
+        "commit": issue.commit_id,
+        "message": issue.message.lines().next().unwrap_or_default(),
+        "author_name": issue.author_name,
+        "author_email": issue.author_email,
+    })
+}
+
+/// One issue as a CI annotation, placed on the repository's .gid when
+/// that is what the commit disagrees with
+fn issue_annotation(result: &AuditResult, issue: &AuditIssue) -> Annotation {
+    let level = match issue.issue_type {
+        IssueType::IdentityMismatch | IssueType::BadSignature => Level::Error,
+        IssueType::UnknownIdentity | IssueType::MixedIdentities => Level::Warning,
+    };
+    let annotation = Annotation::new(
+        level,
+        format!("gid audit: {} in {}", issue.issue_type, issue.commit_id),
+        format!(
+            "{} {} - {} <{}> ({})",
+            issue.commit_id,
+            issue.message.lines().next().unwrap_or_default(),
+            issue.author_name,
+            issue.author_email,
+            result.repo_path.display()
+        ),
+    );
+    let project = result.repo_path.join(".gid");
+    if issue.issue_type == IssueType::IdentityMismatch && project.is_file() {
+        annotation.with_file(&project)
+    } else {
+        annotation
+    }
+}
+
+/// Print audit report
+///
+/// With `unshallow_hint`, shallow clones also get the command that fetches
+/// their full history.
+fn print_report(result: &AuditResult, unshallow_hint: bool) {
+    println!();
+    println!(
+        "{}",
+        format!("{} {}", sym("📁"), result.repo_path.display()).bold()
+    );
+    println!(
+        "   {}",
+        tr!("audit-total-commits", count = result.total_commits)
+    );
+    if result.shallow {
+        println!("   {} {}", "!".yellow(), tr!("audit-shallow"));
+        if unshallow_hint {
+            println!(
+                "     {}",
+                tr!(
+                    "audit-unshallow",
+                    command =
+                        format!("git -C {} fetch --unshallow", result.repo_path.display()).cyan()
+                )
+            );
+        }
+    }
+
+    // Identity usage statistics
+    println!();
+    println!("   {}:", tr!("audit-usage").cyan());
+    for usage in result.identities_used.values() {
+        let status = if usage.is_known {
+            format!("[{}]", usage.identity_id.as_deref().unwrap_or("?")).green()
+        } else {
+            format!("[{}]", tr!("audit-unknown")).yellow()
+        };
         println!(
-            "{} Automatic fix does not support commit history modification yet",
-            "!".yellow()
+            "   {} {} <{}> - {}",
+            status,
+            usage.name,
+            usage.email,
+            tr!("audit-commits", count = usage.commit_count)
         );
-        println!("  Modifying commit history requires git rebase or git filter-branch");
-        println!("  Manual handling or specialized tools like git-filter-repo are recommended");
     }
 
-    Ok(())
+    // Issue list
+    if result.issues.is_empty() {
+        println!();
+        println!("   {} {}", sym("✓").green(), tr!("doctor-no-issues"));
+    } else {
+        println!();
+        println!(
+            "   {} {}",
+            sym("⚠").yellow(),
+            tr!("doctor-issues", count = result.issues.len())
+        );
+
+        // Group by type
+        let mut by_type: HashMap<IssueType, Vec<&AuditIssue>> = HashMap::new();
+        for issue in &result.issues {
+            by_type
+                .entry(issue.issue_type.clone())
+                .or_default()
+                .push(issue);
+        }
+
+        for (issue_type, issues) in by_type {
+            println!();
+            println!("   {} ({}):", issue_type.to_string().yellow(), issues.len());
+            for issue in issues.iter().take(5) {
+                println!(
+                    "     {} {} - {} <{}>",
+                    issue.commit_id.dimmed(),
+                    issue.message.chars().take(40).collect::<String>(),
+                    issue.author_name,
+                    issue.author_email
+                );
+            }
+            if issues.len() > 5 {
+                println!("     {}", tr!("audit-more", count = issues.len() - 5));
+            }
+        }
+    }
 }
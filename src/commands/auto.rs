@@ -1,71 +1,601 @@
 use anyhow::Result;
 use colored::Colorize;
 
-use crate::config::Config;
+use std::path::{Path, PathBuf};
+
+use crate::ascii::sym;
+use crate::config::state::UnmatchedRepo;
+use crate::config::{Config, State};
+use crate::exit::Failure;
 use crate::git::GitConfigManager;
-use crate::rules::{MatchContext, RuleEngine};
+use crate::i18n::tr;
+use crate::rules::cache::resolve_cached;
+use crate::rules::resolve::{fallback_identity, resolve_identity, RepoStatus, Resolution, Source};
+use crate::rules::suggest;
+
+/// Options for `gid auto`
+#[derive(Debug, Default)]
+pub struct AutoOptions {
+    pub shell_hook: bool,
+    pub dry_run: bool,
+    pub cached: bool,
+    pub fallback: Option<String>,
+    pub suggest_rules: bool,
+    pub status: bool,
+    pub prompt: bool,
+    pub global: bool,
+    pub yes: bool,
+    pub scan: Option<PathBuf>,
+    pub apply: bool,
+    pub recurse_submodules: bool,
+}
 
 /// Automatically switch identity based on rules
-pub fn execute() -> Result<()> {
+pub fn execute(options: AutoOptions) -> Result<()> {
+    let AutoOptions {
+        shell_hook,
+        dry_run,
+        cached,
+        fallback,
+        suggest_rules,
+        status,
+        prompt,
+        global,
+        yes,
+        scan,
+        apply,
+        recurse_submodules,
+    } = options;
+
+    if let Some(root) = scan {
+        return scan_workspace(&root, apply);
+    }
+
+    if suggest_rules {
+        return print_rule_suggestions();
+    }
+
+    if status {
+        if prompt {
+            // Prompts must never show errors
+            let _ = print_prompt_segment();
+            return Ok(());
+        }
+        return print_status();
+    }
+
+    if shell_hook {
+        // Shell hooks run on every directory change, never fail the prompt
+        if let Err(e) = run_shell_hook() {
+            eprintln!("gid: {e}");
+        }
+        return Ok(());
+    }
+
+    if global {
+        return apply_global(fallback.as_deref(), dry_run, yes);
+    }
+
     let config = Config::load()?;
     let git = GitConfigManager::new()?;
 
     if !git.is_in_repo() {
-        anyhow::bail!("Current directory is not a Git repository");
+        return Err(crate::git::not_a_repo());
     }
 
     let current_dir = std::env::current_dir()?;
+    let remote = git.get_origin_url();
 
-    // 1. Check .gid project config first
-    if let Ok(Some(project_config)) = crate::config::ProjectConfig::load_from_dir(&current_dir) {
-        let project_identity = project_config.identity;
-        if config.find_identity(&project_identity).is_some() {
-            println!(
-                "{} Using project config (.gid): {}",
-                "→".blue(),
-                format!("[{project_identity}]").cyan()
-            );
-            return crate::commands::switch::execute(&project_identity, false);
+    // 1. .gid project config, 2. rule matching, then 3. fallback identity
+    let matched = if cached {
+        resolve_cached(&config, &current_dir, remote.as_deref())
+    } else {
+        resolve_identity(&config, &current_dir, remote.as_deref())
+    };
+
+    if !dry_run {
+        track_unmatched(&config, &git, matched.is_none());
+    }
+
+    let resolution = matched.or_else(|| fallback_identity(&config, fallback.as_deref()));
+
+    if let Some(resolution) = resolution {
+        if dry_run {
+            print_dry_run(&config, &git, &resolution)?;
+            if recurse_submodules {
+                crate::commands::switch::execute_submodules(Some(&resolution.identity), true)?;
+            }
+            return Ok(());
+        }
+
+        if !crate::logging::is_quiet() {
+            match resolution.source {
+                Source::Project(_) => println!(
+                    "{} {}",
+                    sym("→").blue(),
+                    tr!(
+                        "auto-using-project",
+                        identity = format!("[{}]", resolution.identity).cyan()
+                    )
+                ),
+                Source::Rule(ref rule) => println!(
+                    "{} {}",
+                    sym("→").blue(),
+                    tr!(
+                        "auto-matched-rule",
+                        pattern = rule.pattern().dimmed(),
+                        identity = format!("[{}]", rule.identity).cyan()
+                    )
+                ),
+                Source::Fallback => println!(
+                    "{} {}",
+                    sym("→").blue(),
+                    tr!(
+                        "auto-using-fallback",
+                        identity = format!("[{}]", resolution.identity).cyan()
+                    )
+                ),
+            }
         }
+        crate::commands::switch::execute(&resolution.identity, false)?;
+        if recurse_submodules {
+            crate::commands::switch::execute_submodules(Some(&resolution.identity), false)?;
+        }
+        return Ok(());
+    }
+
+    if dry_run {
+        println!(
+            "{} {}",
+            sym("→").blue(),
+            tr!("auto-source", source = tr!("auto-source-none").dimmed())
+        );
+        println!("  {}", tr!("auto-nothing-would-change"));
+        return Ok(());
     }
 
-    // 2. Check rule matching
     if config.rules.is_empty() {
-        println!("{} No rules configured", "!".yellow());
+        println!("{} {}", "!".yellow(), tr!("rule-none"));
         println!();
-        println!("Use {} to add rules", "gid rule add".cyan());
-        println!("Or create a .gid file in the project root to specify identity");
+        println!("{}", tr!("rule-add-hint", command = "gid rule add".cyan()));
+        println!("{}", tr!("auto-gid-file-hint"));
         return Ok(());
     }
 
-    let mut context = MatchContext::new().with_path(current_dir);
+    // 4. No matching rules
+    println!("{} {}", "!".yellow(), tr!("rule-test-no-match"));
 
-    if let Some(remote) = git.get_origin_url() {
-        context = context.with_remote(remote);
-    }
-
-    let engine = RuleEngine::new(&config.rules);
+    // Show current identity
+    let current_name = git.get_effective_user_name();
+    let current_email = git.get_effective_user_email();
 
-    if let Some(matched_rule) = engine.match_context(&context) {
+    if let (Some(name), Some(email)) = (current_name, current_email) {
         println!(
-            "{} Matched rule: {} -> {}",
-            "→".blue(),
-            matched_rule.pattern().dimmed(),
-            format!("[{}]", matched_rule.identity).cyan()
+            "  {}",
+            tr!("auto-current-identity", name = name, email = email)
         );
-        return crate::commands::switch::execute(&matched_rule.identity, false);
     }
 
-    // 3. No matching rules
-    println!("{} No matching rules", "!".yellow());
+    Ok(())
+}
+
+/// Apply the identity resolved for the current directory to the global config
+///
+/// Works outside repositories too, so home-directory path rules can pick the
+/// machine-wide identity (e.g. from a login script).
+fn apply_global(fallback: Option<&str>, dry_run: bool, yes: bool) -> Result<()> {
+    let config = Config::load()?;
+    let git = GitConfigManager::new()?;
+    let current_dir = std::env::current_dir()?;
+    let remote = git.get_origin_url();
+
+    let Some(resolution) = resolve_identity(&config, &current_dir, remote.as_deref())
+        .or_else(|| fallback_identity(&config, fallback))
+    else {
+        println!("{} {}", "!".yellow(), tr!("rule-test-no-match"));
+        return Ok(());
+    };
+
+    let identity = config
+        .find_identity(&resolution.identity)
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = resolution.identity)))?;
+
+    println!(
+        "{} {}",
+        sym("→").blue(),
+        tr!(
+            "auto-resolved",
+            identity = format!("[{}]", identity.id).cyan(),
+            source = resolution.source
+        )
+    );
+
+    if git.get_user_name(true).as_ref() == Some(&identity.name)
+        && git.get_user_email(true).as_ref() == Some(&identity.email)
+    {
+        println!("  {} {}", sym("✓").green(), tr!("auto-global-already-set"));
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("  {}", tr!("auto-would-switch-global", identity = identity));
+        return Ok(());
+    }
+
+    if crate::commands::switch::confirm_global(&identity.id, yes)? {
+        crate::commands::switch::execute(&identity.id, true)?;
+    }
+
+    Ok(())
+}
+
+/// Report what `gid auto` would do without touching any config
+fn print_dry_run(config: &Config, git: &GitConfigManager, resolution: &Resolution) -> Result<()> {
+    let identity = config
+        .find_identity(&resolution.identity)
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = resolution.identity)))?;
+
+    println!(
+        "{} {}",
+        sym("→").blue(),
+        tr!("auto-source", source = resolution.source)
+    );
+    println!(
+        "  {}",
+        tr!(
+            "auto-identity",
+            identity = format!("[{}]", identity.id).cyan()
+        )
+    );
 
-    // Show current identity
     let current_name = git.get_effective_user_name();
     let current_email = git.get_effective_user_email();
 
-    if let (Some(name), Some(email)) = (current_name, current_email) {
-        println!("  Current identity: {name} <{email}>");
+    if current_name.as_ref() == Some(&identity.name)
+        && current_email.as_ref() == Some(&identity.email)
+    {
+        println!(
+            "  {} {}",
+            sym("✓").green(),
+            tr!(
+                "auto-already-using",
+                name = identity.name,
+                email = identity.email
+            )
+        );
+    } else {
+        let current = match (current_name, current_email) {
+            (Some(name), Some(email)) => format!("{name} <{email}>"),
+            _ => tr!("auto-not-set"),
+        };
+        println!(
+            "  {} {}",
+            "!".yellow(),
+            tr!(
+                "auto-would-switch",
+                current = current.dimmed(),
+                arrow = sym("→"),
+                name = identity.name,
+                email = identity.email
+            )
+        );
+    }
+
+    Ok(())
+}
+
+/// Resolve every repository under a directory and report (or fix) identity drift
+fn scan_workspace(root: &Path, apply: bool) -> Result<()> {
+    let config = Config::load()?;
+    let repos = crate::git::discover_repos(root, crate::git::DEFAULT_SCAN_DEPTH);
+
+    if repos.is_empty() {
+        println!("{}", tr!("auto-no-repos", path = root.display()));
+        return Ok(());
+    }
+
+    let (mut ok, mut drifted, mut fixed, mut unmatched) = (0, 0, 0, 0);
+
+    for repo in &repos {
+        let status = match RepoStatus::inspect(&config, repo) {
+            Ok(status) => status,
+            Err(e) => {
+                println!("  {} {}: {}", sym("✗").red(), repo.display(), e);
+                continue;
+            }
+        };
+
+        if let Ok(git) = GitConfigManager::from_path(repo) {
+            track_unmatched(&config, &git, status.expected.is_none());
+        }
+
+        let current = status
+            .effective_email
+            .clone()
+            .unwrap_or_else(|| tr!("auto-not-set"));
+
+        let Some(expected) = &status.expected else {
+            unmatched += 1;
+            println!("  {} {} {}", "?".dimmed(), repo.display(), current.dimmed());
+            continue;
+        };
+
+        if !status.is_drifted(&config) {
+            ok += 1;
+            println!(
+                "  {} {} {}",
+                sym("✓").green(),
+                repo.display(),
+                format!("[{}]", expected.identity).dimmed()
+            );
+            continue;
+        }
+
+        drifted += 1;
+        let expected_label = format!("[{}]", expected.identity);
+
+        if apply {
+            let result = config
+                .find_identity(&expected.identity)
+                .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = expected.identity)))
+                .and_then(|identity| {
+                    let git = GitConfigManager::from_path(repo)?;
+                    crate::commands::switch::apply_to_repo(&git, identity)
+                });
+            match result {
+                Ok(_) => {
+                    fixed += 1;
+                    println!(
+                        "  {} {} {} {} {}",
+                        sym("✓").green(),
+                        repo.display(),
+                        current.dimmed(),
+                        sym("→"),
+                        expected_label.green()
+                    );
+                }
+                Err(e) => println!("  {} {}: {}", sym("✗").red(), repo.display(), e),
+            }
+        } else {
+            println!(
+                "  {} {} {}",
+                sym("✗").red(),
+                repo.display(),
+                tr!(
+                    "auto-scan-drifted",
+                    current = current.yellow(),
+                    expected = expected_label.cyan(),
+                    source = expected.source
+                )
+            );
+        }
+    }
+
+    println!();
+    println!(
+        "{}",
+        tr!(
+            "auto-scan-summary",
+            total = repos.len(),
+            ok = ok.to_string().green(),
+            drifted = drifted.to_string().red(),
+            unmatched = unmatched
+        )
+    );
+
+    if apply {
+        if fixed > 0 {
+            println!(
+                "{} {}",
+                sym("✓").green(),
+                tr!("auto-scan-fixed", count = fixed)
+            );
+        }
+        if fixed < drifted {
+            anyhow::bail!(tr!("auto-scan-unfixed", count = drifted - fixed));
+        }
+    } else if drifted > 0 {
+        println!(
+            "{}",
+            tr!(
+                "auto-scan-fix-hint",
+                command = "gid auto --scan <DIR> --apply".cyan()
+            )
+        );
+        return Err(Failure::Mismatch.error(tr!("auto-scan-mismatch", count = drifted)));
     }
 
     Ok(())
 }
+
+/// Show whether the current repository uses the identity rules expect
+fn print_status() -> Result<()> {
+    let config = Config::load()?;
+    let status = RepoStatus::inspect(&config, &std::env::current_dir()?)?;
+
+    let current = match (&status.effective_name, &status.effective_email) {
+        (Some(name), Some(email)) => format!("{name} <{email}>"),
+        _ => tr!("auto-not-set"),
+    };
+
+    match &status.expected {
+        Some(expected) => {
+            println!(
+                "{}",
+                tr!(
+                    "auto-status-expected",
+                    identity = format!("[{}]", expected.identity).cyan(),
+                    source = expected.source
+                )
+            );
+            println!("{}", tr!("auto-status-current", current = current));
+            if status.is_drifted(&config) {
+                println!(
+                    "{} {}",
+                    sym("✗").red(),
+                    tr!("auto-status-mismatch", command = "gid auto".cyan())
+                );
+            } else {
+                println!("{} {}", sym("✓").green(), tr!("auto-status-match"));
+            }
+        }
+        None => {
+            println!(
+                "{}",
+                tr!(
+                    "auto-status-unexpected",
+                    reason = tr!("auto-status-no-match").dimmed()
+                )
+            );
+            println!("{}", tr!("auto-status-current", current = current));
+        }
+    }
+
+    Ok(())
+}
+
+/// Minimal token for PS1/starship: `[work✓]`, `[!mismatch]`, or nothing outside repos
+fn print_prompt_segment() -> Result<()> {
+    let config = Config::load()?;
+    let status = RepoStatus::inspect_cached(&config, &std::env::current_dir()?)?;
+
+    crate::color::enable_for_prompt();
+
+    let effective = status.effective_identity(&config).map(|i| i.id.clone());
+
+    let segment = match (&status.expected, effective) {
+        (Some(_), _) if status.is_drifted(&config) => "[!mismatch]".red().to_string(),
+        (Some(expected), _) => format!("[{}{}]", expected.identity, sym("✓"))
+            .green()
+            .to_string(),
+        (None, Some(id)) => format!("[{id}]").dimmed().to_string(),
+        (None, None) => return Ok(()),
+    };
+
+    print!("{segment}");
+    Ok(())
+}
+
+/// Remember repositories no rule matches so `--suggest-rules` can propose rules later
+///
+/// Best effort: state write failures never break `gid auto`.
+fn track_unmatched(config: &Config, git: &GitConfigManager, unmatched: bool) {
+    let Some(workdir) = git.workdir() else {
+        return;
+    };
+    let repo_path: PathBuf = workdir.components().collect();
+
+    let repo = unmatched.then(|| {
+        let email = git.get_effective_user_email();
+        let identity = config
+            .identities
+            .iter()
+            .find(|i| Some(&i.email) == email.as_ref())
+            .map(|i| i.id.clone());
+        UnmatchedRepo {
+            remote: git.get_origin_url(),
+            identity,
+        }
+    });
+
+    // Unchanged state is not written, so the hook on every cd stays cheap
+    let _ = State::update(|state| {
+        match repo {
+            Some(repo) => state.record_unmatched(&repo_path, repo),
+            None => state.forget_unmatched(&repo_path),
+        };
+        Ok(())
+    });
+}
+
+/// Propose `gid rule add` commands for recorded unmatched repositories
+fn print_rule_suggestions() -> Result<()> {
+    let config = Config::load()?;
+
+    // Drop repositories that rules (or .gid files) cover by now
+    let unmatched = State::update(|state| {
+        state.unmatched.retain(|path, repo| {
+            resolve_identity(&config, Path::new(path), repo.remote.as_deref()).is_none()
+        });
+        Ok(state.unmatched.clone())
+    })?;
+
+    if unmatched.is_empty() {
+        println!("{} {}", sym("✓").green(), tr!("auto-unmatched-none"));
+        println!(
+            "  {}",
+            tr!("auto-unmatched-hint", command = "gid auto".cyan())
+        );
+        return Ok(());
+    }
+
+    let suggestions = suggest::suggest_rules(&unmatched);
+
+    println!(
+        "{} {}",
+        sym("→").blue(),
+        tr!("auto-suggestions", count = unmatched.len())
+    );
+    println!();
+
+    for suggestion in &suggestions {
+        println!("  {}", suggestion.command().cyan());
+        for repo in &suggestion.repos {
+            println!("      {}", repo.dimmed());
+        }
+    }
+
+    if suggestions
+        .iter()
+        .any(|s| s.rule.identity == suggest::IDENTITY_PLACEHOLDER)
+    {
+        println!();
+        println!(
+            "  {}",
+            tr!(
+                "auto-suggestions-placeholder",
+                placeholder = suggest::IDENTITY_PLACEHOLDER.yellow()
+            )
+        );
+    }
+
+    Ok(())
+}
+
+/// Quiet check used by `gid shell-init`: only switch when auto_switch is on and identity differs
+///
+/// Runs on every directory change, so it always goes through the resolution cache.
+fn run_shell_hook() -> Result<()> {
+    let config = Config::load()?;
+
+    if !config.settings.auto_switch {
+        return Ok(());
+    }
+
+    let git = GitConfigManager::new()?;
+    if !git.is_in_repo() {
+        return Ok(());
+    }
+
+    let current_dir = std::env::current_dir()?;
+    let remote = git.get_origin_url();
+
+    let matched = resolve_cached(&config, &current_dir, remote.as_deref());
+    track_unmatched(&config, &git, matched.is_none());
+
+    let Some(resolution) = matched.or_else(|| fallback_identity(&config, None)) else {
+        return Ok(());
+    };
+
+    let Some(identity) = config.find_identity(&resolution.identity) else {
+        return Ok(());
+    };
+
+    if git.get_effective_user_name().as_ref() == Some(&identity.name)
+        && git.get_effective_user_email().as_ref() == Some(&identity.email)
+    {
+        return Ok(());
+    }
+
+    crate::commands::switch::execute(&resolution.identity, false)
+}
@@ -0,0 +1,51 @@
+use anyhow::Result;
+
+use crate::config::Config;
+use crate::exit::Failure;
+use crate::git::GitConfigManager;
+use crate::i18n::tr;
+use crate::rules::cache::resolve_cached;
+
+/// Compare the effective identity with the expected one, failing with exit code 3 on mismatch
+///
+/// Kept to a config load, a cached resolution and two config reads so prompts
+/// and hooks can call it on every command. Directories without a .gid file or
+/// matching rule, and directories outside a repository, pass.
+pub fn execute(verbose: bool) -> Result<()> {
+    let config = Config::load()?;
+    let git = GitConfigManager::new()?;
+
+    let expected = git
+        .context_dir()
+        .and_then(|dir| resolve_cached(&config, &dir, git.get_origin_url().as_deref()));
+    let Some(expected) = expected else {
+        if verbose {
+            println!("{}", tr!("check-ok-unexpected"));
+        }
+        return Ok(());
+    };
+
+    let name = git.get_effective_user_name();
+    let email = git.get_effective_user_email();
+    let matches = config
+        .find_identity(&expected.identity)
+        .is_some_and(|i| Some(&i.name) == name.as_ref() && Some(&i.email) == email.as_ref());
+
+    if matches {
+        if verbose {
+            println!("{}", tr!("check-ok", id = expected.identity));
+        }
+        return Ok(());
+    }
+
+    let message = tr!(
+        "check-mismatch",
+        email = email.unwrap_or_else(|| tr!("check-no-email")),
+        source = expected.source,
+        id = expected.identity
+    );
+    if verbose {
+        println!("{message}");
+    }
+    Err(Failure::Mismatch.error(message))
+}
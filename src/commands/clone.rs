@@ -0,0 +1,102 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::ascii::sym;
+use crate::commands::direnv::shell_quote;
+use crate::commands::switch;
+use crate::config::{Config, ProjectConfig};
+use crate::git::GitUrl;
+use crate::i18n::tr;
+use crate::rules::resolve::{fallback_identity, resolve_identity};
+
+/// Clone a repository with the identity resolved from rules before cloning
+pub fn execute(
+    url: &str,
+    dir: Option<PathBuf>,
+    identity_id: Option<String>,
+    write_gid: bool,
+    git_args: &[String],
+) -> Result<()> {
+    if GitUrl::parse(url).is_some_and(|u| u.host.is_some()) {
+        crate::offline::ensure_online(&tr!("clone-cloning-url", url = url))?;
+    }
+    let config = Config::load()?;
+
+    let target = dir.unwrap_or_else(|| PathBuf::from(repo_name(url)));
+    let target_path = std::env::current_dir()?.join(&target);
+
+    // Resolve against the future location and the remote URL
+    let (identity_id, source) = match identity_id {
+        Some(id) => (id, "--identity".to_string()),
+        None => {
+            let resolution = resolve_identity(&config, &target_path, Some(url))
+                .or_else(|| fallback_identity(&config, None))
+                .ok_or_else(|| anyhow::anyhow!(tr!("clone-no-rule", url = url)))?;
+            (resolution.identity, resolution.source.to_string())
+        }
+    };
+
+    let identity = config
+        .find_identity(&identity_id)
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?;
+
+    println!(
+        "{} {}",
+        sym("→").blue(),
+        tr!(
+            "clone-cloning-as",
+            id = format!("[{}]", identity.id).green().bold(),
+            source = source.dimmed()
+        )
+    );
+
+    let mut command = Command::new("git");
+    command.arg("clone").args(git_args).arg(url).arg(&target);
+
+    // Authenticate with the identity's key for this clone only
+    if crate::git::is_ssh_url(url) {
+        if let Some(key) = identity.ssh_key_path().filter(|key| key.exists()) {
+            command.env(
+                "GIT_SSH_COMMAND",
+                format!(
+                    "ssh -i {} -o IdentitiesOnly=yes",
+                    shell_quote(&key.display().to_string())
+                ),
+            );
+        }
+    }
+
+    log::debug!("running {command:?}");
+    let status = command.status().with_context(|| tr!("git-failed"))?;
+    if !status.success() {
+        anyhow::bail!(tr!("clone-failed"));
+    }
+
+    if write_gid {
+        ProjectConfig {
+            identity: identity.id.clone(),
+            rules: Vec::new(),
+        }
+        .save_to_dir(&target_path)?;
+        println!(
+            "{} {}",
+            sym("✓").green(),
+            tr!("clone-wrote", path = target_path.join(".gid").display())
+        );
+    }
+
+    // Write the local identity config (and SSH alias) inside the new repository
+    std::env::set_current_dir(&target_path)
+        .with_context(|| tr!("clone-enter-failed", path = target_path.display()))?;
+    switch::execute(&identity.id, false)
+}
+
+/// Directory name git clone would use for a URL
+fn repo_name(url: &str) -> String {
+    GitUrl::parse(url)
+        .map(|url| url.repo().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "repo".to_string())
+}
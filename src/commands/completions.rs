@@ -3,15 +3,123 @@ use clap::CommandFactory;
 use clap_complete::{generate, Shell};
 use std::io;
 
-use crate::cli::{Cli, ShellType};
+use crate::cli::{Cli, CompleteKind, ShellType};
+use crate::config::Config;
+
+/// bash：身份和规则参数改为读取当前配置，其余交给 clap 生成的 _gid
+const BASH_DYNAMIC: &str = r#"
+# gid dynamic completion of identity and rule ids
+_gid_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+    local kind=""
+    if [ "$COMP_CWORD" -eq 2 ]; then
+        case "${COMP_WORDS[1]}" in
+            switch|sw|show|remove|rm|verify|env) kind=identities ;;
+        esac
+    elif [ "$COMP_CWORD" -eq 3 ] && [ "${COMP_WORDS[1]}" = rule ]; then
+        case "${COMP_WORDS[2]}" in
+            edit|enable|disable|move|remove) kind=rules ;;
+        esac
+    fi
+    case "$prev" in
+        -i|--identity) [ "${COMP_WORDS[1]}" != add ] && kind=identities ;;
+    esac
+    if [ -n "$kind" ] && [[ "$cur" != -* ]]; then
+        local IFS=$'\n'
+        COMPREPLY=($(compgen -W "$(command gid __complete "$kind" 2>/dev/null | cut -f1)" -- "$cur"))
+        return 0
+    fi
+    _gid "$@"
+}
+
+if [[ "${BASH_VERSINFO[0]}" -eq 4 && "${BASH_VERSINFO[1]}" -ge 4 || "${BASH_VERSINFO[0]}" -gt 4 ]]; then
+    complete -F _gid_dynamic -o nosort -o bashdefault -o default gid
+else
+    complete -F _gid_dynamic -o bashdefault -o default gid
+fi
+"#;
+
+/// zsh：同上，候选项带说明
+const ZSH_DYNAMIC: &str = r#"
+# gid dynamic completion of identity and rule ids
+_gid_dynamic() {
+    local kind
+    if (( CURRENT == 3 )); then
+        case $words[2] in
+            switch|sw|show|remove|rm|verify|env) kind=identities ;;
+        esac
+    elif (( CURRENT == 4 )) && [[ $words[2] == rule ]]; then
+        case $words[3] in
+            edit|enable|disable|move|remove) kind=rules ;;
+        esac
+    fi
+    case $words[CURRENT-1] in
+        -i|--identity) [[ $words[2] != add ]] && kind=identities ;;
+    esac
+    if [[ -n $kind && $PREFIX != -* ]]; then
+        local -a candidates
+        candidates=(${(f)"$(command gid __complete $kind 2>/dev/null)"})
+        candidates=(${candidates//$'\t'/:})
+        _describe -t $kind $kind candidates
+        return
+    fi
+    _gid "$@"
+}
+
+compdef _gid_dynamic gid
+"#;
+
+/// fish：按已输入的子命令追加候选项
+const FISH_DYNAMIC: &str = r#"
+# gid dynamic completion of identity and rule ids
+complete -c gid -n '__fish_seen_subcommand_from switch sw show remove rm verify env; and test (count (commandline -opc)) -eq 2' -f -a '(command gid __complete identities 2>/dev/null)'
+complete -c gid -n '__fish_seen_subcommand_from rule; and __fish_seen_subcommand_from edit enable disable move remove; and test (count (commandline -opc)) -eq 3' -f -a '(command gid __complete rules 2>/dev/null)'
+complete -c gid -n '__fish_seen_subcommand_from clone export fix-commit' -s i -l identity -x -a '(command gid __complete identities 2>/dev/null)'
+complete -c gid -n '__fish_seen_subcommand_from rule; and __fish_seen_subcommand_from add edit' -s i -l identity -x -a '(command gid __complete identities 2>/dev/null)'
+"#;
 
 /// 生成 Shell 补全脚本
 pub fn execute(shell: ShellType) -> Result<()> {
     let mut cmd = Cli::command();
     let name = cmd.get_name().to_string();
-    let shell: Shell = shell.into();
 
+    let dynamic = match shell {
+        ShellType::Bash => BASH_DYNAMIC,
+        ShellType::Zsh => ZSH_DYNAMIC,
+        ShellType::Fish => FISH_DYNAMIC,
+        ShellType::PowerShell => "",
+    };
+
+    let shell: Shell = shell.into();
     generate(shell, &mut cmd, name, &mut io::stdout());
+    print!("{dynamic}");
+
+    Ok(())
+}
+
+/// 输出补全候选项（每行“值<TAB>说明”），供补全脚本调用
+pub fn candidates(kind: CompleteKind) -> Result<()> {
+    // 配置无法读取时不输出任何候选项，避免补全时报错
+    let Ok(config) = Config::load() else {
+        return Ok(());
+    };
+
+    match kind {
+        CompleteKind::Identities => {
+            for identity in &config.identities {
+                println!("{}\t{} <{}>", identity.id, identity.name, identity.email);
+            }
+        }
+        CompleteKind::Rules => {
+            // 订阅的规则是只读的，不能删除
+            for rule in &config.rules {
+                if rule.source.is_none() {
+                    println!("{}\t{rule}", rule.id);
+                }
+            }
+        }
+    }
 
     Ok(())
 }
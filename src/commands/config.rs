@@ -0,0 +1,17 @@
+use anyhow::Result;
+
+use crate::cli::ConfigAction;
+use crate::config::Config;
+
+/// 执行 config 命令
+pub fn execute(action: ConfigAction) -> Result<()> {
+    match action {
+        ConfigAction::Schema => schema(),
+    }
+}
+
+/// 输出 config.toml 的 JSON Schema
+fn schema() -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&Config::json_schema())?);
+    Ok(())
+}
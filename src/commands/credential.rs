@@ -0,0 +1,264 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use dialoguer::Password;
+use std::io::{self, BufRead, IsTerminal, Read};
+
+use crate::ascii::sym;
+use crate::cli::CredentialAction;
+use crate::commands::list::pad;
+use crate::config::{Config, Credential, Credentials};
+use crate::i18n::tr;
+
+/// Execute credential command
+pub fn execute(action: CredentialAction) -> Result<()> {
+    match action {
+        CredentialAction::Set {
+            identity,
+            host,
+            username,
+        } => set(&identity, &host, &username),
+        CredentialAction::List => list(),
+        CredentialAction::Unset { identity, host } => unset(&identity, &host),
+        CredentialAction::Helper {
+            identity,
+            operation,
+        } => helper(&identity, &operation),
+    }
+}
+
+/// Store a username and token for an identity on a host
+fn set(identity_id: &str, host: &str, username: &str) -> Result<()> {
+    let config = Config::load()?;
+    if config.find_identity(identity_id).is_none() {
+        anyhow::bail!(tr!("identity-not-found", id = identity_id));
+    }
+
+    // Piped tokens (`gh auth token | gid credential set ...`) skip the prompt
+    let token = if io::stdin().is_terminal() {
+        Password::new()
+            .with_prompt(tr!("credential-prompt", username = username, host = host))
+            .interact()?
+    } else {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .with_context(|| tr!("credential-stdin-failed"))?;
+        input.trim().to_string()
+    };
+    if token.is_empty() {
+        anyhow::bail!(tr!("credential-empty"));
+    }
+
+    Credentials::update(|credentials| {
+        credentials.set(Credential {
+            identity: identity_id.to_string(),
+            host: host.to_string(),
+            username: username.to_string(),
+            token,
+        });
+        Ok(())
+    })?;
+
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!(
+            "credential-stored",
+            identity = format!("[{identity_id}]").green().bold(),
+            host = host.cyan()
+        )
+    );
+    println!(
+        "  {}",
+        tr!("credential-next-switch", id = identity_id).dimmed()
+    );
+
+    Ok(())
+}
+
+/// List stored credentials without revealing tokens
+fn list() -> Result<()> {
+    let credentials = Credentials::load()?;
+
+    if credentials.entries.is_empty() {
+        println!("{} {}", "!".yellow(), tr!("credential-none"));
+        println!(
+            "  {}",
+            tr!(
+                "credential-add-hint",
+                command = "gid credential set <identity> --username <user>".cyan()
+            )
+        );
+        return Ok(());
+    }
+
+    println!("{}", tr!("credential-header").bold());
+    println!();
+    for credential in &credentials.entries {
+        println!(
+            "  {} {} {} {}",
+            pad(&format!("[{}]", credential.identity), 14).green(),
+            pad(&credential.host, 20).cyan(),
+            pad(&credential.username, 20),
+            mask(&credential.token).dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+/// Remove a stored credential
+fn unset(identity_id: &str, host: &str) -> Result<()> {
+    Credentials::update(|credentials| {
+        if !credentials.remove(identity_id, host) {
+            anyhow::bail!(tr!("credential-missing", id = identity_id, host = host));
+        }
+        Ok(())
+    })?;
+
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!("credential-removed", id = identity_id, host = host)
+    );
+
+    Ok(())
+}
+
+/// Git credential helper protocol (`git help credential`)
+///
+/// Git writes `key=value` lines to stdin and reads the answer from stdout.
+/// Anything not stored for the identity is left for git to ask elsewhere.
+fn helper(identity_id: &str, operation: &str) -> Result<()> {
+    let request = read_request(io::stdin().lock())?;
+    if request.protocol.as_deref() != Some("https") {
+        return Ok(());
+    }
+    let Some(host) = request.host else {
+        return Ok(());
+    };
+
+    match operation {
+        "get" => {
+            let credentials = Credentials::load()?;
+            if let Some(credential) = credentials.find(identity_id, &host) {
+                if request
+                    .username
+                    .as_deref()
+                    .is_some_and(|u| u != credential.username)
+                {
+                    return Ok(());
+                }
+                println!("username={}", credential.username);
+                println!("password={}", credential.token);
+            }
+        }
+        "store" => {
+            let (Some(username), Some(token)) = (request.username, request.password) else {
+                return Ok(());
+            };
+            let credentials = Credentials::load()?;
+            if credentials
+                .find(identity_id, &host)
+                .is_some_and(|c| c.username == username && c.token == token)
+            {
+                return Ok(());
+            }
+            Credentials::update(|credentials| {
+                credentials.set(Credential {
+                    identity: identity_id.to_string(),
+                    host,
+                    username,
+                    token,
+                });
+                Ok(())
+            })?;
+        }
+        "erase" => {
+            // Only forget the token git reported as rejected, not a newer one
+            let rejected = |credentials: &Credentials| {
+                credentials
+                    .find(identity_id, &host)
+                    .is_some_and(|c| request.password.as_deref().is_none_or(|p| p == c.token))
+            };
+            if rejected(&Credentials::load()?) {
+                Credentials::update(|credentials| {
+                    if rejected(credentials) {
+                        credentials.remove(identity_id, &host);
+                    }
+                    Ok(())
+                })?;
+            }
+        }
+        // Unknown operations must be ignored
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Fields of a credential request that gid looks at
+#[derive(Debug, Default, PartialEq)]
+struct Request {
+    protocol: Option<String>,
+    host: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+fn read_request(input: impl BufRead) -> Result<Request> {
+    let mut request = Request::default();
+    for line in input.lines() {
+        let line = line.with_context(|| tr!("credential-request-failed"))?;
+        if line.is_empty() {
+            break;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let value = Some(value.to_string());
+        match key {
+            "protocol" => request.protocol = value,
+            "host" => request.host = value,
+            "username" => request.username = value,
+            "password" => request.password = value,
+            _ => {}
+        }
+    }
+    Ok(request)
+}
+
+fn mask(token: &str) -> String {
+    let tail: String = token
+        .chars()
+        .skip(token.chars().count().saturating_sub(4))
+        .collect();
+    format!("****{tail}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_request() {
+        let input =
+            "protocol=https\nhost=github.com\nusername=alice\nwwwauth[]=Basic\n\nignored=1\n";
+        let request = read_request(input.as_bytes()).unwrap();
+        assert_eq!(
+            request,
+            Request {
+                protocol: Some("https".to_string()),
+                host: Some("github.com".to_string()),
+                username: Some("alice".to_string()),
+                password: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_mask() {
+        assert_eq!(mask("ghp_abcdef1234"), "****1234");
+        assert_eq!(mask("ab"), "****ab");
+    }
+}
@@ -1,15 +1,39 @@
 use anyhow::Result;
 use colored::Colorize;
+use serde_json::{json, Value};
+use std::path::Path;
 
-use crate::config::Config;
-use crate::git::GitConfigManager;
+use crate::ascii::sym;
+use crate::config::{Config, Identity};
+use crate::format::Template;
+use crate::git::{GitConfigManager, ValueOrigin};
+use crate::i18n::tr;
+use crate::rules::cache::resolve_cached;
+use crate::rules::resolve::resolve_identity;
+use crate::ssh::{SshManager, SshRoute};
 
 /// 显示当前身份
-pub fn execute() -> Result<()> {
+pub fn execute(json: bool, prompt: bool, format: Option<&str>) -> Result<()> {
+    let template = format.map(Template::parse).transpose()?;
     let config = Config::load()?;
     let git = GitConfigManager::new()?;
 
-    println!("{}", "Current Git Identity:".bold());
+    if prompt {
+        print_prompt_token(&config, &git);
+        return Ok(());
+    }
+
+    if let Some(template) = template {
+        return template.print(&current_json(&config, &git)?);
+    }
+
+    if json {
+        let output = current_json(&config, &git)?;
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    println!("{}", tr!("current-header").bold());
     println!();
 
     // 项目级配置
@@ -19,12 +43,16 @@ pub fn execute() -> Result<()> {
     if local_name.is_some() || local_email.is_some() {
         println!(
             "  {} {} <{}>",
-            "Project:".green(),
-            local_name.as_deref().unwrap_or("Not set"),
-            local_email.as_deref().unwrap_or("Not set").cyan()
+            tr!("current-project").green(),
+            local_name.unwrap_or_else(|| tr!("current-not-set")),
+            local_email.unwrap_or_else(|| tr!("current-not-set")).cyan()
         );
     } else {
-        println!("  {} {}", "Project:".dimmed(), "Not set".dimmed());
+        println!(
+            "  {} {}",
+            tr!("current-project").dimmed(),
+            tr!("current-not-set").dimmed()
+        );
     }
 
     // 全局配置
@@ -34,12 +62,18 @@ pub fn execute() -> Result<()> {
     if global_name.is_some() || global_email.is_some() {
         println!(
             "  {} {} <{}>",
-            "Global:".green(),
-            global_name.as_deref().unwrap_or("Not set"),
-            global_email.as_deref().unwrap_or("Not set").cyan()
+            tr!("current-global").green(),
+            global_name.unwrap_or_else(|| tr!("current-not-set")),
+            global_email
+                .unwrap_or_else(|| tr!("current-not-set"))
+                .cyan()
         );
     } else {
-        println!("  {} {}", "Global:".dimmed(), "Not set".dimmed());
+        println!(
+            "  {} {}",
+            tr!("current-global").dimmed(),
+            tr!("current-not-set").dimmed()
+        );
     }
 
     println!();
@@ -49,7 +83,12 @@ pub fn execute() -> Result<()> {
     let effective_email = git.get_effective_user_email();
 
     if let (Some(name), Some(email)) = (&effective_name, &effective_email) {
-        println!("  {} {} <{}>", "Effective:".bold(), name, email.cyan());
+        println!(
+            "  {} {} <{}>",
+            tr!("current-effective").bold(),
+            name,
+            email.cyan()
+        );
 
         // 尝试匹配已知身份
         let matched = config
@@ -60,7 +99,7 @@ pub fn execute() -> Result<()> {
         if let Some(identity) = matched {
             println!(
                 "  {} {}",
-                "Identity ID:".green(),
+                tr!("current-identity-id").green(),
                 format!("[{}]", identity.id).green().bold()
             );
             if let Some(ref desc) = identity.description {
@@ -72,32 +111,365 @@ pub fn execute() -> Result<()> {
             if let Some(identity) = email_matched {
                 println!(
                     "  {} {} {}",
-                    "Possibly:".yellow(),
+                    tr!("current-possibly").yellow(),
                     format!("[{}]", identity.id).yellow(),
-                    "(Name mismatch)".dimmed()
+                    tr!("current-name-mismatch").dimmed()
                 );
             } else {
                 println!(
                     "  {} {}",
-                    "⚠".yellow(),
-                    "No configured identity matched".yellow()
+                    sym("⚠").yellow(),
+                    tr!("current-no-match").yellow()
                 );
             }
         }
     } else {
-        println!("{} No valid Git user configuration found", "⚠".yellow());
+        println!("{} {}", sym("⚠").yellow(), tr!("current-no-user"));
         println!();
-        println!("Run {} to add identity", "gid add".cyan());
-        println!("Run {} to switch identity", "gid switch <id>".cyan());
+        println!("{}", tr!("current-add-hint", command = "gid add".cyan()));
+        println!(
+            "{}",
+            tr!("current-switch-hint", command = "gid switch <id>".cyan())
+        );
     }
 
+    warn_rule_mismatch(&config, &git, &effective_name, &effective_email);
+
+    print_sources(&git);
+
     // 显示仓库信息
     if git.is_in_repo() {
         println!();
         if let Some(remote) = git.get_origin_url() {
-            println!("  {} {}", "Remote:".dimmed(), remote.dimmed());
+            println!("  {} {}", tr!("current-remote").dimmed(), remote.dimmed());
+            print_push_route(&config, &remote, &effective_email);
         }
     }
 
     Ok(())
 }
+
+/// 当前身份与 .gid / 规则期望不一致时给出醒目警告和修复命令
+fn warn_rule_mismatch(
+    config: &Config,
+    git: &GitConfigManager,
+    effective_name: &Option<String>,
+    effective_email: &Option<String>,
+) {
+    let Some(dir) = git.context_dir() else {
+        return;
+    };
+    let Some(expected) = resolve_identity(config, &dir, git.get_origin_url().as_deref()) else {
+        return;
+    };
+
+    let Some(identity) = config.find_identity(&expected.identity) else {
+        println!();
+        println!(
+            "  {} {}",
+            sym("⚠").yellow().bold(),
+            tr!(
+                "current-expects-unknown",
+                source = expected.source,
+                identity = format!("[{}]", expected.identity).yellow()
+            )
+        );
+        return;
+    };
+
+    if effective_name.as_ref() == Some(&identity.name)
+        && effective_email.as_ref() == Some(&identity.email)
+    {
+        return;
+    }
+
+    println!();
+    println!(
+        "  {} {}",
+        sym("⚠").red().bold(),
+        tr!(
+            "current-should-use",
+            id = identity.id,
+            source = expected.source
+        )
+        .red()
+        .bold()
+    );
+    println!(
+        "    {}",
+        tr!(
+            "current-run",
+            command = format!("gid switch {}", identity.id).cyan().bold()
+        )
+    );
+}
+
+/// 输出单个提示符标记：身份 ID，与规则不符时追加 `!`
+fn print_prompt_token(config: &Config, git: &GitConfigManager) {
+    let name = git.get_effective_user_name();
+    let email = git.get_effective_user_email();
+
+    let Some(email) = email else {
+        return;
+    };
+
+    let identity = config
+        .identities
+        .iter()
+        .find(|i| Some(&i.name) == name.as_ref() && i.email == email);
+
+    // 未配置的身份显示邮箱用户名部分
+    let label = identity
+        .map(|i| i.id.clone())
+        .unwrap_or_else(|| email.split('@').next().unwrap_or(&email).to_string());
+
+    let expected = git
+        .context_dir()
+        .and_then(|dir| resolve_cached(config, &dir, git.get_origin_url().as_deref()));
+
+    crate::color::enable_for_prompt();
+
+    let token = match expected {
+        Some(expected) if identity.map(|i| i.id.as_str()) != Some(expected.identity.as_str()) => {
+            format!("{label}!").red().to_string()
+        }
+        Some(_) => label.green().to_string(),
+        None if identity.is_none() => label.yellow().to_string(),
+        None => label,
+    };
+
+    print!("{token}");
+}
+
+/// 身份相关配置项
+const SOURCE_KEYS: [&str; 4] = [
+    "user.name",
+    "user.email",
+    "user.signingkey",
+    "commit.gpgsign",
+];
+
+/// 显示每个生效值的来源（local、includeIf 文件、global、system 等）
+fn print_sources(git: &GitConfigManager) {
+    let sources: Vec<(&str, Vec<ValueOrigin>)> = SOURCE_KEYS
+        .iter()
+        .map(|key| (*key, git.value_origins(key)))
+        .filter(|(_, origins)| !origins.is_empty())
+        .collect();
+
+    if sources.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("  {}", tr!("current-sources").bold());
+    for (key, origins) in sources {
+        let Some((effective, overridden)) = origins.split_last() else {
+            continue;
+        };
+        println!(
+            "    {:<16} {}  {}",
+            key,
+            effective.value.cyan(),
+            describe_origin(effective).dimmed()
+        );
+        for origin in overridden.iter().rev() {
+            println!(
+                "    {:<16} {}",
+                "",
+                tr!(
+                    "current-overrides",
+                    value = origin.value,
+                    origin = describe_origin(origin)
+                )
+                .dimmed()
+            );
+        }
+    }
+}
+
+fn describe_origin(origin: &ValueOrigin) -> String {
+    let file = origin
+        .file
+        .as_ref()
+        .map(|f| f.display().to_string())
+        .unwrap_or_else(|| tr!("current-unknown-file"));
+
+    if origin.included {
+        tr!(
+            "current-origin-include",
+            level = origin.level_name(),
+            file = file
+        )
+    } else {
+        format!("{} ({})", origin.level_name(), file)
+    }
+}
+
+/// 显示推送时实际使用的 SSH Host 和密钥（以及它属于哪个身份）
+fn print_push_route(config: &Config, remote: &str, effective_email: &Option<String>) {
+    let Some(route) = push_route(remote) else {
+        return;
+    };
+
+    let block = match &route.block {
+        Some(block) if block.gid_managed => {
+            tr!("current-host-managed", patterns = block.patterns.join(" "))
+        }
+        Some(block) => format!("Host {}", block.patterns.join(" ")),
+        None => tr!("current-no-host-block"),
+    };
+    println!(
+        "  {} {} {} {}@{} {}",
+        "SSH:".dimmed(),
+        route.host,
+        sym("→"),
+        route.user.as_deref().unwrap_or("git"),
+        route.hostname,
+        format!("({block})").dimmed()
+    );
+
+    if route.identity_files.is_empty() {
+        println!(
+            "  {} {}",
+            tr!("current-ssh-key").dimmed(),
+            tr!("current-default-keys").dimmed()
+        );
+        return;
+    }
+
+    for key in &route.identity_files {
+        match key_owner(config, key) {
+            Some(owner) => println!(
+                "  {} {} {}",
+                tr!("current-ssh-key").dimmed(),
+                key.display(),
+                format!("[{}]", owner.id).cyan()
+            ),
+            None => println!("  {} {}", tr!("current-ssh-key").dimmed(), key.display()),
+        }
+    }
+
+    // 提交身份与推送所用密钥的身份不一致
+    let key_identity = route
+        .identity_files
+        .iter()
+        .find_map(|k| key_owner(config, k));
+    if let (Some(owner), Some(email)) = (key_identity, effective_email) {
+        if &owner.email != email {
+            println!(
+                "  {} {}",
+                sym("⚠").yellow(),
+                tr!(
+                    "current-key-mismatch",
+                    identity = format!("[{}]", owner.id).yellow(),
+                    email = email.yellow()
+                )
+            );
+        }
+    }
+}
+
+/// 解析 SSH remote 的连接方式（HTTPS remote 返回 None）
+fn push_route(remote: &str) -> Option<SshRoute> {
+    if !crate::git::is_ssh_url(remote) {
+        return None;
+    }
+    let host = crate::git::url_host(remote)?;
+    let ssh = SshManager::new().ok()?;
+    Some(ssh.resolve_route(&host))
+}
+
+/// 查找使用该 SSH 密钥的身份
+fn key_owner<'a>(config: &'a Config, key: &Path) -> Option<&'a Identity> {
+    let key = key.canonicalize().unwrap_or_else(|_| key.to_path_buf());
+    config.identities.iter().find(|identity| {
+        identity
+            .ssh_key_path()
+            .map(|path| path.canonicalize().unwrap_or(path))
+            .is_some_and(|path| path == key)
+    })
+}
+
+/// `current --json` 的完整输出
+fn current_json(config: &Config, git: &GitConfigManager) -> Result<Value> {
+    let mut output = status_json(config, git, &git.context_dir().unwrap_or_default())?;
+    output["local"] = user_json(git.get_user_name(false), git.get_user_email(false));
+    output["global"] = user_json(git.get_user_name(true), git.get_user_email(true));
+    output["remote"] = json!(git.get_origin_url());
+    output["sources"] = SOURCE_KEYS
+        .iter()
+        .filter_map(|key| {
+            let origin = git.effective_origin(key)?;
+            Some((
+                key.to_string(),
+                json!({
+                    "value": origin.value,
+                    "level": origin.level_name(),
+                    "file": origin.file,
+                    "included": origin.included,
+                }),
+            ))
+        })
+        .collect::<serde_json::Map<_, _>>()
+        .into();
+    output["ssh"] = match git.get_origin_url().as_deref().and_then(push_route) {
+        Some(route) => json!({
+            "host": route.host,
+            "hostname": route.hostname,
+            "user": route.user,
+            "host_block": route.block.as_ref().map(|b| b.patterns.join(" ")),
+            "gid_managed": route.block.as_ref().is_some_and(|b| b.gid_managed),
+            "identity_files": route.identity_files,
+            "key_identity": route
+                .identity_files
+                .iter()
+                .find_map(|k| key_owner(config, k))
+                .map(|i| i.id.clone()),
+        }),
+        None => Value::Null,
+    };
+    Ok(output)
+}
+
+/// 指定目录的生效身份与规则期望身份（JSON）
+pub fn status_json(config: &Config, git: &GitConfigManager, path: &Path) -> Result<Value> {
+    let name = git.get_effective_user_name();
+    let email = git.get_effective_user_email();
+
+    let matched = config
+        .identities
+        .iter()
+        .find(|i| Some(&i.name) == name.as_ref() && Some(&i.email) == email.as_ref())
+        .map(|i| i.id.clone());
+
+    let expected = if git.is_in_repo() {
+        resolve_identity(config, path, git.get_origin_url().as_deref())
+    } else {
+        None
+    };
+
+    let expected = match expected {
+        Some(resolution) => json!({
+            "identity": resolution.identity,
+            "source": resolution.source.kind(),
+            "detail": resolution.source.to_string(),
+            "matches": matched.as_deref() == Some(resolution.identity.as_str()),
+        }),
+        None => Value::Null,
+    };
+
+    Ok(json!({
+        "in_repo": git.is_in_repo(),
+        "effective": {
+            "name": name,
+            "email": email,
+            "identity": matched,
+        },
+        "expected": expected,
+    }))
+}
+
+fn user_json(name: Option<String>, email: Option<String>) -> Value {
+    json!({ "name": name, "email": email })
+}
@@ -0,0 +1,97 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+
+use crate::ascii::sym;
+use crate::cli::DirenvAction;
+use crate::commands::env::{env_vars, target_identity};
+use crate::config::{journal, Config, Identity};
+use crate::git::GitConfigManager;
+use crate::i18n::tr;
+
+pub const BLOCK_START: &str = "# >>> gid >>>";
+pub const BLOCK_END: &str = "# <<< gid <<<";
+
+/// Execute direnv command
+pub fn execute(action: DirenvAction) -> Result<()> {
+    match action {
+        DirenvAction::Export { identity, write } => export(identity, write),
+    }
+}
+
+fn export(identity_id: Option<String>, write: bool) -> Result<()> {
+    let config = Config::load()?;
+    let identity_id = target_identity(&config, identity_id)?;
+
+    let identity = config
+        .find_identity(&identity_id)
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?;
+
+    let stanza = envrc_stanza(identity);
+
+    if !write {
+        print!("{stanza}");
+        return Ok(());
+    }
+
+    let git = GitConfigManager::new()?;
+    let workdir = git.workdir().ok_or_else(crate::git::not_a_repo)?;
+    let envrc = workdir.join(".envrc");
+
+    let existing = fs::read_to_string(&envrc).unwrap_or_default();
+    journal::record(&envrc);
+    fs::write(&envrc, replace_block(&existing, &stanza))
+        .with_context(|| tr!("file-write-failed", path = envrc.display()))?;
+
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!(
+            "direnv-wrote",
+            id = format!("[{}]", identity.id).cyan(),
+            path = envrc.display()
+        )
+    );
+    println!(
+        "  {}",
+        tr!("direnv-allow-hint", command = "direnv allow".cyan())
+    );
+
+    Ok(())
+}
+
+/// Build the .envrc stanza exporting the identity
+fn envrc_stanza(identity: &Identity) -> String {
+    let mut lines = vec![format!("{BLOCK_START} ({})", identity.id)];
+    lines.extend(
+        env_vars(identity)
+            .into_iter()
+            .map(|(key, value)| format!("export {key}={}", shell_quote(&value))),
+    );
+    lines.push(BLOCK_END.to_string());
+    lines.join("\n") + "\n"
+}
+
+/// Replace an existing gid block in .envrc (or .mailmap), or append a new one
+pub fn replace_block(existing: &str, stanza: &str) -> String {
+    if let Some(start) = existing.find(BLOCK_START) {
+        if let Some(end_offset) = existing[start..].find(BLOCK_END) {
+            let mut end = start + end_offset + BLOCK_END.len();
+            if existing[end..].starts_with('\n') {
+                end += 1;
+            }
+            return format!("{}{}{}", &existing[..start], stanza, &existing[end..]);
+        }
+    }
+
+    if existing.is_empty() || existing.ends_with('\n') {
+        format!("{existing}{stanza}")
+    } else {
+        format!("{existing}\n{stanza}")
+    }
+}
+
+/// Single-quote a value for POSIX shells
+pub fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
@@ -1,24 +1,31 @@
 use anyhow::Result;
 use colored::Colorize;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
+use crate::annotate::{Annotation, Level};
+use crate::ascii::sym;
+use crate::cli::Annotate;
 use crate::config::Config;
-use crate::git::GitConfigManager;
+use crate::git::{GitConfigManager, SignatureStatus};
+use crate::i18n::tr;
+use crate::rules::resolve::resolve_submodule_identity;
 use crate::rules::{MatchContext, RuleEngine};
 
 /// Check identity configuration in current directory
-pub fn execute(fix: bool) -> Result<()> {
+pub fn execute(fix: bool, recurse_submodules: bool, annotate: Option<Annotate>) -> Result<()> {
     let config = Config::load()?;
     let git = GitConfigManager::new()?;
 
-    println!("{}", "Checking Git identity configuration...".bold());
+    println!("{}", tr!("doctor-header").bold());
     println!();
 
-    let mut issues = Vec::new();
+    let mut issues: Vec<Issue> = Vec::new();
     let mut suggestions = Vec::new();
 
     // 1. Check if in Git repository
     if !git.is_in_repo() {
-        println!("{} Current directory is not a Git repository", "!".yellow());
+        println!("{} {}", "!".yellow(), tr!("doctor-not-a-repo"));
         return Ok(());
     }
 
@@ -28,7 +35,7 @@ pub fn execute(fix: bool) -> Result<()> {
     let current_name = git.get_effective_user_name();
     let current_email = git.get_effective_user_email();
 
-    println!("Current Identity:");
+    println!("{}", tr!("doctor-current-identity"));
     if let (Some(ref name), Some(ref email)) = (&current_name, &current_email) {
         println!("  {} <{}>", name, email.cyan());
 
@@ -41,14 +48,14 @@ pub fn execute(fix: bool) -> Result<()> {
         if let Some(identity) = known {
             println!(
                 "  {} {}",
-                "Identity:".green(),
+                tr!("doctor-identity").green(),
                 format!("[{}]", identity.id).green()
             );
         } else {
-            issues.push("Current identity is not in the configuration list".to_string());
+            issues.push(tr!("doctor-unknown-identity").into());
         }
     } else {
-        issues.push("Git user information not configured".to_string());
+        issues.push(tr!("doctor-no-user").into());
     }
 
     println!();
@@ -56,10 +63,13 @@ pub fn execute(fix: bool) -> Result<()> {
     // 3. Check .gid project config
     if let Ok(Some(project_config)) = crate::config::ProjectConfig::load_from_dir(&current_dir) {
         let project_identity = project_config.identity;
-        println!("Project Config (.gid):");
+        println!("{}", tr!("doctor-project-config"));
         println!(
-            "  Expected Identity: {}",
-            format!("[{project_identity}]").cyan()
+            "  {}",
+            tr!(
+                "doctor-expected-identity",
+                identity = format!("[{project_identity}]").cyan()
+            )
         );
 
         if let Some(identity) = config.find_identity(&project_identity) {
@@ -67,16 +77,18 @@ pub fn execute(fix: bool) -> Result<()> {
             if current_name.as_ref() != Some(&identity.name)
                 || current_email.as_ref() != Some(&identity.email)
             {
-                issues.push(format!(
-                    "Current identity does not match project config (expected: [{project_identity}])"
+                issues.push(Issue::in_file(
+                    tr!("doctor-project-mismatch", id = project_identity),
+                    current_dir.join(".gid"),
                 ));
                 suggestions.push(format!("gid switch {project_identity}"));
             } else {
-                println!("  {} Identity matches", "✓".green());
+                println!("  {} {}", sym("✓").green(), tr!("doctor-identity-matches"));
             }
         } else {
-            issues.push(format!(
-                "Project configured identity '{project_identity}' does not exist"
+            issues.push(Issue::in_file(
+                tr!("doctor-project-unknown", id = project_identity),
+                current_dir.join(".gid"),
             ));
         }
         println!();
@@ -88,15 +100,18 @@ pub fn execute(fix: bool) -> Result<()> {
 
         if let Some(remote) = git.get_origin_url() {
             context = context.with_remote(remote.clone());
-            println!("Remote URL:");
+            println!("{}", tr!("doctor-remote"));
             println!("  {}", remote.dimmed());
         }
+        if let Some(branch) = git.current_branch() {
+            context = context.with_branch(branch);
+        }
 
-        let engine = RuleEngine::new(&config.rules);
+        let engine = RuleEngine::from_config(&config);
 
         if let Some(matched_rule) = engine.match_context(&context) {
             println!();
-            println!("Matched Rules:");
+            println!("{}", tr!("doctor-matched-rules"));
             println!(
                 "  {} -> {}",
                 matched_rule.pattern().cyan(),
@@ -107,48 +122,77 @@ pub fn execute(fix: bool) -> Result<()> {
                 if current_name.as_ref() != Some(&identity.name)
                     || current_email.as_ref() != Some(&identity.email)
                 {
-                    issues.push(format!(
-                        "Current identity does not match rule (expected: [{}])",
-                        matched_rule.identity
-                    ));
+                    issues.push(tr!("doctor-rule-mismatch", id = matched_rule.identity).into());
                     suggestions.push(format!("gid switch {}", matched_rule.identity));
                 } else {
-                    println!("  {} Identity matches", "✓".green());
+                    println!("  {} {}", sym("✓").green(), tr!("doctor-identity-matches"));
                 }
             }
         }
     }
 
-    // 5. Check SSH configuration
+    // 5. Check the signature of the last commit
+    if let Ok(Some(status)) = git.verify_commit("HEAD") {
+        println!();
+        println!("{}", tr!("doctor-signature"));
+        match status {
+            SignatureStatus::Good(signer) => {
+                println!(
+                    "  {} {}",
+                    sym("✓").green(),
+                    tr!("doctor-signature-good", signer = signer)
+                )
+            }
+            SignatureStatus::Bad => issues.push(tr!("doctor-signature-bad").into()),
+            status => println!("  {} {}", "!".yellow(), status),
+        }
+    }
+
+    if recurse_submodules {
+        check_submodules(&config, &git, fix, &mut issues)?;
+    }
+
+    // 6. Check SSH configuration
     if let Some(ref email) = current_email {
         let identity = config.identities.iter().find(|i| &i.email == email);
         if let Some(identity) = identity {
             if let Some(ref ssh_key) = identity.ssh_key {
                 let ssh = crate::ssh::SshManager::new()?;
                 if !ssh.key_exists(ssh_key) {
-                    issues.push(format!(
-                        "SSH key file does not exist: {}",
-                        ssh_key.display()
-                    ));
+                    issues.push(tr!("identity-ssh-key-missing", path = ssh_key.display()).into());
+                } else if let Some(key) = identity.ssh_key_path() {
+                    check_owner_only(&key, fix, &mut issues)?;
+                }
+                if ssh.config_path().exists() {
+                    check_owner_only(ssh.config_path(), fix, &mut issues)?;
                 }
             }
         }
     }
 
-    // 6. Output results
+    // 7. Output results
     println!();
 
     if issues.is_empty() {
-        println!("{} No issues found", "✓".green().bold());
+        println!("{} {}", sym("✓").green().bold(), tr!("doctor-no-issues"));
     } else {
-        println!("{} Found {} issues:", "⚠".yellow().bold(), issues.len());
+        println!(
+            "{} {}",
+            sym("⚠").yellow().bold(),
+            tr!("doctor-issues", count = issues.len())
+        );
         for issue in &issues {
-            println!("  {} {}", "•".red(), issue);
+            println!("  {} {}", sym("•").red(), issue);
+        }
+        if let Some(annotate) = annotate {
+            for issue in &issues {
+                issue.annotation().emit(annotate);
+            }
         }
 
         if !suggestions.is_empty() && fix {
             println!();
-            println!("Fixing...");
+            println!("{}", tr!("doctor-fixing"));
 
             // Execute first suggestion
             if let Some(suggestion) = suggestions.first() {
@@ -159,12 +203,178 @@ pub fn execute(fix: bool) -> Result<()> {
             }
         } else if !suggestions.is_empty() {
             println!();
-            println!("Suggested actions:");
+            println!("{}", tr!("doctor-suggestions"));
             for suggestion in &suggestions {
-                println!("  {} {}", "→".blue(), suggestion.cyan());
+                println!("  {} {}", sym("→").blue(), suggestion.cyan());
             }
             println!();
-            println!("Use {} to fix automatically", "gid doctor --fix".cyan());
+            println!(
+                "{}",
+                tr!("doctor-fix-hint", command = "gid doctor --fix".cyan())
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// A problem doctor found, with the file to fix when there is one
+struct Issue {
+    message: String,
+    file: Option<PathBuf>,
+}
+
+impl Issue {
+    fn in_file(message: String, file: PathBuf) -> Self {
+        Self {
+            message,
+            file: Some(file),
+        }
+    }
+
+    fn annotation(&self) -> Annotation {
+        let annotation = Annotation::new(Level::Error, "gid doctor", &self.message);
+        match &self.file {
+            Some(file) => annotation.with_file(file),
+            None => annotation,
+        }
+    }
+}
+
+impl From<String> for Issue {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            file: None,
+        }
+    }
+}
+
+impl From<&str> for Issue {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// OpenSSH refuses keys and configs other users can access
+fn check_owner_only(path: &Path, fix: bool, issues: &mut Vec<Issue>) -> Result<()> {
+    let Some(access) = crate::permissions::check_owner_only(path)? else {
+        return Ok(());
+    };
+    if fix {
+        crate::permissions::restrict_to_owner(path)?;
+        println!(
+            "{} {}",
+            sym("✓").green(),
+            tr!("doctor-restricted", path = path.display())
+        );
+    } else {
+        issues.push(
+            tr!(
+                "doctor-not-owner-only",
+                path = path.display(),
+                access = access
+            )
+            .into(),
+        );
+    }
+    Ok(())
+}
+
+/// Compare each submodule's identity with its own .gid / rule resolution
+fn check_submodules(
+    config: &Config,
+    git: &GitConfigManager,
+    fix: bool,
+    issues: &mut Vec<Issue>,
+) -> Result<()> {
+    let submodules = git.submodule_workdirs();
+    if submodules.is_empty() {
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", tr!("doctor-submodules"));
+    for workdir in &submodules {
+        let label = git
+            .workdir()
+            .and_then(|root| workdir.strip_prefix(root).ok())
+            .unwrap_or(workdir)
+            .display()
+            .to_string();
+        let sub = GitConfigManager::from_path(workdir)?;
+
+        let Some(resolution) =
+            resolve_submodule_identity(config, workdir, sub.get_origin_url().as_deref())
+        else {
+            println!(
+                "  {} {} {}",
+                "-".dimmed(),
+                label,
+                tr!("doctor-submodule-unresolved").dimmed()
+            );
+            continue;
+        };
+        let Some(identity) = config.find_identity(&resolution.identity) else {
+            issues.push(
+                tr!(
+                    "doctor-submodule-unknown",
+                    label = label,
+                    id = resolution.identity
+                )
+                .into(),
+            );
+            continue;
+        };
+
+        if sub.get_effective_user_name().as_ref() == Some(&identity.name)
+            && sub.get_effective_user_email().as_ref() == Some(&identity.email)
+        {
+            println!(
+                "  {} {} {}",
+                sym("✓").green(),
+                label,
+                format!("[{}]", identity.id).green()
+            );
+        } else if fix {
+            crate::commands::switch::apply_to_repo(&sub, identity)?;
+            println!(
+                "  {} {}",
+                sym("✓").green(),
+                tr!(
+                    "doctor-submodule-switched",
+                    label = label,
+                    identity = format!("[{}]", identity.id).cyan()
+                )
+            );
+        } else {
+            println!(
+                "  {} {}",
+                sym("✗").red(),
+                tr!(
+                    "doctor-submodule-expected",
+                    label = label,
+                    identity = format!("[{}]", identity.id).cyan(),
+                    source = resolution.source
+                )
+            );
+            let issue = Issue::from(tr!(
+                "doctor-submodule-mismatch",
+                label = label,
+                id = identity.id
+            ));
+            let project = workdir.join(".gid");
+            issues.push(if project.is_file() {
+                Issue::in_file(issue.message, project)
+            } else {
+                issue
+            });
         }
     }
 
@@ -1,8 +1,11 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
+use std::path::Path;
 use std::process::Command;
 
+use crate::ascii::sym;
 use crate::config::Config;
+use crate::i18n::tr;
 
 /// 编辑配置文件
 pub fn execute() -> Result<()> {
@@ -10,58 +13,70 @@ pub fn execute() -> Result<()> {
 
     // 确保配置文件存在
     if !config_path.exists() {
-        let config = Config::default();
-        config.save()?;
+        Config::update(|_| Ok(()))?;
         println!(
-            "{} Configuration file created: {}",
-            "→".blue(),
-            config_path.display()
+            "{} {}",
+            sym("→").blue(),
+            tr!("edit-created", path = config_path.display())
         );
     }
 
-    // 获取编辑器
-    let editor = std::env::var("EDITOR")
-        .or_else(|_| std::env::var("VISUAL"))
-        .unwrap_or_else(|_| {
-            if cfg!(target_os = "windows") {
-                "notepad".to_string()
-            } else {
-                "vi".to_string()
-            }
-        });
+    let editor = editor();
 
     println!(
-        "{} Editing configuration file using {}...",
-        "→".blue(),
-        editor
+        "{} {}",
+        sym("→").blue(),
+        tr!("edit-editing", editor = editor)
     );
     println!("  {}", config_path.display().to_string().dimmed());
 
-    // 打开编辑器
-    let status = Command::new(&editor)
-        .arg(&config_path)
-        .status()
-        .with_context(|| format!("Failed to start editor: {editor}"))?;
-
-    if !status.success() {
-        anyhow::bail!("Editor exited abnormally");
-    }
+    open_editor(&editor, &config_path)?;
 
     // 验证配置文件
     match Config::load() {
         Ok(config) => {
             println!(
-                "{} Configuration valid, contains {} identities, {} rules",
-                "✓".green(),
-                config.identities.len(),
-                config.rules.len()
+                "{} {}",
+                sym("✓").green(),
+                tr!(
+                    "edit-valid",
+                    identities = config.identities.len(),
+                    rules = config.rules.len()
+                )
             );
         }
         Err(e) => {
-            println!("{} Configuration format error: {}", "✗".red(), e);
-            println!("Please fix the configuration file and try again");
+            println!("{} {}", sym("✗").red(), tr!("edit-invalid", error = e));
+            println!("{}", tr!("edit-fix-hint"));
         }
     }
 
     Ok(())
 }
+
+/// 获取编辑器（$EDITOR、$VISUAL，否则使用系统默认）
+pub fn editor() -> String {
+    std::env::var("EDITOR")
+        .or_else(|_| std::env::var("VISUAL"))
+        .unwrap_or_else(|_| {
+            if cfg!(target_os = "windows") {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        })
+}
+
+/// 用编辑器打开文件并等待退出
+pub fn open_editor(editor: &str, path: &Path) -> Result<()> {
+    let status = Command::new(editor)
+        .arg(path)
+        .status()
+        .with_context(|| tr!("edit-editor-failed", editor = editor))?;
+
+    if !status.success() {
+        anyhow::bail!(tr!("edit-editor-exited"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use anyhow::Result;
+
+use crate::cli::ShellType;
+use crate::commands::direnv::shell_quote;
+use crate::config::{Config, Identity};
+use crate::git::GitConfigManager;
+use crate::i18n::tr;
+use crate::rules::resolve::{fallback_identity, resolve_identity};
+
+/// Print export statements for the named or resolved identity
+pub fn execute(identity_id: Option<String>, shell: ShellType) -> Result<()> {
+    let config = Config::load()?;
+    let identity_id = target_identity(&config, identity_id)?;
+
+    let identity = config
+        .find_identity(&identity_id)
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?;
+
+    for (key, value) in env_vars(identity) {
+        println!("{}", export_line(shell, key, &value));
+    }
+
+    Ok(())
+}
+
+/// Explicit identity, or the one .gid / rules / default_identity resolve to here
+pub fn target_identity(config: &Config, identity_id: Option<String>) -> Result<String> {
+    if let Some(id) = identity_id {
+        return Ok(id);
+    }
+
+    let git = GitConfigManager::new()?;
+    let current_dir = std::env::current_dir()?;
+    let remote = git.get_origin_url();
+    resolve_identity(config, &current_dir, remote.as_deref())
+        .or_else(|| fallback_identity(config, None))
+        .map(|resolution| resolution.identity)
+        .ok_or_else(|| anyhow::anyhow!(tr!("env-no-identity")))
+}
+
+/// Environment variables that make git use the identity without touching config
+pub fn env_vars(identity: &Identity) -> Vec<(&'static str, String)> {
+    let mut vars = vec![
+        ("GIT_AUTHOR_NAME", identity.name.clone()),
+        ("GIT_AUTHOR_EMAIL", identity.email.clone()),
+        ("GIT_COMMITTER_NAME", identity.name.clone()),
+        ("GIT_COMMITTER_EMAIL", identity.email.clone()),
+    ];
+
+    if let Some(sock) = &identity.ssh_auth_sock {
+        vars.push(("SSH_AUTH_SOCK", sock.clone()));
+    }
+
+    if let Some(key) = identity.ssh_key_path() {
+        vars.push((
+            "GIT_SSH_COMMAND",
+            format!(
+                "ssh -i {} -o IdentitiesOnly=yes",
+                shell_quote(&key.display().to_string())
+            ),
+        ));
+    }
+
+    vars
+}
+
+/// A single export statement in the syntax of the given shell
+fn export_line(shell: ShellType, key: &str, value: &str) -> String {
+    match shell {
+        ShellType::Bash | ShellType::Zsh => format!("export {key}={}", shell_quote(value)),
+        ShellType::Fish => format!(
+            "set -gx {key} '{}'",
+            value.replace('\\', "\\\\").replace('\'', "\\'")
+        ),
+        ShellType::PowerShell => format!("$env:{key} = '{}'", value.replace('\'', "''")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_line() {
+        assert_eq!(
+            export_line(ShellType::Bash, "GIT_AUTHOR_NAME", "Jane O'Neil"),
+            "export GIT_AUTHOR_NAME='Jane O'\\''Neil'"
+        );
+        assert_eq!(
+            export_line(ShellType::Fish, "GIT_AUTHOR_NAME", "Jane O'Neil"),
+            "set -gx GIT_AUTHOR_NAME 'Jane O\\'Neil'"
+        );
+        assert_eq!(
+            export_line(ShellType::PowerShell, "GIT_AUTHOR_NAME", "Jane O'Neil"),
+            "$env:GIT_AUTHOR_NAME = 'Jane O''Neil'"
+        );
+    }
+}
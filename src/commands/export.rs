@@ -1,29 +1,336 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use crate::config::Config;
+#[cfg(unix)]
+use std::os::unix::fs::PermissionsExt;
+
+use crate::age::AgeManager;
+use crate::ascii::sym;
+use crate::cli::ExportFormat;
+use crate::commands::direnv::shell_quote;
+use crate::commands::switch::identity_values;
+use crate::config::{Config, Identity};
+use crate::i18n::tr;
+use crate::rules::{Rule, RuleType};
+
+/// export 命令选项
+#[derive(Debug, Default)]
+pub struct ExportOptions {
+    pub identities: Vec<String>,
+    pub rules_only: bool,
+    pub identities_only: bool,
+    pub rules_matching: Option<String>,
+    pub sanitized: bool,
+    pub encrypt: bool,
+    pub recipients: Vec<String>,
+    pub format: Option<ExportFormat>,
+}
 
 /// 导出配置
-pub fn execute(file: PathBuf) -> Result<()> {
-    let config = Config::load()?;
+///
+/// 指定身份、`--rules-only` / `--identities-only` 或 `--sanitized` 时不包含个人设置
+pub fn execute(file: PathBuf, options: ExportOptions) -> Result<()> {
+    let ExportOptions {
+        identities,
+        rules_only,
+        identities_only,
+        rules_matching,
+        sanitized,
+        encrypt,
+        recipients,
+        format,
+    } = options;
+
+    let age = AgeManager::new();
+    if encrypt && !age.is_available() {
+        anyhow::bail!(tr!("age-missing"));
+    }
+
+    let mut config = Config::load()?;
+
+    for id in &identities {
+        if config.find_identity(id).is_none() {
+            anyhow::bail!(tr!("identity-not-found", id = id));
+        }
+    }
+
+    // 只保留选中的身份及指向它们的规则
+    if !identities.is_empty() {
+        config.identities.retain(|i| identities.contains(&i.id));
+        config.rules.retain(|r| identities.contains(&r.identity));
+    }
+    // 按模式或身份筛选规则，只带上这些规则用到的身份
+    if let Some(ref filter) = rules_matching {
+        config.rules.retain(|r| rule_matches(r, filter));
+        if identities.is_empty() {
+            let used: Vec<String> = config.rules.iter().map(|r| r.identity.clone()).collect();
+            config.identities.retain(|i| used.contains(&i.id));
+        }
+    }
+    if rules_only {
+        config.identities.clear();
+    }
+    if identities_only {
+        config.rules.clear();
+    }
 
     if config.identities.is_empty() && config.rules.is_empty() {
-        println!("{} 没有配置可导出", "!".yellow());
+        println!("{} {}", "!".yellow(), tr!("export-nothing"));
         return Ok(());
     }
 
-    let content = toml::to_string_pretty(&config).context("无法序列化配置")?;
+    if sanitized {
+        sanitize(&mut config);
+    }
+
+    let format = format.unwrap_or_else(|| format_from_path(&file));
+
+    let partial =
+        !identities.is_empty() || rules_only || identities_only || rules_matching.is_some();
+    let mut value =
+        toml::Value::try_from(&config).with_context(|| tr!("config-serialize-failed"))?;
+    if partial || sanitized {
+        if let Some(table) = value.as_table_mut() {
+            table.remove("settings");
+        }
+    }
+
+    let content = match format {
+        ExportFormat::Toml => {
+            toml::to_string_pretty(&value).with_context(|| tr!("config-serialize-failed"))?
+        }
+        ExportFormat::Json => {
+            serde_json::to_string_pretty(&value).with_context(|| tr!("config-serialize-failed"))?
+                + "\n"
+        }
+        ExportFormat::Yaml => {
+            serde_yaml::to_string(&value).with_context(|| tr!("config-serialize-failed"))?
+        }
+        ExportFormat::Script => script(&config),
+    };
 
-    fs::write(&file, content).with_context(|| format!("无法写入文件: {}", file.display()))?;
+    if encrypt {
+        age.encrypt(content.as_bytes(), &recipients, &file)?;
+    } else {
+        fs::write(&file, content)
+            .with_context(|| tr!("file-write-failed", path = file.display()))?;
 
-    println!("{} 配置已导出到: {}", "✓".green(), file.display());
+        // 脚本设置可执行权限 (仅 Unix)
+        #[cfg(unix)]
+        if format == ExportFormat::Script {
+            fs::set_permissions(&file, fs::Permissions::from_mode(0o755))?;
+        }
+    }
+
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!("export-done", path = file.display())
+    );
+    if encrypt {
+        println!("  {}", tr!("export-encrypted").dimmed());
+    }
     println!(
-        "  {} 个身份, {} 条规则",
-        config.identities.len(),
-        config.rules.len()
+        "  {}",
+        tr!(
+            "export-summary",
+            identities = config.identities.len(),
+            rules = config.rules.len()
+        )
     );
 
     Ok(())
 }
+
+/// 规则模式或身份与筛选条件匹配（glob 或不区分大小写的子串）
+fn rule_matches(rule: &Rule, filter: &str) -> bool {
+    let glob = glob::Pattern::new(filter).ok();
+    let filter = filter.to_lowercase();
+    [&*rule.pattern(), rule.identity.as_str()]
+        .into_iter()
+        .any(|field| {
+            glob.as_ref().is_some_and(|g| g.matches(field))
+                || field.to_lowercase().contains(&filter)
+        })
+}
+
+/// 根据扩展名判断配置格式（.age 加密文件看内层扩展名）
+pub fn format_from_path(path: &Path) -> ExportFormat {
+    let path = match path.extension().and_then(|e| e.to_str()) {
+        Some("age") => Path::new(path.file_stem().unwrap_or_default()),
+        _ => path,
+    };
+
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => ExportFormat::Json,
+        Some("yaml" | "yml") => ExportFormat::Yaml,
+        Some("sh") => ExportFormat::Script,
+        _ => ExportFormat::Toml,
+    }
+}
+
+/// 移除本机相关数据，生成可供团队成员导入后补全的模板
+fn sanitize(config: &mut Config) {
+    for identity in &mut config.identities {
+        // 绝对路径改为 ~/.ssh/<文件名>
+        if let Some(key) = identity.ssh_key.take() {
+            identity.ssh_key = if key.starts_with("~") {
+                Some(key)
+            } else {
+                key.file_name().map(|name| Path::new("~/.ssh").join(name))
+            };
+        }
+        identity.gpg_key = None;
+        identity.ssh_auth_sock = None;
+        identity.commit_template = None;
+        identity.gh_account = None;
+        identity.post_switch_command = None;
+        if identity.private {
+            identity.description = None;
+            identity.private = false;
+        }
+    }
+
+    for rule in &mut config.rules {
+        contract_rule_paths(&mut rule.rule_type);
+    }
+}
+
+/// 路径规则（包括组合规则里的）中的主目录写成 ~
+fn contract_rule_paths(rule_type: &mut RuleType) {
+    match rule_type {
+        RuleType::Path { pattern } => *pattern = contract_home(pattern),
+        RuleType::AllOf { conditions } | RuleType::AnyOf { conditions } => {
+            conditions.iter_mut().for_each(contract_rule_paths)
+        }
+        _ => {}
+    }
+}
+
+pub fn contract_home(pattern: &str) -> String {
+    if let Some(home) = home::home_dir() {
+        if let Ok(rest) = Path::new(pattern).strip_prefix(&home) {
+            return format!("~/{}", rest.display());
+        }
+    }
+    pattern.to_string()
+}
+
+/// 生成不依赖 gid 的 shell 脚本：按身份执行 `git config`，并可将路径规则安装为 includeIf
+fn script(config: &Config) -> String {
+    let mut out = String::from(
+        "#!/bin/sh
+# Git identities exported by gid (https://github.com/ygwa/gid)
+#
+# Usage:
+#   sh $0 <identity>            apply an identity to the current repository
+#   sh $0 <identity> --global   apply an identity globally
+#   sh $0 --install-rules       map path rules to includeIf entries in ~/.gitconfig
+#   sh $0 --list                list identities
+set -e
+
+scope=--local
+[ \"$2\" = --global ] && scope=--global
+
+case \"$1\" in
+",
+    );
+
+    for identity in &config.identities {
+        out.push_str(&format!("{})\n", identity.id));
+        for (key, value) in script_values(identity) {
+            out.push_str(&format!(
+                "    git config \"$scope\" {key} {}\n",
+                shell_quote(&value)
+            ));
+        }
+        out.push_str("    ;;\n");
+    }
+
+    out.push_str("--install-rules)\n");
+    // includeIf 后出现的生效，按优先级从低到高写入
+    let mut fragments: Vec<&str> = Vec::new();
+    for rule in config.rules.iter().rev().filter(|r| r.enabled) {
+        let identity = config.find_identity(&rule.identity);
+        match (&rule.rule_type, identity) {
+            (RuleType::Path { pattern } | RuleType::Branch { pattern }, Some(identity)) => {
+                let fragment = format!("\"$HOME\"/.gitconfig-gid-{}", identity.id);
+                if !fragments.contains(&identity.id.as_str()) {
+                    fragments.push(&identity.id);
+                    for (key, value) in script_values(identity) {
+                        out.push_str(&format!(
+                            "    git config --file {fragment} {key} {}\n",
+                            shell_quote(&value)
+                        ));
+                    }
+                }
+                let condition = match &rule.rule_type {
+                    RuleType::Path { .. } => {
+                        let gitdir = match pattern.strip_suffix("**") {
+                            Some(dir) if dir.ends_with('/') => dir,
+                            _ => pattern.as_str(),
+                        };
+                        format!("gitdir:{gitdir}")
+                    }
+                    _ => format!("onbranch:{pattern}"),
+                };
+                out.push_str(&format!(
+                    "    git config --global {} {fragment}\n",
+                    shell_quote(&format!("includeIf.{condition}.path"))
+                ));
+            }
+            (RuleType::Path { pattern } | RuleType::Branch { pattern }, None) => {
+                out.push_str(&format!(
+                    "    # {} rule {pattern} {} {}: identity not exported\n",
+                    rule.type_name(),
+                    sym("→"),
+                    rule.identity
+                ))
+            }
+            _ => out.push_str(&format!(
+                "    # {} rule {} {} {}: not expressible as includeIf\n",
+                rule.type_name(),
+                rule.pattern(),
+                sym("→"),
+                rule.identity
+            )),
+        }
+    }
+    out.push_str("    ;;\n");
+
+    out.push_str("--list|\"\")\n");
+    for identity in &config.identities {
+        out.push_str(&format!(
+            "    echo {}\n",
+            shell_quote(&format!(
+                "{}  {} <{}>",
+                identity.id, identity.name, identity.email
+            ))
+        ));
+    }
+    out.push_str(
+        "    ;;
+*)
+    echo \"Unknown identity: $1\" >&2
+    exit 1
+    ;;
+esac
+",
+    );
+
+    out
+}
+
+/// 身份的 Git 配置项，SSH 私钥通过 core.sshCommand 指定
+fn script_values(identity: &Identity) -> Vec<(&'static str, String)> {
+    let mut values = identity_values(identity);
+    if let Some(ref key) = identity.ssh_key {
+        values.push((
+            "core.sshCommand",
+            format!("ssh -i {} -o IdentitiesOnly=yes", key.display()),
+        ));
+    }
+    values
+}
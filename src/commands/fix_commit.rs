@@ -1,32 +1,44 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::Colorize;
-use git2::{Repository, Signature};
-
-use crate::config::Config;
-use crate::git::GitConfigManager;
+use std::collections::HashMap;
+
+use crate::ascii::sym;
+use crate::config::{Config, Identity};
+use crate::git::{CommitParts, GitConfigManager, Person};
+use crate::gpg::GpgManager;
+use crate::i18n::tr;
+
+/// How rewritten commits are rebuilt
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RewriteOptions {
+    /// Also replace the committer with the identity
+    pub committer: bool,
+    /// Use the current time instead of the original author/committer dates
+    pub reset_dates: bool,
+    /// Sign rewritten commits with the identity's GPG key
+    pub resign: bool,
+}
 
 /// Fix commit identity information
 pub fn execute(
     commit_ref: &str,
     identity_id: Option<String>,
     range: Option<String>,
+    all_by: Option<String>,
+    options: RewriteOptions,
     yes: bool,
 ) -> Result<()> {
     let config = Config::load()?;
     let git = GitConfigManager::new()?;
 
     if !git.is_in_repo() {
-        anyhow::bail!("Current directory is not a Git repository");
+        return Err(crate::git::not_a_repo());
     }
+    git.ensure_no_operation_in_progress()?;
 
-    // Check for uncommitted changes
-    let repo = Repository::discover(".")?;
-    if has_uncommitted_changes(&repo)? {
-        anyhow::bail!(
-            "{}",
-            "Uncommitted changes detected. Please commit or stash changes before fixing history."
-                .red()
-        );
+    // Check for uncommitted changes (bare repositories have no working tree)
+    if !git.is_bare() && git.has_uncommitted_changes()? {
+        anyhow::bail!("{}", tr!("fix-commit-dirty").red());
     }
 
     // Get identity to use
@@ -36,25 +48,31 @@ pub fn execute(
         // Use current identity
         let current_email = git
             .get_effective_user_email()
-            .ok_or_else(|| anyhow::anyhow!("Could not get current email"))?;
+            .ok_or_else(|| anyhow::anyhow!(tr!("fix-commit-no-email")))?;
 
         config
             .identities
             .iter()
             .find(|i| i.email == current_email)
             .map(|i| i.id.clone())
-            .ok_or_else(|| anyhow::anyhow!("Current identity not in configuration list"))?
+            .ok_or_else(|| anyhow::anyhow!(tr!("fix-commit-unknown-identity")))?
     };
 
     let identity = config
         .find_identity(&identity_id)
-        .ok_or_else(|| anyhow::anyhow!("Identity '{identity_id}' not found"))?;
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?;
+
+    if options.resign && identity.gpg_key.is_none() {
+        anyhow::bail!(tr!("fix-commit-resign-no-key", id = identity.id));
+    }
 
     // Handle batch fix
-    if let Some(range_str) = range {
-        fix_commit_range(&repo, &range_str, identity, yes)?;
+    if let Some(email) = all_by {
+        fix_all_by(&git, &email, identity, options, yes)?;
+    } else if let Some(range_str) = range {
+        fix_commit_range(&git, &range_str, identity, options, yes)?;
     } else {
-        fix_single_commit(&repo, commit_ref, identity, yes)?;
+        fix_single_commit(&git, commit_ref, identity, options, yes)?;
     }
 
     Ok(())
@@ -62,107 +80,91 @@ pub fn execute(
 
 /// Fix single commit
 fn fix_single_commit(
-    repo: &Repository,
+    git: &GitConfigManager,
     commit_ref: &str,
-    identity: &crate::config::Identity,
+    identity: &Identity,
+    options: RewriteOptions,
     yes: bool,
 ) -> Result<()> {
     // Only support fixing HEAD
     if commit_ref != "HEAD" {
+        println!("{} {}", "!".yellow(), tr!("fix-commit-non-head"));
         println!(
-            "{} Fixing non-HEAD commits requires --range argument",
-            "!".yellow()
+            "  {}",
+            tr!(
+                "fix-commit-example",
+                command = "gid fix-commit --range HEAD~3..HEAD"
+            )
         );
-        println!("  Example: gid fix-commit --range HEAD~3..HEAD");
-        anyhow::bail!("Fixing non-HEAD commits is not supported");
+        anyhow::bail!(tr!("fix-commit-non-head-unsupported"));
     }
 
-    let obj = repo.revparse_single(commit_ref)?;
-    let commit = obj.peel_to_commit()?;
-
-    let current_author = commit.author();
-    let current_name = current_author.name().unwrap_or("");
-    let current_email = current_author.email().unwrap_or("");
+    let id = git.resolve_commit(commit_ref)?;
+    let commit = git.read_commit(&id)?;
 
-    println!("{}", "Fixing commit identity...".bold());
+    println!("{}", tr!("fix-commit-single-header").bold());
     println!();
-    println!("Commit: {}", commit.id().to_string()[..7].dimmed());
+    println!("{}", tr!("fix-commit-commit", id = id[..7].dimmed()));
     println!(
-        "Message: {}",
-        commit.message().unwrap_or("").lines().next().unwrap_or("")
+        "{}",
+        tr!(
+            "fix-commit-message",
+            message = commit.message.lines().next().unwrap_or("")
+        )
     );
     println!();
     println!(
-        "Current Identity: {} <{}>",
-        current_name,
-        current_email.cyan()
+        "{}",
+        tr!(
+            "fix-commit-current-identity",
+            name = commit.author.name,
+            email = commit.author.email.cyan()
+        )
     );
     println!(
-        "New Identity:   {} <{}>",
-        identity.name,
-        identity.email.cyan()
+        "{}",
+        tr!(
+            "fix-commit-new-identity-aligned",
+            name = identity.name,
+            email = identity.email.cyan()
+        )
     );
     println!();
 
     // Confirm
     if !yes {
         let confirm = dialoguer::Confirm::new()
-            .with_prompt("Confirm fix?")
+            .with_prompt(tr!("fix-commit-confirm"))
             .default(false)
             .interact()?;
 
         if !confirm {
-            println!("Operation cancelled");
+            println!("{}", tr!("operation-cancelled"));
             return Ok(());
         }
     }
 
     // Modify commit
-    let new_author = Signature::now(&identity.name, &identity.email)?;
-    let tree = commit.tree()?;
-
-    let new_commit_oid = if commit.parent_count() > 0 {
-        let parent = commit.parent(0)?;
-        repo.commit(
-            None,
-            &new_author,
-            &commit.committer(),
-            commit.message().unwrap_or(""),
-            &tree,
-            &[&parent],
-        )?
-    } else {
-        // Initial commit
-        repo.commit(
-            None,
-            &new_author,
-            &commit.committer(),
-            commit.message().unwrap_or(""),
-            &tree,
-            &[],
-        )?
-    };
+    let new_commit = rewrite_commit(git, &commit, identity, options)?;
 
     // Update HEAD
-    let head_ref = repo.head()?;
-    if head_ref.is_branch() {
-        let branch_name = head_ref.name().unwrap();
-        repo.reference(branch_name, new_commit_oid, true, "gid fix-commit")?;
-    } else {
-        // Detached HEAD
-        repo.set_head_detached(new_commit_oid)?;
-    }
-
-    let new_commit = new_commit_oid;
+    update_target(git, git.head_branch().as_deref(), &new_commit)?;
 
     println!();
-    println!("{} Commit fixed", "✓".green());
-    println!("  New commit: {}", new_commit.to_string()[..7].green());
+    println!("{} {}", sym("✓").green(), tr!("fix-commit-fixed"));
+    warn_dropped_signatures(git, &[(id, new_commit.clone())], options);
+    println!(
+        "  {}",
+        tr!("fix-commit-new-commit", id = new_commit[..7].green())
+    );
     println!();
     println!(
-        "{} Commit hash changed, use {} to force push if already pushed",
-        "⚠".yellow(),
-        "git push --force".cyan()
+        "{} {}",
+        sym("⚠").yellow(),
+        tr!(
+            "fix-commit-hash-changed",
+            command = "git push --force".cyan()
+        )
     );
 
     Ok(())
@@ -170,95 +172,351 @@ fn fix_single_commit(
 
 /// Batch fix commit range
 fn fix_commit_range(
-    repo: &Repository,
+    git: &GitConfigManager,
     range: &str,
-    identity: &crate::config::Identity,
+    identity: &Identity,
+    options: RewriteOptions,
     yes: bool,
 ) -> Result<()> {
-    println!("{}", "Batch fixing commits...".bold());
+    println!("{}", tr!("fix-commit-range-header").bold());
     println!();
-    println!("Range: {}", range.cyan());
+    println!("{}", tr!("fix-commit-range", range = range.cyan()));
     println!(
-        "New Identity: {} <{}>",
-        identity.name,
-        identity.email.cyan()
+        "{}",
+        tr!(
+            "fix-commit-new-identity",
+            name = identity.name,
+            email = identity.email.cyan()
+        )
     );
     println!();
 
     // Parse range
-    let revspec = repo.revparse(range)?;
-
-    if revspec.mode().contains(git2::RevparseMode::SINGLE) {
-        anyhow::bail!("Please use range format, e.g., HEAD~3..HEAD");
-    }
-
-    let from = revspec
-        .from()
-        .ok_or_else(|| anyhow::anyhow!("Invalid range"))?;
-    let to = revspec
-        .to()
-        .ok_or_else(|| anyhow::anyhow!("Invalid range"))?;
+    let Some((from, to)) = git.resolve_range(range)? else {
+        anyhow::bail!(tr!("fix-commit-range-format"));
+    };
 
     // Get commits in range
-    let mut revwalk = repo.revwalk()?;
-    revwalk.push(to.id())?;
-    revwalk.hide(from.id())?;
-
-    let commit_count = revwalk.count();
+    let commit_count = git.rev_list(&to, Some(&from))?.len();
 
     if commit_count == 0 {
-        println!("{} No commits in range", "!".yellow());
+        println!("{} {}", "!".yellow(), tr!("fix-commit-range-empty"));
         return Ok(());
     }
 
-    println!("Will fix {commit_count} commits");
+    println!("{}", tr!("fix-commit-will-fix", count = commit_count));
     println!();
 
     // Warning
     println!(
-        "{} {} This will modify commit history, all subsequent commit hashes will change",
-        "⚠".yellow().bold(),
-        "WARNING:".yellow().bold()
-    );
-    println!("  If pushed, you will need to use git push --force");
-    println!(
-        "  Recommend backing up current branch: git branch backup-$(git branch --show-current)"
+        "{} {} {}",
+        sym("⚠").yellow().bold(),
+        tr!("fix-commit-warning").yellow().bold(),
+        tr!("fix-commit-range-warning")
     );
+    println!("  {}", tr!("fix-commit-range-force-push"));
+    println!("  {}", tr!("fix-commit-range-backup"));
     println!();
 
     // Confirm
     if !yes {
         let confirm = dialoguer::Confirm::new()
-            .with_prompt("Confirm continue?")
+            .with_prompt(tr!("fix-commit-confirm-continue"))
             .default(false)
             .interact()?;
 
         if !confirm {
-            println!("Operation cancelled");
+            println!("{}", tr!("operation-cancelled"));
             return Ok(());
         }
     }
 
+    let target = range_target(git, range, &to)?;
+    let backup = create_backup_ref(git, target.as_deref())?;
+    let (new_head, rewritten) =
+        rewrite_commits(git, Some(&from), &to, &|_| true, identity, options)?;
+    update_target(git, target.as_deref(), &new_head)?;
+
+    print_rewrite_summary(git, commit_count, &new_head, &rewritten, &backup, options);
+
+    Ok(())
+}
+
+/// Rewrite every commit on the current branch authored with an email
+fn fix_all_by(
+    git: &GitConfigManager,
+    email: &str,
+    identity: &Identity,
+    options: RewriteOptions,
+    yes: bool,
+) -> Result<()> {
+    let authored_by = |commit: &CommitParts| commit.author.email.eq_ignore_ascii_case(email);
+
+    let head = git.resolve_commit("HEAD")?;
+    let mut matching = 0;
+    for id in git.rev_list(&head, None)? {
+        if authored_by(&git.read_commit(&id)?) {
+            matching += 1;
+        }
+    }
+
+    println!("{}", tr!("fix-commit-email-header").bold());
+    println!();
+    println!("{}", tr!("fix-commit-author", email = email.cyan()));
+    println!(
+        "{}",
+        tr!(
+            "fix-commit-new-identity",
+            name = identity.name,
+            email = identity.email.cyan()
+        )
+    );
+    println!();
+
+    if matching == 0 {
+        println!(
+            "{} {}",
+            "!".yellow(),
+            tr!("fix-commit-email-none", email = email)
+        );
+        return Ok(());
+    }
+
+    println!("{}", tr!("fix-commit-email-will-fix", count = matching));
+    println!();
+    println!(
+        "{} {} {}",
+        sym("⚠").yellow().bold(),
+        tr!("fix-commit-warning").yellow().bold(),
+        tr!("fix-commit-email-warning")
+    );
+    println!("  {}", tr!("fix-commit-email-new-hashes"));
+    println!("  {}", tr!("fix-commit-email-force-push"));
+    println!("  {}", tr!("fix-commit-email-backup"));
     println!();
-    println!("{} Batch fix not supported yet", "!".yellow());
-    println!("  Recommend using git rebase or git filter-branch");
-    println!("  Or use specialized tools like git-filter-repo");
+
+    if !yes {
+        let confirm = dialoguer::Confirm::new()
+            .with_prompt(tr!("fix-commit-confirm-continue"))
+            .default(false)
+            .interact()?;
+
+        if !confirm {
+            println!("{}", tr!("operation-cancelled"));
+            return Ok(());
+        }
+    }
+
+    let target = git.head_branch();
+    let backup = create_backup_ref(git, target.as_deref())?;
+    let (new_head, rewritten) = rewrite_commits(git, None, &head, &authored_by, identity, options)?;
+    update_target(git, target.as_deref(), &new_head)?;
+
+    print_rewrite_summary(git, matching, &new_head, &rewritten, &backup, options);
+
+    Ok(())
+}
+
+fn print_rewrite_summary(
+    git: &GitConfigManager,
+    fixed: usize,
+    new_head: &str,
+    rewritten: &[(String, String)],
+    backup: &str,
+    options: RewriteOptions,
+) {
     println!();
-    println!("Example command:");
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!("fix-commit-done", count = fixed)
+    );
+    warn_dropped_signatures(git, rewritten, options);
+    println!(
+        "  {}",
+        tr!("fix-commit-new-head", id = new_head[..7].green())
+    );
+    println!("  {}", tr!("fix-commit-backup", backup = backup.cyan()));
     println!(
         "  {}",
-        format!(
-            "git filter-branch --env-filter 'export GIT_AUTHOR_NAME=\"{}\" GIT_AUTHOR_EMAIL=\"{}\"' {}",
-            identity.name, identity.email, range
+        tr!(
+            "fix-commit-restore",
+            command = format!("git reset --hard {backup}").dimmed()
         )
-        .dimmed()
     );
+    println!();
+    println!(
+        "{} {}",
+        sym("⚠").yellow(),
+        tr!(
+            "fix-commit-hashes-changed",
+            command = "git push --force-with-lease".cyan()
+        )
+    );
+}
 
-    Ok(())
+/// Recreate the commits in `hide..tip` (all of `tip` without `hide`), applying
+/// the identity to those selected by `fix`
+///
+/// Commits are rebuilt parents first; any parent that was rewritten is replaced
+/// by its new commit, so merges keep their shape. Unselected commits are copied
+/// unchanged when a parent moved and left alone otherwise. Returns the new tip
+/// and (original, rewritten) pairs.
+fn rewrite_commits(
+    git: &GitConfigManager,
+    hide: Option<&str>,
+    tip: &str,
+    fix: &dyn Fn(&CommitParts) -> bool,
+    identity: &Identity,
+    options: RewriteOptions,
+) -> Result<(String, Vec<(String, String)>)> {
+    let mut rewritten: HashMap<String, String> = HashMap::new();
+    let mut new_tip = tip.to_string();
+    for id in git.rev_list(tip, hide)?.into_iter().rev() {
+        let mut commit = git.read_commit(&id)?;
+
+        let parents_moved = commit.parents.iter().any(|p| rewritten.contains_key(p));
+        let selected = fix(&commit);
+        if !selected && !parents_moved {
+            new_tip = id;
+            continue;
+        }
+
+        for parent in &mut commit.parents {
+            if let Some(new_parent) = rewritten.get(parent) {
+                *parent = new_parent.clone();
+            }
+        }
+
+        let new_id = if selected {
+            rewrite_commit(git, &commit, identity, options)?
+        } else {
+            git.write_commit(&commit, None)?
+        };
+
+        rewritten.insert(id, new_id.clone());
+        new_tip = new_id;
+    }
+
+    Ok((new_tip, rewritten.into_iter().collect()))
 }
 
-/// Check for uncommitted changes
-fn has_uncommitted_changes(repo: &Repository) -> Result<bool> {
-    let statuses = repo.statuses(None)?;
-    Ok(!statuses.is_empty())
+/// Write a copy of a commit with the identity applied
+fn rewrite_commit(
+    git: &GitConfigManager,
+    commit: &CommitParts,
+    identity: &Identity,
+    options: RewriteOptions,
+) -> Result<String> {
+    let person = |original: &Person| {
+        if options.reset_dates {
+            Person::now(&identity.name, &identity.email)
+        } else {
+            Person {
+                name: identity.name.clone(),
+                email: identity.email.clone(),
+                ..original.clone()
+            }
+        }
+    };
+
+    let mut rewritten = commit.clone();
+    rewritten.author = person(&commit.author);
+    rewritten.committer = if options.committer {
+        person(&commit.committer)
+    } else if options.reset_dates {
+        Person::now(&commit.committer.name, &commit.committer.email)
+    } else {
+        commit.committer.clone()
+    };
+
+    if !options.resign {
+        return git.write_commit(&rewritten, None);
+    }
+
+    let key = identity
+        .gpg_key
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!(tr!("fix-commit-no-gpg-key", id = identity.id)))?;
+    let sign = |content: &[u8]| {
+        GpgManager::new()
+            .sign(key, content)
+            .with_context(|| tr!("fix-commit-sign-failed", key = key))
+    };
+
+    git.write_commit(&rewritten, Some(&sign))
+}
+
+/// Tell the user when signed commits lost their signatures in the rewrite
+fn warn_dropped_signatures(
+    git: &GitConfigManager,
+    rewritten: &[(String, String)],
+    options: RewriteOptions,
+) {
+    let signed = |id: &str| git.extract_signature(id).ok().flatten().is_some();
+    let dropped = rewritten
+        .iter()
+        .filter(|(old, new)| signed(old) && !signed(new))
+        .count();
+    if dropped > 0 {
+        let message = if options.resign {
+            tr!("fix-commit-signatures-dropped", count = dropped)
+        } else {
+            tr!("fix-commit-signatures-dropped-resign", count = dropped)
+        };
+        println!("{} {}", "!".yellow(), message);
+    }
+}
+
+/// Branch a range rewrite moves
+///
+/// A range ending at a branch name (`main~3..main`) moves that branch, which
+/// is how bare repositories are fixed; any other end must be HEAD.
+fn range_target(git: &GitConfigManager, range: &str, tip: &str) -> Result<Option<String>> {
+    let end = range.rsplit_once("..").map_or("", |(_, end)| end);
+    let branch = if end.starts_with("refs/heads/") {
+        end.to_string()
+    } else {
+        format!("refs/heads/{end}")
+    };
+    if git.find_ref(&branch).as_deref() == Some(tip) {
+        return Ok(Some(branch));
+    }
+
+    let head = git.resolve_commit("HEAD")?;
+    if head != tip {
+        anyhow::bail!(tr!("fix-commit-range-end", head = &head[..7]));
+    }
+    Ok(git.head_branch())
+}
+
+/// Save the tip of the branch about to be rewritten under refs/gid/backup/
+fn create_backup_ref(git: &GitConfigManager, target: Option<&str>) -> Result<String> {
+    let (name, id) = match target {
+        Some(branch) => (
+            branch.strip_prefix("refs/heads/").unwrap_or(branch),
+            git.find_ref(branch).ok_or_else(|| {
+                anyhow::anyhow!(tr!("fix-commit-branch-no-commit", branch = branch))
+            })?,
+        ),
+        None => ("HEAD", git.resolve_commit("HEAD")?),
+    };
+
+    let backup = format!(
+        "refs/gid/backup/{}-{}",
+        name,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    );
+    git.set_ref(&backup, &id, false, "gid fix-commit backup")
+        .with_context(|| tr!("fix-commit-backup-failed", backup = backup))?;
+
+    Ok(backup)
+}
+
+/// Point the rewritten branch (or detached HEAD) at a rewritten commit
+fn update_target(git: &GitConfigManager, target: Option<&str>, id: &str) -> Result<()> {
+    match target {
+        Some(name) => git.set_ref(name, id, true, "gid fix-commit"),
+        None => git.detach_head(id, "gid fix-commit"),
+    }
 }
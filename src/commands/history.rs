@@ -0,0 +1,107 @@
+use anyhow::Result;
+use chrono::Local;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+use crate::ascii::sym;
+use crate::commands::export::contract_home;
+use crate::commands::list::pad;
+use crate::config::state::SwitchRecord;
+use crate::config::State;
+use crate::git::GitConfigManager;
+use crate::i18n::tr;
+
+/// Show past identity switches, newest first
+pub fn execute(repo: Option<PathBuf>, limit: Option<usize>, json: bool) -> Result<()> {
+    let state = State::load()?;
+
+    let repo = repo.map(|path| repo_key(&path));
+    let records: Vec<&SwitchRecord> = state
+        .history
+        .iter()
+        .rev()
+        .filter(|r| {
+            repo.as_ref()
+                .is_none_or(|key| r.repo.as_deref() == Some(key.as_str()))
+        })
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&records)?);
+        return Ok(());
+    }
+
+    if records.is_empty() {
+        match repo {
+            Some(key) => println!(
+                "{} {}",
+                "!".yellow(),
+                tr!("history-empty-repo", path = contract_home(&key))
+            ),
+            None => println!("{} {}", "!".yellow(), tr!("history-empty")),
+        }
+        return Ok(());
+    }
+
+    println!("{}", tr!("history-title").bold());
+    println!();
+
+    let rows: Vec<(String, String, String, &str)> = records
+        .iter()
+        .map(|r| {
+            let time = r
+                .time_at()
+                .map(|t| t.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| r.time.clone());
+            let target = r
+                .repo
+                .as_deref()
+                .map(contract_home)
+                .unwrap_or_else(|| tr!("history-global"));
+            let change = format!(
+                "{} {} {}",
+                r.from.clone().unwrap_or_else(|| tr!("history-none")),
+                sym("→"),
+                r.to
+            );
+            (time, target, change, r.scope.as_str())
+        })
+        .collect();
+
+    let target_width = rows
+        .iter()
+        .map(|r| console::measure_text_width(&r.1))
+        .max()
+        .unwrap_or(0);
+    let change_width = rows
+        .iter()
+        .map(|r| console::measure_text_width(&r.2))
+        .max()
+        .unwrap_or(0);
+
+    for (time, target, change, scope) in &rows {
+        println!(
+            "  {}  {}  {}  {}",
+            time.dimmed(),
+            pad(target, target_width),
+            pad(change, change_width).green(),
+            scope.dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+/// Key under which switches for a repository are recorded (canonical work tree path)
+fn repo_key(path: &Path) -> String {
+    let workdir = GitConfigManager::from_path(path)
+        .ok()
+        .and_then(|git| git.workdir().map(Path::to_path_buf))
+        .unwrap_or_else(|| path.to_path_buf());
+    workdir
+        .canonicalize()
+        .unwrap_or(workdir)
+        .to_string_lossy()
+        .to_string()
+}
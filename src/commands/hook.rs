@@ -6,8 +6,13 @@ use std::path::PathBuf;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
+use crate::ascii::sym;
 use crate::cli::HookAction;
+use crate::config::{journal, Config};
+use crate::exit::Failure;
 use crate::git::GitConfigManager;
+use crate::i18n::tr;
+use crate::rules::cache::resolve_cached;
 
 /// Git hook 脚本内容
 const PRE_COMMIT_HOOK: &str = r#"#!/bin/sh
@@ -53,9 +58,67 @@ pub fn execute(action: HookAction) -> Result<()> {
         HookAction::Install { global } => install_hook(global),
         HookAction::Uninstall { global } => uninstall_hook(global),
         HookAction::Status => show_status(),
+        HookAction::PreCommitFramework { files: _ } => run_framework_hook(),
     }
 }
 
+/// pre-commit 框架调用的检查（见 .pre-commit-hooks.yaml）
+///
+/// pre-commit 在仓库根目录运行它，传入暂存的文件名（忽略）且没有可用的
+/// stdin，所以从不交互；输出只在失败时显示。
+fn run_framework_hook() -> Result<()> {
+    if std::env::var_os("GID_SKIP").is_some_and(|v| v == "1") {
+        return Ok(());
+    }
+
+    let config = Config::load()?;
+    let git = GitConfigManager::new()?;
+    if !git.is_in_repo() {
+        return Err(crate::git::not_a_repo());
+    }
+
+    // 未配置期望身份时通过（包括 CI 机器和没有这个身份的成员）
+    let expected = git
+        .context_dir()
+        .and_then(|dir| resolve_cached(&config, &dir, git.get_origin_url().as_deref()));
+    let Some((expected, identity)) =
+        expected.and_then(|e| config.find_identity(&e.identity).map(|i| (e, i)))
+    else {
+        return Ok(());
+    };
+
+    let name = git.get_effective_user_name();
+    let email = git.get_effective_user_email();
+    if Some(&identity.name) == name.as_ref() && Some(&identity.email) == email.as_ref() {
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        tr!(
+            "hook-mismatch",
+            name = name.unwrap_or_else(|| tr!("hook-no-name")),
+            email = email.unwrap_or_else(|| tr!("hook-no-email")),
+            source = expected.source,
+            identity = format!("[{}]", identity.id).cyan(),
+            expected_name = identity.name,
+            expected_email = identity.email
+        )
+    );
+    println!();
+    println!("{}", tr!("hook-fix", command = "gid auto".cyan()));
+    println!(
+        "{}",
+        tr!("hook-skip", command = "GID_SKIP=1 git commit".cyan())
+    );
+    println!(
+        "{}",
+        tr!("hook-or", command = "git commit --no-verify".cyan())
+    );
+
+    Err(Failure::Mismatch.error(tr!("hook-mismatch-error", id = identity.id)))
+}
+
 /// 安装 hook
 fn install_hook(global: bool) -> Result<()> {
     if global {
@@ -70,15 +133,16 @@ fn install_local_hook() -> Result<()> {
     let git = GitConfigManager::new()?;
 
     if !git.is_in_repo() {
-        anyhow::bail!("Current directory is not a Git repository");
+        return Err(crate::git::not_a_repo());
     }
 
     let hooks_dir = git
         .repo_path()
-        .ok_or_else(|| anyhow::anyhow!("Could not get repository path"))?
+        .ok_or_else(|| anyhow::anyhow!(tr!("hook-no-repo-path")))?
         .join("hooks");
 
-    fs::create_dir_all(&hooks_dir).context("Could not create hooks directory")?;
+    fs::create_dir_all(&hooks_dir)
+        .with_context(|| tr!("dir-create-failed", path = hooks_dir.display()))?;
 
     let hook_path = hooks_dir.join("pre-commit");
 
@@ -86,29 +150,31 @@ fn install_local_hook() -> Result<()> {
     if hook_path.exists() {
         let content = fs::read_to_string(&hook_path)?;
         if !content.contains("gid") {
-            println!("{} pre-commit hook already exists", "!".yellow());
+            println!("{} {}", "!".yellow(), tr!("hook-exists"));
             println!("  {}", hook_path.display().to_string().dimmed());
 
             let confirm = dialoguer::Confirm::new()
-                .with_prompt("Overwrite?")
+                .with_prompt(tr!("hook-overwrite"))
                 .default(false)
                 .interact()?;
 
             if !confirm {
-                println!("Operation cancelled");
+                println!("{}", tr!("operation-cancelled"));
                 return Ok(());
             }
         }
     }
 
     // 写入 hook
-    fs::write(&hook_path, PRE_COMMIT_HOOK).context("Could not write hook file")?;
+    journal::record(&hook_path);
+    fs::write(&hook_path, crate::ascii::text(PRE_COMMIT_HOOK).as_ref())
+        .with_context(|| tr!("hook-write-failed"))?;
 
     // 设置可执行权限 (仅 Unix)
     #[cfg(unix)]
     fs::set_permissions(&hook_path, fs::Permissions::from_mode(0o755))?;
 
-    println!("{} pre-commit hook installed", "✓".green());
+    println!("{} {}", sym("✓").green(), tr!("hook-installed"));
     println!("  {}", hook_path.display().to_string().dimmed());
 
     Ok(())
@@ -116,22 +182,26 @@ fn install_local_hook() -> Result<()> {
 
 /// 安装全局 hook
 fn install_global_hook() -> Result<()> {
-    let home = home::home_dir().ok_or_else(|| anyhow::anyhow!("Could not get home directory"))?;
+    let home = home::home_dir().ok_or_else(|| anyhow::anyhow!(tr!("home-dir-unknown")))?;
 
     let hooks_dir = home.join(".config").join("git").join("hooks");
 
-    fs::create_dir_all(&hooks_dir).context("Could not create global hooks directory")?;
+    fs::create_dir_all(&hooks_dir)
+        .with_context(|| tr!("dir-create-failed", path = hooks_dir.display()))?;
 
     let hook_path = hooks_dir.join("pre-commit");
 
     // 写入 hook
-    fs::write(&hook_path, PRE_COMMIT_HOOK).context("Could not write hook file")?;
+    journal::record(&hook_path);
+    fs::write(&hook_path, crate::ascii::text(PRE_COMMIT_HOOK).as_ref())
+        .with_context(|| tr!("hook-write-failed"))?;
 
     // 设置可执行权限 (仅 Unix)
     #[cfg(unix)]
     fs::set_permissions(&hook_path, fs::Permissions::from_mode(0o755))?;
 
     // 设置 Git 全局配置
+    journal::record(&crate::git::global_config_path()?);
     let output = std::process::Command::new("git")
         .args([
             "config",
@@ -140,16 +210,23 @@ fn install_global_hook() -> Result<()> {
             hooks_dir.to_str().unwrap(),
         ])
         .output()
-        .context("Could not set core.hooksPath")?;
+        .with_context(|| tr!("hook-hooks-path-failed"))?;
 
     if !output.status.success() {
-        anyhow::bail!("Failed to set core.hooksPath");
+        anyhow::bail!(tr!("hook-hooks-path-failed"));
     }
 
-    println!("{} Global pre-commit hook installed", "✓".green());
+    println!("{} {}", sym("✓").green(), tr!("hook-global-installed"));
     println!("  {}", hook_path.display().to_string().dimmed());
     println!();
-    println!("Set {} = {}", "core.hooksPath".cyan(), hooks_dir.display());
+    println!(
+        "{}",
+        tr!(
+            "hook-hooks-path-set",
+            key = "core.hooksPath".cyan(),
+            path = hooks_dir.display()
+        )
+    );
 
     Ok(())
 }
@@ -168,37 +245,39 @@ fn uninstall_local_hook() -> Result<()> {
     let git = GitConfigManager::new()?;
 
     if !git.is_in_repo() {
-        anyhow::bail!("Current directory is not a Git repository");
+        return Err(crate::git::not_a_repo());
     }
 
     let hook_path = git
         .repo_path()
-        .ok_or_else(|| anyhow::anyhow!("Could not get repository path"))?
+        .ok_or_else(|| anyhow::anyhow!(tr!("hook-no-repo-path")))?
         .join("hooks")
         .join("pre-commit");
 
     if !hook_path.exists() {
-        println!("{} hook does not exist", "!".yellow());
+        println!("{} {}", "!".yellow(), tr!("hook-missing"));
         return Ok(());
     }
 
     // 检查是否是 gid 的 hook
     let content = fs::read_to_string(&hook_path)?;
     if !content.contains("gid") {
-        println!("{} This is not a gid hook, skipping removal", "!".yellow());
+        println!("{} {}", "!".yellow(), tr!("hook-not-gid"));
         return Ok(());
     }
 
-    fs::remove_file(&hook_path).context("Could not remove hook file")?;
+    journal::record(&hook_path);
+    fs::remove_file(&hook_path)
+        .with_context(|| tr!("file-delete-failed", path = hook_path.display()))?;
 
-    println!("{} pre-commit hook uninstalled", "✓".green());
+    println!("{} {}", sym("✓").green(), tr!("hook-uninstalled"));
 
     Ok(())
 }
 
 /// 卸载全局 hook
 fn uninstall_global_hook() -> Result<()> {
-    let home = home::home_dir().ok_or_else(|| anyhow::anyhow!("Could not get home directory"))?;
+    let home = home::home_dir().ok_or_else(|| anyhow::anyhow!(tr!("home-dir-unknown")))?;
 
     let hook_path = home
         .join(".config")
@@ -209,24 +288,28 @@ fn uninstall_global_hook() -> Result<()> {
     if hook_path.exists() {
         let content = fs::read_to_string(&hook_path)?;
         if content.contains("gid") {
+            journal::record(&hook_path);
             fs::remove_file(&hook_path)?;
-            println!("{} Global hook removed", "✓".green());
+            println!("{} {}", sym("✓").green(), tr!("hook-global-removed"));
         }
     }
 
     // 移除 Git 全局配置
+    if let Ok(path) = crate::git::global_config_path() {
+        journal::record(&path);
+    }
     let _ = std::process::Command::new("git")
         .args(["config", "--global", "--unset", "core.hooksPath"])
         .output();
 
-    println!("{} core.hooksPath configuration removed", "✓".green());
+    println!("{} {}", sym("✓").green(), tr!("hook-hooks-path-removed"));
 
     Ok(())
 }
 
 /// 显示 hook 状态
 fn show_status() -> Result<()> {
-    println!("{}", "Git Hook Status:".bold());
+    println!("{}", tr!("hook-status").bold());
     println!();
 
     // 检查本地 hook
@@ -242,31 +325,56 @@ fn show_status() -> Result<()> {
 
                 if is_gid {
                     println!(
-                        "  {} Local hook: {}",
-                        "✓".green(),
-                        "Installed (gid)".green()
+                        "  {} {}: {}",
+                        sym("✓").green(),
+                        tr!("hook-local"),
+                        tr!("hook-state-installed").green()
                     );
                 } else {
                     println!(
-                        "  {} Local hook: {}",
+                        "  {} {}: {}",
                         "!".yellow(),
-                        "Exists (non-gid)".yellow()
+                        tr!("hook-local"),
+                        tr!("hook-state-foreign").yellow()
                     );
                 }
                 println!("    {}", hook_path.display().to_string().dimmed());
             } else {
                 println!(
-                    "  {} Local hook: {}",
-                    "○".dimmed(),
-                    "Not installed".dimmed()
+                    "  {} {}: {}",
+                    sym("○").dimmed(),
+                    tr!("hook-local"),
+                    tr!("hook-state-not-installed").dimmed()
+                );
+            }
+        }
+
+        // pre-commit 框架通过 .pre-commit-config.yaml 调用 gid
+        let framework_config = git.workdir().map(|dir| dir.join(".pre-commit-config.yaml"));
+        if let Some(path) = framework_config.filter(|p| p.exists()) {
+            let content = fs::read_to_string(&path).unwrap_or_default();
+            if content.contains("gid-check") {
+                println!(
+                    "  {} {}: {}",
+                    sym("✓").green(),
+                    tr!("hook-framework"),
+                    tr!("hook-state-framework").green()
+                );
+            } else {
+                println!(
+                    "  {} {}: {}",
+                    sym("○").dimmed(),
+                    tr!("hook-framework"),
+                    tr!("hook-state-no-framework").dimmed()
                 );
             }
         }
     } else {
         println!(
-            "  {} Local hook: {}",
-            "○".dimmed(),
-            "Not in a Git repository".dimmed()
+            "  {} {}: {}",
+            sym("○").dimmed(),
+            tr!("hook-local"),
+            tr!("hook-state-no-repo").dimmed()
         );
     }
 
@@ -294,31 +402,35 @@ fn show_status() -> Result<()> {
 
             if is_gid {
                 println!(
-                    "  {} Global hook: {}",
-                    "✓".green(),
-                    "Installed (gid)".green()
+                    "  {} {}: {}",
+                    sym("✓").green(),
+                    tr!("hook-global"),
+                    tr!("hook-state-installed").green()
                 );
             } else {
                 println!(
-                    "  {} Global hook: {}",
+                    "  {} {}: {}",
                     "!".yellow(),
-                    "Exists (non-gid)".yellow()
+                    tr!("hook-global"),
+                    tr!("hook-state-foreign").yellow()
                 );
             }
             println!("    {}", hook_path.display().to_string().dimmed());
         } else {
             println!(
-                "  {} Global hook: {}",
-                "○".dimmed(),
-                "Not installed".dimmed()
+                "  {} {}: {}",
+                sym("○").dimmed(),
+                tr!("hook-global"),
+                tr!("hook-state-not-installed").dimmed()
             );
         }
         println!("    core.hooksPath = {}", hooks_path.dimmed());
     } else {
         println!(
-            "  {} Global hook: {}",
-            "○".dimmed(),
-            "Not configured".dimmed()
+            "  {} {}: {}",
+            sym("○").dimmed(),
+            tr!("hook-global"),
+            tr!("hook-state-not-configured").dimmed()
         );
     }
 
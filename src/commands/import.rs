@@ -1,87 +1,125 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
-use dialoguer::Confirm;
+use dialoguer::{Confirm, Input};
 use std::fs;
+use std::io::IsTerminal;
 use std::path::Path;
 
-use crate::config::Config;
+use crate::age::{self, AgeManager};
+use crate::ascii::sym;
+use crate::cli::{ConflictStrategy, ExportFormat};
+use crate::commands::export::format_from_path;
+use crate::config::{Config, Identity};
+use crate::git::{scan_config_identities, ConfigIdentity};
+use crate::i18n::tr;
+use crate::rules::Rule;
 
-/// 导入配置
-pub fn execute(file: &Path) -> Result<()> {
+/// 导入配置（age 加密的导出文件会先解密）
+///
+/// 指定 `on_conflict` 时直接合并，不再询问导入方式；会保存 post_switch_command 时需要确认或 `yes`
+pub fn execute(
+    file: &Path,
+    age_identity: Option<&Path>,
+    format: Option<ExportFormat>,
+    dry_run: bool,
+    on_conflict: Option<ConflictStrategy>,
+    yes: bool,
+) -> Result<()> {
     if !file.exists() {
-        anyhow::bail!("File not found: {}", file.display());
+        anyhow::bail!(tr!("import-file-not-found", path = file.display()));
     }
 
     // 读取并解析导入文件
-    let content = fs::read_to_string(file)
-        .with_context(|| format!("Could not read file: {}", file.display()))?;
+    let mut bytes =
+        fs::read(file).with_context(|| tr!("file-read-failed", path = file.display()))?;
 
-    let import_config: Config =
-        toml::from_str(&content).with_context(|| "Configuration file format error")?;
+    if age::is_encrypted(&bytes) {
+        let age = AgeManager::new();
+        if !age.is_available() {
+            anyhow::bail!(tr!("import-age-missing"));
+        }
+        bytes = age.decrypt(file, age_identity)?;
+    }
+
+    let content = String::from_utf8(bytes).with_context(|| tr!("import-not-utf8"))?;
+
+    let import_config: Config = match format.unwrap_or_else(|| format_from_path(file)) {
+        ExportFormat::Toml => toml::from_str(&content).with_context(|| tr!("import-invalid"))?,
+        ExportFormat::Json => {
+            serde_json::from_str(&content).with_context(|| tr!("import-invalid"))?
+        }
+        ExportFormat::Yaml => {
+            serde_yaml::from_str(&content).with_context(|| tr!("import-invalid"))?
+        }
+        ExportFormat::Script => anyhow::bail!(tr!("import-script")),
+    };
 
     if import_config.identities.is_empty() && import_config.rules.is_empty() {
-        println!("{} No valid configuration found in file", "!".yellow());
+        println!("{} {}", "!".yellow(), tr!("import-empty"));
         return Ok(());
     }
 
     println!(
-        "Found {} identities, {} rules",
-        import_config.identities.len(),
-        import_config.rules.len()
+        "{}",
+        tr!(
+            "import-found",
+            identities = import_config.identities.len(),
+            rules = import_config.rules.len()
+        )
     );
 
     // 加载现有配置
-    let mut config = Config::load()?;
+    let config = Config::load()?;
     let had_existing = !config.identities.is_empty() || !config.rules.is_empty();
 
-    if had_existing {
+    let strategy = on_conflict.unwrap_or_default();
+
+    if dry_run {
+        println!();
+        ImportPlan::new(&config, &import_config, strategy).print(had_existing);
         println!();
-        println!("{}", "Import Options:".cyan());
-        println!("  1. Merge (keep existing, add new)");
-        println!("  2. Replace (delete existing configuration)");
-        println!("  3. Cancel");
+        println!("{}", tr!("import-dry-run").dimmed());
+        return Ok(());
+    }
+
+    let merge_commands = ImportPlan::new(&config, &import_config, strategy).commands();
+
+    if had_existing && on_conflict.is_some() {
+        if !confirm_commands(&merge_commands, yes)? {
+            println!("{}", tr!("operation-cancelled"));
+            return Ok(());
+        }
+        Config::update(|config| merge(config, &import_config, strategy))?;
+    } else if had_existing {
+        println!();
+        println!("{}", tr!("import-options").cyan());
+        println!("  1. {}", tr!("import-option-merge"));
+        println!("  2. {}", tr!("import-option-replace"));
+        println!("  3. {}", tr!("import-option-cancel"));
 
         let choice: String = dialoguer::Input::new()
-            .with_prompt("Select [1/2/3]")
+            .with_prompt(tr!("add-prompt-choice"))
             .default("1".to_string())
             .interact_text()?;
 
         match choice.trim() {
             "1" => {
                 // 合并模式
-                let mut added_identities = 0;
-                let mut skipped_identities = 0;
-
-                for identity in import_config.identities {
-                    if config.find_identity(&identity.id).is_none() {
-                        config.identities.push(identity);
-                        added_identities += 1;
-                    } else {
-                        skipped_identities += 1;
-                    }
-                }
-
-                let added_rules = import_config.rules.len();
-                for rule in import_config.rules {
-                    config.add_rule(rule);
+                if !confirm_commands(&merge_commands, yes)? {
+                    println!("{}", tr!("operation-cancelled"));
+                    return Ok(());
                 }
-
-                config.save()?;
-
-                println!();
-                println!("{} Import complete:", "✓".green());
-                println!("  Identities: Added {added_identities}, Skipped {skipped_identities} (already exists)");
-                println!("  Rules: Added {added_rules}");
+                Config::update(|config| merge(config, &import_config, strategy))?;
             }
             "2" => {
                 // 替换模式
                 let confirm = Confirm::new()
-                    .with_prompt("Are you sure you want to replace existing configuration? This cannot be undone")
+                    .with_prompt(tr!("import-replace-confirm"))
                     .default(false)
                     .interact()?;
 
-                if !confirm {
-                    println!("Operation cancelled");
+                if !confirm || !confirm_commands(&commands(&import_config), yes)? {
+                    println!("{}", tr!("operation-cancelled"));
                     return Ok(());
                 }
 
@@ -90,34 +128,651 @@ pub fn execute(file: &Path) -> Result<()> {
                 let backup_path = config_path.with_extension("toml.backup");
                 if config_path.exists() {
                     fs::copy(&config_path, &backup_path)?;
-                    println!("{} Backed up to: {}", "→".blue(), backup_path.display());
+                    println!(
+                        "{} {}",
+                        sym("→").blue(),
+                        tr!("import-backed-up", path = backup_path.display())
+                    );
                 }
 
                 import_config.save()?;
 
                 println!(
-                    "{} Configuration replaced: {} identities, {} rules",
-                    "✓".green(),
-                    import_config.identities.len(),
-                    import_config.rules.len()
+                    "{} {}",
+                    sym("✓").green(),
+                    tr!(
+                        "import-replaced",
+                        identities = import_config.identities.len(),
+                        rules = import_config.rules.len()
+                    )
                 );
+                print_commands(&commands(&import_config));
             }
             _ => {
-                println!("Operation cancelled");
+                println!("{}", tr!("operation-cancelled"));
                 return Ok(());
             }
         }
     } else {
         // 没有现有配置，直接导入
+        if !confirm_commands(&commands(&import_config), yes)? {
+            println!("{}", tr!("operation-cancelled"));
+            return Ok(());
+        }
         import_config.save()?;
 
         println!(
-            "{} Configuration imported: {} identities, {} rules",
-            "✓".green(),
-            import_config.identities.len(),
-            import_config.rules.len()
+            "{} {}",
+            sym("✓").green(),
+            tr!(
+                "import-imported",
+                identities = import_config.identities.len(),
+                rules = import_config.rules.len()
+            )
+        );
+        print_commands(&commands(&import_config));
+    }
+
+    Ok(())
+}
+
+/// 导入后会保存的 post_switch_command（身份 ID，命令）
+fn commands(config: &Config) -> Vec<(String, &str)> {
+    config
+        .identities
+        .iter()
+        .filter_map(|i| Some((i.id.clone(), i.post_switch_command.as_deref()?)))
+        .collect()
+}
+
+/// 列出导入的身份在切换后会执行的命令
+fn print_commands(commands: &[(String, &str)]) {
+    if commands.is_empty() {
+        return;
+    }
+    println!();
+    println!("{}", tr!("import-commands").yellow());
+    for (id, command) in commands {
+        println!("  {} [{}] {}", "!".yellow(), id, command);
+    }
+}
+
+/// 保存导入的 post_switch_command 前确认，非交互时要求 `--yes`
+fn confirm_commands(commands: &[(String, &str)], yes: bool) -> Result<bool> {
+    if commands.is_empty() || yes {
+        return Ok(true);
+    }
+
+    print_commands(commands);
+    if !std::io::stdin().is_terminal() || !std::io::stderr().is_terminal() {
+        anyhow::bail!(tr!("import-commands-need-confirmation"));
+    }
+
+    Ok(Confirm::new()
+        .with_prompt(tr!("import-commands-confirm"))
+        .default(false)
+        .interact()?)
+}
+
+/// 合并导入的身份和规则，按冲突策略处理同 ID 但内容不同的身份
+fn merge(config: &mut Config, incoming: &Config, strategy: ConflictStrategy) -> Result<()> {
+    let current = config.clone();
+    let plan = ImportPlan::new(&current, incoming, strategy);
+
+    let mut added = 0;
+    let mut skipped = 0;
+    let mut conflicts = Vec::new();
+
+    for change in &plan.identities {
+        match change {
+            IdentityChange::New(identity) => {
+                config.identities.push((*identity).clone());
+                added += 1;
+            }
+            IdentityChange::Unchanged(_) => skipped += 1,
+            IdentityChange::Conflict(identity, fields, renamed) => {
+                let fields = fields.join(", ");
+                match (strategy, renamed) {
+                    (ConflictStrategy::Overwrite, _) => {
+                        if let Some(existing) =
+                            config.identities.iter_mut().find(|i| i.id == identity.id)
+                        {
+                            *existing = (*identity).clone();
+                        }
+                        conflicts.push(tr!(
+                            "import-conflict-overwritten",
+                            id = identity.id,
+                            fields = fields
+                        ));
+                    }
+                    (ConflictStrategy::Rename, Some(new_id)) => {
+                        let mut renamed = (*identity).clone();
+                        renamed.id = new_id.clone();
+                        config.identities.push(renamed);
+                        added += 1;
+                        conflicts.push(tr!(
+                            "import-conflict-renamed",
+                            id = identity.id,
+                            new_id = new_id,
+                            fields = fields
+                        ));
+                    }
+                    _ => {
+                        skipped += 1;
+                        conflicts.push(tr!(
+                            "import-conflict-skipped",
+                            id = identity.id,
+                            fields = fields
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut added_rules = 0;
+    let mut skipped_rules = 0;
+    for change in &plan.rules {
+        match change {
+            RuleChange::New(rule) => {
+                config.add_rule(rule.clone());
+                added_rules += 1;
+            }
+            RuleChange::Duplicate(_) => skipped_rules += 1,
+            RuleChange::Conflict(rule, local) if strategy == ConflictStrategy::Overwrite => {
+                for existing in config
+                    .rules
+                    .iter_mut()
+                    .filter(|r| r.rule_type == rule.rule_type && r.source.is_none())
+                {
+                    existing.identity = rule.identity.clone();
+                }
+                conflicts.push(tr!("import-rule-overwritten", rule = rule, local = local));
+            }
+            RuleChange::Conflict(rule, local) => {
+                skipped_rules += 1;
+                conflicts.push(tr!("import-rule-skipped", rule = rule, local = local));
+            }
+        }
+    }
+
+    println!();
+    println!("{} {}", sym("✓").green(), tr!("import-complete"));
+    println!(
+        "  {}",
+        tr!("import-identities", added = added, skipped = skipped)
+    );
+    println!(
+        "  {}",
+        tr!("import-rules", added = added_rules, skipped = skipped_rules)
+    );
+
+    print_commands(&plan.commands());
+
+    if !conflicts.is_empty() {
+        println!();
+        println!("{}", tr!("import-conflicts").yellow());
+        for conflict in &conflicts {
+            println!("  {} {}", "~".yellow(), conflict);
+        }
+        if strategy == ConflictStrategy::Skip {
+            println!();
+            println!(
+                "{}",
+                tr!(
+                    "import-conflicts-hint",
+                    overwrite = "--on-conflict overwrite".cyan(),
+                    rename = "--on-conflict rename".cyan()
+                )
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// 导入文件与当前配置的差异
+struct ImportPlan<'a> {
+    identities: Vec<IdentityChange<'a>>,
+    rules: Vec<RuleChange>,
+    /// 仅存在于当前配置中的身份（替换模式下会被删除）
+    removed: Vec<&'a Identity>,
+    strategy: ConflictStrategy,
+}
+
+/// 单个导入身份与当前配置的比较结果
+enum IdentityChange<'a> {
+    New(&'a Identity),
+    Unchanged(&'a Identity),
+    /// 同 ID 但字段不同（不同的字段，rename 策略下的新 ID）
+    Conflict(&'a Identity, Vec<String>, Option<String>),
+}
+
+/// 单条导入规则与当前配置的比较结果（身份已改指向重命名后的 ID）
+enum RuleChange {
+    New(Rule),
+    /// 与现有规则或文件中前面的规则完全相同
+    Duplicate(Rule),
+    /// 相同类型和模式在本地映射到其他身份
+    Conflict(Rule, String),
+}
+
+impl<'a> ImportPlan<'a> {
+    fn new(current: &'a Config, incoming: &'a Config, strategy: ConflictStrategy) -> Self {
+        let mut renames: Vec<(String, String)> = Vec::new();
+        let identities = incoming
+            .identities
+            .iter()
+            .map(|identity| match current.find_identity(&identity.id) {
+                None => IdentityChange::New(identity),
+                Some(existing) => match changed_fields(existing, identity) {
+                    fields if fields.is_empty() => IdentityChange::Unchanged(identity),
+                    fields => {
+                        let renamed = (strategy == ConflictStrategy::Rename).then(|| {
+                            let new_id = rename_id(current, incoming, &renames, &identity.id);
+                            renames.push((identity.id.clone(), new_id.clone()));
+                            new_id
+                        });
+                        IdentityChange::Conflict(identity, fields, renamed)
+                    }
+                },
+            })
+            .collect();
+
+        // rename 策略下导入的规则随之改指向新 ID
+        let mut rules: Vec<RuleChange> = Vec::new();
+        for rule in &incoming.rules {
+            let mut rule = rule.clone();
+            if let Some((_, to)) = renames.iter().find(|(from, _)| *from == rule.identity) {
+                rule.identity = to.clone();
+            }
+
+            let seen = rules
+                .iter()
+                .any(|change| matches!(change, RuleChange::New(r) if same_rule(r, &rule)));
+            let change = if seen || current.rules.iter().any(|r| same_rule(r, &rule)) {
+                RuleChange::Duplicate(rule)
+            } else if let Some(local) = current.rules.iter().find(|r| r.rule_type == rule.rule_type)
+            {
+                let local = local.identity.clone();
+                RuleChange::Conflict(rule, local)
+            } else {
+                RuleChange::New(rule)
+            };
+            rules.push(change);
+        }
+
+        let removed = current
+            .identities
+            .iter()
+            .filter(|i| incoming.find_identity(&i.id).is_none())
+            .collect();
+
+        Self {
+            identities,
+            rules,
+            removed,
+            strategy,
+        }
+    }
+
+    /// 合并后会保存的 post_switch_command（最终的身份 ID，命令）
+    fn commands(&self) -> Vec<(String, &'a str)> {
+        self.identities
+            .iter()
+            .filter_map(|change| match change {
+                IdentityChange::New(identity) => Some((identity.id.clone(), *identity)),
+                IdentityChange::Conflict(identity, _, renamed) => match (self.strategy, renamed) {
+                    (ConflictStrategy::Overwrite, _) => Some((identity.id.clone(), *identity)),
+                    (ConflictStrategy::Rename, Some(new_id)) => Some((new_id.clone(), *identity)),
+                    _ => None,
+                },
+                IdentityChange::Unchanged(_) => None,
+            })
+            .filter_map(|(id, identity)| Some((id, identity.post_switch_command.as_deref()?)))
+            .collect()
+    }
+
+    /// 输出结构化差异：+ 新增，= 相同，~ 冲突，- 替换时删除
+    fn print(&self, had_existing: bool) {
+        println!("{}", tr!("import-plan-identities").bold());
+        for change in &self.identities {
+            match change {
+                IdentityChange::New(identity) => {
+                    println!("  {} {}", "+".green(), identity.to_string().green())
+                }
+                IdentityChange::Unchanged(identity) => println!(
+                    "  {} {} {}",
+                    "=".dimmed(),
+                    identity.to_string().dimmed(),
+                    tr!("import-plan-unchanged").dimmed()
+                ),
+                IdentityChange::Conflict(identity, fields, renamed) => {
+                    let resolution = match (self.strategy, renamed) {
+                        (ConflictStrategy::Overwrite, _) => tr!("import-plan-overwritten"),
+                        (ConflictStrategy::Rename, Some(new_id)) => {
+                            tr!("import-plan-renamed", new_id = new_id)
+                        }
+                        _ => tr!("import-plan-skipped-on-merge"),
+                    };
+                    println!(
+                        "  {} {} {}",
+                        "~".yellow(),
+                        identity.to_string().yellow(),
+                        tr!(
+                            "import-plan-differs",
+                            fields = fields.join(", "),
+                            resolution = resolution
+                        )
+                        .dimmed()
+                    )
+                }
+            }
+        }
+        if had_existing {
+            for identity in &self.removed {
+                println!(
+                    "  {} {} {}",
+                    "-".red(),
+                    identity.to_string().red(),
+                    tr!("import-plan-removed").dimmed()
+                );
+            }
+        }
+
+        println!("{}", tr!("import-plan-rules").bold());
+        for change in &self.rules {
+            match change {
+                RuleChange::New(rule) => println!("  {} {}", "+".green(), rule.to_string().green()),
+                RuleChange::Duplicate(rule) => println!(
+                    "  {} {} {}",
+                    "=".dimmed(),
+                    rule.to_string().dimmed(),
+                    tr!("import-plan-duplicate").dimmed()
+                ),
+                RuleChange::Conflict(rule, local) => {
+                    let resolution = if self.strategy == ConflictStrategy::Overwrite {
+                        tr!("import-plan-overwritten")
+                    } else {
+                        tr!("import-plan-skipped")
+                    };
+                    println!(
+                        "  {} {} {}",
+                        "~".yellow(),
+                        rule.to_string().yellow(),
+                        tr!(
+                            "import-plan-rule-conflict",
+                            local = local,
+                            resolution = resolution
+                        )
+                        .dimmed()
+                    )
+                }
+            }
+        }
+
+        print_commands(&self.commands());
+    }
+}
+
+/// rename 策略下的新 ID：追加 -imported 后缀，仍冲突时再追加数字
+fn rename_id(
+    current: &Config,
+    incoming: &Config,
+    renames: &[(String, String)],
+    id: &str,
+) -> String {
+    let taken = |candidate: &str| {
+        current.find_identity(candidate).is_some()
+            || incoming.find_identity(candidate).is_some()
+            || renames.iter().any(|(_, to)| to == candidate)
+    };
+
+    let base = format!("{id}-imported");
+    let mut candidate = base.clone();
+    let mut n = 2;
+    while taken(&candidate) {
+        candidate = format!("{base}-{n}");
+        n += 1;
+    }
+    candidate
+}
+
+/// 比较两个身份，返回不同的字段名
+fn changed_fields(current: &Identity, incoming: &Identity) -> Vec<String> {
+    let (Ok(toml::Value::Table(current)), Ok(toml::Value::Table(incoming))) = (
+        toml::Value::try_from(current),
+        toml::Value::try_from(incoming),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut fields: Vec<String> = current
+        .keys()
+        .chain(incoming.keys())
+        .filter(|key| current.get(*key) != incoming.get(*key))
+        .cloned()
+        .collect();
+    fields.sort();
+    fields.dedup();
+    fields
+}
+
+/// 规则类型、模式和身份相同即视为同一条规则
+fn same_rule(a: &Rule, b: &Rule) -> bool {
+    a.rule_type == b.rule_type && a.identity == b.identity
+}
+
+/// 从现有 Git 配置（system、global 及 include/includeIf 文件）创建身份和 gitdir 规则
+pub fn execute_from_gitconfig(yes: bool) -> Result<()> {
+    let mut config = Config::load()?;
+
+    // 跳过已配置的身份
+    let discovered: Vec<ConfigIdentity> = scan_config_identities()
+        .into_iter()
+        .filter(|found| {
+            !config
+                .identities
+                .iter()
+                .any(|i| i.name == found.name && i.email == found.email)
+        })
+        .collect();
+
+    if discovered.is_empty() {
+        println!("{} {}", "!".yellow(), tr!("import-gitconfig-none"));
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        tr!(
+            "import-gitconfig-found",
+            count = discovered.len().to_string().bold()
+        )
+    );
+
+    let mut added_identities = 0;
+    let mut added_rules = 0;
+    // `config` only tracks the ids taken so far; the additions are saved under the lock
+    let mut identities = Vec::new();
+    let mut rules = Vec::new();
+
+    for found in discovered {
+        println!();
+        println!("  {} <{}>", found.name, found.email.cyan());
+        println!(
+            "    {}",
+            tr!("import-gitconfig-from", path = found.file.display()).dimmed()
         );
+        if let Some(ref key) = found.signing_key {
+            println!(
+                "    {}",
+                tr!("import-gitconfig-signing-key", key = key).dimmed()
+            );
+        }
+        if let Some(ref gitdir) = found.gitdir {
+            println!("    {}", format!("includeIf gitdir:{gitdir}").dimmed());
+        }
+
+        if !yes
+            && !Confirm::new()
+                .with_prompt(tr!("import-gitconfig-create"))
+                .default(true)
+                .interact()?
+        {
+            continue;
+        }
+
+        let default_id = unique_id(&config, &suggest_id(&found));
+        let id = if yes {
+            default_id
+        } else {
+            Input::<String>::new()
+                .with_prompt(tr!("import-gitconfig-id"))
+                .default(default_id)
+                .validate_with(|input: &String| -> Result<(), String> {
+                    if !input
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+                    {
+                        Err(tr!("identity-id-invalid"))
+                    } else if config.find_identity(input).is_some() {
+                        Err(tr!("identity-exists", id = input))
+                    } else {
+                        Ok(())
+                    }
+                })
+                .interact_text()?
+        };
+
+        let mut identity = Identity::new(id.clone(), found.name.clone(), found.email.clone())
+            .with_gpg_key(found.signing_key.clone());
+        identity.gpg_sign = found.gpg_sign;
+        config.add_identity(identity.clone())?;
+        identities.push(identity);
+        added_identities += 1;
+        println!(
+            "  {} {}",
+            sym("✓").green(),
+            tr!("import-gitconfig-added", id = id)
+        );
+
+        if let Some(ref gitdir) = found.gitdir {
+            let pattern = gitdir_pattern(gitdir);
+            if yes
+                || Confirm::new()
+                    .with_prompt(tr!(
+                        "import-gitconfig-rule-confirm",
+                        pattern = pattern,
+                        arrow = sym("→"),
+                        id = id
+                    ))
+                    .default(true)
+                    .interact()?
+            {
+                println!(
+                    "  {} {}",
+                    sym("✓").green(),
+                    tr!(
+                        "import-gitconfig-rule-added",
+                        pattern = pattern,
+                        arrow = sym("→"),
+                        id = id
+                    )
+                );
+                rules.push(Rule::path(pattern, id));
+                added_rules += 1;
+            }
+        }
     }
 
+    Config::update(|config| {
+        for identity in identities {
+            config.add_identity(identity)?;
+        }
+        for rule in rules {
+            config.add_rule(rule);
+        }
+        Ok(())
+    })?;
+
+    println!();
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!(
+            "import-gitconfig-done",
+            identities = added_identities,
+            rules = added_rules
+        )
+    );
+
     Ok(())
 }
+
+/// 推荐的身份 ID：includeIf 目录名、配置文件后缀（.gitconfig-work）或邮箱用户名
+fn suggest_id(found: &ConfigIdentity) -> String {
+    let from_gitdir = found.gitdir.as_deref().and_then(|gitdir| {
+        gitdir
+            .trim_end_matches(['/', '*'])
+            .rsplit('/')
+            .next()
+            .map(str::to_string)
+    });
+    let from_file = found
+        .file
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| {
+            name.strip_prefix(".gitconfig")
+                .or_else(|| name.strip_prefix("gitconfig"))
+        })
+        .map(|suffix| suffix.trim_start_matches(['-', '.', '_']).to_string());
+    let from_email = found.email.split('@').next().map(str::to_string);
+
+    [from_gitdir, from_file, from_email]
+        .into_iter()
+        .flatten()
+        .map(|candidate| {
+            candidate
+                .to_lowercase()
+                .chars()
+                .map(|c| {
+                    if c.is_alphanumeric() || c == '_' {
+                        c
+                    } else {
+                        '-'
+                    }
+                })
+                .collect::<String>()
+                .trim_matches('-')
+                .to_string()
+        })
+        .find(|candidate| !candidate.is_empty())
+        .unwrap_or_else(|| "imported".to_string())
+}
+
+/// 已存在时追加数字后缀
+fn unique_id(config: &Config, base: &str) -> String {
+    let mut id = base.to_string();
+    let mut n = 2;
+    while config.find_identity(&id).is_some() {
+        id = format!("{base}-{n}");
+        n += 1;
+    }
+    id
+}
+
+/// includeIf gitdir 模式转换为路径规则（结尾的 / 表示目录下所有仓库）
+fn gitdir_pattern(gitdir: &str) -> String {
+    let pattern = if gitdir.starts_with("~/") || gitdir.starts_with('/') {
+        gitdir.to_string()
+    } else {
+        format!("**/{gitdir}")
+    };
+
+    match pattern.strip_suffix('/') {
+        Some(dir) => format!("{dir}/**"),
+        None => pattern,
+    }
+}
@@ -0,0 +1,144 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::ascii::sym;
+use crate::commands::switch;
+use crate::config::{journal, Config, ProjectConfig};
+use crate::git::GitConfigManager;
+use crate::i18n::tr;
+use crate::rules::resolve::resolve_identity;
+
+/// Options for `gid init`
+pub struct InitOptions {
+    pub identity: Option<String>,
+    pub toml: bool,
+    pub gitignore: bool,
+    pub commit: bool,
+    pub force: bool,
+}
+
+/// Pin an identity for the repository with a .gid file in its root
+pub fn execute(options: InitOptions) -> Result<()> {
+    let config = Config::load()?;
+    let git = GitConfigManager::new()?;
+    let root = git
+        .workdir()
+        .ok_or_else(crate::git::not_a_repo)?
+        .to_path_buf();
+    git.ensure_no_operation_in_progress()?;
+
+    let gid_path = root.join(".gid");
+    if !options.force {
+        if let Ok(Some(existing)) = ProjectConfig::load_from_dir(&root) {
+            anyhow::bail!(tr!(
+                "init-already-pinned",
+                path = gid_path.display(),
+                id = existing.identity
+            ));
+        }
+    }
+
+    // Without an argument, pin what rules resolve to or what the repository uses now
+    let identity_id = match options.identity {
+        Some(id) => id,
+        None => resolve_identity(&config, &root, git.get_origin_url().as_deref())
+            .map(|r| r.identity)
+            .or_else(|| {
+                let email = git.get_effective_user_email()?;
+                config
+                    .identities
+                    .iter()
+                    .find(|i| i.email == email)
+                    .map(|i| i.id.clone())
+            })
+            .ok_or_else(|| anyhow::anyhow!(tr!("init-nothing-to-pin")))?,
+    };
+    let identity = config
+        .find_identity(&identity_id)
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?;
+
+    ProjectConfig {
+        identity: identity.id.clone(),
+        rules: Vec::new(),
+    }
+    .write_to_dir(&root, options.toml)?;
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!(
+            "init-wrote",
+            path = gid_path.display(),
+            id = format!("[{}]", identity.id).green().bold()
+        )
+    );
+
+    if options.gitignore && add_to_gitignore(&root)? {
+        println!("{} {}", sym("✓").green(), tr!("init-gitignore-added"));
+    }
+
+    // Apply first so the commit is authored by the pinned identity
+    switch::execute(&identity.id, false)?;
+
+    if options.commit {
+        commit_gid_file(&root, &identity.id)?;
+        println!("{} {}", sym("✓").green(), tr!("init-committed"));
+    }
+
+    Ok(())
+}
+
+/// Append .gid to .gitignore unless an entry exists, returning whether it was added
+fn add_to_gitignore(root: &Path) -> Result<bool> {
+    let path = root.join(".gitignore");
+    let content = fs::read_to_string(&path).unwrap_or_default();
+    if content
+        .lines()
+        .any(|line| matches!(line.trim(), ".gid" | "/.gid"))
+    {
+        return Ok(false);
+    }
+
+    journal::record(&path);
+    let separator = if content.is_empty() || content.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    fs::write(&path, format!("{content}{separator}.gid\n"))
+        .with_context(|| tr!("file-write-failed", path = path.display()))?;
+
+    Ok(true)
+}
+
+/// Commit only the .gid file, leaving anything else staged untouched
+fn commit_gid_file(root: &Path, identity_id: &str) -> Result<()> {
+    let run = |args: &[&str]| -> Result<()> {
+        log::debug!("running git {}", args.join(" "));
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .with_context(|| tr!("git-failed"))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!(tr!(
+                "git-command-failed",
+                command = args[0],
+                error = stderr.trim()
+            ));
+        }
+        Ok(())
+    };
+
+    run(&["add", "--", ".gid"])?;
+    run(&[
+        "commit",
+        "-m",
+        &format!("Pin gid identity {identity_id}"),
+        "--",
+        ".gid",
+    ])
+}
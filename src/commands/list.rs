@@ -1,29 +1,132 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Local};
 use colored::Colorize;
+use serde_json::Value;
+use std::fs;
+use std::net::ToSocketAddrs;
 
-use crate::config::Config;
+use crate::ascii::sym;
+use crate::cli::ListSort;
+use crate::config::{Config, Identity, State};
+use crate::format::Template;
+use crate::git::GitConfigManager;
+use crate::gpg::{GpgManager, KeyStatus};
+use crate::i18n::tr;
+use crate::rules::resolve::{RepoStatus, Resolution};
+use crate::ssh::SshManager;
+
+/// list 命令选项
+#[derive(Debug, Default)]
+pub struct ListOptions {
+    pub json: bool,
+    pub table: bool,
+    pub filter: Option<String>,
+    pub tag: Option<String>,
+    pub sort: Option<ListSort>,
+    pub verify: bool,
+    pub format: Option<String>,
+}
 
 /// 列出所有身份
-pub fn execute() -> Result<()> {
+pub fn execute(options: ListOptions) -> Result<()> {
+    let template = options.format.as_deref().map(Template::parse).transpose()?;
     let config = Config::load()?;
 
+    let state = State::load().unwrap_or_default();
+
+    let mut identities: Vec<&Identity> = config
+        .identities
+        .iter()
+        .filter(|i| {
+            options
+                .filter
+                .as_deref()
+                .is_none_or(|f| matches_filter(i, f))
+        })
+        .filter(|i| {
+            options
+                .tag
+                .as_deref()
+                .is_none_or(|t| i.tags.iter().any(|tag| tag == t))
+        })
+        .collect();
+
+    match options.sort {
+        Some(ListSort::Id) => identities.sort_by(|a, b| a.id.cmp(&b.id)),
+        Some(ListSort::Recent) => identities.sort_by_key(|i| {
+            std::cmp::Reverse(state.usage.get(&i.id).and_then(|u| u.last_used_at()))
+        }),
+        Some(ListSort::Usage) => identities
+            .sort_by_key(|i| std::cmp::Reverse(state.usage.get(&i.id).map_or(0, |u| u.count))),
+        None => {}
+    }
+
+    if let Some(template) = template {
+        let fields = identity_fields();
+        for identity in &identities {
+            let mut record = serde_json::to_value(identity)?;
+            if let Some(record) = record.as_object_mut() {
+                // 未设置的字段在 --json 中省略，模板里输出为空
+                for field in &fields {
+                    record.entry(field.clone()).or_insert(Value::Null);
+                }
+            }
+            template.print(&record)?;
+        }
+        return Ok(());
+    }
+
+    if options.json {
+        let git = GitConfigManager::new()?;
+        let mut output = crate::commands::current::status_json(
+            &config,
+            &git,
+            &git.context_dir().unwrap_or_default(),
+        )?;
+        output["identities"] = serde_json::to_value(&identities)?;
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
     if config.identities.is_empty() {
-        println!("{} No identities configured", "!".yellow());
+        println!("{} {}", "!".yellow(), tr!("list-empty"));
         println!();
-        println!("Run {} to add a new identity", "gid add".cyan());
+        println!("{}", tr!("list-add-hint", command = "gid add".cyan()));
         return Ok(());
     }
 
-    println!("{}", "Configured Identities:".bold());
+    if identities.is_empty() {
+        println!("{} {}", "!".yellow(), tr!("list-no-match"));
+        return Ok(());
+    }
+
+    let markers = RepoMarkers::detect(&config);
+
+    // 每个身份的问题列表（未启用 --verify 时为 None）
+    let problems = options.verify.then(|| verify_identities(&identities));
+
+    if options.table {
+        print_table(&identities, &markers, &state, problems.as_deref());
+        markers.print_legend();
+        if let Some(problems) = &problems {
+            print_problems(&identities, problems);
+            print_offline_skip(&identities);
+        }
+        return finish_verify(problems.as_deref());
+    }
+
+    println!("{}", tr!("list-header").bold());
     println!();
 
-    for identity in &config.identities {
+    for (index, identity) in identities.iter().enumerate() {
         // 身份 ID 和基本信息
         println!(
-            "  {} {} <{}>",
+            "{} {} {} <{}>{}",
+            markers.symbol(&identity.id),
             format!("[{}]", identity.id).green().bold(),
             identity.name,
-            identity.email.cyan()
+            identity.email.cyan(),
+            markers.label(&identity.id)
         );
 
         // 描述
@@ -43,13 +146,378 @@ pub fn execute() -> Result<()> {
             println!("       {}", format!("[{}]", extras.join(", ")).dimmed());
         }
 
+        if let Some(problems) = &problems {
+            if problems[index].is_empty() {
+                println!(
+                    "       {} {}",
+                    sym("✓").green(),
+                    tr!("list-verified").green()
+                );
+            }
+            for problem in &problems[index] {
+                println!("       {} {}", sym("✗").red(), problem.red());
+            }
+        }
+
         println!();
     }
 
-    println!(
-        "Total {} identities",
-        config.identities.len().to_string().bold()
-    );
+    if identities.len() == config.identities.len() {
+        println!(
+            "{}",
+            tr!(
+                "list-total",
+                count = config.identities.len().to_string().bold()
+            )
+        );
+    } else {
+        println!(
+            "{}",
+            tr!(
+                "list-showing",
+                count = identities.len().to_string().bold(),
+                total = config.identities.len()
+            )
+        );
+    }
+
+    if problems.is_some() {
+        print_offline_skip(&identities);
+    }
+    finish_verify(problems.as_deref())
+}
+
+/// Identity 的全部字段名（取自配置的 JSON Schema）
+fn identity_fields() -> Vec<String> {
+    Config::json_schema()
+        .as_value()
+        .pointer("/definitions/Identity/properties")
+        .and_then(Value::as_object)
+        .map(|properties| properties.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// 校验每个身份：SSH 私钥可读、GPG 密钥存在且未过期、SSH 主机可解析（离线时跳过）
+fn verify_identities(identities: &[&Identity]) -> Vec<Vec<String>> {
+    let offline = crate::offline::is_offline();
+    let gpg = GpgManager::new();
+    let gpg_available = gpg.is_available();
+    let ssh = SshManager::new().ok();
+
+    identities
+        .iter()
+        .map(|identity| {
+            let mut problems = Vec::new();
+
+            if let Some(key) = identity.ssh_key_path() {
+                if !key.exists() {
+                    problems.push(tr!("list-ssh-key-missing", path = key.display()));
+                } else if fs::File::open(&key).is_err() {
+                    problems.push(tr!("list-ssh-key-unreadable", path = key.display()));
+                }
+            }
+
+            if let Some(key_id) = &identity.gpg_key {
+                if !gpg_available {
+                    problems.push(tr!("list-gpg-unavailable", key = key_id));
+                } else {
+                    match gpg.key_status(key_id) {
+                        Ok(KeyStatus::Valid) => {}
+                        Ok(KeyStatus::Expired) => {
+                            problems.push(tr!("list-gpg-expired", key = key_id))
+                        }
+                        Ok(KeyStatus::Revoked) => {
+                            problems.push(tr!("list-gpg-revoked", key = key_id))
+                        }
+                        Ok(KeyStatus::Missing) | Err(_) => {
+                            problems.push(tr!("list-gpg-missing", key = key_id))
+                        }
+                    }
+                }
+            }
+
+            for host in identity.ssh_hosts.iter().filter(|_| !offline) {
+                // 经过 SSH 配置（Host 别名 → HostName）后再做 DNS 解析
+                let hostname = ssh
+                    .as_ref()
+                    .map(|ssh| ssh.resolve_route(host).hostname)
+                    .unwrap_or_else(|| host.clone());
+                let resolves = (hostname.as_str(), 22)
+                    .to_socket_addrs()
+                    .is_ok_and(|mut addrs| addrs.next().is_some());
+                if !resolves {
+                    problems.push(tr!("list-host-unresolved", host = host));
+                }
+            }
+
+            problems
+        })
+        .collect()
+}
+
+/// 表格下方列出每个失败身份的具体问题
+fn print_problems(identities: &[&Identity], problems: &[Vec<String>]) {
+    if problems.iter().all(Vec::is_empty) {
+        return;
+    }
+
+    println!();
+    for (identity, list) in identities.iter().zip(problems) {
+        for problem in list {
+            println!(
+                "{} {} {}",
+                sym("✗").red(),
+                format!("[{}]", identity.id).red().bold(),
+                problem
+            );
+        }
+    }
+}
+
+/// 离线时说明 SSH 主机解析已跳过
+fn print_offline_skip(identities: &[&Identity]) {
+    if crate::offline::is_offline() && identities.iter().any(|i| !i.ssh_hosts.is_empty()) {
+        println!();
+        println!("{} {}", "!".yellow(), tr!("list-hosts-skipped-offline"));
+    }
+}
 
+/// 存在校验失败的身份时以非零状态退出
+fn finish_verify(problems: Option<&[Vec<String>]>) -> Result<()> {
+    let Some(problems) = problems else {
+        return Ok(());
+    };
+    let failed = problems.iter().filter(|list| !list.is_empty()).count();
+    if failed > 0 {
+        bail!(tr!(
+            "list-verify-failed",
+            failed = failed,
+            total = problems.len()
+        ));
+    }
     Ok(())
 }
+
+/// 当前仓库中实际生效的身份和规则期望的身份
+#[derive(Default)]
+struct RepoMarkers {
+    active: Option<String>,
+    expected: Option<Resolution>,
+}
+
+impl RepoMarkers {
+    /// 仅在 Git 仓库中检测，仓库外不做标记
+    fn detect(config: &Config) -> Self {
+        let Some(dir) = GitConfigManager::new()
+            .ok()
+            .and_then(|git| git.context_dir())
+        else {
+            return Self::default();
+        };
+
+        match RepoStatus::inspect(config, &dir) {
+            Ok(status) => Self {
+                active: status.effective_identity(config).map(|i| i.id.clone()),
+                expected: status.expected,
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn is_active(&self, id: &str) -> bool {
+        self.active.as_deref() == Some(id)
+    }
+
+    fn is_expected(&self, id: &str) -> bool {
+        self.expected.as_ref().map(|e| e.identity.as_str()) == Some(id)
+    }
+
+    fn symbol(&self, id: &str) -> String {
+        if self.is_active(id) {
+            sym("●").green().to_string()
+        } else if self.is_expected(id) {
+            sym("→").yellow().to_string()
+        } else {
+            " ".to_string()
+        }
+    }
+
+    fn label(&self, id: &str) -> String {
+        let mut labels = Vec::new();
+        if self.is_active(id) {
+            labels.push(tr!("list-active").green().to_string());
+        }
+        if let Some(expected) = self.expected.as_ref().filter(|_| self.is_expected(id)) {
+            labels.push(
+                tr!("list-expected-by", source = expected.source)
+                    .yellow()
+                    .to_string(),
+            );
+        }
+
+        if labels.is_empty() {
+            String::new()
+        } else {
+            format!("  ({})", labels.join(", "))
+        }
+    }
+
+    fn print_legend(&self) {
+        if self.active.is_none() && self.expected.is_none() {
+            return;
+        }
+
+        println!();
+        let mut legend = vec![format!(
+            "{} {}",
+            sym("●").green(),
+            tr!("list-legend-active")
+        )];
+        if let Some(expected) = &self.expected {
+            if !self.is_active(&expected.identity) {
+                legend.push(format!(
+                    "{} {}",
+                    sym("→").yellow(),
+                    tr!("list-expected-by", source = expected.source)
+                ));
+            }
+        }
+        println!("{}", legend.join("   "));
+    }
+}
+
+/// 按 ID、姓名、邮箱和描述进行不区分大小写的子串匹配
+fn matches_filter(identity: &Identity, filter: &str) -> bool {
+    let filter = filter.to_lowercase();
+    [
+        Some(identity.id.as_str()),
+        Some(identity.name.as_str()),
+        Some(identity.email.as_str()),
+        identity.description.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .any(|field| field.to_lowercase().contains(&filter))
+}
+
+/// 以对齐的表格输出身份列表
+fn print_table(
+    identities: &[&Identity],
+    markers: &RepoMarkers,
+    state: &State,
+    problems: Option<&[Vec<String>]>,
+) {
+    let mut headers = vec![
+        tr!("list-column-id"),
+        tr!("list-column-name"),
+        tr!("list-column-email"),
+        "SSH".to_string(),
+        "GPG".to_string(),
+        tr!("list-column-tags"),
+        tr!("list-column-last-used"),
+        tr!("list-column-uses"),
+    ];
+    if problems.is_some() {
+        headers.push(tr!("list-column-status"));
+    }
+
+    let rows: Vec<Vec<String>> = identities
+        .iter()
+        .enumerate()
+        .map(|(index, identity)| {
+            let usage = state.usage.get(&identity.id);
+            let mut row = vec![
+                identity.id.clone(),
+                identity.name.clone(),
+                identity.email.clone(),
+                mark(identity.ssh_key.is_some()),
+                mark(identity.gpg_key.is_some()),
+                identity.tags.join(","),
+                usage
+                    .and_then(|u| u.last_used_at())
+                    .map(|t| format_relative(t.with_timezone(&Local)))
+                    .unwrap_or_else(|| tr!("list-never")),
+                usage.map_or(0, |u| u.count).to_string(),
+            ];
+            if let Some(problems) = problems {
+                row.push(
+                    if problems[index].is_empty() {
+                        sym("✓")
+                    } else {
+                        sym("✗")
+                    }
+                    .to_string(),
+                );
+            }
+            row
+        })
+        .collect();
+
+    // 按显示宽度计算列宽（名字可能包含中文）
+    let mut widths: Vec<usize> = headers
+        .iter()
+        .map(|h| console::measure_text_width(h))
+        .collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(console::measure_text_width(cell));
+        }
+    }
+
+    let header_line = headers
+        .iter()
+        .zip(&widths)
+        .map(|(header, &width)| pad(header, width))
+        .collect::<Vec<_>>()
+        .join("  ");
+    println!("  {}", header_line.trim_end().bold());
+
+    for (identity, row) in identities.iter().zip(&rows) {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, &width))| {
+                let padded = pad(cell, width);
+                match i {
+                    0 => padded.green().to_string(),
+                    2 => padded.cyan().to_string(),
+                    8 if cell == sym("✓") => padded.green().to_string(),
+                    8 => padded.red().to_string(),
+                    _ => padded,
+                }
+            })
+            .collect();
+        println!(
+            "{} {}",
+            markers.symbol(&identity.id),
+            cells.join("  ").trim_end()
+        );
+    }
+}
+
+/// 相对时间（一个月以上显示日期）
+pub fn format_relative(time: DateTime<Local>) -> String {
+    let elapsed = Local::now().signed_duration_since(time);
+
+    if elapsed.num_minutes() < 1 {
+        tr!("time-just-now")
+    } else if elapsed.num_hours() < 1 {
+        tr!("time-minutes-ago", count = elapsed.num_minutes())
+    } else if elapsed.num_days() < 1 {
+        tr!("time-hours-ago", count = elapsed.num_hours())
+    } else if elapsed.num_days() < 30 {
+        tr!("time-days-ago", count = elapsed.num_days())
+    } else {
+        time.format("%Y-%m-%d").to_string()
+    }
+}
+
+fn mark(present: bool) -> String {
+    if present { sym("✓") } else { "-" }.to_string()
+}
+
+pub fn pad(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(console::measure_text_width(text));
+    format!("{text}{}", " ".repeat(padding))
+}
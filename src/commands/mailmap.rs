@@ -0,0 +1,98 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::ascii::sym;
+use crate::audit::{Auditor, IdentityUsage};
+use crate::cli::MailmapAction;
+use crate::commands::direnv::{replace_block, BLOCK_END, BLOCK_START};
+use crate::config::{journal, Config};
+use crate::git::{GitConfigManager, Mailmap};
+use crate::i18n::tr;
+
+/// Execute mailmap command
+pub fn execute(action: MailmapAction) -> Result<()> {
+    match action {
+        MailmapAction::Generate { output, dry_run } => generate(output, dry_run),
+    }
+}
+
+/// Map historical author names and emails to the configured identities
+fn generate(output: Option<PathBuf>, dry_run: bool) -> Result<()> {
+    let config = Config::load()?;
+    let git = GitConfigManager::new()?;
+    let root = git
+        .workdir()
+        .ok_or_else(crate::git::not_a_repo)?
+        .to_path_buf();
+
+    let path = output.unwrap_or_else(|| root.join(".mailmap"));
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    // Authors already mapped by hand-written lines are left to them
+    let hand_written = Mailmap::parse(&replace_block(&existing, ""));
+
+    let authors: Vec<IdentityUsage> = Auditor::new(config.clone())
+        .authors(&root)?
+        .into_iter()
+        .filter(|a| hand_written.resolve(&a.name, &a.email) == (a.name.clone(), a.email.clone()))
+        .collect();
+    let stanza = mailmap_stanza(&config, &authors);
+    let unknown: Vec<&IdentityUsage> = authors.iter().filter(|a| !a.is_known).collect();
+
+    if dry_run {
+        print!("{stanza}");
+    } else {
+        journal::record(&path);
+        fs::write(&path, replace_block(&existing, &stanza))
+            .with_context(|| tr!("file-write-failed", path = path.display()))?;
+
+        let mapped = stanza.lines().filter(|l| !l.starts_with('#')).count();
+        println!(
+            "{} {}",
+            sym("✓").green(),
+            tr!("mailmap-wrote", path = path.display(), count = mapped)
+        );
+    }
+
+    if !unknown.is_empty() {
+        eprintln!(
+            "{} {}",
+            "!".yellow(),
+            tr!("mailmap-unknown", count = unknown.len())
+        );
+        for author in unknown {
+            eprintln!(
+                "    {} <{}> ({})",
+                author.name,
+                author.email,
+                tr!("mailmap-commits", count = author.commit_count)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// gid's block of .mailmap lines: `Canonical Name <email> Commit Name <commit email>`
+fn mailmap_stanza(config: &Config, authors: &[IdentityUsage]) -> String {
+    let mut lines = vec![format!("{BLOCK_START} (gid mailmap generate)")];
+    for author in authors {
+        let Some(identity) = author
+            .identity_id
+            .as_deref()
+            .and_then(|id| config.find_identity(id))
+        else {
+            continue;
+        };
+        if identity.name == author.name && identity.email == author.email {
+            continue;
+        }
+        lines.push(format!(
+            "{} <{}> {} <{}>",
+            identity.name, identity.email, author.name, author.email
+        ));
+    }
+    lines.push(BLOCK_END.to_string());
+    lines.join("\n") + "\n"
+}
@@ -1,15 +1,40 @@
 pub mod add;
+pub mod apply;
 pub mod audit;
 pub mod auto;
+pub mod check;
+pub mod clone;
 pub mod completions;
+pub mod config;
+pub mod credential;
 pub mod current;
+pub mod direnv;
 pub mod doctor;
 pub mod edit;
+pub mod env;
 pub mod export;
 pub mod fix_commit;
+pub mod history;
 pub mod hook;
 pub mod import;
+pub mod init;
 pub mod list;
+pub mod mailmap;
+pub mod onboard;
+pub mod plugin;
+pub mod prune;
 pub mod remove;
+pub mod repos;
 pub mod rule;
+pub mod ruleset;
+pub mod serve;
+pub mod shell_init;
+pub mod show;
+pub mod stats;
 pub mod switch;
+pub mod sync_gitconfig;
+pub mod template;
+pub mod undo;
+pub mod verify;
+pub mod watch;
+pub mod whoami;
@@ -0,0 +1,278 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use dialoguer::{Confirm, Input};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::ascii::sym;
+use crate::cli::HookAction;
+use crate::commands::export::contract_home;
+use crate::commands::{add, hook, import};
+use crate::config::identity::expand_path;
+use crate::config::{journal, Config};
+use crate::i18n::tr;
+use crate::rules::{Rule, RuleType};
+use crate::ssh::SshManager;
+
+/// First-run wizard: identities, keys, starter rules, hook and shell integration
+pub fn execute(yes: bool) -> Result<()> {
+    if !yes && !console::Term::stdout().is_term() {
+        anyhow::bail!(tr!("onboard-not-interactive"));
+    }
+
+    println!("{}", tr!("onboard-welcome").bold());
+    println!("{}", tr!("onboard-intro").dimmed());
+
+    step(1, &tr!("onboard-step-identities"));
+    import::execute_from_gitconfig(yes)?;
+    if Config::load()?.identities.is_empty() {
+        if yes {
+            anyhow::bail!(tr!("onboard-no-identity"));
+        }
+        println!();
+        add::execute(None, None, None, None, None, None)?;
+    }
+
+    let config = Config::load()?;
+
+    step(2, &tr!("onboard-step-ssh"));
+    let keys = generate_keys(&config, yes)?;
+
+    step(3, &tr!("onboard-step-rules"));
+    let workspaces = add_workspace_rules(&config, yes)?;
+
+    // The answers are applied under the config lock, not the prompts
+    Config::update(|config| {
+        for (id, key) in keys {
+            if let Some(identity) = config.identities.iter_mut().find(|i| i.id == id) {
+                identity.ssh_key = Some(key);
+            }
+        }
+        for (id, root) in workspaces {
+            config.add_rule(Rule::path(format!("{root}/**"), id));
+            if !config.settings.workspace_roots.contains(&root) {
+                config.settings.workspace_roots.push(root);
+            }
+        }
+        Ok(())
+    })?;
+
+    step(4, &tr!("onboard-step-integration"));
+    if confirm(yes, &tr!("onboard-hook-confirm"))? {
+        hook::execute(HookAction::Install { global: true })?;
+    }
+    install_shell_init(yes)?;
+
+    println!();
+    println!("{} {}", sym("✓").green(), tr!("onboard-ready"));
+    println!(
+        "  {}",
+        tr!("onboard-hint-list", command = "gid list".cyan())
+    );
+    println!(
+        "  {}",
+        tr!("onboard-hint-doctor", command = "gid doctor".cyan())
+    );
+    println!(
+        "  {}",
+        tr!("onboard-hint-apply", command = "gid apply".cyan())
+    );
+
+    Ok(())
+}
+
+fn step(number: usize, title: &str) {
+    println!();
+    println!("{}", format!("[{number}/4] {title}").cyan().bold());
+}
+
+/// Defaults are accepted with --yes
+fn confirm(yes: bool, prompt: &str) -> Result<bool> {
+    if yes {
+        return Ok(true);
+    }
+    Ok(Confirm::new()
+        .with_prompt(prompt)
+        .default(true)
+        .interact()?)
+}
+
+/// Offer a dedicated key to identities that have none (never generated with --yes)
+///
+/// Returns the identity ids and their new keys.
+fn generate_keys(config: &Config, yes: bool) -> Result<Vec<(String, PathBuf)>> {
+    let missing: Vec<usize> = (0..config.identities.len())
+        .filter(|&i| config.identities[i].ssh_key.is_none())
+        .collect();
+
+    if missing.is_empty() {
+        println!("  {}", tr!("onboard-all-keys").dimmed());
+        return Ok(Vec::new());
+    }
+    if yes {
+        println!(
+            "  {} {}",
+            "!".yellow(),
+            tr!("onboard-missing-keys", count = missing.len())
+        );
+        return Ok(Vec::new());
+    }
+
+    let ssh = SshManager::new()?;
+    let mut keys = Vec::new();
+    for index in missing {
+        let identity = &config.identities[index];
+        if !Confirm::new()
+            .with_prompt(tr!("onboard-generate-key", id = identity.id))
+            .default(false)
+            .interact()?
+        {
+            continue;
+        }
+
+        let key_path = ssh.generate_key(&identity.id, &identity.email)?;
+        println!(
+            "  {} {}",
+            sym("✓").green(),
+            tr!("add-ssh-generated", path = key_path.display())
+        );
+        if let Ok(public_key) = ssh.read_public_key(&key_path) {
+            println!("  {}", tr!("onboard-public-key").dimmed());
+            println!("  {}", public_key.trim());
+        }
+        keys.push((
+            identity.id.clone(),
+            PathBuf::from(contract_home(&key_path.display().to_string())),
+        ));
+    }
+
+    Ok(keys)
+}
+
+/// Ask for each identity's workspace directory and add a path rule for it
+///
+/// Returns the identity ids and their workspace roots.
+fn add_workspace_rules(config: &Config, yes: bool) -> Result<Vec<(String, String)>> {
+    let without_rules: Vec<String> = config
+        .identities
+        .iter()
+        .filter(|identity| {
+            !config.rules.iter().any(|rule| {
+                rule.identity == identity.id && matches!(rule.rule_type, RuleType::Path { .. })
+            })
+        })
+        .map(|identity| identity.id.clone())
+        .collect();
+
+    if without_rules.is_empty() {
+        println!("  {}", tr!("onboard-all-rules").dimmed());
+        return Ok(Vec::new());
+    }
+    if yes {
+        println!(
+            "  {} {}",
+            "!".yellow(),
+            tr!("onboard-missing-rules", ids = without_rules.join(", "))
+        );
+        return Ok(Vec::new());
+    }
+
+    let mut workspaces = Vec::new();
+    for id in without_rules {
+        let input: String = Input::new()
+            .with_prompt(tr!("onboard-workspace", id = id))
+            .allow_empty(true)
+            .interact_text()?;
+        let input = input.trim().trim_end_matches('/');
+        if input.is_empty() {
+            continue;
+        }
+
+        let dir = expand_path(Path::new(input));
+        if !dir.is_dir() {
+            println!(
+                "  {} {}",
+                "!".yellow(),
+                tr!("onboard-workspace-missing", path = dir.display())
+            );
+            continue;
+        }
+
+        let root = contract_home(&dir.display().to_string());
+        println!(
+            "  {} {}",
+            sym("✓").green(),
+            tr!(
+                "import-gitconfig-rule-added",
+                pattern = format!("{root}/**"),
+                arrow = sym("→"),
+                id = id
+            )
+        );
+        workspaces.push((id, root));
+    }
+
+    Ok(workspaces)
+}
+
+/// Append the shell-init line to the rc file of the login shell
+fn install_shell_init(yes: bool) -> Result<()> {
+    let shell = std::env::var("SHELL").unwrap_or_default();
+    let shell = shell.rsplit('/').next().unwrap_or_default();
+
+    let home = home::home_dir().ok_or_else(|| anyhow::anyhow!(tr!("home-dir-unknown")))?;
+    let (rc, line) = match shell {
+        "bash" => (home.join(".bashrc"), r#"eval "$(gid shell-init bash)""#),
+        "zsh" => (home.join(".zshrc"), r#"eval "$(gid shell-init zsh)""#),
+        "fish" => (
+            home.join(".config/fish/config.fish"),
+            "gid shell-init fish | source",
+        ),
+        _ => {
+            println!(
+                "  {} {}",
+                "!".yellow(),
+                tr!(
+                    "onboard-unknown-shell",
+                    command = "gid shell-init --help".cyan()
+                )
+            );
+            return Ok(());
+        }
+    };
+
+    let rc_display = contract_home(&rc.display().to_string());
+    let content = fs::read_to_string(&rc).unwrap_or_default();
+    if content.contains("gid shell-init") {
+        println!(
+            "  {}",
+            tr!("onboard-shell-present", path = rc_display).dimmed()
+        );
+        return Ok(());
+    }
+
+    if !confirm(yes, &tr!("onboard-shell-confirm", path = rc_display))? {
+        return Ok(());
+    }
+
+    if let Some(parent) = rc.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    journal::record(&rc);
+    let separator = if content.is_empty() || content.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    fs::write(&rc, format!("{content}{separator}\n# gid\n{line}\n"))
+        .with_context(|| tr!("file-write-failed", path = rc_display))?;
+
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!("onboard-shell-added", path = rc_display)
+    );
+    println!("  {}", tr!("onboard-shell-activate").dimmed());
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+//! External subcommands: `gid foo` runs `gid-foo` from PATH, like git and cargo
+//!
+//! The plugin gets the remaining arguments and the context gid resolved:
+//!
+//! - `GID`: path of the gid executable, for calling back into it
+//! - `GID_CONFIG`: config file in use (`--config` included)
+//! - `GID_IDENTITY`: id of the active identity, when a configured identity is in use
+//! - `GID_IDENTITY_NAME`, `GID_IDENTITY_EMAIL`: effective user.name / user.email
+//! - `GID_EXPECTED_IDENTITY`: identity .gid or rules expect here
+//! - `GID_REPO`: work tree of the current repository
+//! - `GID_OFFLINE`: `1` when gid runs offline, so plugins can skip the network too
+
+use anyhow::{Context, Result};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::Config;
+use crate::exit::Failure;
+use crate::git::GitConfigManager;
+use crate::i18n::tr;
+use crate::rules::resolve::RepoStatus;
+
+/// Run `gid-<name>` with the rest of the arguments
+pub fn execute(args: Vec<OsString>) -> Result<()> {
+    let Some((name, args)) = args.split_first() else {
+        return Err(Failure::Usage.error(tr!("plugin-missing")));
+    };
+    let name = name.to_string_lossy();
+    let program = find_plugin(&name)
+        .ok_or_else(|| Failure::Usage.error(tr!("plugin-unknown", name = name)))?;
+
+    let mut command = Command::new(&program);
+    command.args(args);
+    set_context(&mut command);
+    log::debug!("running {command:?}");
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        // Only returns if the plugin could not be started
+        let e = command.exec();
+        Err(e).with_context(|| tr!("plugin-failed", program = program.display()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let status = command
+            .status()
+            .with_context(|| tr!("plugin-failed", program = program.display()))?;
+        std::process::exit(status.code().unwrap_or(1));
+    }
+}
+
+/// First `gid-<name>` executable on PATH
+fn find_plugin(name: &str) -> Option<PathBuf> {
+    // Names with separators would escape the PATH lookup
+    if name.is_empty() || name.contains(['/', '\\']) {
+        return None;
+    }
+    let file = format!("gid-{name}{}", std::env::consts::EXE_SUFFIX);
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(&file))
+        .find(|candidate| is_executable(candidate))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Best effort: a broken config or missing repository leaves variables unset
+fn set_context(command: &mut Command) {
+    // Not inherited from an outer gid, e.g. a plugin run by post_switch_command
+    for var in [
+        "GID_IDENTITY",
+        "GID_IDENTITY_NAME",
+        "GID_IDENTITY_EMAIL",
+        "GID_EXPECTED_IDENTITY",
+        "GID_REPO",
+    ] {
+        command.env_remove(var);
+    }
+
+    if let Ok(exe) = std::env::current_exe() {
+        command.env("GID", exe);
+    }
+    if let Ok(path) = Config::config_path() {
+        command.env("GID_CONFIG", path);
+    }
+    if crate::offline::is_offline() {
+        command.env("GID_OFFLINE", "1");
+    }
+
+    let Ok(git) = GitConfigManager::new() else {
+        return;
+    };
+    if let Some(workdir) = git.workdir() {
+        command.env("GID_REPO", workdir);
+    }
+    let Ok(config) = Config::load() else {
+        return;
+    };
+
+    let status = match git.context_dir() {
+        Some(dir) => RepoStatus::inspect(&config, &dir).ok(),
+        None => None,
+    };
+    let name = git.get_effective_user_name();
+    let email = git.get_effective_user_email();
+    if let Some(identity) = config
+        .identities
+        .iter()
+        .find(|i| Some(&i.name) == name.as_ref() && Some(&i.email) == email.as_ref())
+    {
+        command.env("GID_IDENTITY", &identity.id);
+    }
+    if let Some(name) = name {
+        command.env("GID_IDENTITY_NAME", name);
+    }
+    if let Some(email) = email {
+        command.env("GID_IDENTITY_EMAIL", email);
+    }
+    if let Some(expected) = status.and_then(|s| s.expected) {
+        command.env("GID_EXPECTED_IDENTITY", expected.identity);
+    }
+}
@@ -0,0 +1,269 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use dialoguer::Confirm;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::ascii::sym;
+use crate::commands::export::contract_home;
+use crate::commands::list::pad;
+use crate::config::identity::expand_path;
+use crate::config::{journal, Config, Credentials, State};
+use crate::git::GitConfigManager;
+use crate::i18n::tr;
+use crate::rules::Rule;
+use crate::ssh::{host_alias, SshManager};
+
+/// Something gid wrote for an identity that no longer exists
+enum Orphan {
+    Rule(Rule),
+    DefaultIdentity(String),
+    SshHost(String),
+    Fragment(PathBuf),
+    IncludeIf { gitdir: String, path: String },
+    Usage(String),
+    Credential { identity: String, host: String },
+}
+
+impl Orphan {
+    fn kind(&self) -> String {
+        match self {
+            Orphan::Rule(_) => tr!("prune-kind-rule"),
+            Orphan::DefaultIdentity(_) => tr!("prune-kind-default"),
+            Orphan::SshHost(_) => tr!("prune-kind-ssh-host"),
+            Orphan::Fragment(_) => tr!("prune-kind-fragment"),
+            Orphan::IncludeIf { .. } => "includeIf".to_string(),
+            Orphan::Usage(_) => tr!("prune-kind-usage"),
+            Orphan::Credential { .. } => tr!("prune-kind-credential"),
+        }
+    }
+
+    fn detail(&self) -> String {
+        match self {
+            Orphan::Rule(rule) => format!("{}: {rule}", rule.id),
+            Orphan::DefaultIdentity(id) => format!("settings.default_identity = {id}"),
+            Orphan::SshHost(alias) => format!("Host {alias}"),
+            Orphan::Fragment(path) => contract_home(&path.display().to_string()),
+            Orphan::IncludeIf { gitdir, path } => {
+                format!("gitdir:{gitdir} {} {}", sym("→"), contract_home(path))
+            }
+            Orphan::Usage(id) => tr!("prune-usage", id = id),
+            Orphan::Credential { identity, host } => {
+                tr!("prune-credential", identity = identity, host = host)
+            }
+        }
+    }
+}
+
+/// Find and remove artifacts that reference deleted identities
+pub fn execute(dry_run: bool, yes: bool) -> Result<()> {
+    let config = Config::load()?;
+    let orphans = find_orphans(&config)?;
+
+    // Subscribed rules come back on the next `gid ruleset update`, so they are left alone
+    let subscribed = config
+        .rules
+        .iter()
+        .filter(|r| r.source.is_some() && config.find_identity(&r.identity).is_none())
+        .count();
+    if subscribed > 0 {
+        println!(
+            "{} {}",
+            "!".yellow(),
+            tr!("prune-subscribed-kept", count = subscribed)
+        );
+    }
+
+    if orphans.is_empty() {
+        println!("{} {}", sym("✓").green(), tr!("prune-nothing"));
+        return Ok(());
+    }
+
+    println!("{}", tr!("prune-header").bold());
+    println!();
+    for orphan in &orphans {
+        println!("  {} {}", pad(&orphan.kind(), 10).yellow(), orphan.detail());
+    }
+    println!();
+
+    if dry_run {
+        println!(
+            "{} {}",
+            "!".yellow(),
+            tr!("prune-dry-run", count = orphans.len())
+        );
+        return Ok(());
+    }
+
+    if !yes
+        && !Confirm::new()
+            .with_prompt(tr!("prune-confirm", count = orphans.len()))
+            .default(false)
+            .interact()?
+    {
+        println!("{}", tr!("operation-cancelled"));
+        return Ok(());
+    }
+
+    remove_orphans(&orphans)?;
+
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!("prune-done", count = orphans.len())
+    );
+
+    Ok(())
+}
+
+fn find_orphans(config: &Config) -> Result<Vec<Orphan>> {
+    let known = |id: &str| config.find_identity(id).is_some();
+    let mut orphans = Vec::new();
+
+    for rule in &config.rules {
+        if rule.source.is_none() && !known(&rule.identity) {
+            orphans.push(Orphan::Rule(rule.clone()));
+        }
+    }
+
+    if let Some(ref id) = config.settings.default_identity {
+        if !known(id) {
+            orphans.push(Orphan::DefaultIdentity(id.clone()));
+        }
+    }
+
+    // An alias belongs to an identity when the template renders it or it uses the identity's key
+    let keys: Vec<PathBuf> = config
+        .identities
+        .iter()
+        .filter_map(|i| i.ssh_key_path())
+        .collect();
+    for block in SshManager::new()?.managed_hosts() {
+        let Some(alias) = block.patterns.first() else {
+            continue;
+        };
+        let hostname = block.hostname.as_deref().unwrap_or(alias);
+        let rendered = config
+            .identities
+            .iter()
+            .any(|i| host_alias(&config.settings.ssh_alias_template, hostname, &i.id) == *alias);
+        let uses_key = block
+            .identity_files
+            .iter()
+            .any(|file| keys.contains(&expand_path(Path::new(file))));
+        if !rendered && !uses_key {
+            orphans.push(Orphan::SshHost(alias.clone()));
+        }
+    }
+
+    let fragment_dir = fragment_dir()?;
+    let fragment_id = |path: &Path| -> Option<String> {
+        if path.parent() != Some(fragment_dir.as_path()) {
+            return None;
+        }
+        path.file_name()?
+            .to_str()?
+            .strip_suffix(".gitconfig")
+            .map(str::to_string)
+    };
+
+    if let Ok(entries) = fs::read_dir(&fragment_dir) {
+        let mut fragments: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| fragment_id(path).is_some_and(|id| !known(&id)))
+            .collect();
+        fragments.sort();
+        orphans.extend(fragments.into_iter().map(Orphan::Fragment));
+    }
+
+    for (gitdir, path) in GitConfigManager::new()?.include_if_entries() {
+        if fragment_id(&expand_path(Path::new(&path))).is_some_and(|id| !known(&id)) {
+            orphans.push(Orphan::IncludeIf { gitdir, path });
+        }
+    }
+
+    let state = State::load()?;
+    for id in state.usage.keys() {
+        if !known(id) {
+            orphans.push(Orphan::Usage(id.clone()));
+        }
+    }
+
+    for credential in &Credentials::load()?.entries {
+        if !known(&credential.identity) {
+            orphans.push(Orphan::Credential {
+                identity: credential.identity.clone(),
+                host: credential.host.clone(),
+            });
+        }
+    }
+
+    Ok(orphans)
+}
+
+fn remove_orphans(orphans: &[Orphan]) -> Result<()> {
+    let ssh = SshManager::new()?;
+    let git = GitConfigManager::new()?;
+
+    for orphan in orphans {
+        match orphan {
+            Orphan::Rule(_)
+            | Orphan::DefaultIdentity(_)
+            | Orphan::Credential { .. }
+            | Orphan::Usage(_) => {}
+            Orphan::SshHost(alias) => ssh.remove_host_config(alias)?,
+            Orphan::Fragment(path) => {
+                journal::record(path);
+                fs::remove_file(path)
+                    .with_context(|| tr!("file-delete-failed", path = path.display()))?;
+            }
+            Orphan::IncludeIf { gitdir, .. } => git.remove_include_if(gitdir)?,
+        }
+    }
+
+    // Config and credentials are reloaded under their locks, so match by content, not position
+    Config::update(|config| {
+        for orphan in orphans {
+            match orphan {
+                Orphan::Rule(rule) => config.rules.retain(|r| r.id != rule.id),
+                Orphan::DefaultIdentity(id)
+                    if config.settings.default_identity.as_ref() == Some(id) =>
+                {
+                    config.settings.default_identity = None;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    })?;
+    State::update(|state| {
+        for orphan in orphans {
+            if let Orphan::Usage(id) = orphan {
+                state.usage.remove(id);
+            }
+        }
+        Ok(())
+    })?;
+    if orphans
+        .iter()
+        .any(|o| matches!(o, Orphan::Credential { .. }))
+    {
+        Credentials::update(|credentials| {
+            for orphan in orphans {
+                if let Orphan::Credential { identity, host } = orphan {
+                    credentials.remove(identity, host);
+                }
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Directory holding the includeIf fragments written by `gid switch --include-if`
+fn fragment_dir() -> Result<PathBuf> {
+    let path = Config::fragment_path("_")?;
+    Ok(path.parent().map(Path::to_path_buf).unwrap_or_default())
+}
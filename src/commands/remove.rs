@@ -2,41 +2,53 @@ use anyhow::Result;
 use colored::Colorize;
 use dialoguer::Confirm;
 
+use crate::ascii::sym;
 use crate::config::Config;
+use crate::i18n::tr;
 
 /// 删除身份
 pub fn execute(identity_id: &str) -> Result<()> {
-    let mut config = Config::load()?;
+    let config = Config::load()?;
 
     // 查找身份
     let identity = config
         .find_identity(identity_id)
-        .ok_or_else(|| anyhow::anyhow!("Identity '{identity_id}' not found"))?
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?
         .clone();
 
     // 确认删除
     println!(
-        "About to remove identity: {} {} <{}>",
-        format!("[{}]", identity.id).yellow(),
-        identity.name,
-        identity.email
+        "{}",
+        tr!(
+            "remove-about-to",
+            id = format!("[{}]", identity.id).yellow(),
+            name = identity.name,
+            email = identity.email
+        )
     );
 
     let confirm = Confirm::new()
-        .with_prompt("Are you sure you want to remove?")
+        .with_prompt(tr!("confirm-remove"))
         .default(false)
         .interact()?;
 
     if !confirm {
-        println!("Operation cancelled");
+        println!("{}", tr!("operation-cancelled"));
         return Ok(());
     }
 
     // 删除身份
-    config.remove_identity(identity_id)?;
-    config.save()?;
+    Config::update(|config| config.remove_identity(identity_id).map(drop))?;
 
-    println!("{} Identity '{}' removed", "✓".green(), identity_id);
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!("remove-done", id = identity_id)
+    );
+    println!(
+        "  {}",
+        tr!("remove-prune-hint", command = "gid prune".cyan())
+    );
 
     Ok(())
 }
@@ -0,0 +1,196 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+use crate::ascii::sym;
+use crate::commands::export::contract_home;
+use crate::commands::list::pad;
+use crate::config::Config;
+use crate::format::Template;
+use crate::i18n::tr;
+use crate::rules::resolve::RepoStatus;
+
+/// List repositories under the scan roots with their effective and expected identity
+pub fn execute(scan: Vec<PathBuf>, json: bool, format: Option<&str>) -> Result<()> {
+    let template = format.map(Template::parse).transpose()?;
+    let config = Config::load()?;
+
+    let repos = workspace_repos(&config, scan)?;
+
+    let statuses: Vec<RepoStatus> = repos
+        .iter()
+        .filter_map(|repo| RepoStatus::inspect(&config, repo).ok())
+        .collect();
+
+    if let Some(template) = template {
+        for status in &statuses {
+            template.print(&repo_json(&config, status))?;
+        }
+        return Ok(());
+    }
+
+    if json {
+        let output: Vec<Value> = statuses
+            .iter()
+            .map(|status| repo_json(&config, status))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    if statuses.is_empty() {
+        println!("{} {}", "!".yellow(), tr!("apply-no-repos"));
+        return Ok(());
+    }
+
+    print_table(&config, &statuses);
+
+    let drifted = statuses.iter().filter(|s| s.is_drifted(&config)).count();
+    let unmatched = statuses.iter().filter(|s| s.expected.is_none()).count();
+    println!();
+    println!(
+        "{}",
+        tr!(
+            "repos-summary",
+            total = statuses.len(),
+            ok = (statuses.len() - drifted - unmatched).to_string().green(),
+            drifted = drifted.to_string().red(),
+            unmatched = unmatched
+        )
+    );
+    if drifted > 0 {
+        println!(
+            "{}",
+            tr!(
+                "repos-fix-hint",
+                command = "gid auto --scan <DIR> --apply".cyan()
+            )
+        );
+    }
+
+    Ok(())
+}
+
+/// Repositories under the given directories, or under settings.workspace_roots
+pub fn workspace_repos(config: &Config, scan: Vec<PathBuf>) -> Result<Vec<PathBuf>> {
+    let roots = if scan.is_empty() {
+        config.settings.workspace_root_paths()
+    } else {
+        scan
+    };
+    if roots.is_empty() {
+        anyhow::bail!(tr!("repos-no-roots"));
+    }
+
+    let mut repos: Vec<PathBuf> = roots
+        .iter()
+        .flat_map(|root| crate::git::discover_repos(root, crate::git::DEFAULT_SCAN_DEPTH))
+        .collect();
+    repos.sort();
+    repos.dedup();
+
+    Ok(repos)
+}
+
+fn print_table(config: &Config, statuses: &[RepoStatus]) {
+    let headers = [
+        String::new(),
+        tr!("repos-column-path"),
+        tr!("repos-column-current"),
+        tr!("repos-column-expected"),
+        tr!("repos-column-remote"),
+    ];
+
+    let rows: Vec<[String; 5]> = statuses
+        .iter()
+        .map(|status| {
+            let marker = if status.expected.is_none() {
+                "?"
+            } else if status.is_drifted(config) {
+                sym("✗")
+            } else {
+                sym("✓")
+            };
+            let current = status
+                .effective_identity(config)
+                .map(|i| i.id.clone())
+                .or_else(|| status.effective_email.clone())
+                .unwrap_or_else(|| tr!("repos-not-set"));
+            let expected = status
+                .expected
+                .as_ref()
+                .map(|e| format!("{} ({})", e.identity, e.source.kind()))
+                .unwrap_or_else(|| "-".to_string());
+            [
+                marker.to_string(),
+                contract_home(&status.path.display().to_string()),
+                current,
+                expected,
+                status.remote.clone().unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = headers
+        .iter()
+        .map(|h| console::measure_text_width(h))
+        .collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(console::measure_text_width(cell));
+        }
+    }
+
+    let header_line = headers
+        .iter()
+        .zip(&widths)
+        .map(|(header, &width)| pad(header, width))
+        .collect::<Vec<_>>()
+        .join("  ");
+    println!("  {}", header_line.trim_end().bold());
+
+    for row in &rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, &width))| {
+                let padded = pad(cell, width);
+                let marker = row[0].as_str();
+                match i {
+                    0 if marker == sym("✓") => padded.green().to_string(),
+                    0 | 2 if marker == sym("✗") => padded.red().to_string(),
+                    0 | 4 => padded.dimmed().to_string(),
+                    3 => padded.cyan().to_string(),
+                    _ => padded,
+                }
+            })
+            .collect();
+        println!("  {}", cells.join("  ").trim_end());
+    }
+}
+
+fn repo_json(config: &Config, status: &RepoStatus) -> Value {
+    let effective = status.effective_identity(config).map(|i| i.id.clone());
+    let expected = match &status.expected {
+        Some(resolution) => json!({
+            "identity": resolution.identity,
+            "source": resolution.source.kind(),
+            "detail": resolution.source.to_string(),
+        }),
+        None => Value::Null,
+    };
+
+    json!({
+        "path": status.path,
+        "remote": status.remote,
+        "effective": {
+            "name": status.effective_name,
+            "email": status.effective_email,
+            "identity": effective,
+        },
+        "expected": expected,
+        "drifted": status.is_drifted(config),
+    })
+}
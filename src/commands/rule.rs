@@ -2,10 +2,14 @@ use anyhow::Result;
 use colored::Colorize;
 use std::path::PathBuf;
 
+use crate::ascii::sym;
 use crate::cli::{RuleAction, RuleType as CliRuleType};
+use crate::commands::sync_gitconfig;
 use crate::config::Config;
+use crate::exit::Failure;
 use crate::git::GitConfigManager;
-use crate::rules::{MatchContext, Rule, RuleEngine, RuleType};
+use crate::i18n::tr;
+use crate::rules::{MatchContext, MatchStrategy, Rule, RuleEngine, RuleType};
 
 /// Execute rule command
 pub fn execute(action: RuleAction) -> Result<()> {
@@ -13,156 +17,625 @@ pub fn execute(action: RuleAction) -> Result<()> {
         RuleAction::Add {
             rule_type,
             pattern,
+            host,
+            org,
+            repo,
+            when,
             identity,
             priority,
-        } => add_rule(rule_type, pattern, identity, priority),
+        } => add_rule(
+            rule_type,
+            pattern,
+            [host, org, repo],
+            &when,
+            identity,
+            priority,
+        ),
         RuleAction::List => list_rules(),
-        RuleAction::Remove { index } => remove_rule(index),
-        RuleAction::Test { path, remote } => test_rule(path, remote),
+        RuleAction::Edit {
+            id,
+            rule_type,
+            pattern,
+            host,
+            org,
+            repo,
+            when,
+            identity,
+            priority,
+            description,
+        } => edit_rule(
+            &id,
+            RuleChanges {
+                rule_type,
+                pattern,
+                host,
+                org,
+                repo,
+                when,
+                identity,
+                priority,
+                description,
+            },
+        ),
+        RuleAction::Enable { id } => set_enabled(&id, true),
+        RuleAction::Disable { id } => set_enabled(&id, false),
+        RuleAction::Move { id, priority } => move_rule(&id, priority),
+        RuleAction::Remove { id } => remove_rule(&id),
+        RuleAction::Test {
+            path,
+            remote,
+            branch,
+        } => test_rule(path, remote, branch),
     }
 }
 
 /// Add rule
+///
+/// Remote rules can match the parsed URL by `[host, org, repo]` instead of, or
+/// in addition to, a pattern; all-of / any-of rules combine `when` conditions.
 fn add_rule(
     rule_type: CliRuleType,
-    pattern: String,
+    pattern: Option<String>,
+    [host, org, repo]: [Option<String>; 3],
+    when: &[String],
     identity: String,
     priority: u32,
 ) -> Result<()> {
-    let mut config = Config::load()?;
-
-    // Verify identity exists
-    if config.find_identity(&identity).is_none() {
-        anyhow::bail!("Identity '{identity}' does not exist");
+    let structured = host.is_some() || org.is_some() || repo.is_some();
+    if structured && rule_type != CliRuleType::Remote {
+        return Err(Failure::Usage.error(tr!("rule-parts-remote-only")));
+    }
+    let composite = matches!(rule_type, CliRuleType::AllOf | CliRuleType::AnyOf);
+    if composite == when.is_empty() {
+        return Err(Failure::Usage.error(tr!("rule-when-composite-only")));
     }
+    let conditions = when
+        .iter()
+        .map(|spec| parse_condition(spec))
+        .collect::<Result<Vec<_>>>()?;
 
     // Create rule
+    let pattern = pattern.unwrap_or_default();
     let rule = match rule_type {
-        CliRuleType::Path => Rule::path(pattern.clone(), identity.clone()),
-        CliRuleType::Remote => Rule::remote(pattern.clone(), identity.clone()),
+        CliRuleType::Path => Rule::path(pattern, identity.clone()),
+        CliRuleType::Remote => {
+            Rule::remote(pattern, identity.clone()).with_remote_parts(host, org, repo)
+        }
+        CliRuleType::Branch => Rule::branch(pattern, identity.clone()),
+        CliRuleType::AllOf => Rule::all_of(conditions, identity.clone()),
+        CliRuleType::AnyOf => Rule::any_of(conditions, identity.clone()),
     }
     .with_priority(priority);
+    let pattern = rule.pattern().into_owned();
 
-    config.add_rule(rule);
-    config.save()?;
+    let config = Config::update(|config| {
+        // Verify identity exists
+        if config.find_identity(&identity).is_none() {
+            anyhow::bail!(tr!("rule-identity-unknown", id = identity));
+        }
+        config.add_rule(rule);
+        Ok(config.clone())
+    })?;
 
     let type_name = match rule_type {
-        CliRuleType::Path => "Path",
-        CliRuleType::Remote => "Remote URL",
+        CliRuleType::Path => tr!("rule-type-path"),
+        CliRuleType::Remote => tr!("rule-type-remote"),
+        CliRuleType::Branch => tr!("rule-type-branch"),
+        CliRuleType::AllOf => tr!("rule-type-all-of"),
+        CliRuleType::AnyOf => tr!("rule-type-any-of"),
     };
 
     println!(
-        "{} Added {} rule: {} -> {}",
-        "✓".green(),
-        type_name,
-        pattern.cyan(),
-        format!("[{identity}]").green()
+        "{} {}",
+        sym("✓").green(),
+        tr!(
+            "rule-added",
+            kind = type_name,
+            pattern = pattern.cyan(),
+            identity = format!("[{identity}]").green()
+        )
     );
+    sync_gitconfig::resync(&config);
 
     Ok(())
 }
 
+/// One `--when KIND=PATTERN` condition of an all-of / any-of rule
+fn parse_condition(spec: &str) -> Result<RuleType> {
+    let invalid = || Failure::Usage.error(tr!("rule-condition-invalid", spec = spec));
+    let (kind, pattern) = spec.split_once('=').ok_or_else(invalid)?;
+    let pattern = pattern.to_string();
+    let remote = |host, org, repo| RuleType::Remote {
+        pattern: String::new(),
+        host,
+        org,
+        repo,
+    };
+    Ok(match kind.trim() {
+        "path" => RuleType::Path { pattern },
+        "branch" => RuleType::Branch { pattern },
+        "remote" => RuleType::Remote {
+            pattern,
+            host: None,
+            org: None,
+            repo: None,
+        },
+        "host" => remote(Some(pattern), None, None),
+        "org" => remote(None, Some(pattern), None),
+        "repo" => remote(None, None, Some(pattern)),
+        _ => return Err(invalid()),
+    })
+}
+
 /// List all rules
 fn list_rules() -> Result<()> {
     let config = Config::load()?;
 
     if config.rules.is_empty() {
-        println!("{} No rules configured", "!".yellow());
+        println!("{} {}", "!".yellow(), tr!("rule-none"));
         println!();
-        println!("Use {} to add rules", "gid rule add".cyan());
+        println!("{}", tr!("rule-add-hint", command = "gid rule add".cyan()));
         println!();
-        println!("Example:");
+        println!("{}", tr!("rule-examples"));
+        println!(
+            "  {} {}",
+            "gid rule add -t path -p '~/work/**' -i work".dimmed(),
+            tr!("rule-example-path")
+        );
         println!(
-            "  {} Add path rule",
-            "gid rule add -t path -p '~/work/**' -i work".dimmed()
+            "  {} {}",
+            "gid rule add -t remote -p 'github.com/company/*' -i work".dimmed(),
+            tr!("rule-example-remote")
         );
         println!(
-            "  {} Add remote rule",
-            "gid rule add -t remote -p 'github.com/company/*' -i work".dimmed()
+            "  {} {}",
+            "gid rule add -t branch -p 'release/*' -i work".dimmed(),
+            tr!("rule-example-branch")
         );
         return Ok(());
     }
 
-    println!("{}", "Configured Rules:".bold());
+    println!("{}", tr!("rule-list-header").bold());
     println!();
 
-    for (i, rule) in config.rules.iter().enumerate() {
+    for rule in &config.rules {
         let type_badge = match &rule.rule_type {
             RuleType::Path { .. } => "[Path]".cyan(),
             RuleType::Remote { .. } => "[Remote]".magenta(),
+            RuleType::Branch { .. } => "[Branch]".blue(),
+            RuleType::AllOf { .. } => "[All of]".yellow(),
+            RuleType::AnyOf { .. } => "[Any of]".yellow(),
         };
 
         let status = if rule.enabled {
-            "✓".green()
+            sym("✓").green()
         } else {
-            "○".dimmed()
+            sym("○").dimmed()
         };
 
+        let source = rule
+            .source
+            .as_ref()
+            .map(|s| {
+                format!(" {}", tr!("rule-from-ruleset", name = s))
+                    .dimmed()
+                    .to_string()
+            })
+            .unwrap_or_default();
+
         println!(
-            "  {} {} {} {} -> {}",
-            format!("{i}.").dimmed(),
+            "  {} {} {} {} -> {}{}",
+            rule.id.dimmed(),
             status,
             type_badge,
             rule.pattern(),
-            format!("[{}]", rule.identity).green()
+            format!("[{}]", rule.identity).green(),
+            source
         );
 
         if let Some(ref desc) = rule.description {
             println!("       {}", desc.dimmed());
         }
 
-        println!("       Priority: {}", rule.priority.to_string().dimmed());
+        println!(
+            "       {}",
+            tr!(
+                "rule-priority",
+                priority = rule.priority.to_string().dimmed()
+            )
+        );
     }
 
     println!();
-    println!("Total {} rules", config.rules.len());
+    println!("{}", tr!("rule-total", count = config.rules.len()));
 
     Ok(())
 }
 
-/// Remove rule
-fn remove_rule(index: usize) -> Result<()> {
-    let mut config = Config::load()?;
-
-    if index >= config.rules.len() {
-        anyhow::bail!(
-            "Rule index {} out of range (total {} rules)",
-            index,
-            config.rules.len()
+/// Changes requested by `gid rule edit`; none at all means edit interactively
+pub struct RuleChanges {
+    pub rule_type: Option<CliRuleType>,
+    pub pattern: Option<String>,
+    pub host: Option<String>,
+    pub org: Option<String>,
+    pub repo: Option<String>,
+    pub when: Vec<String>,
+    pub identity: Option<String>,
+    pub priority: Option<u32>,
+    pub description: Option<String>,
+}
+
+impl RuleChanges {
+    fn is_empty(&self) -> bool {
+        self.rule_type.is_none()
+            && self.pattern.is_none()
+            && self.host.is_none()
+            && self.org.is_none()
+            && self.repo.is_none()
+            && self.when.is_empty()
+            && self.identity.is_none()
+            && self.priority.is_none()
+            && self.description.is_none()
+    }
+}
+
+/// Edit rule in place; the id stays the same
+fn edit_rule(id: &str, changes: RuleChanges) -> Result<()> {
+    let config = Config::load()?;
+
+    let index = local_rule_index(&config, id)?;
+    let mut rule = config.rules[index].clone();
+
+    let changes = if changes.is_empty() {
+        prompt_changes(&config, &rule)?
+    } else {
+        changes
+    };
+    apply_changes(&mut rule, changes)?;
+
+    // The prompts ran without the lock, so look the rule up again
+    let config = Config::update(|config| {
+        let index = local_rule_index(config, id)?;
+
+        // Verify identity exists
+        if config.find_identity(&rule.identity).is_none() {
+            anyhow::bail!(tr!("rule-identity-unknown", id = rule.identity));
+        }
+
+        println!(
+            "{} {}",
+            sym("✓").green(),
+            tr!("rule-updated", id = rule.id.dimmed(), rule = rule)
         );
+        config.rules[index] = rule;
+        config.sort_rules();
+        Ok(config.clone())
+    })?;
+    sync_gitconfig::resync(&config);
+
+    Ok(())
+}
+
+/// Ask for each field, defaulting to the current value
+///
+/// all-of / any-of conditions are only changed with `--when`.
+fn prompt_changes(config: &Config, rule: &Rule) -> Result<RuleChanges> {
+    println!("{} {}", tr!("rule-editing").bold(), rule.id.cyan());
+    println!("  {rule}");
+    println!();
+
+    let current = cli_rule_type(&rule.rule_type);
+    let composite = matches!(current, CliRuleType::AllOf | CliRuleType::AnyOf);
+    let (types, labels): (&[CliRuleType], &[&str]) = if composite {
+        (
+            &[CliRuleType::AllOf, CliRuleType::AnyOf],
+            &["all-of", "any-of"],
+        )
+    } else {
+        (
+            &[CliRuleType::Path, CliRuleType::Remote, CliRuleType::Branch],
+            &["path", "remote", "branch"],
+        )
+    };
+    let selected = dialoguer::Select::new()
+        .with_prompt(tr!("rule-prompt-type"))
+        .items(labels)
+        .default(types.iter().position(|t| *t == current).unwrap_or(0))
+        .interact()?;
+    let rule_type = types[selected];
+
+    let pattern = match &rule.rule_type {
+        RuleType::AllOf { .. } | RuleType::AnyOf { .. } => None,
+        RuleType::Remote {
+            pattern,
+            host,
+            org,
+            repo,
+        } => Some(
+            dialoguer::Input::<String>::new()
+                .with_prompt(tr!("rule-prompt-pattern"))
+                .with_initial_text(pattern)
+                // --host / --org / --repo can stand in for the pattern
+                .allow_empty(host.is_some() || org.is_some() || repo.is_some())
+                .interact_text()?,
+        ),
+        RuleType::Path { pattern } | RuleType::Branch { pattern } => Some(
+            dialoguer::Input::<String>::new()
+                .with_prompt(tr!("rule-prompt-pattern"))
+                .with_initial_text(pattern)
+                .interact_text()?,
+        ),
+    };
+
+    let ids: Vec<&str> = config.identities.iter().map(|i| i.id.as_str()).collect();
+    let identity = dialoguer::Select::new()
+        .with_prompt(tr!("rule-prompt-identity"))
+        .items(&ids)
+        .default(ids.iter().position(|id| *id == rule.identity).unwrap_or(0))
+        .interact()?;
+
+    let priority = dialoguer::Input::<u32>::new()
+        .with_prompt(tr!("rule-prompt-priority"))
+        .default(rule.priority)
+        .interact_text()?;
+
+    let description = dialoguer::Input::<String>::new()
+        .with_prompt(tr!("rule-prompt-description"))
+        .with_initial_text(rule.description.as_deref().unwrap_or_default())
+        .allow_empty(true)
+        .interact_text()?;
+
+    Ok(RuleChanges {
+        rule_type: Some(rule_type),
+        pattern,
+        host: None,
+        org: None,
+        repo: None,
+        when: Vec::new(),
+        identity: ids.get(identity).map(|id| id.to_string()),
+        priority: Some(priority),
+        description: Some(description),
+    })
+}
+
+/// Apply changes to a rule; fields that are not given keep their value
+fn apply_changes(rule: &mut Rule, changes: RuleChanges) -> Result<()> {
+    let RuleChanges {
+        rule_type,
+        pattern,
+        host,
+        org,
+        repo,
+        when,
+        identity,
+        priority,
+        description,
+    } = changes;
+
+    let kind = rule_type.unwrap_or_else(|| cli_rule_type(&rule.rule_type));
+    let structured = host.is_some() || org.is_some() || repo.is_some();
+    if structured && kind != CliRuleType::Remote {
+        return Err(Failure::Usage.error(tr!("rule-parts-remote-only")));
+    }
+    let composite = matches!(kind, CliRuleType::AllOf | CliRuleType::AnyOf);
+    if composite && pattern.is_some() || !composite && !when.is_empty() {
+        return Err(Failure::Usage.error(tr!("rule-when-composite-only")));
+    }
+
+    // Whatever is not given carries over from the current rule
+    let (old_pattern, old_parts, old_conditions) = match &rule.rule_type {
+        RuleType::Path { pattern } | RuleType::Branch { pattern } => {
+            (pattern.clone(), [None, None, None], Vec::new())
+        }
+        RuleType::Remote {
+            pattern,
+            host,
+            org,
+            repo,
+        } => (
+            pattern.clone(),
+            [host.clone(), org.clone(), repo.clone()],
+            Vec::new(),
+        ),
+        RuleType::AllOf { conditions } | RuleType::AnyOf { conditions } => {
+            (String::new(), [None, None, None], conditions.clone())
+        }
+    };
+    let pattern = pattern.unwrap_or(old_pattern);
+    let conditions = if when.is_empty() {
+        old_conditions
+    } else {
+        when.iter()
+            .map(|spec| parse_condition(spec))
+            .collect::<Result<Vec<_>>>()?
+    };
+    // An empty value clears a URL part
+    let part = |new: Option<String>, old: Option<String>| match new {
+        Some(value) if value.is_empty() => None,
+        Some(value) => Some(value),
+        None => old,
+    };
+    let [old_host, old_org, old_repo] = old_parts;
+    let (host, org, repo) = (
+        part(host, old_host),
+        part(org, old_org),
+        part(repo, old_repo),
+    );
+
+    rule.rule_type = match kind {
+        CliRuleType::Path => RuleType::Path { pattern },
+        CliRuleType::Remote => RuleType::Remote {
+            pattern,
+            host,
+            org,
+            repo,
+        },
+        CliRuleType::Branch => RuleType::Branch { pattern },
+        CliRuleType::AllOf => RuleType::AllOf { conditions },
+        CliRuleType::AnyOf => RuleType::AnyOf { conditions },
+    };
+
+    let missing = match &rule.rule_type {
+        RuleType::Path { pattern } | RuleType::Branch { pattern } => pattern.is_empty(),
+        RuleType::Remote {
+            pattern,
+            host,
+            org,
+            repo,
+        } => pattern.is_empty() && host.is_none() && org.is_none() && repo.is_none(),
+        RuleType::AllOf { conditions } | RuleType::AnyOf { conditions } => conditions.is_empty(),
+    };
+    if missing {
+        let needs = match kind {
+            CliRuleType::Remote => tr!("rule-needs-remote"),
+            CliRuleType::AllOf | CliRuleType::AnyOf => tr!("rule-needs-conditions"),
+            _ => tr!("rule-needs-pattern"),
+        };
+        return Err(Failure::Usage.error(tr!(
+            "rule-incomplete",
+            kind = rule.type_name(),
+            needs = needs
+        )));
+    }
+
+    if let Some(identity) = identity {
+        rule.identity = identity;
     }
+    if let Some(priority) = priority {
+        rule.priority = priority;
+    }
+    if let Some(description) = description {
+        rule.description = (!description.is_empty()).then_some(description);
+    }
+
+    Ok(())
+}
+
+/// The `--type` value for a rule type
+fn cli_rule_type(rule_type: &RuleType) -> CliRuleType {
+    match rule_type {
+        RuleType::Path { .. } => CliRuleType::Path,
+        RuleType::Remote { .. } => CliRuleType::Remote,
+        RuleType::Branch { .. } => CliRuleType::Branch,
+        RuleType::AllOf { .. } => CliRuleType::AllOf,
+        RuleType::AnyOf { .. } => CliRuleType::AnyOf,
+    }
+}
 
+/// Enable or disable rule
+fn set_enabled(id: &str, enabled: bool) -> Result<()> {
+    let config = Config::load()?;
+    if config.rules[local_rule_index(&config, id)?].enabled == enabled {
+        let message = if enabled {
+            tr!("rule-already-enabled", id = id)
+        } else {
+            tr!("rule-already-disabled", id = id)
+        };
+        println!("{} {}", "!".yellow(), message);
+        return Ok(());
+    }
+
+    let config = Config::update(|config| {
+        let index = local_rule_index(config, id)?;
+        let rule = &mut config.rules[index];
+        rule.enabled = enabled;
+        let message = if enabled {
+            tr!("rule-enabled", id = id, rule = rule)
+        } else {
+            tr!("rule-disabled", id = id, rule = rule)
+        };
+        println!("{} {}", sym("✓").green(), message);
+        Ok(config.clone())
+    })?;
+    sync_gitconfig::resync(&config);
+
+    Ok(())
+}
+
+/// Change rule priority
+fn move_rule(id: &str, priority: u32) -> Result<()> {
+    let (config, old) = Config::update(|config| {
+        let index = local_rule_index(config, id)?;
+        let old = std::mem::replace(&mut config.rules[index].priority, priority);
+        config.sort_rules();
+        Ok((config.clone(), old))
+    })?;
+
+    let position = config.rule_index(id)?;
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!(
+            "rule-moved",
+            id = id,
+            old = old,
+            arrow = sym("→"),
+            new = priority,
+            position = position + 1,
+            total = config.rules.len()
+        )
+    );
+    sync_gitconfig::resync(&config);
+
+    Ok(())
+}
+
+/// Position of a rule the user may change
+fn local_rule_index(config: &Config, id: &str) -> Result<usize> {
+    let index = config.rule_index(id)?;
+
+    // 订阅的规则只读，随规则集更新
+    if let Some(ref source) = config.rules[index].source {
+        anyhow::bail!(tr!("rule-read-only", id = id, source = source));
+    }
+
+    Ok(index)
+}
+
+/// Remove rule
+fn remove_rule(id: &str) -> Result<()> {
+    let config = Config::load()?;
+
+    let index = local_rule_index(&config, id)?;
     let rule = &config.rules[index];
+
     println!(
-        "About to remove rule: {} -> {}",
-        rule.pattern().yellow(),
-        rule.identity
+        "{}",
+        tr!(
+            "rule-remove-about",
+            pattern = rule.pattern().yellow(),
+            identity = rule.identity
+        )
     );
 
     let confirm = dialoguer::Confirm::new()
-        .with_prompt("Are you sure you want to remove?")
+        .with_prompt(tr!("confirm-remove"))
         .default(false)
         .interact()?;
 
     if !confirm {
-        println!("Operation cancelled");
+        println!("{}", tr!("operation-cancelled"));
         return Ok(());
     }
 
-    config.remove_rule(index)?;
-    config.save()?;
+    let config = Config::update(|config| {
+        let index = local_rule_index(config, id)?;
+        config.remove_rule(index)?;
+        Ok(config.clone())
+    })?;
 
-    println!("{} Rule removed", "✓".green());
+    println!("{} {}", sym("✓").green(), tr!("rule-removed"));
+    sync_gitconfig::resync(&config);
 
     Ok(())
 }
 
 /// Test rule matching
-fn test_rule(path: Option<PathBuf>, remote: Option<String>) -> Result<()> {
+fn test_rule(path: Option<PathBuf>, remote: Option<String>, branch: Option<String>) -> Result<()> {
     let config = Config::load()?;
 
     if config.rules.is_empty() {
-        println!("{} No rules configured", "!".yellow());
+        println!("{} {}", "!".yellow(), tr!("rule-none"));
         return Ok(());
     }
 
@@ -174,43 +647,61 @@ fn test_rule(path: Option<PathBuf>, remote: Option<String>) -> Result<()> {
     context = context.with_path(test_path.clone());
 
     // Remote URL
-    let test_remote = if let Some(remote) = remote {
-        Some(remote)
-    } else {
-        let git = GitConfigManager::new()?;
-        git.get_origin_url()
-    };
+    let git = GitConfigManager::new()?;
+    let test_remote = remote.or_else(|| git.get_origin_url());
 
     if let Some(ref remote) = test_remote {
         context = context.with_remote(remote.clone());
     }
 
-    println!("{}", "Test Rule Matching:".bold());
+    // Branch
+    let test_branch = branch.or_else(|| git.current_branch());
+    if let Some(ref branch) = test_branch {
+        context = context.with_branch(branch.clone());
+    }
+
+    println!("{}", tr!("rule-test-header").bold());
     println!();
-    println!("  Path: {}", test_path.display().to_string().cyan());
+    println!(
+        "  {}",
+        tr!(
+            "rule-test-path",
+            path = test_path.display().to_string().cyan()
+        )
+    );
     if let Some(ref remote) = test_remote {
-        println!("  Remote: {}", remote.cyan());
+        println!("  {}", tr!("rule-test-remote", remote = remote.cyan()));
+    }
+    if let Some(ref branch) = test_branch {
+        println!("  {}", tr!("rule-test-branch", branch = branch.cyan()));
     }
     println!();
 
-    let engine = RuleEngine::new(&config.rules);
+    let engine = RuleEngine::from_config(&config);
 
     // Show all matched rules
     let matched_rules = engine.match_all(&context);
 
     if matched_rules.is_empty() {
-        println!("{} No matching rules", "!".yellow());
+        println!("{} {}", "!".yellow(), tr!("rule-test-no-match"));
     } else {
-        println!("Matched Rules:");
+        match config.settings.match_strategy {
+            MatchStrategy::Priority => println!("{}", tr!("rule-test-matched")),
+            MatchStrategy::MostSpecific => println!("{}", tr!("rule-test-matched-specific")),
+        }
         for (i, rule) in matched_rules.iter().enumerate() {
-            let marker = if i == 0 { "→".green() } else { " ".into() };
+            let marker = if i == 0 {
+                sym("→").green()
+            } else {
+                " ".into()
+            };
             println!(
-                "  {} [{}] {} -> {} (Priority: {})",
+                "  {} [{}] {} -> {} ({})",
                 marker,
                 rule.type_name(),
                 rule.pattern(),
                 format!("[{}]", rule.identity).green(),
-                rule.priority
+                tr!("rule-priority", priority = rule.priority)
             );
         }
 
@@ -218,11 +709,14 @@ fn test_rule(path: Option<PathBuf>, remote: Option<String>) -> Result<()> {
         if let Some(first) = matched_rules.first() {
             if let Some(identity) = config.find_identity(&first.identity) {
                 println!(
-                    "{} Will use identity: {} {} <{}>",
-                    "✓".green(),
-                    format!("[{}]", identity.id).green().bold(),
-                    identity.name,
-                    identity.email.cyan()
+                    "{} {}",
+                    sym("✓").green(),
+                    tr!(
+                        "rule-test-identity",
+                        identity = format!("[{}]", identity.id).green().bold(),
+                        name = identity.name,
+                        email = identity.email.cyan()
+                    )
                 );
             }
         }
@@ -0,0 +1,311 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::ascii::sym;
+use crate::cli::{ExportFormat, RulesetAction};
+use crate::commands::export::format_from_path;
+use crate::commands::sync_gitconfig;
+use crate::config::{Config, RulesetSubscription};
+use crate::exit::Failure;
+use crate::i18n::tr;
+use crate::rules::Rule;
+
+/// Execute ruleset command
+pub fn execute(action: RulesetAction) -> Result<()> {
+    match action {
+        RulesetAction::Subscribe { url, name } => subscribe(&url, &name),
+        RulesetAction::Update { name } => update(name.as_deref()),
+        RulesetAction::Unsubscribe { name } => unsubscribe(&name),
+        RulesetAction::List => list(),
+    }
+}
+
+/// Subscribe to a team ruleset and import its rules
+fn subscribe(url: &str, name: &str) -> Result<()> {
+    let config = Config::load()?;
+
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+    {
+        anyhow::bail!(tr!("ruleset-name-invalid"));
+    }
+    if config.rulesets.iter().any(|r| r.name == name) {
+        anyhow::bail!(tr!("ruleset-exists", name = name));
+    }
+
+    let rules = fetch_rules(url, name)?;
+    let count = rules.len();
+
+    warn_unknown_identities(&config, &rules);
+
+    // Fetched without holding the config lock
+    let config = Config::update(|config| {
+        if config.rulesets.iter().any(|r| r.name == name) {
+            anyhow::bail!(tr!("ruleset-exists", name = name));
+        }
+        config.rulesets.push(RulesetSubscription {
+            name: name.to_string(),
+            url: url.to_string(),
+            updated_at: Some(chrono::Local::now().to_rfc3339()),
+        });
+        for rule in rules {
+            config.add_rule(rule);
+        }
+        Ok(config.clone())
+    })?;
+
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!("ruleset-subscribed", name = name.cyan(), count = count)
+    );
+    println!(
+        "  {}",
+        tr!(
+            "ruleset-update-hint",
+            command = format!("gid ruleset update {name}").cyan()
+        )
+    );
+    sync_gitconfig::resync(&config);
+
+    Ok(())
+}
+
+/// Re-fetch subscribed rulesets and replace their rules
+fn update(name: Option<&str>) -> Result<()> {
+    let config = Config::load()?;
+
+    if let Some(name) = name {
+        if !config.rulesets.iter().any(|r| r.name == name) {
+            anyhow::bail!(tr!("ruleset-not-subscribed", name = name));
+        }
+    }
+    if config.rulesets.is_empty() {
+        println!("{} {}", "!".yellow(), tr!("ruleset-none"));
+        return Ok(());
+    }
+
+    let subscriptions: Vec<RulesetSubscription> = config
+        .rulesets
+        .iter()
+        .filter(|r| name.is_none_or(|n| r.name == n))
+        .cloned()
+        .collect();
+
+    let mut failed = 0;
+    let mut fetched: Vec<(String, Vec<Rule>)> = Vec::new();
+    for subscription in subscriptions {
+        // Keep the rules from the last download
+        if crate::offline::is_offline() && is_remote(&subscription.url) {
+            println!(
+                "{} {}: {}",
+                "-".dimmed(),
+                subscription.name,
+                tr!("skipped-offline").dimmed()
+            );
+            continue;
+        }
+        let rules = match fetch_rules(&subscription.url, &subscription.name) {
+            Ok(rules) => rules,
+            Err(e) => {
+                println!("{} {}: {:#}", sym("✗").red(), subscription.name, e);
+                failed += 1;
+                continue;
+            }
+        };
+
+        let old: Vec<Rule> = config
+            .rules
+            .iter()
+            .filter(|r| r.source.as_deref() == Some(subscription.name.as_str()))
+            .cloned()
+            .collect();
+        let added = rules.iter().filter(|r| !contains(&old, r)).count();
+        let removed = old.iter().filter(|r| !contains(&rules, r)).count();
+
+        warn_unknown_identities(&config, &rules);
+        println!(
+            "{} {}",
+            sym("✓").green(),
+            tr!(
+                "ruleset-updated",
+                name = subscription.name.cyan(),
+                count = rules.len(),
+                added = format!("+{added}").green(),
+                removed = format!("-{removed}").red()
+            )
+        );
+        fetched.push((subscription.name, rules));
+    }
+
+    // Downloads happen first so the config lock is only held for the replacement
+    let config = Config::update(|config| {
+        for (name, rules) in fetched {
+            // Unsubscribed by another process in the meantime
+            let Some(entry) = config.rulesets.iter_mut().find(|r| r.name == name) else {
+                continue;
+            };
+            entry.updated_at = Some(chrono::Local::now().to_rfc3339());
+            config
+                .rules
+                .retain(|r| r.source.as_deref() != Some(name.as_str()));
+            for rule in rules {
+                config.add_rule(rule);
+            }
+        }
+        Ok(config.clone())
+    })?;
+    sync_gitconfig::resync(&config);
+
+    if failed > 0 {
+        anyhow::bail!(tr!("ruleset-update-failed", count = failed));
+    }
+
+    Ok(())
+}
+
+/// Remove a subscription and its rules
+fn unsubscribe(name: &str) -> Result<()> {
+    let (config, removed) = Config::update(|config| {
+        let Some(index) = config.rulesets.iter().position(|r| r.name == name) else {
+            anyhow::bail!(tr!("ruleset-not-subscribed", name = name));
+        };
+        config.rulesets.remove(index);
+
+        let before = config.rules.len();
+        config.rules.retain(|r| r.source.as_deref() != Some(name));
+        Ok((config.clone(), before - config.rules.len()))
+    })?;
+
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!("ruleset-unsubscribed", name = name.cyan(), count = removed)
+    );
+    sync_gitconfig::resync(&config);
+
+    Ok(())
+}
+
+/// List subscribed rulesets
+fn list() -> Result<()> {
+    let config = Config::load()?;
+
+    if config.rulesets.is_empty() {
+        println!("{} {}", "!".yellow(), tr!("ruleset-none"));
+        println!();
+        println!(
+            "{}",
+            tr!(
+                "ruleset-subscribe-hint",
+                command = "gid ruleset subscribe <url> --name <name>".cyan()
+            )
+        );
+        return Ok(());
+    }
+
+    println!("{}", tr!("ruleset-list-header").bold());
+    println!();
+
+    for subscription in &config.rulesets {
+        let count = config
+            .rules
+            .iter()
+            .filter(|r| r.source.as_deref() == Some(subscription.name.as_str()))
+            .count();
+        println!(
+            "  {} {}",
+            subscription.name.green().bold(),
+            tr!("ruleset-rule-count", count = count)
+        );
+        println!("    {}", subscription.url.dimmed());
+        if let Some(ref updated_at) = subscription.updated_at {
+            println!(
+                "    {}",
+                tr!("ruleset-updated-at", time = updated_at).dimmed()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn is_remote(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+/// Download a ruleset and tag its rules with the subscription name
+fn fetch_rules(url: &str, name: &str) -> Result<Vec<Rule>> {
+    let content = if is_remote(url) {
+        crate::offline::ensure_online(&tr!("ruleset-downloading", url = url))?;
+        let output = Command::new("curl")
+            .args(["--fail", "--silent", "--show-error", "--location", url])
+            .output()
+            .with_context(|| tr!("curl-failed"))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(Failure::Network.error(tr!(
+                "ruleset-download-failed",
+                url = url,
+                error = stderr.trim()
+            )));
+        }
+        String::from_utf8(output.stdout).with_context(|| tr!("ruleset-not-utf8"))?
+    } else {
+        let path = url.strip_prefix("file://").unwrap_or(url);
+        fs::read_to_string(path).with_context(|| tr!("ruleset-read-failed", path = path))?
+    };
+
+    // Ignore query strings when detecting the format from the URL
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let ruleset: Config = match format_from_path(Path::new(path)) {
+        ExportFormat::Json => serde_json::from_str(&content)?,
+        ExportFormat::Yaml => serde_yaml::from_str(&content)?,
+        ExportFormat::Toml | ExportFormat::Script => toml::from_str(&content)?,
+    };
+
+    if ruleset.rules.is_empty() {
+        anyhow::bail!(tr!("ruleset-empty", url = url));
+    }
+
+    Ok(ruleset
+        .rules
+        .into_iter()
+        .map(|mut rule| {
+            rule.source = Some(name.to_string());
+            rule
+        })
+        .collect())
+}
+
+fn contains(rules: &[Rule], rule: &Rule) -> bool {
+    rules.iter().any(|r| {
+        r.rule_type == rule.rule_type && r.identity == rule.identity && r.priority == rule.priority
+    })
+}
+
+/// Subscribed rules may reference identities not configured on this machine
+fn warn_unknown_identities(config: &Config, rules: &[Rule]) {
+    let mut missing: Vec<&str> = rules
+        .iter()
+        .map(|r| r.identity.as_str())
+        .filter(|id| config.find_identity(id).is_none())
+        .collect();
+    missing.sort();
+    missing.dedup();
+
+    if !missing.is_empty() {
+        println!(
+            "{} {}",
+            sym("⚠").yellow(),
+            tr!(
+                "ruleset-unknown-identities",
+                ids = missing.join(", ").yellow()
+            )
+        );
+    }
+}
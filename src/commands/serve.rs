@@ -0,0 +1,269 @@
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use crate::atomic;
+use crate::commands::current::status_json;
+use crate::commands::switch::apply_to_repo;
+use crate::config::{journal, Config};
+use crate::git::GitConfigManager;
+use crate::i18n::tr;
+use crate::rules::resolve::resolve_identity;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+
+/// A failed request: JSON-RPC error code and message
+struct RpcError(i64, String);
+
+impl From<anyhow::Error> for RpcError {
+    fn from(e: anyhow::Error) -> Self {
+        RpcError(SERVER_ERROR, format!("{e:#}"))
+    }
+}
+
+/// Serve JSON-RPC 2.0 requests, one JSON object per line
+///
+/// Listens on a Unix socket (default `<data dir>/gid.sock`), or talks over
+/// stdin/stdout with `--stdio` for editors that spawn gid as a child process.
+pub fn execute(socket: Option<PathBuf>, stdio: bool) -> Result<()> {
+    if stdio {
+        return handle_connection(io::stdin().lock(), io::stdout().lock());
+    }
+
+    let socket = match socket {
+        Some(path) => path,
+        None => Config::data_dir()?.join("gid.sock"),
+    };
+    listen(&socket)
+}
+
+#[cfg(unix)]
+fn listen(socket: &Path) -> Result<()> {
+    use rustix::fs::Mode;
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    if socket.exists() {
+        if UnixStream::connect(socket).is_ok() {
+            anyhow::bail!(tr!("serve-already-listening", path = socket.display()));
+        }
+        // Left behind by a server that was killed
+        std::fs::remove_file(socket)
+            .with_context(|| tr!("serve-stale-socket", path = socket.display()))?;
+    }
+    if let Some(parent) = socket.parent() {
+        atomic::create_private_dir(parent)
+            .with_context(|| tr!("serve-listen-failed", path = socket.display()))?;
+    }
+
+    // Owner-only from the moment the socket appears, not only after the chmod
+    let umask = rustix::process::umask(Mode::RWXG | Mode::RWXO);
+    let listener = UnixListener::bind(socket);
+    rustix::process::umask(umask);
+    let listener = listener.with_context(|| tr!("serve-listen-failed", path = socket.display()))?;
+    std::fs::set_permissions(socket, std::fs::Permissions::from_mode(0o600))?;
+    eprintln!("{}", tr!("serve-listening", path = socket.display()));
+
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("{}", tr!("serve-connection-failed", error = e));
+                continue;
+            }
+        };
+        // Editors keep their connection open, so each client gets its own thread
+        std::thread::spawn(move || {
+            let reader = match stream.try_clone() {
+                Ok(reader) => BufReader::new(reader),
+                Err(e) => {
+                    eprintln!("{}", tr!("serve-connection-failed", error = e));
+                    return;
+                }
+            };
+            if let Err(e) = handle_connection(reader, stream) {
+                eprintln!(
+                    "{}",
+                    tr!("serve-connection-closed", error = format!("{e:#}"))
+                );
+            }
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn listen(_socket: &Path) -> Result<()> {
+    anyhow::bail!(tr!("serve-no-unix-sockets"))
+}
+
+fn handle_connection(input: impl BufRead, mut output: impl Write) -> Result<()> {
+    for line in input.lines() {
+        let line = line.with_context(|| tr!("serve-read-failed"))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = handle_line(&line) {
+            writeln!(output, "{response}")?;
+            output.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Answer one request line (notifications without an id get no response)
+fn handle_line(line: &str) -> Option<Value> {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => return Some(error_response(Value::Null, PARSE_ERROR, e.to_string())),
+    };
+
+    let id = request.get("id").cloned();
+    let Some(method) = request.get("method").and_then(Value::as_str) else {
+        return Some(error_response(
+            id.unwrap_or(Value::Null),
+            INVALID_REQUEST,
+            tr!("serve-missing-method"),
+        ));
+    };
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = dispatch(method, &params);
+    // Each request is its own operation for `gid undo`
+    journal::seal();
+    let id = id?;
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(RpcError(code, message)) => error_response(id, code, message),
+    })
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn dispatch(method: &str, params: &Value) -> Result<Value, RpcError> {
+    match method {
+        "version" => Ok(json!(env!("CARGO_PKG_VERSION"))),
+        "identities" => identities(),
+        "current" => current(params),
+        "resolve" => resolve(params),
+        "switch" => switch(params),
+        _ => Err(RpcError(
+            METHOD_NOT_FOUND,
+            tr!("serve-unknown-method", method = method),
+        )),
+    }
+}
+
+/// All configured identities
+fn identities() -> Result<Value, RpcError> {
+    let config = Config::load()?;
+    Ok(serde_json::to_value(&config.identities).map_err(anyhow::Error::from)?)
+}
+
+/// Effective and expected identity of a directory, same shape as `gid current --json`
+fn current(params: &Value) -> Result<Value, RpcError> {
+    let path = path_param(params)?;
+    let config = Config::load()?;
+    let git = GitConfigManager::from_path(&path)?;
+    Ok(status_json(&config, &git, &path)?)
+}
+
+/// Identity the .gid file or rules expect for a directory
+fn resolve(params: &Value) -> Result<Value, RpcError> {
+    let path = path_param(params)?;
+    let config = Config::load()?;
+    let git = GitConfigManager::from_path(&path)?;
+
+    Ok(
+        match resolve_identity(&config, &path, git.get_origin_url().as_deref()) {
+            Some(resolution) => json!({
+                "identity": resolution.identity,
+                "source": resolution.source.kind(),
+                "detail": resolution.source.to_string(),
+            }),
+            None => Value::Null,
+        },
+    )
+}
+
+/// Apply an identity (default: the resolved one) to the repository containing a directory
+fn switch(params: &Value) -> Result<Value, RpcError> {
+    let path = path_param(params)?;
+    let config = Config::load()?;
+    let git = GitConfigManager::from_path(&path)?;
+    if !git.is_in_repo() {
+        return Err(RpcError(
+            SERVER_ERROR,
+            tr!("serve-not-a-repo", path = path.display()),
+        ));
+    }
+
+    let identity_id = match params.get("identity").and_then(Value::as_str) {
+        Some(id) => id.to_string(),
+        None => resolve_identity(&config, &path, git.get_origin_url().as_deref())
+            .map(|r| r.identity)
+            .ok_or_else(|| RpcError(SERVER_ERROR, tr!("serve-no-resolution")))?,
+    };
+    let identity = config
+        .find_identity(&identity_id)
+        .ok_or_else(|| RpcError(INVALID_PARAMS, tr!("identity-not-found", id = identity_id)))?;
+
+    let changed = apply_to_repo(&git, identity)?;
+
+    Ok(json!({
+        "identity": identity.id,
+        "changed": changed,
+    }))
+}
+
+/// The required `path` parameter, which must be an absolute directory
+fn path_param(params: &Value) -> Result<PathBuf, RpcError> {
+    let path = params
+        .get("path")
+        .and_then(Value::as_str)
+        .map(PathBuf::from)
+        .ok_or_else(|| RpcError(INVALID_PARAMS, tr!("serve-missing-path")))?;
+    if !path.is_absolute() {
+        return Err(RpcError(
+            INVALID_PARAMS,
+            tr!("serve-path-relative", path = path.display()),
+        ));
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_line_errors() {
+        let response = handle_line("not json").unwrap();
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+
+        let response = handle_line(r#"{"jsonrpc":"2.0","id":1,"method":"nope"}"#).unwrap();
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+
+        let response =
+            handle_line(r#"{"jsonrpc":"2.0","id":"a","method":"resolve","params":{"path":"rel"}}"#)
+                .unwrap();
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        // Notifications are answered with silence
+        assert!(handle_line(r#"{"jsonrpc":"2.0","method":"version"}"#).is_none());
+    }
+}
@@ -0,0 +1,63 @@
+use anyhow::Result;
+
+use crate::cli::ShellType;
+
+const BASH_INIT: &str = r#"# gid shell integration (bash)
+# Add to ~/.bashrc: eval "$(gid shell-init bash)"
+_gid_hook() {
+    local previous_exit_status=$?
+    if [ "${_GID_LAST_PWD:-}" != "$PWD" ]; then
+        _GID_LAST_PWD="$PWD"
+        command gid auto --shell-hook
+    fi
+    return $previous_exit_status
+}
+if [[ ";${PROMPT_COMMAND:-};" != *";_gid_hook;"* ]]; then
+    PROMPT_COMMAND="_gid_hook${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+fi
+"#;
+
+const ZSH_INIT: &str = r#"# gid shell integration (zsh)
+# Add to ~/.zshrc: eval "$(gid shell-init zsh)"
+_gid_hook() {
+    command gid auto --shell-hook
+}
+autoload -Uz add-zsh-hook
+add-zsh-hook chpwd _gid_hook
+_gid_hook
+"#;
+
+const FISH_INIT: &str = r#"# gid shell integration (fish)
+# Add to ~/.config/fish/config.fish: gid shell-init fish | source
+function __gid_hook --on-variable PWD
+    command gid auto --shell-hook
+end
+__gid_hook
+"#;
+
+const POWERSHELL_INIT: &str = r#"# gid shell integration (PowerShell)
+# Add to $PROFILE: Invoke-Expression (& gid shell-init powershell | Out-String)
+$global:__GidLastPwd = $null
+$global:__GidOriginalPrompt = $function:prompt
+function global:prompt {
+    if ($global:__GidLastPwd -ne $PWD.Path) {
+        $global:__GidLastPwd = $PWD.Path
+        & gid auto --shell-hook
+    }
+    & $global:__GidOriginalPrompt
+}
+"#;
+
+/// 输出 Shell 初始化脚本（进入目录时自动切换身份）
+pub fn execute(shell: ShellType) -> Result<()> {
+    let script = match shell {
+        ShellType::Bash => BASH_INIT,
+        ShellType::Zsh => ZSH_INIT,
+        ShellType::Fish => FISH_INIT,
+        ShellType::PowerShell => POWERSHELL_INIT,
+    };
+
+    print!("{script}");
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use anyhow::Result;
+use chrono::Local;
+use colored::Colorize;
+use serde_json::json;
+
+use crate::commands::export::contract_home;
+use crate::commands::list::format_relative;
+use crate::commands::repos::workspace_repos;
+use crate::commands::switch::identity_values;
+use crate::config::{Config, Identity, State};
+use crate::i18n::tr;
+use crate::rules::resolve::RepoStatus;
+use crate::ssh::SshManager;
+
+/// Print the full record of one identity
+pub fn execute(identity_id: &str, json: bool) -> Result<()> {
+    let config = Config::load()?;
+    let state = State::load()?;
+
+    let identity = config
+        .find_identity(identity_id)
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?;
+
+    let fingerprint = identity
+        .ssh_key
+        .as_deref()
+        .and_then(|key| SshManager::new().ok()?.fingerprint(key).ok());
+    let rules: Vec<(usize, &crate::rules::Rule)> = config
+        .rules
+        .iter()
+        .enumerate()
+        .filter(|(_, rule)| rule.identity == identity.id)
+        .collect();
+    let repos = repos_using(&config, identity);
+    let usage = state.usage.get(&identity.id);
+
+    if json {
+        let output = json!({
+            "identity": identity,
+            "ssh_fingerprint": fingerprint,
+            "signing": signing_backend(identity),
+            "git_config": identity_values(identity)
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), json!(value)))
+                .collect::<serde_json::Map<_, _>>(),
+            "rules": rules
+                .iter()
+                .map(|(index, rule)| json!({ "index": index, "rule": rule.to_string() }))
+                .collect::<Vec<_>>(),
+            "repos": repos,
+            "last_used": usage.and_then(|u| u.last_used.clone()),
+            "switches": usage.map_or(0, |u| u.count),
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    println!(
+        "{} {} <{}>",
+        format!("[{}]", identity.id).green().bold(),
+        identity.name,
+        identity.email.cyan()
+    );
+    if let Some(ref desc) = identity.description {
+        let private = if identity.private {
+            format!(" {}", tr!("show-private"))
+        } else {
+            String::new()
+        };
+        println!("  {}{}", desc.dimmed(), private.dimmed());
+    }
+    println!();
+
+    let mut fields: Vec<(String, String)> = Vec::new();
+    if let Some(ref key) = identity.ssh_key {
+        fields.push((tr!("show-ssh-key"), key.display().to_string()));
+        fields.push((
+            tr!("show-fingerprint"),
+            fingerprint.unwrap_or_else(|| tr!("show-unavailable")),
+        ));
+    }
+    if let Some(ref sock) = identity.ssh_auth_sock {
+        fields.push((tr!("show-ssh-agent"), sock.clone()));
+    }
+    if !identity.ssh_hosts.is_empty() {
+        fields.push((tr!("show-ssh-hosts"), identity.ssh_hosts.join(", ")));
+    }
+    fields.push((tr!("show-signing"), signing_backend(identity)));
+    if let Some(ref account) = identity.gh_account {
+        fields.push((tr!("show-gh-account"), account.clone()));
+    }
+    if !identity.tags.is_empty() {
+        fields.push((tr!("show-tags"), identity.tags.join(", ")));
+    }
+    if let Some(ref command) = identity.post_switch_command {
+        fields.push((tr!("show-post-switch"), command.clone()));
+    }
+    if let Some(ref template) = identity.commit_template {
+        fields.push((tr!("show-commit-template"), template.display().to_string()));
+    }
+    fields.push((
+        tr!("show-last-used"),
+        usage
+            .and_then(|u| u.last_used_at())
+            .map(|t| {
+                tr!(
+                    "show-last-used-value",
+                    time = format_relative(t.with_timezone(&Local)),
+                    count = usage.map_or(0, |u| u.count)
+                )
+            })
+            .unwrap_or_else(|| tr!("list-never")),
+    ));
+    print_fields(&fields);
+
+    println!();
+    println!("{}", tr!("show-git-config").bold());
+    for (key, value) in identity_values(identity) {
+        println!("  {:<16} {}", key, value.cyan());
+    }
+
+    println!();
+    println!("{}", tr!("show-rules").bold());
+    if rules.is_empty() {
+        println!("  {}", tr!("show-no-rules").dimmed());
+    }
+    for (index, rule) in &rules {
+        println!("  {} {}", format!("{index}.").dimmed(), rule);
+    }
+
+    if !config.settings.workspace_roots.is_empty() {
+        println!();
+        println!("{}", tr!("show-repos").bold());
+        if repos.is_empty() {
+            println!("  {}", tr!("show-no-repos").dimmed());
+        }
+        for repo in &repos {
+            println!("  {repo}");
+        }
+    }
+
+    Ok(())
+}
+
+fn print_fields(fields: &[(String, String)]) {
+    let width = fields
+        .iter()
+        .map(|(label, _)| console::measure_text_width(label))
+        .max()
+        .unwrap_or(0);
+    for (label, value) in fields {
+        println!(
+            "  {}  {}",
+            crate::commands::list::pad(&format!("{label}:"), width + 1).dimmed(),
+            value
+        );
+    }
+}
+
+fn signing_backend(identity: &Identity) -> String {
+    match (&identity.gpg_key, identity.gpg_sign) {
+        (Some(key), true) => tr!("show-signing-on", key = key),
+        (Some(key), false) => tr!("show-signing-off", key = key),
+        (None, _) => tr!("show-signing-none"),
+    }
+}
+
+/// Repositories under settings.workspace_roots whose effective identity is this one
+fn repos_using(config: &Config, identity: &Identity) -> Vec<String> {
+    if config.settings.workspace_roots.is_empty() {
+        return Vec::new();
+    }
+
+    workspace_repos(config, Vec::new())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|repo| RepoStatus::inspect(config, repo).ok())
+        .filter(|status| {
+            status
+                .effective_identity(config)
+                .is_some_and(|i| i.id == identity.id)
+        })
+        .map(|status| contract_home(&status.path.display().to_string()))
+        .collect()
+}
@@ -0,0 +1,275 @@
+use anyhow::Result;
+use chrono::Local;
+use colored::Colorize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+use crate::commands::export::contract_home;
+use crate::commands::list::{format_relative, pad};
+use crate::commands::repos::workspace_repos;
+use crate::config::{Config, State};
+use crate::i18n::tr;
+use crate::rules::resolve::{RepoStatus, Source};
+
+/// Usage statistics across identities, rules and workspace repositories
+struct Stats {
+    /// Identity ID -> repositories whose effective identity it is
+    repos: BTreeMap<String, usize>,
+    /// Hits per rule, in config order
+    rule_hits: Vec<usize>,
+    /// Repositories no .gid or rule matches (path -> remote)
+    unmatched: BTreeMap<String, Option<String>>,
+    /// Repositories scanned
+    scanned: usize,
+}
+
+/// Report per-identity repository counts, rule hits, switches and unmatched repositories
+pub fn execute(scan: Vec<PathBuf>, json: bool) -> Result<()> {
+    let config = Config::load()?;
+    let state = State::load()?;
+
+    // Without scan roots only recorded state is reported
+    let repos = if scan.is_empty() && config.settings.workspace_roots.is_empty() {
+        Vec::new()
+    } else {
+        workspace_repos(&config, scan)?
+    };
+    let stats = collect(&config, &state, &repos);
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&stats_json(&config, &state, &stats))?
+        );
+        return Ok(());
+    }
+
+    print_identities(&config, &state, &stats);
+    print_rules(&config, &stats);
+    print_unmatched(&stats);
+
+    Ok(())
+}
+
+fn collect(config: &Config, state: &State, repos: &[PathBuf]) -> Stats {
+    let mut stats = Stats {
+        repos: BTreeMap::new(),
+        rule_hits: vec![0; config.rules.len()],
+        unmatched: BTreeMap::new(),
+        scanned: 0,
+    };
+
+    // Repositories `gid auto` recorded as unmatched, still present on disk
+    for (path, repo) in &state.unmatched {
+        if PathBuf::from(path).exists() {
+            stats.unmatched.insert(path.clone(), repo.remote.clone());
+        }
+    }
+
+    for repo in repos {
+        let Ok(status) = RepoStatus::inspect(config, repo) else {
+            continue;
+        };
+        stats.scanned += 1;
+
+        if let Some(identity) = status.effective_identity(config) {
+            *stats.repos.entry(identity.id.clone()).or_default() += 1;
+        }
+
+        match &status.expected {
+            Some(expected) => {
+                if let Source::Rule(rule) = &expected.source {
+                    if let Some(index) = config.rules.iter().position(|r| {
+                        r.rule_type == rule.rule_type
+                            && r.identity == rule.identity
+                            && r.priority == rule.priority
+                    }) {
+                        stats.rule_hits[index] += 1;
+                    }
+                }
+            }
+            None => {
+                stats
+                    .unmatched
+                    .insert(status.path.display().to_string(), status.remote.clone());
+            }
+        }
+    }
+
+    stats
+}
+
+fn print_identities(config: &Config, state: &State, stats: &Stats) {
+    println!("{}", tr!("stats-identities").bold());
+
+    let headers = [
+        tr!("list-column-id"),
+        tr!("stats-column-repos"),
+        tr!("stats-column-switches"),
+        tr!("list-column-last-used"),
+    ];
+    let rows: Vec<[String; 4]> = config
+        .identities
+        .iter()
+        .map(|identity| {
+            let usage = state.usage.get(&identity.id);
+            [
+                identity.id.clone(),
+                stats
+                    .repos
+                    .get(&identity.id)
+                    .copied()
+                    .unwrap_or(0)
+                    .to_string(),
+                usage.map_or(0, |u| u.count).to_string(),
+                usage
+                    .and_then(|u| u.last_used_at())
+                    .map(|t| format_relative(t.with_timezone(&Local)))
+                    .unwrap_or_else(|| tr!("list-never")),
+            ]
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = headers
+        .iter()
+        .map(|h| console::measure_text_width(h))
+        .collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(console::measure_text_width(cell));
+        }
+    }
+
+    let header_line = headers
+        .iter()
+        .zip(&widths)
+        .map(|(header, &width)| pad(header, width))
+        .collect::<Vec<_>>()
+        .join("  ");
+    println!("  {}", header_line.trim_end().dimmed());
+
+    for row in &rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, &width)| pad(cell, width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        // Identities never switched to and not used by any repository are pruning candidates
+        if row[1] == "0" && row[2] == "0" {
+            println!("  {}", line.trim_end().dimmed());
+        } else {
+            println!("  {}", line.trim_end());
+        }
+    }
+
+    if stats.scanned > 0 {
+        let configured: usize = stats.repos.values().sum();
+        println!();
+        println!(
+            "  {}",
+            tr!(
+                "stats-scanned",
+                scanned = stats.scanned,
+                configured = configured
+            )
+        );
+    }
+}
+
+fn print_rules(config: &Config, stats: &Stats) {
+    if config.rules.is_empty() || stats.scanned == 0 {
+        return;
+    }
+
+    println!();
+    println!("{}", tr!("stats-rule-hits").bold());
+    for (rule, hits) in config.rules.iter().zip(&stats.rule_hits) {
+        if *hits == 0 {
+            println!("  {:>4}  {}", "0".dimmed(), rule.to_string().dimmed());
+        } else {
+            println!("  {:>4}  {}", hits, rule);
+        }
+    }
+}
+
+fn print_unmatched(stats: &Stats) {
+    if stats.unmatched.is_empty() {
+        return;
+    }
+
+    println!();
+    println!(
+        "{}",
+        tr!("stats-unmatched", count = stats.unmatched.len()).bold()
+    );
+    for (path, remote) in &stats.unmatched {
+        println!(
+            "  {} {}",
+            contract_home(path),
+            remote.as_deref().unwrap_or("").dimmed()
+        );
+    }
+    println!();
+    println!(
+        "{}",
+        tr!(
+            "stats-suggest-hint",
+            command = "gid auto --suggest-rules".cyan()
+        )
+    );
+}
+
+fn stats_json(config: &Config, state: &State, stats: &Stats) -> Value {
+    let identities: Vec<Value> = config
+        .identities
+        .iter()
+        .map(|identity| {
+            let usage = state.usage.get(&identity.id);
+            json!({
+                "id": identity.id,
+                "repos": stats.repos.get(&identity.id).copied().unwrap_or(0),
+                "switches": usage.map_or(0, |u| u.count),
+                "last_used": usage.and_then(|u| u.last_used.clone()),
+            })
+        })
+        .collect();
+
+    let rules: Vec<Value> = config
+        .rules
+        .iter()
+        .zip(&stats.rule_hits)
+        .map(|(rule, hits)| {
+            json!({
+                "rule": rule.to_string(),
+                "identity": rule.identity,
+                "hits": hits,
+            })
+        })
+        .collect();
+
+    let unmatched: Vec<Value> = stats
+        .unmatched
+        .iter()
+        .map(|(path, remote)| json!({ "path": path, "remote": remote }))
+        .collect();
+
+    // Identities no scanned repository uses and never switched to
+    let unused: BTreeSet<&str> = config
+        .identities
+        .iter()
+        .map(|i| i.id.as_str())
+        .filter(|id| {
+            !stats.repos.contains_key(*id) && state.usage.get(*id).is_none_or(|u| u.count == 0)
+        })
+        .collect();
+
+    json!({
+        "scanned": stats.scanned,
+        "identities": identities,
+        "rules": rules,
+        "unmatched": unmatched,
+        "unused": unused,
+    })
+}
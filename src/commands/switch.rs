@@ -1,10 +1,23 @@
 use anyhow::Result;
 use colored::Colorize;
+use dialoguer::Confirm;
+use std::io::IsTerminal;
+use std::path::Path;
+use std::process::Command;
 
-use crate::config::Config;
+use crate::ascii::sym;
+use crate::config::identity::expand_path;
+use crate::config::{Config, Credentials, Identity, State};
+use crate::exit::Failure;
+use crate::gh::GhManager;
 use crate::git::GitConfigManager;
+use crate::i18n::tr;
+use crate::rules::resolve::resolve_submodule_identity;
 use crate::ssh::SshManager;
 
+/// 无法从仓库确定主机时配置的常见 Git 托管服务
+const DEFAULT_SSH_HOSTS: [&str; 3] = ["github.com", "gitlab.com", "bitbucket.org"];
+
 /// 切换身份
 pub fn execute(identity_id: &str, global: bool) -> Result<()> {
     let config = Config::load()?;
@@ -12,86 +25,849 @@ pub fn execute(identity_id: &str, global: bool) -> Result<()> {
     // 查找身份
     let identity = config
         .find_identity(identity_id)
-        .ok_or_else(|| anyhow::anyhow!("Identity '{identity_id}' not found"))?;
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?;
 
     let git = GitConfigManager::new()?;
 
     // 非全局模式需要在 Git 仓库中
     if !global && !git.is_in_repo() {
-        anyhow::bail!("Current directory is not a Git repository. Use --global for global switch");
+        return Err(Failure::NotARepo.error(tr!("switch-not-a-repo")));
     }
 
+    let previous_email = git.get_effective_user_email();
+
     // 设置 Git 配置
-    git.set_user_name(&identity.name, global)?;
-    git.set_user_email(&identity.email, global)?;
+    let changes = apply_git_config(&git, identity, global)?;
 
-    // 设置 GPG 签名
-    if let Some(ref gpg_key) = identity.gpg_key {
-        git.set_signing_key(gpg_key, global)?;
-        git.set_gpg_sign(identity.gpg_sign, global)?;
+    // 配置 SSH（如果有）
+    let ssh_aliases = configure_ssh(&config, identity, &remote_hosts(&git))?;
+
+    // 输出结果（-q 时只保留警告）
+    let scope = if global { "global" } else { "project" };
+    if !crate::logging::is_quiet() {
+        let scope_label = if global {
+            tr!("switch-scope-global")
+        } else {
+            tr!("switch-scope-project")
+        };
+        println!(
+            "{} {}",
+            sym("✓").green(),
+            tr!(
+                "switch-done",
+                scope = scope_label,
+                identity = format!("[{}]", identity.id).green().bold(),
+                name = identity.name,
+                email = identity.email.cyan()
+            )
+        );
+
+        if let Some(ref desc) = identity.description {
+            println!("  {}", desc.dimmed());
+        }
+
+        if identity.ssh_key.is_some() {
+            println!("  {} {}", sym("🔑").dimmed(), tr!("switch-ssh-key"));
+        }
+
+        if identity.gpg_key.is_some() {
+            println!("  {} {}", sym("🔏").dimmed(), tr!("switch-gpg-signing"));
+        }
+
+        print_changes(&changes, &ssh_aliases);
     }
 
-    // 配置 SSH（如果有）
-    if let Some(ref ssh_key) = identity.ssh_key {
-        let ssh = SshManager::new()?;
-        if ssh.key_exists(ssh_key) {
-            // 检查 ssh-agent 是否运行
-            if ssh.is_agent_running() {
-                // 添加密钥到 ssh-agent
-                if let Err(e) = ssh.add_to_agent(ssh_key) {
-                    eprintln!("{} Failed to add key to ssh-agent: {}", "!".yellow(), e);
-                } else {
-                    println!("  {} SSH key added to agent", "🔑".dimmed());
+    switch_gh_account(identity);
+
+    if !global {
+        warn_unpushed_commits(&git, previous_email.as_deref(), identity);
+    }
+
+    record_usage(identity);
+    run_post_switch_command(&config, identity, scope, git.workdir());
+
+    Ok(())
+}
+
+/// 全局切换前确认（受 confirm_global_switch 设置控制）
+pub fn confirm_global(identity_id: &str, yes: bool) -> Result<bool> {
+    let config = Config::load()?;
+
+    if yes || !config.settings.confirm_global_switch {
+        return Ok(true);
+    }
+
+    // The prompt reads stdin and draws on stderr; stdout may be piped (| tee log)
+    if !std::io::stdin().is_terminal() || !std::io::stderr().is_terminal() {
+        anyhow::bail!(tr!("switch-global-needs-confirmation"));
+    }
+
+    let git = GitConfigManager::new()?;
+    if let (Some(name), Some(email)) = (git.get_user_name(true), git.get_user_email(true)) {
+        println!(
+            "{}",
+            tr!("switch-global-current", name = name, email = email.cyan())
+        );
+    }
+    println!("{} {}", "!".yellow(), tr!("switch-global-warning"));
+
+    let confirm = Confirm::new()
+        .with_prompt(tr!("switch-global-confirm", id = identity_id))
+        .default(false)
+        .interact()?;
+
+    if !confirm {
+        println!("{}", tr!("operation-cancelled"));
+    }
+
+    Ok(confirm)
+}
+
+/// 递归切换目录下所有仓库的身份
+pub fn execute_recursive(identity_id: &str, root: &Path) -> Result<()> {
+    let config = Config::load()?;
+
+    let identity = config
+        .find_identity(identity_id)
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?;
+
+    if !root.is_dir() {
+        anyhow::bail!(tr!("switch-dir-not-found", path = root.display()));
+    }
+
+    let repos = crate::git::discover_repos(root, crate::git::DEFAULT_SCAN_DEPTH);
+
+    if repos.is_empty() {
+        println!(
+            "{} {}",
+            "!".yellow(),
+            tr!("auto-no-repos", path = root.display())
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        tr!(
+            "switch-recursive-start",
+            identity = format!("[{}]", identity.id).green().bold(),
+            count = repos.len(),
+            path = root.display().to_string().cyan()
+        )
+    );
+    println!();
+
+    let mut switched = 0;
+    let mut failed = 0;
+    let mut hosts = Vec::new();
+
+    for repo in &repos {
+        let result = GitConfigManager::from_path(repo).and_then(|git| {
+            for host in remote_hosts(&git) {
+                if !hosts.contains(&host) {
+                    hosts.push(host);
                 }
-            } else {
+            }
+            apply_git_config(&git, identity, false)
+        });
+
+        match result {
+            Ok(changes) => {
+                switched += 1;
+                let detail = if changes.is_empty() {
+                    tr!("switch-unchanged")
+                } else {
+                    changes
+                        .iter()
+                        .map(|c| c.key.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
                 println!(
-                    "  {} ssh-agent not running, skipping key addition",
-                    "!".yellow()
+                    "  {} {} {}",
+                    sym("✓").green(),
+                    repo.display(),
+                    detail.dimmed()
                 );
+            }
+            Err(e) => {
+                failed += 1;
+                println!("  {} {}: {}", sym("✗").red(), repo.display(), e);
+            }
+        }
+    }
+
+    // SSH 配置是全局的，只需配置一次
+    let ssh_aliases = configure_ssh(&config, identity, &hosts)?;
+    for alias in &ssh_aliases {
+        println!(
+            "  {} {}",
+            sym("🔑").dimmed(),
+            tr!("switch-wrote-host", alias = alias.cyan())
+        );
+    }
+
+    println!();
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!(
+            "switch-recursive-done",
+            count = switched,
+            identity = format!("[{}]", identity.id).green().bold(),
+            name = identity.name,
+            email = identity.email.cyan()
+        )
+    );
+
+    if failed > 0 {
+        println!(
+            "{} {}",
+            "!".yellow(),
+            tr!("switch-recursive-failed", count = failed)
+        );
+    }
+
+    if switched > 0 {
+        switch_gh_account(identity);
+        record_usage(identity);
+        run_post_switch_command(&config, identity, "recursive", None);
+    }
+
+    Ok(())
+}
+
+/// 通过全局 includeIf 为当前仓库切换身份
+pub fn execute_include_if(identity_id: &str) -> Result<()> {
+    let config = Config::load()?;
+
+    let identity = config
+        .find_identity(identity_id)
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?;
+
+    let git = GitConfigManager::new()?;
+
+    let workdir = git
+        .workdir()
+        .ok_or_else(|| Failure::NotARepo.error(tr!("switch-no-worktree")))?;
+    let workdir = workdir
+        .canonicalize()
+        .unwrap_or_else(|_| workdir.to_path_buf());
+    let gitdir = format!("{}/", workdir.display().to_string().trim_end_matches('/'));
+
+    let previous = (
+        git.get_effective_user_name(),
+        git.get_effective_user_email(),
+    );
+    let previous_email = previous.1.clone();
+
+    // 写入身份配置片段并指向它
+    let fragment = Config::fragment_path(&identity.id)?;
+
+    let mut changes = Vec::new();
+
+    let include_key = format!("includeIf.gitdir:{gitdir}.path");
+    let before = git.get_include_if(&gitdir);
+    let after = fragment.display().to_string();
+    git.write_include_if(&gitdir, &fragment, &identity_values(identity))?;
+    if before.as_ref() != Some(&after) {
+        changes.push(ConfigChange {
+            key: include_key,
+            before,
+            after,
+        });
+    }
+
+    // 本地配置优先级高于 includeIf，需要移除
+    for (key, _) in identity_values(identity) {
+        if let Some(before) = git.get_scoped_value(key, false) {
+            git.unset_value(key, false)?;
+            changes.push(ConfigChange {
+                key: format!("{key} (local)"),
+                before: Some(before),
+                after: "(unset)".to_string(),
+            });
+        }
+    }
+
+    record_switch(&git, previous, identity, "include-if");
+
+    let ssh_aliases = configure_ssh(&config, identity, &remote_hosts(&git))?;
+
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!(
+            "switch-include-if-done",
+            identity = format!("[{}]", identity.id).green().bold(),
+            name = identity.name,
+            email = identity.email.cyan()
+        )
+    );
+    println!(
+        "  {} {}",
+        tr!("switch-fragment").dimmed(),
+        fragment.display()
+    );
+
+    print_changes(&changes, &ssh_aliases);
+
+    switch_gh_account(identity);
+
+    warn_unpushed_commits(&git, previous_email.as_deref(), identity);
+
+    record_usage(identity);
+    run_post_switch_command(&config, identity, "include-if", git.workdir());
+
+    Ok(())
+}
+
+/// 为指定仓库写入身份的 Git 配置（不输出），返回发生变化的配置项
+///
+/// 仓库正在 rebase、merge 或 cherry-pick 时拒绝修改
+pub fn apply_to_repo(git: &GitConfigManager, identity: &Identity) -> Result<Vec<String>> {
+    Ok(apply_git_config(git, identity, false)?
+        .into_iter()
+        .map(|c| c.key)
+        .collect())
+}
+
+/// 为当前仓库的每个已初始化子模块应用其自身 .gid / 规则解析出的身份
+///
+/// 没有匹配的子模块使用 `fallback`（通常是主仓库的身份），否则保持不变。
+pub fn execute_submodules(fallback: Option<&str>, dry_run: bool) -> Result<()> {
+    let config = Config::load()?;
+    let git = GitConfigManager::new()?;
+    let submodules = git.submodule_workdirs();
+
+    println!();
+    if submodules.is_empty() {
+        println!("{}", tr!("switch-no-submodules").dimmed());
+        return Ok(());
+    }
+    println!("{}", tr!("switch-submodules"));
+
+    let mut failed = 0;
+    for workdir in &submodules {
+        let label = git
+            .workdir()
+            .and_then(|root| workdir.strip_prefix(root).ok())
+            .unwrap_or(workdir)
+            .display()
+            .to_string();
+        let sub = GitConfigManager::from_path(workdir)?;
+
+        let resolved =
+            resolve_submodule_identity(&config, workdir, sub.get_origin_url().as_deref())
+                .map(|r| (r.identity, r.source.to_string()))
+                .or_else(|| fallback.map(|id| (id.to_string(), tr!("switch-superproject"))));
+        let Some((identity_id, source)) = resolved else {
+            println!(
+                "  {} {} {}",
+                "?".dimmed(),
+                label,
+                tr!("switch-submodule-unmatched").dimmed()
+            );
+            continue;
+        };
+        let Some(identity) = config.find_identity(&identity_id) else {
+            failed += 1;
+            println!(
+                "  {} {}: {}",
+                sym("✗").red(),
+                label,
+                tr!("identity-not-found", id = identity_id)
+            );
+            continue;
+        };
+
+        let result = if dry_run {
+            Ok(pending_changes(&sub, identity))
+        } else {
+            apply_to_repo(&sub, identity)
+        };
+        match result {
+            Ok(changes) => {
+                let detail = match (changes.is_empty(), dry_run) {
+                    (true, _) => tr!("switch-unchanged"),
+                    (false, true) => tr!("switch-would-change", keys = changes.join(", ")),
+                    (false, false) => changes.join(", "),
+                };
                 println!(
-                    "    Tip: Run 'ssh-add {}' after starting ssh-agent",
-                    ssh_key.display()
+                    "  {} {} {} ({}) {}",
+                    sym("✓").green(),
+                    label,
+                    format!("[{}]", identity.id).green(),
+                    source,
+                    detail.dimmed()
                 );
             }
-
-            // 为常见的 Git 托管服务配置 SSH
-            let hosts = ["github.com", "gitlab.com", "bitbucket.org"];
-            for host in hosts {
-                if let Err(e) = ssh.configure_for_identity(identity_id, host, ssh_key) {
-                    eprintln!("{} Failed to configure SSH ({}): {}", "!".yellow(), host, e);
-                }
+            Err(e) => {
+                failed += 1;
+                println!("  {} {}: {}", sym("✗").red(), label, e);
             }
-        } else {
-            eprintln!(
-                "{} SSH key file does not exist: {}",
-                "!".yellow(),
-                ssh_key.display()
-            );
         }
     }
 
-    // 输出结果
+    if failed > 0 {
+        anyhow::bail!(tr!("switch-submodules-failed", count = failed));
+    }
+
+    Ok(())
+}
+
+/// 仓库本地配置中与身份不一致、切换时会被修改的配置项
+pub fn pending_changes(git: &GitConfigManager, identity: &Identity) -> Vec<String> {
+    identity_values(identity)
+        .into_iter()
+        .filter(|(key, value)| git.get_scoped_value(key, false).as_ref() != Some(value))
+        .map(|(key, _)| key.to_string())
+        .collect()
+}
+
+/// 一项被修改的 Git 配置
+struct ConfigChange {
+    key: String,
+    before: Option<String>,
+    after: String,
+}
+
+/// 身份对应的 Git 配置项
+pub fn identity_values(identity: &Identity) -> Vec<(&'static str, String)> {
+    let mut values = vec![
+        ("user.name", identity.name.clone()),
+        ("user.email", identity.email.clone()),
+    ];
+
+    // 设置 GPG 签名
+    if let Some(ref gpg_key) = identity.gpg_key {
+        values.push(("user.signingkey", gpg_key.clone()));
+        values.push(("commit.gpgsign", identity.gpg_sign.to_string()));
+    }
+
+    if let Some(ref template) = identity.commit_template {
+        values.push((
+            "commit.template",
+            expand_path(template).display().to_string(),
+        ));
+    }
+
+    values
+}
+
+/// 写入身份对应的 Git 配置，返回实际发生变化的配置项
+///
+/// 所有写入仓库配置的路径都经过这里，未完成的 rebase/merge 等操作在此统一拦截
+fn apply_git_config(
+    git: &GitConfigManager,
+    identity: &Identity,
+    global: bool,
+) -> Result<Vec<ConfigChange>> {
+    if !global {
+        git.ensure_no_operation_in_progress()?;
+    }
+
+    let values = identity_values(identity);
+
+    let before: Vec<_> = values
+        .iter()
+        .map(|(key, _)| git.get_scoped_value(key, global))
+        .collect();
+    let previous = if global {
+        (git.get_user_name(true), git.get_user_email(true))
+    } else {
+        (
+            git.get_effective_user_name(),
+            git.get_effective_user_email(),
+        )
+    };
+
+    git.set_user_name(&identity.name, global)?;
+    git.set_user_email(&identity.email, global)?;
+
+    if let Some(ref gpg_key) = identity.gpg_key {
+        git.set_signing_key(gpg_key, global)?;
+        git.set_gpg_sign(identity.gpg_sign, global)?;
+    }
+
+    let template_removed = match identity.commit_template {
+        Some(ref template) => {
+            git.set_commit_template(&expand_path(template), global)?;
+            None
+        }
+        None => remove_managed_template(git, global)?,
+    };
+
     let scope = if global { "global" } else { "project" };
+    record_switch(git, previous, identity, scope);
+
+    let mut changes: Vec<ConfigChange> = values
+        .into_iter()
+        .zip(before)
+        .filter(|((_, after), before)| before.as_ref() != Some(after))
+        .map(|((key, after), before)| ConfigChange {
+            key: key.to_string(),
+            before,
+            after,
+        })
+        .collect();
+    if let Some(before) = template_removed {
+        changes.push(ConfigChange {
+            key: "commit.template".to_string(),
+            before: Some(before),
+            after: "(unset)".to_string(),
+        });
+    }
+    changes.extend(apply_credential_helpers(git, identity, global)?);
+
+    Ok(changes)
+}
+
+/// 移除其他身份留下的 gid 提交模板（用户自己的 commit.template 保持不变），返回被移除的值
+fn remove_managed_template(git: &GitConfigManager, global: bool) -> Result<Option<String>> {
+    let Some(current) = git.get_scoped_value("commit.template", global) else {
+        return Ok(None);
+    };
+    let templates_dir = Config::template_path("_")?
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    if expand_path(Path::new(&current)).parent() != Some(templates_dir.as_path()) {
+        return Ok(None);
+    }
+
+    git.unset_value("commit.template", global)?;
+    Ok(Some(current))
+}
+
+/// 为身份保存了凭据的主机配置 HTTPS 凭据助手，并移除其他身份的助手
+fn apply_credential_helpers(
+    git: &GitConfigManager,
+    identity: &Identity,
+    global: bool,
+) -> Result<Vec<ConfigChange>> {
+    let credentials = Credentials::load()?;
+    let wanted: Vec<_> = credentials.for_identity(&identity.id).collect();
+
+    for (host, _) in git.credential_helpers(global) {
+        if !wanted.iter().any(|c| c.host.eq_ignore_ascii_case(&host)) {
+            git.unset_credential_helper(&host, global)?;
+        }
+    }
+
+    let mut changes = Vec::new();
+    for credential in wanted {
+        let key = format!("credential.https://{}.username", credential.host);
+        let before = git.get_scoped_value(&key, global);
+        git.set_credential_helper(&credential.host, &identity.id, &credential.username, global)?;
+        if before.as_deref() != Some(credential.username.as_str()) {
+            changes.push(ConfigChange {
+                key,
+                before,
+                after: credential.username.clone(),
+            });
+        }
+    }
+
+    Ok(changes)
+}
+
+/// 输出切换前后的配置差异
+fn print_changes(changes: &[ConfigChange], ssh_aliases: &[String]) {
+    if changes.is_empty() && ssh_aliases.is_empty() {
+        println!("  {}", tr!("switch-no-changes").dimmed());
+        return;
+    }
+
+    println!("  {}", tr!("switch-changes").dimmed());
+    for change in changes {
+        println!(
+            "    {:<16} {} {} {}",
+            change.key,
+            change.before.as_deref().unwrap_or("(unset)").red(),
+            sym("→"),
+            change.after.green()
+        );
+    }
+
+    for alias in ssh_aliases {
+        println!(
+            "    {:<16} {}",
+            "ssh config",
+            tr!("switch-wrote-host", alias = alias.cyan())
+        );
+    }
+}
+
+/// 提示使用旧身份创建但尚未推送的提交
+fn warn_unpushed_commits(
+    git: &GitConfigManager,
+    previous_email: Option<&str>,
+    identity: &Identity,
+) {
+    let Some(previous_email) = previous_email else {
+        return;
+    };
+
+    if previous_email == identity.email {
+        return;
+    }
+
+    let commits: Vec<_> = git
+        .get_unpushed_commits()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|c| c.author_email == previous_email)
+        .collect();
+
+    if commits.is_empty() {
+        return;
+    }
+
+    println!();
     println!(
-        "{} Switched to {} identity: {} {} <{}>",
-        "✓".green(),
-        scope,
-        format!("[{}]", identity.id).green().bold(),
-        identity.name,
-        identity.email.cyan()
+        "{} {}",
+        sym("⚠").yellow(),
+        tr!(
+            "switch-unpushed",
+            count = commits.len(),
+            email = previous_email.yellow()
+        )
     );
+    for commit in commits.iter().take(5) {
+        println!("    {} {}", commit.id.dimmed(), commit.message);
+    }
+    if commits.len() > 5 {
+        println!(
+            "    {}",
+            tr!("switch-unpushed-more", count = commits.len() - 5)
+        );
+    }
+    println!(
+        "  {}",
+        tr!(
+            "switch-unpushed-fix",
+            command = format!(
+                "gid fix-commit --range @{{u}}..HEAD --identity {}",
+                identity.id
+            )
+            .cyan()
+        )
+    );
+}
+
+/// 同步切换 GitHub CLI 账号
+fn switch_gh_account(identity: &Identity) {
+    let Some(ref account) = identity.gh_account else {
+        return;
+    };
 
-    if let Some(ref desc) = identity.description {
-        println!("  {}", desc.dimmed());
+    let gh = GhManager::new();
+    if !gh.is_available() {
+        eprintln!(
+            "{} {}",
+            "!".yellow(),
+            tr!("switch-gh-missing", account = account)
+        );
+        return;
     }
 
-    if identity.ssh_key.is_some() {
-        println!("  {} SSH key configured", "🔑".dimmed());
+    let host = crate::gh::DEFAULT_HOST;
+    if gh.active_account(host).as_deref() == Some(account.as_str()) {
+        return;
     }
 
-    if identity.gpg_key.is_some() {
-        println!("  {} GPG signing enabled", "🔏".dimmed());
+    match gh.switch_account(host, account) {
+        Ok(()) => println!(
+            "  {} {}",
+            sym("🐙").dimmed(),
+            tr!("switch-gh-switched", account = account.cyan())
+        ),
+        Err(e) => eprintln!("{} {}", "!".yellow(), tr!("switch-gh-failed", error = e)),
     }
+}
 
-    Ok(())
+/// 记录身份使用统计（失败不影响切换）
+fn record_usage(identity: &Identity) {
+    let _ = State::update(|state| {
+        state.record_usage(&identity.id);
+        Ok(())
+    });
+}
+
+/// 记录切换历史（身份没有变化时跳过）
+fn record_switch(
+    git: &GitConfigManager,
+    previous: (Option<String>, Option<String>),
+    identity: &Identity,
+    scope: &str,
+) {
+    let (name, email) = previous;
+    if name.as_deref() == Some(identity.name.as_str())
+        && email.as_deref() == Some(identity.email.as_str())
+    {
+        return;
+    }
+
+    // 尽量对应到已配置的身份，否则记录邮箱
+    let from = email.map(|email| {
+        Config::load()
+            .ok()
+            .and_then(|config| {
+                config
+                    .identities
+                    .iter()
+                    .find(|i| i.email == email && name.as_deref().is_none_or(|n| n == i.name))
+                    .map(|i| i.id.clone())
+            })
+            .unwrap_or(email)
+    });
+
+    let repo = if scope == "global" {
+        None
+    } else {
+        git.workdir()
+            .map(|w| w.canonicalize().unwrap_or_else(|_| w.to_path_buf()))
+    };
+
+    let _ = State::update(|state| {
+        state.record_switch(repo.as_deref(), from, &identity.id, scope);
+        Ok(())
+    });
+}
+
+/// 执行切换后命令（身份配置优先于全局设置）
+fn run_post_switch_command(config: &Config, identity: &Identity, scope: &str, repo: Option<&Path>) {
+    let Some(command) = identity
+        .post_switch_command
+        .as_ref()
+        .or(config.settings.post_switch_command.as_ref())
+    else {
+        return;
+    };
+
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    };
+
+    cmd.env("GID_IDENTITY", &identity.id)
+        .env("GID_IDENTITY_NAME", &identity.name)
+        .env("GID_IDENTITY_EMAIL", &identity.email)
+        .env("GID_SCOPE", scope);
+
+    if let Some(repo) = repo {
+        cmd.env("GID_REPO", repo);
+    }
+
+    log::debug!("running post_switch_command: {command}");
+    match cmd.status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => eprintln!(
+            "{} {}",
+            "!".yellow(),
+            tr!("switch-post-command-status", status = status)
+        ),
+        Err(e) => eprintln!(
+            "{} {}",
+            "!".yellow(),
+            tr!("switch-post-command-failed", error = e)
+        ),
+    }
+}
+
+/// 获取仓库 remote 使用的主机
+pub fn remote_hosts(git: &GitConfigManager) -> Vec<String> {
+    let mut hosts = Vec::new();
+
+    for host in git
+        .get_remote_urls()
+        .iter()
+        .filter_map(|url| crate::git::url_host(url))
+    {
+        // 不含点的主机名通常已经是 SSH 别名，无需再配置
+        if host.contains('.') && !hosts.contains(&host) {
+            hosts.push(host);
+        }
+    }
+
+    hosts
+}
+
+/// 配置身份的 SSH 密钥，返回写入的 Host 别名
+pub fn configure_ssh(
+    config: &Config,
+    identity: &Identity,
+    repo_hosts: &[String],
+) -> Result<Vec<String>> {
+    let mut aliases = Vec::new();
+
+    let Some(ref ssh_key) = identity.ssh_key else {
+        return Ok(aliases);
+    };
+
+    let ssh = SshManager::new()?;
+    if !ssh.key_exists(ssh_key) {
+        eprintln!(
+            "{} {}",
+            "!".yellow(),
+            tr!("identity-ssh-key-missing", path = ssh_key.display())
+        );
+        return Ok(aliases);
+    }
+
+    // 检查 ssh-agent 是否运行
+    if ssh.is_agent_running() {
+        // 添加密钥到 ssh-agent
+        if let Err(e) = ssh.add_to_agent(ssh_key) {
+            eprintln!(
+                "{} {}",
+                "!".yellow(),
+                tr!("switch-ssh-agent-failed", error = e)
+            );
+        } else {
+            println!("  {} {}", sym("🔑").dimmed(), tr!("switch-ssh-agent-added"));
+        }
+    } else {
+        println!("  {} {}", "!".yellow(), tr!("switch-ssh-agent-missing"));
+        println!(
+            "    {}",
+            tr!("switch-ssh-agent-tip", path = ssh_key.display())
+        );
+    }
+
+    // 只为仓库 remote 和身份声明的主机配置 SSH
+    let mut hosts: Vec<String> = repo_hosts.to_vec();
+    for host in &identity.ssh_hosts {
+        if !hosts.contains(host) {
+            hosts.push(host.clone());
+        }
+    }
+
+    if hosts.is_empty() {
+        hosts = DEFAULT_SSH_HOSTS.iter().map(|h| h.to_string()).collect();
+    }
+
+    for host in &hosts {
+        match ssh.configure_for_identity(
+            &identity.id,
+            host,
+            ssh_key,
+            &config.settings.ssh_alias_template,
+        ) {
+            Ok(alias) => aliases.push(alias),
+            Err(e) => eprintln!(
+                "{} {}",
+                "!".yellow(),
+                tr!("switch-ssh-config-failed", host = host, error = e)
+            ),
+        }
+    }
+
+    Ok(aliases)
 }
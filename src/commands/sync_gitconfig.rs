@@ -0,0 +1,283 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+
+use crate::ascii::sym;
+use crate::commands::export::contract_home;
+use crate::commands::switch::identity_values;
+use crate::config::{Config, State};
+use crate::git::{self, GitConfigManager};
+use crate::i18n::tr;
+use crate::rules::RuleType;
+
+/// includeIf blocks wanted in the global config and the ones already there
+struct Plan {
+    /// (gitdir, identity) from path rules, lowest priority first
+    blocks: Vec<(String, String)>,
+    /// Blocks from `gid switch --include-if`, kept after the rule blocks so they win
+    pinned: Vec<(String, String)>,
+    /// Previously synced blocks whose rule is gone
+    stale: Vec<String>,
+    /// Blocks pointing at gid fragments, in file order
+    current: Vec<(String, String)>,
+}
+
+impl Plan {
+    fn target(&self) -> Result<Vec<(String, String)>> {
+        let mut target = Vec::new();
+        for (gitdir, identity) in &self.blocks {
+            target.push((
+                gitdir.clone(),
+                Config::fragment_path(identity)?.display().to_string(),
+            ));
+        }
+        target.extend(self.pinned.iter().cloned());
+        Ok(target)
+    }
+}
+
+/// Write includeIf blocks for path rules into the global Git config
+pub fn execute(dry_run: bool, remove: bool) -> Result<()> {
+    let config = Config::load()?;
+    let git = GitConfigManager::new()?;
+    let global = contract_home(&git::global_config_path()?.display().to_string());
+
+    if remove {
+        return remove_synced(&git, dry_run);
+    }
+
+    let plan = plan(&config, &git)?;
+    if plan.blocks.is_empty() && plan.stale.is_empty() {
+        println!("{} {}", "!".yellow(), tr!("sync-no-rules"));
+        return Ok(());
+    }
+
+    println!("{}", tr!("sync-header", path = global).bold());
+    for (gitdir, identity) in &plan.blocks {
+        let existing = plan.current.iter().any(|(g, _)| g == gitdir);
+        let marker = if existing { "=".dimmed() } else { "+".green() };
+        println!(
+            "  {} gitdir:{}  {} {}",
+            marker,
+            gitdir,
+            sym("→"),
+            identity.cyan()
+        );
+    }
+    for gitdir in &plan.stale {
+        println!(
+            "  {} gitdir:{}  {}",
+            "-".red(),
+            gitdir,
+            tr!("sync-rule-removed").dimmed()
+        );
+    }
+    println!();
+
+    if dry_run {
+        println!("{} {}", "!".yellow(), tr!("sync-dry-run", path = global));
+        return Ok(());
+    }
+
+    let changed = apply(&config, &git, &plan)?;
+    if changed {
+        println!(
+            "{} {}",
+            sym("✓").green(),
+            tr!("sync-done", count = plan.blocks.len())
+        );
+        println!("  {}", tr!("sync-automatic").dimmed());
+    } else {
+        println!("{} {}", sym("✓").green(), tr!("sync-in-sync"));
+    }
+
+    Ok(())
+}
+
+/// Re-sync after rules change, once `gid sync-gitconfig` has been run
+pub fn resync(config: &Config) {
+    let synced = State::load()
+        .map(|state| !state.synced_gitdirs.is_empty())
+        .unwrap_or(false);
+    if !synced {
+        return;
+    }
+
+    let result = GitConfigManager::new().and_then(|git| {
+        let plan = plan(config, &git)?;
+        apply(config, &git, &plan)
+    });
+    match result {
+        Ok(true) => println!("{} {}", sym("✓").green(), tr!("sync-resynced")),
+        Ok(false) => {}
+        Err(e) => println!(
+            "{} {}",
+            "!".yellow(),
+            tr!("sync-resync-failed", error = format!("{e:#}"))
+        ),
+    }
+}
+
+fn plan(config: &Config, git: &GitConfigManager) -> Result<Plan> {
+    let state = State::load()?;
+    let fragment_dir = fragment_dir()?;
+
+    // Rules are ordered by priority and the first match wins; in Git the last block wins
+    let mut blocks: Vec<(String, String)> = Vec::new();
+    for rule in &config.rules {
+        let RuleType::Path { ref pattern } = rule.rule_type else {
+            continue;
+        };
+        if !rule.enabled || config.find_identity(&rule.identity).is_none() {
+            continue;
+        }
+        let Some(gitdir) = rule_gitdir(pattern) else {
+            continue;
+        };
+        if !blocks.iter().any(|(g, _)| *g == gitdir) {
+            blocks.push((gitdir, rule.identity.clone()));
+        }
+    }
+    blocks.reverse();
+
+    let current: Vec<(String, String)> = git
+        .include_if_entries()
+        .into_iter()
+        .filter(|(_, path)| Path::new(path).parent() == Some(fragment_dir.as_path()))
+        .collect();
+
+    let wanted = |gitdir: &str| blocks.iter().any(|(g, _)| g == gitdir);
+    let stale = state
+        .synced_gitdirs
+        .iter()
+        .filter(|gitdir| !wanted(gitdir))
+        .cloned()
+        .collect();
+    let pinned = current
+        .iter()
+        .filter(|(gitdir, _)| !wanted(gitdir) && !state.synced_gitdirs.contains(gitdir))
+        .cloned()
+        .collect();
+
+    Ok(Plan {
+        blocks,
+        pinned,
+        stale,
+        current,
+    })
+}
+
+/// Write fragments and blocks, returning whether anything changed
+fn apply(config: &Config, git: &GitConfigManager, plan: &Plan) -> Result<bool> {
+    let mut changed = false;
+
+    let mut identities: Vec<&str> = plan.blocks.iter().map(|(_, id)| id.as_str()).collect();
+    identities.sort();
+    identities.dedup();
+    for id in identities {
+        let Some(identity) = config.find_identity(id) else {
+            continue;
+        };
+        let fragment = Config::fragment_path(id)?;
+        let values = identity_values(identity);
+        if !git::config_fragment_matches(&fragment, &values) {
+            git::write_config_fragment(&fragment, &values)?;
+            changed = true;
+        }
+    }
+
+    let target = plan.target()?;
+    if plan.current != target {
+        let mut remove: Vec<String> = plan.current.iter().map(|(g, _)| g.clone()).collect();
+        remove.extend(plan.stale.iter().cloned());
+        remove.sort();
+        remove.dedup();
+        git.rewrite_include_ifs(&remove, &target)?;
+        changed = true;
+    }
+
+    let synced: Vec<String> = plan.blocks.iter().map(|(g, _)| g.clone()).collect();
+    State::update(|state| {
+        state.synced_gitdirs = synced;
+        Ok(())
+    })?;
+
+    Ok(changed)
+}
+
+/// Remove every block written by sync-gitconfig and stop syncing
+fn remove_synced(git: &GitConfigManager, dry_run: bool) -> Result<()> {
+    let state = State::load()?;
+    if state.synced_gitdirs.is_empty() {
+        println!("{} {}", "!".yellow(), tr!("sync-none"));
+        return Ok(());
+    }
+
+    for gitdir in &state.synced_gitdirs {
+        println!("  {} gitdir:{}", "-".red(), gitdir);
+    }
+    println!();
+
+    if dry_run {
+        println!("{} {}", "!".yellow(), tr!("sync-remove-dry-run"));
+        return Ok(());
+    }
+
+    // Fragments still used by `gid switch --include-if` blocks are kept
+    let fragment_dir = fragment_dir()?;
+    let mut fragments = 0;
+    for gitdir in &state.synced_gitdirs {
+        if git
+            .remove_include_if_with_fragment(gitdir, &fragment_dir)?
+            .is_some()
+        {
+            fragments += 1;
+        }
+    }
+    let count = state.synced_gitdirs.len();
+    State::update(|current| {
+        current
+            .synced_gitdirs
+            .retain(|gitdir| !state.synced_gitdirs.contains(gitdir));
+        Ok(())
+    })?;
+
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!("sync-removed", count = count, fragments = fragments)
+    );
+
+    Ok(())
+}
+
+/// Directory holding the identity fragments gid writes
+fn fragment_dir() -> Result<PathBuf> {
+    let path = Config::fragment_path("_")?;
+    Ok(path.parent().map(Path::to_path_buf).unwrap_or_default())
+}
+
+/// includeIf gitdir for a path rule (a trailing `/` covers every repository below)
+fn rule_gitdir(pattern: &str) -> Option<String> {
+    let dir = pattern.trim_end_matches("**").trim_end_matches('/');
+    if dir.is_empty() {
+        return None;
+    }
+    Some(format!("{dir}/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_gitdir() {
+        assert_eq!(rule_gitdir("~/work/**").as_deref(), Some("~/work/"));
+        assert_eq!(rule_gitdir("/src/oss").as_deref(), Some("/src/oss/"));
+        assert_eq!(
+            rule_gitdir("**/clients/*/**").as_deref(),
+            Some("**/clients/*/")
+        );
+        assert_eq!(rule_gitdir("**"), None);
+    }
+}
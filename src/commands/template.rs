@@ -0,0 +1,124 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+
+use crate::ascii::sym;
+use crate::cli::TemplateAction;
+use crate::commands::edit::{editor, open_editor};
+use crate::config::identity::expand_path;
+use crate::config::{journal, Config};
+use crate::i18n::tr;
+
+/// Starting point for a new template; git strips the comment lines
+const STARTER: &str = "\n\n# Commit message template for {id}, applied as commit.template by gid switch.\n# Lines starting with # are removed from the commit message.\n";
+
+/// Execute template command
+pub fn execute(action: TemplateAction) -> Result<()> {
+    match action {
+        TemplateAction::Edit { identity } => edit(&identity),
+        TemplateAction::Show { identity } => show(&identity),
+        TemplateAction::Remove { identity } => remove(&identity),
+    }
+}
+
+/// Create or edit the identity's commit template and reference it from the identity
+fn edit(identity_id: &str) -> Result<()> {
+    let config = Config::load()?;
+    let identity = config
+        .find_identity(identity_id)
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?;
+
+    let path = match identity.commit_template {
+        Some(ref template) => expand_path(template),
+        None => Config::template_path(identity_id)?,
+    };
+    if !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| tr!("dir-create-failed", path = parent.display()))?;
+        }
+        journal::record(&path);
+        fs::write(&path, STARTER.replace("{id}", identity_id))
+            .with_context(|| tr!("file-write-failed", path = path.display()))?;
+    }
+
+    let editor = editor();
+    println!(
+        "{} {}",
+        sym("→").blue(),
+        tr!("template-editing", id = identity_id, editor = editor)
+    );
+    println!("  {}", path.display().to_string().dimmed());
+    open_editor(&editor, &path)?;
+
+    if identity.commit_template.is_none() {
+        Config::update(|config| {
+            let identity = config
+                .identities
+                .iter_mut()
+                .find(|i| i.id == identity_id)
+                .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?;
+            identity.commit_template.get_or_insert(path);
+            Ok(())
+        })?;
+    }
+
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!(
+            "template-saved",
+            command = format!("gid switch {identity_id}").cyan()
+        )
+    );
+
+    Ok(())
+}
+
+/// Print the identity's commit template
+fn show(identity_id: &str) -> Result<()> {
+    let config = Config::load()?;
+    let identity = config
+        .find_identity(identity_id)
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?;
+    let template = identity
+        .commit_template
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!(tr!("template-none-hint", id = identity_id)))?;
+
+    let path = expand_path(template);
+    let content = fs::read_to_string(&path)
+        .with_context(|| tr!("file-read-failed", path = path.display()))?;
+    print!("{content}");
+
+    Ok(())
+}
+
+/// Stop using a commit template for the identity, deleting it if gid manages it
+fn remove(identity_id: &str) -> Result<()> {
+    let template = Config::update(|config| {
+        let identity = config
+            .identities
+            .iter_mut()
+            .find(|i| i.id == identity_id)
+            .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?;
+        identity
+            .commit_template
+            .take()
+            .ok_or_else(|| anyhow::anyhow!(tr!("template-none", id = identity_id)))
+    })?;
+
+    let path = expand_path(&template);
+    if path == Config::template_path(identity_id)? && path.exists() {
+        journal::record(&path);
+        fs::remove_file(&path).with_context(|| tr!("file-delete-failed", path = path.display()))?;
+    }
+
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!("template-removed", id = identity_id)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use colored::Colorize;
+
+use crate::ascii::sym;
+use crate::commands::export::contract_home;
+use crate::commands::list::format_relative;
+use crate::config::journal::{self, FileSnapshot, Journal, JournalEntry};
+use crate::i18n::tr;
+
+/// Revert the most recent gid operation, or list the journal
+pub fn execute(list: bool, force: bool) -> Result<()> {
+    // Restoring files must not create a new journal entry
+    journal::suspend();
+
+    if list {
+        print_journal(&Journal::load()?);
+        return Ok(());
+    }
+
+    // The journal stays locked while files are restored, so no entry recorded meanwhile is lost
+    let undone = Journal::update(|journal| {
+        // Operations whose files have not changed (e.g. switching to the current identity) have nothing to revert
+        while journal
+            .entries
+            .last()
+            .is_some_and(|entry| entry.files.iter().all(FileSnapshot::is_current))
+        {
+            journal.entries.pop();
+        }
+
+        let Some(entry) = journal.entries.pop() else {
+            return Ok(None);
+        };
+
+        // Restoring would silently discard edits made after the operation
+        let edited: Vec<&FileSnapshot> = entry.files.iter().filter(|f| f.changed_since()).collect();
+        if !edited.is_empty() && !force {
+            for file in &edited {
+                println!(
+                    "  {} {}",
+                    tr!("undo-edited").yellow(),
+                    contract_home(&file.path.display().to_string())
+                );
+            }
+            anyhow::bail!(tr!("undo-edited-since", command = entry.command));
+        }
+
+        for file in entry.files.iter().rev() {
+            file.restore()?;
+            let action = if file.content.is_some() {
+                tr!("undo-restored")
+            } else {
+                tr!("undo-removed")
+            };
+            println!(
+                "  {} {}",
+                action.dimmed(),
+                contract_home(&file.path.display().to_string())
+            );
+        }
+
+        Ok(Some(entry))
+    })?;
+
+    let Some(entry) = undone else {
+        println!("{} {}", "!".yellow(), tr!("undo-nothing"));
+        return Ok(());
+    };
+
+    println!(
+        "{} {}",
+        sym("✓").green(),
+        tr!(
+            "undo-done",
+            command = entry.command.cyan(),
+            when = when(&entry)
+        )
+    );
+
+    Ok(())
+}
+
+fn print_journal(journal: &Journal) {
+    if journal.entries.is_empty() {
+        println!("{} {}", "!".yellow(), tr!("undo-empty"));
+        return;
+    }
+
+    println!("{}", tr!("undo-list-header").bold());
+    println!();
+    for entry in journal.entries.iter().rev() {
+        println!(
+            "  {:<10} {} {}",
+            when(entry).dimmed(),
+            entry.command,
+            tr!("undo-list-files", count = entry.files.len()).dimmed()
+        );
+    }
+}
+
+fn when(entry: &JournalEntry) -> String {
+    DateTime::parse_from_rfc3339(&entry.time)
+        .map(|t| format_relative(t.with_timezone(&Local)))
+        .unwrap_or_else(|_| entry.time.clone())
+}
@@ -0,0 +1,318 @@
+use anyhow::Result;
+use colored::Colorize;
+
+use crate::ascii::sym;
+use crate::commands::list::pad;
+use crate::config::{Config, Identity};
+use crate::git::{CommitSignature, GitConfigManager, SignatureStatus};
+use crate::gpg::{GpgManager, KeyStatus};
+use crate::i18n::tr;
+use crate::ssh::SshManager;
+
+/// Forge checked with `--forge` when the identity lists no ssh_hosts
+const DEFAULT_FORGE_HOST: &str = "github.com";
+
+/// Outcome of a single check
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+struct Check {
+    label: String,
+    outcome: Outcome,
+    detail: String,
+}
+
+impl Check {
+    fn new(label: impl Into<String>, outcome: Outcome, detail: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            outcome,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Validate one identity end to end and print a pass/fail checklist
+pub fn execute(identity_id: &str, forge: bool) -> Result<()> {
+    let config = Config::load()?;
+    let identity = config
+        .find_identity(identity_id)
+        .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = identity_id)))?;
+
+    println!(
+        "{}",
+        tr!(
+            "verify-header",
+            id = format!("[{}]", identity.id).green().bold(),
+            name = identity.name,
+            email = identity.email.cyan()
+        )
+    );
+    println!();
+
+    let mut checks = check_fields(identity);
+    checks.extend(check_ssh(identity, forge));
+    checks.extend(check_gpg(identity));
+
+    let width = checks
+        .iter()
+        .map(|c| console::measure_text_width(&c.label))
+        .max()
+        .unwrap_or(0);
+    for check in &checks {
+        let marker = match check.outcome {
+            Outcome::Pass => sym("✓").green(),
+            Outcome::Warn => "!".yellow(),
+            Outcome::Fail => sym("✗").red(),
+            Outcome::Skip => "-".dimmed(),
+        };
+        let detail = match check.outcome {
+            Outcome::Fail => check.detail.red(),
+            Outcome::Warn => check.detail.yellow(),
+            Outcome::Pass | Outcome::Skip => check.detail.dimmed(),
+        };
+        println!("  {} {}  {}", marker, pad(&check.label, width), detail);
+    }
+
+    let count = |outcome| checks.iter().filter(|c| c.outcome == outcome).count();
+    let failed = count(Outcome::Fail);
+    println!();
+    println!(
+        "{}",
+        tr!(
+            "verify-summary",
+            passed = count(Outcome::Pass).to_string().green(),
+            warnings = count(Outcome::Warn),
+            failed = failed.to_string().red()
+        )
+    );
+
+    if failed > 0 {
+        anyhow::bail!(tr!("verify-failed", id = identity.id));
+    }
+
+    Ok(())
+}
+
+fn check_fields(identity: &Identity) -> Vec<Check> {
+    let name = if identity.name.trim().is_empty() {
+        Check::new(tr!("verify-name"), Outcome::Fail, tr!("verify-name-empty"))
+    } else {
+        Check::new(tr!("verify-name"), Outcome::Pass, identity.name.clone())
+    };
+
+    let email = match identity.email.split_once('@') {
+        Some((local, domain))
+            if !local.is_empty()
+                && domain.contains('.')
+                && !identity.email.contains(char::is_whitespace) =>
+        {
+            Check::new(tr!("verify-email"), Outcome::Pass, identity.email.clone())
+        }
+        _ => Check::new(
+            tr!("verify-email"),
+            Outcome::Fail,
+            tr!("verify-email-invalid", email = identity.email),
+        ),
+    };
+
+    vec![name, email]
+}
+
+fn check_ssh(identity: &Identity, forge: bool) -> Vec<Check> {
+    let Some(key) = identity.ssh_key_path() else {
+        return vec![Check::new(
+            tr!("verify-ssh-key"),
+            Outcome::Skip,
+            tr!("verify-ssh-key-none"),
+        )];
+    };
+    let display = key.display().to_string();
+
+    if !key.exists() {
+        return vec![Check::new(
+            tr!("verify-ssh-key"),
+            Outcome::Fail,
+            tr!("verify-not-found", path = display),
+        )];
+    }
+
+    let mut checks = vec![Check::new(
+        tr!("verify-ssh-key"),
+        Outcome::Pass,
+        display.clone(),
+    )];
+
+    match crate::permissions::check_owner_only(&key) {
+        Ok(None) => checks.push(Check::new(
+            tr!("verify-permissions"),
+            Outcome::Pass,
+            tr!("verify-owner-only"),
+        )),
+        Ok(Some(access)) => checks.push(Check::new(
+            tr!("verify-permissions"),
+            Outcome::Fail,
+            tr!("verify-too-open", access = access),
+        )),
+        Err(e) => log::debug!("could not check permissions of {display}: {e}"),
+    }
+
+    let Ok(ssh) = SshManager::new() else {
+        return checks;
+    };
+
+    let fingerprint = match ssh.fingerprint(&key) {
+        Ok(fingerprint) => {
+            checks.push(Check::new(
+                tr!("verify-fingerprint"),
+                Outcome::Pass,
+                fingerprint.clone(),
+            ));
+            Some(fingerprint)
+        }
+        Err(e) => {
+            checks.push(Check::new(
+                tr!("verify-fingerprint"),
+                Outcome::Fail,
+                e.to_string(),
+            ));
+            None
+        }
+    };
+
+    let agent_keys = ssh.list_agent_keys().unwrap_or_default();
+    checks.push(match fingerprint {
+        Some(ref fingerprint) if agent_keys.iter().any(|k| k.contains(fingerprint.as_str())) => {
+            Check::new("ssh-agent", Outcome::Pass, tr!("verify-agent-loaded"))
+        }
+        _ if std::env::var_os("SSH_AUTH_SOCK").is_none() => {
+            Check::new("ssh-agent", Outcome::Skip, tr!("verify-agent-none"))
+        }
+        _ => Check::new(
+            "ssh-agent",
+            Outcome::Warn,
+            tr!("verify-agent-not-loaded", path = display),
+        ),
+    });
+
+    let hosts: Vec<&str> = if identity.ssh_hosts.is_empty() {
+        vec![DEFAULT_FORGE_HOST]
+    } else {
+        identity.ssh_hosts.iter().map(String::as_str).collect()
+    };
+    for host in hosts {
+        let label = tr!("verify-forge", host = host);
+        if !forge {
+            checks.push(Check::new(
+                label,
+                Outcome::Skip,
+                tr!("verify-forge-skipped"),
+            ));
+            continue;
+        }
+        if crate::offline::is_offline() {
+            checks.push(Check::new(label, Outcome::Skip, tr!("skipped-offline")));
+            continue;
+        }
+        checks.push(match ssh.test_auth(host, &key) {
+            Ok(greeting) => Check::new(label, Outcome::Pass, greeting),
+            Err(e) => Check::new(label, Outcome::Fail, e.to_string()),
+        });
+    }
+
+    checks
+}
+
+fn check_gpg(identity: &Identity) -> Vec<Check> {
+    let Some(key_id) = &identity.gpg_key else {
+        let outcome = if identity.gpg_sign {
+            Outcome::Fail
+        } else {
+            Outcome::Skip
+        };
+        let detail = if identity.gpg_sign {
+            tr!("verify-gpg-key-unset")
+        } else {
+            tr!("verify-gpg-key-none")
+        };
+        return vec![Check::new(tr!("verify-gpg-key"), outcome, detail)];
+    };
+
+    let gpg = GpgManager::new();
+    if !gpg.is_available() {
+        return vec![Check::new(
+            tr!("verify-gpg-key"),
+            Outcome::Fail,
+            tr!("verify-gpg-missing"),
+        )];
+    }
+
+    let status = match gpg.key_status(key_id) {
+        Ok(KeyStatus::Valid) => Check::new(tr!("verify-gpg-key"), Outcome::Pass, key_id.clone()),
+        Ok(KeyStatus::Expired) => Check::new(
+            tr!("verify-gpg-key"),
+            Outcome::Fail,
+            tr!("verify-gpg-expired", key = key_id),
+        ),
+        Ok(KeyStatus::Revoked) => Check::new(
+            tr!("verify-gpg-key"),
+            Outcome::Fail,
+            tr!("verify-gpg-revoked", key = key_id),
+        ),
+        Ok(KeyStatus::Missing) | Err(_) => Check::new(
+            tr!("verify-gpg-key"),
+            Outcome::Fail,
+            tr!("verify-gpg-not-in-keyring", key = key_id),
+        ),
+    };
+    if status.outcome == Outcome::Fail {
+        return vec![status];
+    }
+
+    let mut checks = vec![status];
+
+    // Signed commits only verify on forges when the key carries the commit email
+    let key_email = gpg.list_keys().ok().and_then(|keys| {
+        keys.into_iter()
+            .find(|k| k.key_id.ends_with(key_id.as_str()) || key_id.ends_with(&k.key_id))
+            .and_then(|k| k.email)
+    });
+    if let Some(key_email) = key_email {
+        checks.push(if key_email.eq_ignore_ascii_case(&identity.email) {
+            Check::new("GPG UID", Outcome::Pass, key_email)
+        } else {
+            Check::new(
+                "GPG UID",
+                Outcome::Warn,
+                tr!(
+                    "verify-gpg-uid-mismatch",
+                    key_email = key_email,
+                    email = identity.email
+                ),
+            )
+        });
+    }
+
+    // Sign some data and check the result the way Git checks commit signatures
+    let data = b"gid verify\n";
+    let verified = gpg.sign(key_id, data).and_then(|signature| {
+        let signature = CommitSignature::new(signature, data.to_vec());
+        Ok(GitConfigManager::new()?.verify_signature(&signature))
+    });
+    checks.push(match verified {
+        Ok(SignatureStatus::Good(_)) => Check::new(
+            tr!("verify-gpg-signing"),
+            Outcome::Pass,
+            tr!("verify-gpg-signing-ok"),
+        ),
+        Ok(status) => Check::new(tr!("verify-gpg-signing"), Outcome::Fail, status.to_string()),
+        Err(e) => Check::new(tr!("verify-gpg-signing"), Outcome::Fail, e.to_string()),
+    });
+
+    checks
+}
@@ -0,0 +1,183 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::ascii::sym;
+use crate::config::{journal, Config};
+use crate::git::GitConfigManager;
+use crate::i18n::tr;
+use crate::rules::resolve::RepoStatus;
+
+/// Watch workspace roots and enforce rule-resolved identities
+pub fn execute(paths: Vec<PathBuf>, interval: u64, check: bool, once: bool) -> Result<()> {
+    let roots = if paths.is_empty() {
+        Config::load()?.settings.workspace_root_paths()
+    } else {
+        paths
+    };
+
+    if roots.is_empty() {
+        anyhow::bail!(tr!("watch-no-paths"));
+    }
+
+    let mode = if check {
+        tr!("watch-mode-check")
+    } else {
+        tr!("watch-mode-enforce")
+    };
+    let roots_list = roots
+        .iter()
+        .map(|r| r.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    println!(
+        "{} {}",
+        sym("→").blue(),
+        tr!(
+            "watch-started",
+            paths = roots_list.cyan(),
+            interval = interval,
+            mode = mode
+        )
+    );
+
+    // Repository -> last seen modification times of its config and .gid
+    let mut seen: HashMap<PathBuf, Fingerprint> = HashMap::new();
+    let mut config_stamp = config_mtime();
+    let mut config = Config::load()?;
+
+    loop {
+        // Reload when config.toml changes so rule and identity changes take
+        // effect, and look at every repository again under the new config
+        let stamp = config_mtime();
+        if stamp != config_stamp {
+            match Config::load() {
+                Ok(loaded) => {
+                    config = loaded;
+                    config_stamp = stamp;
+                    seen.clear();
+                }
+                // Half-saved while being edited: keep going with the last good one
+                Err(e) => log_line(&format!(
+                    "{} {}",
+                    sym("⚠").yellow(),
+                    tr!("watch-config-invalid", error = format!("{e:#}"))
+                )),
+            }
+        }
+
+        for root in &roots {
+            for repo in crate::git::discover_repos(root, crate::git::DEFAULT_SCAN_DEPTH) {
+                let fingerprint = fingerprint(&repo);
+                if seen.get(&repo) == Some(&fingerprint) {
+                    continue;
+                }
+                seen.insert(repo.clone(), fingerprint);
+
+                let result = process_repo(&config, &repo, check);
+                // Each repository is its own operation for `gid undo`
+                journal::seal();
+                if let Err(e) = result {
+                    log_line(&format!("{} {}: {}", sym("✗").red(), repo.display(), e));
+                    // Retry on the next pass, e.g. once an unfinished rebase is done
+                    seen.remove(&repo);
+                }
+            }
+        }
+
+        if once {
+            break;
+        }
+
+        std::thread::sleep(Duration::from_secs(interval));
+    }
+
+    Ok(())
+}
+
+/// Check a new or changed repository and apply (or report) the expected identity
+fn process_repo(config: &Config, repo: &Path, check: bool) -> Result<()> {
+    let status = RepoStatus::inspect(config, repo)?;
+
+    if !status.is_drifted(config) {
+        return Ok(());
+    }
+
+    let Some(expected) = status.expected else {
+        return Ok(());
+    };
+
+    let identity = config
+        .find_identity(&expected.identity)
+        .ok_or_else(|| anyhow::anyhow!(tr!("watch-unknown-identity", id = expected.identity)))?;
+
+    let current = status
+        .effective_email
+        .clone()
+        .unwrap_or_else(|| tr!("repos-not-set"));
+
+    let location = match &status.remote {
+        Some(remote) => format!("{} ({})", status.path.display(), remote),
+        None => status.path.display().to_string(),
+    };
+
+    if check {
+        log_line(&format!(
+            "{} {}: {}",
+            sym("⚠").yellow(),
+            location,
+            tr!(
+                "watch-drifted",
+                current = current.yellow(),
+                expected = format!("[{}]", identity.id).cyan(),
+                source = expected.source
+            )
+        ));
+        return Ok(());
+    }
+
+    let git = GitConfigManager::from_path(&status.path)?;
+    crate::commands::switch::apply_to_repo(&git, identity)?;
+
+    log_line(&format!(
+        "{} {}: {} {} {} ({})",
+        sym("✓").green(),
+        location,
+        current.dimmed(),
+        sym("→"),
+        format!("[{}]", identity.id).green(),
+        expected.source
+    ));
+
+    Ok(())
+}
+
+/// Modification times of the repository config and its .gid
+type Fingerprint = (Option<SystemTime>, Option<SystemTime>);
+
+/// What a repository's expected and current identity depend on, to detect changes
+fn fingerprint(repo: &Path) -> Fingerprint {
+    let dot_git = repo.join(".git");
+    let config = dot_git.join("config");
+    let path = if config.exists() { config } else { dot_git };
+    (mtime(&path), mtime(&repo.join(".gid")))
+}
+
+/// Modification time of config.toml
+fn config_mtime() -> Option<SystemTime> {
+    mtime(&Config::config_path().ok()?)
+}
+
+fn mtime(path: &Path) -> Option<SystemTime> {
+    path.metadata().and_then(|m| m.modified()).ok()
+}
+
+fn log_line(message: &str) {
+    println!(
+        "[{}] {}",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+        message
+    );
+}
@@ -0,0 +1,228 @@
+use anyhow::Result;
+use colored::Colorize;
+use serde_json::json;
+
+use crate::ascii::sym;
+use crate::commands::list::pad;
+use crate::config::{Config, Identity};
+use crate::exit::Failure;
+use crate::gh::{GhManager, DEFAULT_HOST};
+use crate::git::GitConfigManager;
+use crate::i18n::tr;
+use crate::ssh::{greeting_login, SshManager};
+
+/// What a forge reported for one way of authenticating
+struct RemoteAccount {
+    login: Option<String>,
+    emails: Vec<String>,
+    error: Option<String>,
+}
+
+/// Show the active identity, and with `--remote` the forge accounts behind it
+pub fn execute(remote: bool, json: bool) -> Result<()> {
+    let config = Config::load()?;
+    let git = GitConfigManager::new()?;
+
+    let name = git.get_effective_user_name();
+    let email = git
+        .get_effective_user_email()
+        .ok_or_else(|| anyhow::anyhow!(tr!("whoami-no-email")))?;
+    let identity = config
+        .identities
+        .iter()
+        .find(|i| i.email == email && name.as_deref().is_none_or(|n| n == i.name))
+        .or_else(|| config.identities.iter().find(|i| i.email == email));
+
+    if !remote {
+        if json {
+            let value = json!({
+                "identity": identity.map(|i| &i.id),
+                "name": name,
+                "email": email,
+            });
+            println!("{}", serde_json::to_string_pretty(&value)?);
+            return Ok(());
+        }
+        match identity {
+            Some(identity) => println!(
+                "{} {} <{}>",
+                format!("[{}]", identity.id).green().bold(),
+                identity.name,
+                identity.email.cyan()
+            ),
+            None => println!(
+                "{} <{}> {}",
+                name.unwrap_or_else(|| tr!("hook-no-name")),
+                email.cyan(),
+                tr!("whoami-not-gid").dimmed()
+            ),
+        }
+        return Ok(());
+    }
+
+    let identity =
+        identity.ok_or_else(|| anyhow::anyhow!(tr!("whoami-unknown-email", email = email)))?;
+    crate::offline::ensure_online(&tr!("whoami-checking"))?;
+
+    // The remote's host (possibly an SSH alias) decides which key git would use
+    let ssh_host = git
+        .get_origin_url()
+        .filter(|url| crate::git::is_ssh_url(url))
+        .and_then(|url| crate::git::url_host(&url))
+        .or_else(|| identity.ssh_hosts.first().cloned())
+        .unwrap_or_else(|| DEFAULT_HOST.to_string());
+    let ssh = SshManager::new()?;
+    let api_host = ssh.resolve_route(&ssh_host).hostname;
+
+    let via_ssh = match ssh.whoami(&ssh_host) {
+        Ok(greeting) => RemoteAccount {
+            login: greeting_login(&greeting),
+            emails: Vec::new(),
+            error: None,
+        },
+        Err(e) => RemoteAccount {
+            login: None,
+            emails: Vec::new(),
+            error: Some(format!("{e:#}")),
+        },
+    };
+
+    let gh = GhManager::new();
+    let via_api = if gh.is_available() {
+        match gh.api_user(&api_host) {
+            Ok(user) => RemoteAccount {
+                login: Some(user.login),
+                emails: user.emails,
+                error: None,
+            },
+            Err(e) => RemoteAccount {
+                login: None,
+                emails: Vec::new(),
+                error: Some(format!("{e:#}")),
+            },
+        }
+    } else {
+        RemoteAccount {
+            login: None,
+            emails: Vec::new(),
+            error: Some(tr!("whoami-gh-missing")),
+        }
+    };
+
+    let mismatches = compare(identity, &via_ssh, &via_api);
+
+    if json {
+        let account = |a: &RemoteAccount| {
+            json!({
+                "login": a.login,
+                "emails": a.emails,
+                "error": a.error,
+            })
+        };
+        let value = json!({
+            "identity": identity.id,
+            "email": identity.email,
+            "gh_account": identity.gh_account,
+            "ssh_host": ssh_host,
+            "api_host": api_host,
+            "ssh": account(&via_ssh),
+            "api": account(&via_api),
+            "mismatches": mismatches,
+        });
+        println!("{}", serde_json::to_string_pretty(&value)?);
+    } else {
+        println!(
+            "{} {} <{}>",
+            format!("[{}]", identity.id).green().bold(),
+            identity.name,
+            identity.email.cyan()
+        );
+        print_account(&format!("SSH ({ssh_host})"), &via_ssh);
+        print_account(&tr!("whoami-token", host = api_host), &via_api);
+        if !via_api.emails.is_empty() {
+            println!(
+                "  {} {}",
+                pad(&tr!("whoami-emails"), 24).dimmed(),
+                via_api.emails.join(", ")
+            );
+        }
+
+        println!();
+        if mismatches.is_empty() {
+            println!(
+                "{} {}",
+                sym("✓").green(),
+                tr!("whoami-match", id = identity.id)
+            );
+        } else {
+            for mismatch in &mismatches {
+                println!("{} {}", sym("✗").red(), mismatch);
+            }
+        }
+    }
+
+    if !mismatches.is_empty() {
+        return Err(Failure::Mismatch.error(tr!("whoami-mismatch", id = identity.id)));
+    }
+
+    Ok(())
+}
+
+fn print_account(label: &str, account: &RemoteAccount) {
+    let label = pad(label, 24);
+    match (&account.login, &account.error) {
+        (Some(login), _) => println!("  {} {}", label.dimmed(), login.green()),
+        (None, Some(error)) => println!("  {} {}", label.dimmed(), error.yellow()),
+        (None, None) => println!(
+            "  {} {}",
+            label.dimmed(),
+            tr!("whoami-account-unknown").yellow()
+        ),
+    }
+}
+
+/// Everything the forge reported that disagrees with the identity
+fn compare(identity: &Identity, via_ssh: &RemoteAccount, via_api: &RemoteAccount) -> Vec<String> {
+    let mut mismatches = Vec::new();
+    let same = |a: &str, b: &str| a.eq_ignore_ascii_case(b);
+
+    if let Some(ref expected) = identity.gh_account {
+        for (how, account) in [
+            (tr!("whoami-via-ssh"), via_ssh),
+            (tr!("whoami-via-token"), via_api),
+        ] {
+            if let Some(ref login) = account.login {
+                if !same(login, expected) {
+                    mismatches.push(tr!(
+                        "whoami-login-mismatch",
+                        how = how,
+                        login = login,
+                        id = identity.id,
+                        expected = expected
+                    ));
+                }
+            }
+        }
+    } else if let (Some(ssh_login), Some(api_login)) = (&via_ssh.login, &via_api.login) {
+        if !same(ssh_login, api_login) {
+            mismatches.push(tr!(
+                "whoami-logins-differ",
+                ssh_login = ssh_login,
+                api_login = api_login
+            ));
+        }
+    }
+
+    if !via_api.emails.is_empty() && !via_api.emails.iter().any(|e| same(e, &identity.email)) {
+        mismatches.push(tr!(
+            "whoami-email-not-on-account",
+            email = identity.email,
+            account = via_api
+                .login
+                .clone()
+                .unwrap_or_else(|| tr!("whoami-the-account"))
+        ));
+    }
+
+    mismatches
+}
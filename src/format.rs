@@ -0,0 +1,189 @@
+//! `--format` templates for list, current, repos and audit
+//!
+//! `{field}` prints a field of the record the command would output with
+//! `--json`, and `{effective.email}` reaches into nested objects. Unset values
+//! print as nothing and lists as comma-separated values. `\t`, `\n` and `\\`
+//! are unescaped so shells need no `$'...'` quoting; `{{` and `}}` print
+//! literal braces. Each record is printed on its own line.
+
+use anyhow::Result;
+use serde_json::Value;
+
+use crate::exit::Failure;
+use crate::i18n::tr;
+
+/// A parsed `--format` template
+#[derive(Debug)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Part {
+    Text(String),
+    Field(Vec<String>),
+}
+
+impl Template {
+    /// Parse a template, rejecting unbalanced braces and empty fields
+    pub fn parse(template: &str) -> Result<Self> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('t') => text.push('\t'),
+                    Some('n') => text.push('\n'),
+                    Some('\\') => text.push('\\'),
+                    Some(other) => {
+                        text.push('\\');
+                        text.push(other);
+                    }
+                    None => text.push('\\'),
+                },
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some('{') | None => {
+                                return Err(Failure::Usage
+                                    .error(tr!("format-unclosed", template = template)))
+                            }
+                            Some(c) => name.push(c),
+                        }
+                    }
+                    let path: Vec<String> = name.trim().split('.').map(str::to_string).collect();
+                    if path.iter().any(String::is_empty) {
+                        return Err(Failure::Usage.error(tr!("format-invalid-field", name = name)));
+                    }
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(Part::Field(path));
+                }
+                '}' => return Err(Failure::Usage.error(tr!("format-unmatched"))),
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+
+        Ok(Self { parts })
+    }
+
+    /// Fill the template from one record
+    pub fn render(&self, record: &Value) -> Result<String> {
+        let mut output = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Text(text) => output.push_str(text),
+                Part::Field(path) => output.push_str(&scalar(lookup(record, path)?)),
+            }
+        }
+        Ok(output)
+    }
+
+    /// Print one record as a line
+    pub fn print(&self, record: &Value) -> Result<()> {
+        println!("{}", self.render(record)?);
+        Ok(())
+    }
+}
+
+/// Follow a field path; unset parents (null) yield null, unknown names fail
+fn lookup<'a>(record: &'a Value, path: &[String]) -> Result<&'a Value> {
+    let mut value = record;
+    for (depth, key) in path.iter().enumerate() {
+        value = match value {
+            Value::Null => return Ok(value),
+            Value::Object(map) => map.get(key).ok_or_else(|| {
+                let mut fields: Vec<&str> = map.keys().map(String::as_str).collect();
+                fields.sort_unstable();
+                Failure::Usage.error(tr!(
+                    "format-unknown-field",
+                    field = path[..=depth].join("."),
+                    available = fields.join(", ")
+                ))
+            })?,
+            _ => {
+                return Err(Failure::Usage.error(tr!(
+                    "format-not-an-object",
+                    field = path[..depth].join("."),
+                    key = key
+                )))
+            }
+        };
+    }
+    Ok(value)
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items.iter().map(scalar).collect::<Vec<_>>().join(","),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_render_fields() {
+        let record = json!({
+            "id": "work",
+            "tags": ["client", "oss"],
+            "gpg_sign": true,
+            "ssh_key": null,
+            "expected": { "identity": "work" },
+            "missing": null,
+        });
+        let template = Template::parse(
+            r"{id}\t{tags}|{gpg_sign}|{ssh_key}|{expected.identity}|{missing.identity}",
+        )
+        .unwrap();
+        assert_eq!(
+            template.render(&record).unwrap(),
+            "work\tclient,oss|true||work|"
+        );
+
+        let braces = Template::parse("{{{id}}}").unwrap();
+        assert_eq!(braces.render(&record).unwrap(), "{work}");
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        for template in ["{id", "id}", "{}", "{a..b}", "{a{b}}"] {
+            let err = Template::parse(template).unwrap_err();
+            assert_eq!(Failure::of(&err), Failure::Usage, "{template}");
+        }
+
+        let record = json!({ "id": "work", "email": "w@example.com" });
+        let err = Template::parse("{mail}")
+            .unwrap()
+            .render(&record)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unknown field 'mail' in --format (available: email, id)"));
+        assert!(Template::parse("{id.name}")
+            .unwrap()
+            .render(&record)
+            .is_err());
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::{Context, Result};
+use std::process::Command;
+
+use crate::exit::Failure;
+use crate::i18n::tr;
+
+/// Default GitHub host used by gh
+pub const DEFAULT_HOST: &str = "github.com";
+
+/// Account behind the gh token for a host
+#[derive(Debug, Clone)]
+pub struct ApiUser {
+    pub login: String,
+    /// Public email plus the account's emails when the token may read them
+    pub emails: Vec<String>,
+}
+
+/// GitHub CLI (gh) Manager
+pub struct GhManager;
+
+impl GhManager {
+    /// Create new gh manager
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check if gh is available
+    pub fn is_available(&self) -> bool {
+        Command::new("gh")
+            .arg("--version")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Get the active gh account for a host
+    pub fn active_account(&self, host: &str) -> Option<String> {
+        // gh auth status validates the token against the API
+        if crate::offline::is_offline() {
+            return None;
+        }
+        let output = Command::new("gh")
+            .args(["auth", "status", "--active", "--hostname", host])
+            .output()
+            .ok()?;
+
+        // gh prints status to stderr on older versions and stdout on newer ones
+        let text = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        // ✓ Logged in to github.com account octocat (keyring)
+        text.lines().find_map(|line| {
+            let (_, rest) = line.split_once(" account ")?;
+            rest.split_whitespace().next().map(|s| s.to_string())
+        })
+    }
+
+    /// Switch the active gh account for a host
+    pub fn switch_account(&self, host: &str, user: &str) -> Result<()> {
+        log::info!("switching gh account on {host} to {user}");
+        let output = Command::new("gh")
+            .args(["auth", "switch", "--hostname", host, "--user", user])
+            .output()
+            .with_context(|| tr!("gh-failed"))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!(tr!(
+                "gh-command-failed",
+                command = "auth switch",
+                error = stderr.trim()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Ask the API which account the stored token belongs to
+    pub fn api_user(&self, host: &str) -> Result<ApiUser> {
+        let user = self.api(host, "user")?;
+        let login = user["login"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!(tr!("gh-unexpected-response")))?
+            .to_string();
+
+        let mut emails: Vec<String> = user["email"]
+            .as_str()
+            .map(str::to_string)
+            .into_iter()
+            .collect();
+        // Needs the user:email scope; the public email is all we get without it
+        if let Ok(list) = self.api(host, "user/emails") {
+            for entry in list.as_array().into_iter().flatten() {
+                if let Some(email) = entry["email"].as_str() {
+                    if !emails.iter().any(|e| e.eq_ignore_ascii_case(email)) {
+                        emails.push(email.to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(ApiUser { login, emails })
+    }
+
+    fn api(&self, host: &str, endpoint: &str) -> Result<serde_json::Value> {
+        crate::offline::ensure_online(&format!("gh api {endpoint}"))?;
+        let output = Command::new("gh")
+            .args(["api", "--hostname", host, endpoint])
+            .output()
+            .with_context(|| tr!("gh-failed"))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(Failure::Network.error(tr!(
+                "gh-command-failed",
+                command = format!("api {endpoint}"),
+                error = stderr.trim()
+            )));
+        }
+
+        serde_json::from_slice(&output.stdout).with_context(|| tr!("gh-unexpected-response"))
+    }
+}
@@ -0,0 +1,45 @@
+//! Diagnostics on stderr (`-v`, `-vv`) and the `-q` switch for scripts
+//!
+//! Log records describe what gid touches: git config keys, files it writes
+//! and the processes it runs. `GID_LOG` (env_logger filter syntax, e.g.
+//! `GID_LOG=debug`) takes precedence over the flags.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Informational output suppressed with -q
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Set up logging for the process
+///
+/// Warnings are shown by default, `-v` adds what gid changes and `-vv` every
+/// command it runs. `-q` only leaves errors.
+pub fn init(verbose: u8, quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+
+    let level = match (quiet, verbose) {
+        (true, _) => log::LevelFilter::Error,
+        (false, 0) => log::LevelFilter::Warn,
+        (false, 1) => log::LevelFilter::Info,
+        (false, 2) => log::LevelFilter::Debug,
+        (false, _) => log::LevelFilter::Trace,
+    };
+
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_env("GID_LOG")
+        .format(|buf, record| {
+            writeln!(
+                buf,
+                "gid: {}: {}",
+                record.level().as_str().to_lowercase(),
+                record.args()
+            )
+        })
+        .init();
+}
+
+/// Whether informational output should be left out (-q)
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
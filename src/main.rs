@@ -1,28 +1,160 @@
-mod audit;
+mod age;
+mod annotate;
+mod ascii;
 mod cli;
+mod color;
 mod commands;
-mod config;
-mod git;
-mod gpg;
-mod rules;
-mod ssh;
+mod format;
+mod gh;
+mod logging;
+mod tui;
 
-use anyhow::Result;
+// The library modules, reachable as crate::config etc. from the commands
+use gid_core::{atomic, audit, config, exit, git, gpg, i18n, offline, permissions, rules, ssh};
+
+use anyhow::{Context, Result};
 use clap::Parser;
 use cli::{Cli, Commands};
+use exit::Failure;
+use i18n::tr;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(e) => {
+            let _ = e.print();
+            // --help and --version end up here too
+            return if e.use_stderr() {
+                ExitCode::from(Failure::Usage.code())
+            } else {
+                ExitCode::SUCCESS
+            };
+        }
+    };
+
+    // gid check reports a mismatch on stdout itself (with -v), otherwise only
+    // through the exit code, so prompts and hooks can call it silently
+    let check = matches!(cli.command, Commands::Check);
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+    let result = run(cli);
+    // Even a failed command may have changed some files
+    config::journal::seal();
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            let failure = Failure::of(&e);
+            if !(check && failure == Failure::Mismatch) {
+                eprintln!("{} {e:?}", tr!("error-label"));
+            }
+            ExitCode::from(failure.code())
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    logging::init(cli.verbose, cli.quiet);
+
+    // Before --git-dir, so a relative Git directory is resolved from here
+    if let Some(ref dir) = cli.chdir {
+        std::env::set_current_dir(dir)
+            .with_context(|| tr!("chdir-failed", path = dir.display()))?;
+    }
+
+    if let Some(ref config) = cli.config {
+        config::use_config_file(config)?;
+    }
+    color::init(cli.color);
+    ascii::init(cli.ascii);
+    i18n::init();
+    offline::init(cli.offline);
+
+    if let Some(ref git_dir) = cli.git_dir {
+        git::use_git_dir(git_dir)?;
+    }
 
     match cli.command {
-        Commands::Switch { identity, global } => {
-            commands::switch::execute(&identity, global)?;
+        Commands::Switch {
+            identity,
+            global,
+            recursive,
+            include_if,
+            recurse_submodules,
+            yes,
+        } => {
+            if let Some(root) = recursive {
+                commands::switch::execute_recursive(&identity, &root)?;
+            } else if include_if {
+                commands::switch::execute_include_if(&identity)?;
+            } else if !global || commands::switch::confirm_global(&identity, yes)? {
+                commands::switch::execute(&identity, global)?;
+                if recurse_submodules {
+                    commands::switch::execute_submodules(Some(&identity), false)?;
+                }
+            }
+        }
+        Commands::Check => {
+            commands::check::execute(cli.verbose > 0)?;
+        }
+        Commands::Whoami { remote, json } => {
+            commands::whoami::execute(remote, json)?;
+        }
+        Commands::Init {
+            identity,
+            toml,
+            gitignore,
+            commit,
+            force,
+        } => {
+            commands::init::execute(commands::init::InitOptions {
+                identity,
+                toml,
+                gitignore,
+                commit,
+                force,
+            })?;
+        }
+        Commands::Clone {
+            url,
+            dir,
+            identity,
+            write_gid,
+            git_args,
+        } => {
+            commands::clone::execute(&url, dir, identity, write_gid, &git_args)?;
+        }
+        Commands::List {
+            json,
+            table,
+            filter,
+            tag,
+            sort,
+            verify,
+            format,
+        } => {
+            commands::list::execute(commands::list::ListOptions {
+                json,
+                table,
+                filter,
+                tag,
+                sort,
+                verify,
+                format,
+            })?;
         }
-        Commands::List => {
-            commands::list::execute()?;
+        Commands::Show { identity, json } => {
+            commands::show::execute(&identity, json)?;
         }
-        Commands::Current => {
-            commands::current::execute()?;
+        Commands::Current {
+            json,
+            prompt,
+            format,
+        } => {
+            commands::current::execute(json, prompt, format.as_deref())?;
+        }
+        Commands::Onboard { yes } => {
+            commands::onboard::execute(yes)?;
         }
         Commands::Add {
             id,
@@ -37,41 +169,204 @@ fn main() -> Result<()> {
         Commands::Remove { identity } => {
             commands::remove::execute(&identity)?;
         }
+        Commands::Prune { dry_run, yes } => {
+            commands::prune::execute(dry_run, yes)?;
+        }
         Commands::Edit => {
             commands::edit::execute()?;
         }
-        Commands::Export { file } => {
-            commands::export::execute(file)?;
+        Commands::Config { action } => {
+            commands::config::execute(action)?;
+        }
+        Commands::Export {
+            file,
+            identities,
+            rules_only,
+            identities_only,
+            rules_matching,
+            sanitized,
+            encrypt,
+            recipient,
+            format,
+        } => {
+            commands::export::execute(
+                file,
+                commands::export::ExportOptions {
+                    identities,
+                    rules_only,
+                    identities_only,
+                    rules_matching,
+                    sanitized,
+                    encrypt,
+                    recipients: recipient,
+                    format,
+                },
+            )?;
+        }
+        Commands::Import {
+            file,
+            age_identity,
+            format,
+            dry_run,
+            on_conflict,
+            from_gitconfig,
+            yes,
+        } => match file {
+            Some(file) if !from_gitconfig => {
+                commands::import::execute(
+                    &file,
+                    age_identity.as_deref(),
+                    format,
+                    dry_run,
+                    on_conflict,
+                    yes,
+                )?;
+            }
+            _ => commands::import::execute_from_gitconfig(yes)?,
+        },
+        Commands::Undo { list, force } => {
+            commands::undo::execute(list, force)?;
         }
-        Commands::Import { file } => {
-            commands::import::execute(&file)?;
+        Commands::SyncGitconfig { dry_run, remove } => {
+            commands::sync_gitconfig::execute(dry_run, remove)?;
         }
         Commands::Rule { action } => {
             commands::rule::execute(action)?;
         }
-        Commands::Doctor { fix } => {
-            commands::doctor::execute(fix)?;
+        Commands::Template { action } => {
+            commands::template::execute(action)?;
+        }
+        Commands::Credential { action } => {
+            commands::credential::execute(action)?;
+        }
+        Commands::Ruleset { action } => {
+            commands::ruleset::execute(action)?;
+        }
+        Commands::Verify { identity, forge } => {
+            commands::verify::execute(&identity, forge)?;
+        }
+        Commands::Tui => {
+            tui::run()?;
+        }
+        Commands::Doctor {
+            fix,
+            recurse_submodules,
+            annotate,
+        } => {
+            commands::doctor::execute(fix, recurse_submodules, annotate)?;
+        }
+        Commands::Apply { scan, dry_run } => {
+            commands::apply::execute(scan, dry_run)?;
+        }
+        Commands::Repos { scan, json, format } => {
+            commands::repos::execute(scan, json, format.as_deref())?;
         }
-        Commands::Auto => {
-            commands::auto::execute()?;
+        Commands::Stats { scan, json } => {
+            commands::stats::execute(scan, json)?;
+        }
+        Commands::History { repo, limit, json } => {
+            commands::history::execute(repo, limit, json)?;
+        }
+        Commands::Auto {
+            shell_hook,
+            dry_run,
+            cached,
+            fallback,
+            suggest_rules,
+            status,
+            prompt,
+            global,
+            yes,
+            scan,
+            apply,
+            check: _,
+            recurse_submodules,
+        } => {
+            commands::auto::execute(commands::auto::AutoOptions {
+                shell_hook,
+                dry_run,
+                cached,
+                fallback,
+                suggest_rules,
+                status,
+                prompt,
+                global,
+                yes,
+                scan,
+                apply,
+                recurse_submodules,
+            })?;
+        }
+        Commands::Env { identity, shell } => {
+            commands::env::execute(identity, shell)?;
+        }
+        Commands::Direnv { action } => {
+            commands::direnv::execute(action)?;
+        }
+        Commands::Mailmap { action } => {
+            commands::mailmap::execute(action)?;
         }
         Commands::Hook { action } => {
             commands::hook::execute(action)?;
         }
-        Commands::Audit { path, fix } => {
-            commands::audit::execute(path, fix)?;
+        Commands::Audit {
+            path,
+            fix,
+            unshallow_hint,
+            verify_signatures,
+            format,
+            annotate,
+        } => {
+            commands::audit::execute(
+                path,
+                fix,
+                unshallow_hint,
+                verify_signatures,
+                format.as_deref(),
+                annotate,
+            )?;
         }
         Commands::FixCommit {
             commit,
             identity,
             range,
+            all_by,
+            committer,
+            keep_dates: _,
+            reset_dates,
+            resign,
             yes,
         } => {
-            commands::fix_commit::execute(&commit, identity, range, yes)?;
+            let options = commands::fix_commit::RewriteOptions {
+                committer,
+                reset_dates,
+                resign,
+            };
+            commands::fix_commit::execute(&commit, identity, range, all_by, options, yes)?;
+        }
+        Commands::Watch {
+            paths,
+            interval,
+            check,
+            once,
+        } => {
+            commands::watch::execute(paths, interval, check, once)?;
+        }
+        Commands::ShellInit { shell } => {
+            commands::shell_init::execute(shell)?;
+        }
+        Commands::Serve { socket, stdio } => {
+            commands::serve::execute(socket, stdio)?;
         }
         Commands::Completions { shell } => {
             commands::completions::execute(shell)?;
         }
+        Commands::Complete { kind } => {
+            commands::completions::candidates(kind)?;
+        }
+        Commands::External(args) => {
+            commands::plugin::execute(args)?;
+        }
     }
 
     Ok(())
@@ -0,0 +1,277 @@
+use anyhow::Result;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::PathBuf;
+
+use crate::config::{journal, Config};
+use crate::git::GitConfigManager;
+use crate::i18n::tr;
+use crate::rules::resolve::RepoStatus;
+use crate::rules::Rule;
+
+/// Pane receiving keyboard input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Identities,
+    Rules,
+}
+
+/// State of the interactive view
+pub struct App {
+    pub config: Config,
+    /// Working tree of the repository gid was started in
+    pub repo: Option<PathBuf>,
+    pub status: Option<RepoStatus>,
+    pub focus: Pane,
+    pub identity_index: usize,
+    pub rule_index: usize,
+    /// Result of the last action, shown in the footer
+    pub message: Option<(bool, String)>,
+    /// Identity to switch to once the view has stepped aside
+    pub pending_switch: Option<String>,
+    pub should_quit: bool,
+}
+
+impl App {
+    pub fn load() -> Result<Self> {
+        let repo = GitConfigManager::new()
+            .ok()
+            .and_then(|git| git.workdir().map(|p| p.components().collect()));
+
+        let mut app = Self {
+            config: Config::load()?,
+            repo,
+            status: None,
+            focus: Pane::Identities,
+            identity_index: 0,
+            rule_index: 0,
+            message: None,
+            pending_switch: None,
+            should_quit: false,
+        };
+        app.refresh_status();
+        Ok(app)
+    }
+
+    fn refresh_status(&mut self) {
+        self.status = self
+            .repo
+            .as_ref()
+            .and_then(|repo| RepoStatus::inspect(&self.config, repo).ok());
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        let result = match (key.code, key.modifiers) {
+            (KeyCode::Char('q') | KeyCode::Esc, _) => {
+                self.should_quit = true;
+                return;
+            }
+            (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
+                self.should_quit = true;
+                return;
+            }
+            (KeyCode::Tab | KeyCode::BackTab, _) => {
+                self.focus = match self.focus {
+                    Pane::Identities => Pane::Rules,
+                    Pane::Rules => Pane::Identities,
+                };
+                return;
+            }
+            (KeyCode::Up, KeyModifiers::SHIFT) | (KeyCode::Char('K'), _)
+                if self.focus == Pane::Rules =>
+            {
+                self.move_rule(true).map(Some)
+            }
+            (KeyCode::Down, KeyModifiers::SHIFT) | (KeyCode::Char('J'), _)
+                if self.focus == Pane::Rules =>
+            {
+                self.move_rule(false).map(Some)
+            }
+            (KeyCode::Up | KeyCode::Char('k'), _) => {
+                self.select(-1);
+                return;
+            }
+            (KeyCode::Down | KeyCode::Char('j'), _) => {
+                self.select(1);
+                return;
+            }
+            (KeyCode::Enter | KeyCode::Char('s'), _) if self.focus == Pane::Identities => {
+                self.switch_selected().map(|()| None)
+            }
+            (KeyCode::Char('f'), _) => self.fix_repo().map(|()| None),
+            (KeyCode::Char('r'), _) => self.reload().map(Some),
+            _ => return,
+        };
+
+        self.message = match result {
+            // The switch itself runs from the event loop
+            Ok(None) => return,
+            Ok(Some(message)) => Some((true, message)),
+            Err(e) => Some((false, format!("{e:#}"))),
+        };
+        // Each action is its own operation for `gid undo`
+        journal::seal();
+    }
+
+    /// Record the outcome of a switch started with Enter or f
+    pub fn finish_switch(&mut self, id: &str, result: Result<()>) {
+        journal::seal();
+        self.message = Some(match result {
+            Ok(()) => (true, tr!("tui-switched", id = id)),
+            Err(e) => (false, format!("{e:#}")),
+        });
+        self.refresh_status();
+    }
+
+    fn select(&mut self, delta: isize) {
+        let (index, len) = match self.focus {
+            Pane::Identities => (&mut self.identity_index, self.config.identities.len()),
+            Pane::Rules => (&mut self.rule_index, self.config.rules.len()),
+        };
+        if len == 0 {
+            return;
+        }
+        *index = (*index as isize + delta).clamp(0, len as isize - 1) as usize;
+    }
+
+    /// Queue a full `gid switch` (Git config, SSH hosts, gh account,
+    /// post_switch_command) to the selected identity for this repository
+    fn switch_selected(&mut self) -> Result<()> {
+        if self.repo.is_none() {
+            anyhow::bail!(tr!("tui-not-a-repo"));
+        }
+        let identity = self
+            .config
+            .identities
+            .get(self.identity_index)
+            .ok_or_else(|| anyhow::anyhow!(tr!("tui-no-identity")))?;
+
+        self.pending_switch = Some(identity.id.clone());
+        Ok(())
+    }
+
+    /// Apply the identity .gid or rules expect, like `gid doctor --fix`
+    fn fix_repo(&mut self) -> Result<()> {
+        let expected = self
+            .status
+            .as_ref()
+            .and_then(|status| status.expected.as_ref())
+            .ok_or_else(|| anyhow::anyhow!(tr!("tui-no-expected")))?;
+
+        let index = self
+            .config
+            .identities
+            .iter()
+            .position(|i| i.id == expected.identity)
+            .ok_or_else(|| anyhow::anyhow!(tr!("identity-not-found", id = expected.identity)))?;
+
+        self.identity_index = index;
+        self.switch_selected()
+    }
+
+    /// Swap the selected rule with its neighbour and save the config
+    fn move_rule(&mut self, up: bool) -> Result<String> {
+        let from = self.rule_index;
+        let to = if up {
+            from.checked_sub(1)
+        } else {
+            Some(from + 1).filter(|&i| i < self.config.rules.len())
+        };
+        let Some(to) = to else {
+            return Ok(tr!("tui-rule-at-edge"));
+        };
+
+        // Swap by id: another process may have changed the config since it was loaded
+        let moved = self.config.rules[from].id.clone();
+        let other = self.config.rules[to].id.clone();
+        self.config = Config::update(|config| {
+            let (from, to) = (config.rule_index(&moved)?, config.rule_index(&other)?);
+            swap_rules(&mut config.rules, from, to)?;
+            Ok(config.clone())
+        })?;
+        self.rule_index = self.config.rule_index(&moved)?;
+        self.refresh_status();
+        Ok(if up {
+            tr!("tui-rule-moved-up", id = moved, other = other)
+        } else {
+            tr!("tui-rule-moved-down", id = moved, other = other)
+        })
+    }
+
+    fn reload(&mut self) -> Result<String> {
+        self.config = Config::load()?;
+        self.identity_index = self
+            .identity_index
+            .min(self.config.identities.len().saturating_sub(1));
+        self.rule_index = self
+            .rule_index
+            .min(self.config.rules.len().saturating_sub(1));
+        self.refresh_status();
+        Ok(tr!("tui-reloaded"))
+    }
+
+    /// Problems with the current repository, as `gid doctor` reports them
+    pub fn repo_issues(&self) -> Vec<String> {
+        let Some(status) = &self.status else {
+            return Vec::new();
+        };
+
+        let mut issues = Vec::new();
+        let effective = status.effective_identity(&self.config);
+        if effective.is_none() {
+            issues.push(tr!("doctor-unknown-identity"));
+        }
+        if let Some(expected) = &status.expected {
+            if self.config.find_identity(&expected.identity).is_none() {
+                issues.push(tr!("tui-expected-unknown", id = expected.identity));
+            } else if status.is_drifted(&self.config) {
+                issues.push(tr!(
+                    "tui-drifted",
+                    source = expected.source,
+                    id = expected.identity
+                ));
+            }
+        }
+        if let Some(key) = effective.and_then(|i| i.ssh_key_path()) {
+            if !key.exists() {
+                issues.push(tr!("identity-ssh-key-missing", path = key.display()));
+            }
+        }
+        issues
+    }
+}
+
+/// Swap two rules, exchanging their priorities so the order survives re-sorting
+fn swap_rules(rules: &mut [Rule], a: usize, b: usize) -> Result<()> {
+    for index in [a, b] {
+        if let Some(source) = &rules[index].source {
+            anyhow::bail!(tr!("rule-read-only", id = rules[index].id, source = source));
+        }
+    }
+
+    let priority = rules[a].priority;
+    rules[a].priority = rules[b].priority;
+    rules[b].priority = priority;
+    rules.swap(a, b);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_rules() {
+        let mut rules = vec![
+            Rule::path("~/work/**".to_string(), "work".to_string()).with_priority(10),
+            Rule::remote("github.com/me/*".to_string(), "personal".to_string()).with_priority(20),
+        ];
+
+        swap_rules(&mut rules, 0, 1).unwrap();
+        assert_eq!(rules[0].identity, "personal");
+        assert_eq!(rules[0].priority, 10);
+        assert_eq!(rules[1].priority, 20);
+
+        rules[1].source = Some("company".to_string());
+        assert!(swap_rules(&mut rules, 0, 1).is_err());
+    }
+}
@@ -0,0 +1,62 @@
+mod app;
+mod ui;
+
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::DefaultTerminal;
+use std::io::stdout;
+
+use crate::i18n::tr;
+use app::App;
+
+/// Run the interactive view until the user quits
+pub fn run() -> Result<()> {
+    if !console::Term::stdout().is_term() {
+        anyhow::bail!(tr!("tui-needs-terminal"));
+    }
+
+    let mut app = App::load()?;
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    while !app.should_quit {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+
+        if let Event::Key(key) = event::read()? {
+            // Windows also reports key releases
+            if key.kind == KeyEventKind::Press {
+                app.handle_key(key);
+            }
+        }
+
+        if let Some(id) = app.pending_switch.take() {
+            let result = suspend(terminal, || crate::commands::switch::execute(&id, false))?;
+            app.finish_switch(&id, result);
+        }
+    }
+
+    Ok(())
+}
+
+/// Leave the view while `f` runs, so its output and any post_switch_command
+/// use the normal screen and stay there after gid tui exits
+fn suspend<T>(terminal: &mut DefaultTerminal, f: impl FnOnce() -> T) -> Result<T> {
+    disable_raw_mode()?;
+    execute!(stdout(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    let result = f();
+
+    execute!(stdout(), EnterAlternateScreen)?;
+    enable_raw_mode()?;
+    terminal.clear()?;
+    Ok(result)
+}
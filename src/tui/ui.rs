@@ -0,0 +1,207 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::symbols::border;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::Frame;
+
+use super::app::{App, Pane};
+use crate::ascii::sym;
+use crate::commands::list::pad;
+use crate::i18n::tr;
+
+/// Borders drawn with + - | in ASCII mode
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [main, footer] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(3)]).areas(frame.area());
+    let [left, right] =
+        Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(main);
+    let [rules, repo] =
+        Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(right);
+
+    draw_identities(frame, app, left);
+    draw_rules(frame, app, rules);
+    draw_repo(frame, app, repo);
+    draw_footer(frame, app, footer);
+}
+
+fn bordered() -> Block<'static> {
+    if crate::ascii::is_enabled() {
+        Block::bordered().border_set(ASCII_BORDER)
+    } else {
+        Block::bordered()
+    }
+}
+
+fn pane_block(title: String, focused: bool) -> Block<'static> {
+    let block = bordered().title(format!(" {title} "));
+    if focused {
+        block.border_style(Style::new().fg(Color::Cyan))
+    } else {
+        block
+    }
+}
+
+fn draw_identities(frame: &mut Frame, app: &App, area: Rect) {
+    let effective = app
+        .status
+        .as_ref()
+        .and_then(|status| status.effective_identity(&app.config))
+        .map(|i| i.id.as_str());
+    let expected = app
+        .status
+        .as_ref()
+        .and_then(|status| status.expected.as_ref())
+        .map(|e| e.identity.as_str());
+
+    let items: Vec<ListItem> = app
+        .config
+        .identities
+        .iter()
+        .map(|identity| {
+            // Same markers as `gid list`: ● active, → expected
+            let marker = match (
+                Some(identity.id.as_str()) == effective,
+                Some(identity.id.as_str()) == expected,
+            ) {
+                (true, _) => sym("●").green(),
+                (false, true) => sym("→").yellow(),
+                (false, false) => " ".into(),
+            };
+            ListItem::new(Line::from(vec![
+                marker,
+                " ".into(),
+                format!("[{}]", identity.id).green().bold(),
+                format!(" {} ", identity.name).into(),
+                format!("<{}>", identity.email).cyan(),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(pane_block(
+            tr!("tui-identities"),
+            app.focus == Pane::Identities,
+        ))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(Some(app.identity_index));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_rules(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .config
+        .rules
+        .iter()
+        .enumerate()
+        .map(|(index, rule)| {
+            let mut spans = vec![
+                Span::from(format!("{index}. ")).dark_gray(),
+                Span::from(format!("[{}] ", rule.type_name())).magenta(),
+                rule.pattern().to_string().into(),
+                " -> ".dark_gray(),
+                format!("[{}]", rule.identity).green(),
+            ];
+            if let Some(source) = &rule.source {
+                spans.push(format!(" (ruleset {source})").dark_gray());
+            }
+            let line = Line::from(spans);
+            ListItem::new(if rule.enabled { line } else { line.dim() })
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(pane_block(tr!("tui-rules"), app.focus == Pane::Rules))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(Some(app.rule_index));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// Label of a repository field, padded so the values line up
+fn label(text: String) -> Span<'static> {
+    pad(&text, 10).dark_gray()
+}
+
+fn draw_repo(frame: &mut Frame, app: &App, area: Rect) {
+    let mut lines = Vec::new();
+
+    match &app.status {
+        None => lines.push(Line::from(tr!("tui-outside-repo").dark_gray())),
+        Some(status) => {
+            lines.push(Line::from(vec![
+                label(tr!("tui-path")),
+                status.path.display().to_string().into(),
+            ]));
+            lines.push(Line::from(vec![
+                label(tr!("tui-remote")),
+                status
+                    .remote
+                    .clone()
+                    .unwrap_or_else(|| "-".to_string())
+                    .into(),
+            ]));
+            lines.push(Line::from(vec![
+                label(tr!("tui-current")),
+                match status.effective_identity(&app.config) {
+                    Some(identity) => format!("[{}]", identity.id).green(),
+                    None => status
+                        .effective_email
+                        .clone()
+                        .unwrap_or_else(|| tr!("repos-not-set"))
+                        .yellow(),
+                },
+            ]));
+            lines.push(Line::from(vec![
+                label(tr!("tui-expected")),
+                match &status.expected {
+                    Some(expected) => {
+                        format!("[{}] ({})", expected.identity, expected.source).cyan()
+                    }
+                    None => tr!("tui-no-match").dark_gray(),
+                },
+            ]));
+
+            lines.push(Line::default());
+            let issues = app.repo_issues();
+            if issues.is_empty() {
+                lines.push(Line::from(
+                    format!("{} {}", sym("✓"), tr!("doctor-no-issues")).green(),
+                ));
+            }
+            for issue in issues {
+                lines.push(Line::from(vec![sym("•").red(), " ".into(), issue.into()]));
+            }
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(pane_block(tr!("tui-repository"), false))
+        .wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, area);
+}
+
+fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let line = match &app.message {
+        Some((true, message)) => {
+            Line::from(vec![sym("✓").green(), " ".into(), message.clone().into()])
+        }
+        Some((false, message)) => {
+            Line::from(vec![sym("✗").red(), " ".into(), message.clone().red()])
+        }
+        None => Line::default(),
+    };
+    let block = bordered()
+        .title_bottom(Line::from(crate::ascii::text(&tr!("tui-help")).into_owned()).dark_gray());
+    frame.render_widget(Paragraph::new(line).block(block), area);
+}
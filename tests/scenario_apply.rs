@@ -0,0 +1,139 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_scenario_apply_workspace() {
+    // Scenario: User enforces .gid and rule identities on a whole workspace in one go
+    let workspace = TempDir::new().unwrap();
+    let client = workspace.path().join("client");
+    let repo = git2::Repository::init(&client).unwrap();
+    fs::write(client.join(".gid"), "work\n").unwrap();
+    git2::Repository::init(workspace.path().join("scratch")).unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+gpg_key = "ABCD1234"
+gpg_sign = true
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .args(args)
+            .arg("--scan")
+            .arg(workspace.path());
+        cmd
+    };
+
+    gid(&["apply", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "would set user.name, user.email, user.signingkey, commit.gpgsign",
+        ))
+        .stdout(predicate::str::contains(
+            "2 repositories: 1 to update, 0 unchanged, 1 unmatched, 0 failed",
+        ));
+
+    let local = repo
+        .config()
+        .unwrap()
+        .open_level(git2::ConfigLevel::Local)
+        .unwrap();
+    assert!(local.get_string("user.email").is_err());
+
+    gid(&["apply"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 updated, 0 unchanged"));
+
+    let local = repo
+        .config()
+        .unwrap()
+        .open_level(git2::ConfigLevel::Local)
+        .unwrap();
+    assert_eq!(local.get_string("user.email").unwrap(), "work@example.com");
+    assert_eq!(local.get_string("user.signingkey").unwrap(), "ABCD1234");
+
+    // A second run has nothing left to do
+    gid(&["apply"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("0 updated, 1 unchanged"));
+}
+
+#[test]
+fn test_scenario_bulk_paths_leave_rebasing_repos_alone() {
+    // Scenario: A checkout is mid-rebase while apply and watch sweep the workspace
+    let workspace = TempDir::new().unwrap();
+    let client = workspace.path().join("client");
+    let repo = git2::Repository::init(&client).unwrap();
+    fs::write(client.join(".gid"), "work\n").unwrap();
+    fs::create_dir_all(client.join(".git/rebase-merge")).unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .args(args)
+            .arg(workspace.path());
+        cmd
+    };
+    let local_email = || {
+        repo.config()
+            .unwrap()
+            .open_level(git2::ConfigLevel::Local)
+            .unwrap()
+            .get_string("user.email")
+            .ok()
+    };
+
+    gid(&["apply", "--scan"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("A rebase is in progress"));
+    assert_eq!(local_email(), None);
+
+    gid(&["watch", "--once"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("A rebase is in progress"));
+    assert_eq!(local_email(), None);
+
+    // Once the rebase is finished the identity is applied
+    fs::remove_dir_all(client.join(".git/rebase-merge")).unwrap();
+    gid(&["apply", "--scan"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1 updated"));
+    assert_eq!(local_email().as_deref(), Some("work@example.com"));
+}
@@ -0,0 +1,68 @@
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_ascii_output() {
+    // Scenario: A CI log viewer mangles ✓ and box drawing, so gid prints ASCII
+    let (temp_dir, repo) = common::setup_repo();
+    common::create_commit(&repo, "Initial commit");
+    fs::write(temp_dir.path().join(".gid"), "test\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    let config = r#"
+[[identities]]
+id = "test"
+name = "Test User"
+email = "test@example.com"
+"#;
+    fs::write(config_dir.join("config.toml"), config).unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.current_dir(temp_dir.path())
+            .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .env_remove("GID_ASCII")
+            .args(args);
+        cmd
+    };
+    let stdout = |cmd: &mut Command| {
+        let output = cmd.output().unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    gid(&["auto", "--status", "--prompt"])
+        .assert()
+        .stdout("[test✓]");
+    gid(&["--ascii", "auto", "--status", "--prompt"])
+        .assert()
+        .stdout("[test+]");
+
+    for args in [&["list"][..], &["doctor"], &["audit"], &["current"]] {
+        let output = stdout(gid(args).env("GID_ASCII", "1"));
+        assert!(output.is_ascii(), "gid {args:?}:\n{output}");
+    }
+
+    // Or always, from the settings
+    fs::write(
+        config_dir.join("config.toml"),
+        format!("{config}\n[settings]\nascii = true\n"),
+    )
+    .unwrap();
+    let output = stdout(&mut gid(&["audit"]));
+    assert!(output.is_ascii(), "{output}");
+    assert!(output.contains("repo "));
+
+    // The installed hook script is written in ASCII too
+    gid(&["hook", "install"]).assert().success();
+    let hook = fs::read_to_string(temp_dir.path().join(".git/hooks/pre-commit")).unwrap();
+    assert!(hook.is_ascii());
+    assert!(hook.contains("|  !  Git Identity Check Failed"));
+}
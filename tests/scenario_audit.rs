@@ -21,3 +21,107 @@ fn test_scenario_audit_repo() {
         .stdout(predicate::str::contains("Total Commits: 2"))
         .stdout(predicate::str::contains("Identity Usage Statistics"));
 }
+
+#[test]
+fn test_scenario_audit_shallow_clone() {
+    // Scenario: CI checks out with --depth 1, so the audit only sees part of the history
+    let (temp_dir, repo) = common::setup_repo();
+    common::create_commit(&repo, "Initial commit");
+    common::create_commit(&repo, "Feature A");
+
+    let clone_dir = tempfile::TempDir::new().unwrap();
+    let url = format!("file://{}", temp_dir.path().display());
+    Command::new("git")
+        .args(["clone", "-q", "--depth", "1", &url])
+        .arg(clone_dir.path().join("shallow"))
+        .assert()
+        .success();
+    let shallow = clone_dir.path().join("shallow");
+
+    Command::cargo_bin("gid")
+        .unwrap()
+        .current_dir(&shallow)
+        .arg("audit")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Total Commits: 1"))
+        .stdout(predicate::str::contains("Shallow clone"))
+        .stdout(predicate::str::contains("gid audit --unshallow-hint"));
+
+    Command::cargo_bin("gid")
+        .unwrap()
+        .current_dir(&shallow)
+        .args(["audit", "--unshallow-hint"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("fetch --unshallow"));
+
+    // A full clone carries no note
+    Command::cargo_bin("gid")
+        .unwrap()
+        .current_dir(temp_dir.path())
+        .arg("audit")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Shallow clone").not());
+}
+
+#[test]
+fn test_scenario_github_annotations() {
+    // Scenario: A GitHub Actions job flags commits that ignore the repo's .gid
+    let (temp_dir, repo) = common::setup_repo();
+    common::create_commit(&repo, "Initial commit");
+    std::fs::write(temp_dir.path().join(".gid"), "work\n").unwrap();
+
+    let home_dir = tempfile::TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "test"
+name = "Test User"
+email = "test@example.com"
+
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.current_dir(temp_dir.path())
+            .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("GITHUB_WORKSPACE", temp_dir.path())
+            .env("NO_COLOR", "1")
+            .args(args);
+        cmd
+    };
+
+    gid(&["audit", "--annotate", "github"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Identity Mismatch"))
+        .stdout(predicate::str::is_match(
+            r"(?m)^::error file=\.gid,title=gid audit%3A Identity Mismatch in [0-9a-f]{7}::[0-9a-f]{7} Initial commit - Test User <test@example.com>",
+        )
+        .unwrap());
+
+    gid(&["doctor", "--annotate", "github"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "::error file=.gid,title=gid doctor::Current identity does not match project config (expected: [work])",
+        ));
+
+    // Without the flag nothing changes
+    gid(&["audit"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("::error").not());
+}
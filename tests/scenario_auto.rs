@@ -0,0 +1,865 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+fn write_config(dir: &TempDir, settings: &str) -> std::path::PathBuf {
+    let config_dir = dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[settings]
+{settings}
+"#
+        ),
+    )
+    .unwrap();
+    config_dir
+}
+
+#[test]
+fn test_scenario_shell_hook_switches_when_enabled() {
+    // Scenario: User enabled auto_switch and cds into a project with a .gid file
+    let (temp_dir, repo) = common::setup_repo();
+    fs::write(temp_dir.path().join(".gid"), "work\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "auto_switch = true");
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .current_dir(temp_dir.path())
+        .args(["auto", "--shell-hook"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Switched to project identity"));
+
+    let config = repo.config().unwrap();
+    assert_eq!(config.get_string("user.email").unwrap(), "work@example.com");
+
+    // Entering again is silent once the identity matches
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .current_dir(temp_dir.path())
+        .args(["auto", "--shell-hook"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn test_scenario_shell_hook_respects_auto_switch_setting() {
+    // Scenario: auto_switch is off, the shell hook must not touch the repository
+    let (temp_dir, repo) = common::setup_repo();
+    fs::write(temp_dir.path().join(".gid"), "work\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "auto_switch = false");
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .current_dir(temp_dir.path())
+        .args(["auto", "--shell-hook"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    let config = repo.config().unwrap();
+    assert_eq!(config.get_string("user.email").unwrap(), "test@example.com");
+}
+
+#[test]
+fn test_scenario_shell_init_bash() {
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.args(["shell-init", "bash"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("gid auto --shell-hook"));
+}
+
+#[test]
+fn test_scenario_auto_dry_run_reports_without_changes() {
+    // Scenario: A CI script reports identity drift but may not modify the checkout
+    let (temp_dir, repo) = common::setup_repo();
+    fs::write(temp_dir.path().join(".gid"), "work\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "");
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .current_dir(temp_dir.path())
+        .args(["auto", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("project config"))
+        .stdout(predicate::str::contains("Would switch"));
+
+    let config = repo.config().unwrap();
+    assert_eq!(config.get_string("user.email").unwrap(), "test@example.com");
+}
+
+#[test]
+fn test_scenario_auto_applies_default_identity() {
+    // Scenario: A freshly cloned personal repo matches no rule and should not keep the old identity
+    let (temp_dir, repo) = common::setup_repo();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "default_identity = \"work\"");
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .current_dir(temp_dir.path())
+        .arg("auto")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("using fallback identity"));
+
+    let config = repo.config().unwrap();
+    assert_eq!(config.get_string("user.email").unwrap(), "work@example.com");
+}
+
+#[test]
+fn test_scenario_cached_resolution_invalidated_by_config_change() {
+    // Scenario: The shell hook caches rule results until the user edits their rules
+    let (temp_dir, repo) = common::setup_repo();
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    let write_rules = |identity: &str| {
+        fs::write(
+            config_dir.join("config.toml"),
+            format!(
+                r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[identities]]
+id = "oss"
+name = "OSS User"
+email = "oss@example.com"
+
+[[rules]]
+type = "path"
+pattern = "{}/**"
+identity = "{identity}"
+"#,
+                temp_dir.path().display()
+            ),
+        )
+        .unwrap();
+    };
+
+    write_rules("work");
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .current_dir(temp_dir.path())
+        .args(["auto", "--cached"])
+        .assert()
+        .success();
+    assert!(config_dir.join("cache/resolve.toml").exists());
+    assert_eq!(
+        repo.config().unwrap().get_string("user.email").unwrap(),
+        "work@example.com"
+    );
+
+    // Editing config.toml must invalidate the cached result
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    write_rules("oss");
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .current_dir(temp_dir.path())
+        .args(["auto", "--cached"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[oss]"));
+    assert_eq!(
+        repo.config().unwrap().get_string("user.email").unwrap(),
+        "oss@example.com"
+    );
+}
+
+#[test]
+fn test_scenario_suggest_rules_from_unmatched_repos() {
+    // Scenario: New user runs auto in a few checkouts, then asks gid to draft their rules
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Test User"
+email = "test@example.com"
+"#,
+    )
+    .unwrap();
+
+    for name in ["api", "web"] {
+        let (temp_dir, repo) = common::setup_repo();
+        repo.remote("origin", &format!("git@github.com:acme/{name}.git"))
+            .unwrap();
+
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .current_dir(temp_dir.path())
+            .arg("auto")
+            .assert()
+            .success();
+    }
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .args(["auto", "--suggest-rules"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "gid rule add -t remote -p \"github.com/acme/*\" -i work",
+        ));
+}
+
+#[test]
+fn test_scenario_prompt_segment_reflects_match() {
+    // Scenario: User embeds gid in PS1 to see identity drift at a glance
+    let (temp_dir, _repo) = common::setup_repo();
+    fs::write(temp_dir.path().join(".gid"), "work\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "");
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("NO_COLOR", "1")
+        .current_dir(temp_dir.path())
+        .args(["auto", "--status", "--prompt"])
+        .assert()
+        .success()
+        .stdout("[!mismatch]");
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .current_dir(temp_dir.path())
+        .args(["switch", "work"])
+        .assert()
+        .success();
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("NO_COLOR", "1")
+        .current_dir(temp_dir.path())
+        .args(["auto", "--status", "--prompt"])
+        .assert()
+        .success()
+        .stdout("[work✓]");
+}
+
+#[test]
+fn test_scenario_auto_global_applies_home_rule() {
+    // Scenario: A login script sets the machine-wide identity from a home-directory rule
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[rules]]
+type = "path"
+pattern = "{}/**"
+identity = "work"
+"#,
+            home_dir.path().display()
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .current_dir(home_dir.path())
+        .args(["auto", "--global", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Switched to global identity"));
+
+    let gitconfig = fs::read_to_string(home_dir.path().join(".gitconfig")).unwrap();
+    assert!(gitconfig.contains("work@example.com"));
+}
+
+#[test]
+fn test_scenario_auto_scan_reports_and_fixes_drift() {
+    // Scenario: User enforces rules across a whole workspace of checkouts
+    let workspace = TempDir::new().unwrap();
+    let repo_path = workspace.path().join("client");
+    let repo = git2::Repository::init(&repo_path).unwrap();
+    fs::write(repo_path.join(".gid"), "work\n").unwrap();
+    git2::Repository::init(workspace.path().join("scratch")).unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "");
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .args(["auto", "--check", "--scan"])
+        .arg(workspace.path())
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("0 ok, 1 drifted, 1 unmatched"));
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .args(["auto", "--apply", "--scan"])
+        .arg(workspace.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fixed 1 repositories"));
+
+    let local = repo
+        .config()
+        .unwrap()
+        .open_level(git2::ConfigLevel::Local)
+        .unwrap();
+    assert_eq!(local.get_string("user.email").unwrap(), "work@example.com");
+}
+
+#[test]
+fn test_scenario_remote_rule_matches_ssh_url_with_port() {
+    // Scenario: A self-hosted forge serves SSH on a non-standard port
+    let (temp_dir, repo) = common::setup_repo();
+    repo.remote("origin", "ssh://git@git.company.com:2222/team/api.git")
+        .unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "");
+    let mut config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    config.push_str(
+        r#"
+[[rules]]
+type = "remote"
+pattern = "git.company.com/team/*"
+identity = "work"
+"#,
+    );
+    fs::write(config_dir.join("config.toml"), config).unwrap();
+
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .current_dir(temp_dir.path())
+        .arg("auto")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Matched rule"));
+    assert_eq!(
+        repo.config().unwrap().get_string("user.email").unwrap(),
+        "work@example.com"
+    );
+}
+
+#[test]
+fn test_scenario_branch_rule() {
+    // Scenario: Release branches are committed with the work identity
+    let (temp_dir, repo) = common::setup_repo();
+    common::create_commit(&repo, "Initial commit");
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "");
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+
+    gid(&[
+        "rule",
+        "add",
+        "-t",
+        "branch",
+        "-p",
+        "release/*",
+        "-i",
+        "work",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Added Branch rule: release/*"));
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(config.contains("type = \"branch\""));
+
+    // Not on a release branch: nothing expected, no known identity to show
+    gid(&["auto", "--status", "--prompt"])
+        .assert()
+        .success()
+        .stdout("");
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("release/1.0", &head, false).unwrap();
+    repo.set_head("refs/heads/release/1.0").unwrap();
+
+    // The cached resolution follows the branch change
+    gid(&["auto", "--status", "--prompt"])
+        .assert()
+        .success()
+        .stdout("[!mismatch]");
+    gid(&["rule", "test", "--branch", "release/2.0"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Branch: release/2.0"))
+        .stdout(predicate::str::contains("[branch] release/* -> [work]"));
+
+    gid(&["auto"]).assert().success();
+    assert_eq!(
+        repo.config().unwrap().get_string("user.email").unwrap(),
+        "work@example.com"
+    );
+}
+
+#[test]
+fn test_scenario_structured_remote_rule() {
+    // Scenario: Every mycompany repository on GitHub uses work, whether cloned over SSH or HTTPS
+    let (temp_dir, repo) = common::setup_repo();
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "");
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+
+    gid(&[
+        "rule",
+        "add",
+        "-t",
+        "remote",
+        "--host",
+        "github.com",
+        "--org",
+        "mycompany",
+        "-i",
+        "work",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "Added Remote URL rule: host=github.com org=mycompany",
+    ));
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(config.contains("host = \"github.com\""));
+    assert!(!config.contains("pattern"));
+
+    for url in [
+        "git@github.com:mycompany/api.git",
+        "https://github.com/MyCompany/api",
+    ] {
+        gid(&["rule", "test", "--remote", url])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Will use identity: [work]"));
+    }
+    gid(&[
+        "rule",
+        "test",
+        "--remote",
+        "git@github.com:mycompany-fork/api.git",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("No matching rules"));
+
+    repo.remote("origin", "https://github.com/mycompany/api.git")
+        .unwrap();
+    gid(&["auto"]).assert().success();
+    assert_eq!(
+        repo.config().unwrap().get_string("user.email").unwrap(),
+        "work@example.com"
+    );
+
+    // URL parts only make sense for remote rules
+    gid(&[
+        "rule",
+        "add",
+        "-t",
+        "path",
+        "--host",
+        "github.com",
+        "-i",
+        "work",
+    ])
+    .assert()
+    .code(64);
+    gid(&["rule", "add", "-t", "remote", "-i", "work"])
+        .assert()
+        .code(64);
+}
+
+#[test]
+fn test_scenario_all_of_rule() {
+    // Scenario: Only Acme checkouts that also push to Acme's GitLab use the acme identity
+    let (temp_dir, _repo) = common::setup_repo();
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "");
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+
+    gid(&[
+        "rule",
+        "add",
+        "-t",
+        "all-of",
+        "--when",
+        "path=/clients/acme/**",
+        "--when",
+        "host=acme.gitlab.com",
+        "-i",
+        "work",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "Added All-of rule: path /clients/acme/** & remote host=acme.gitlab.com",
+    ));
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(config.contains("type = \"all_of\""));
+
+    let test = |path: &str, remote: &str| {
+        gid(&["rule", "test", "--path", path, "--remote", remote])
+            .assert()
+            .success()
+    };
+    test("/clients/acme/api", "git@acme.gitlab.com:team/api.git")
+        .stdout(predicate::str::contains("Will use identity: [work]"));
+    test("/clients/acme/api", "git@github.com:me/api.git")
+        .stdout(predicate::str::contains("No matching rules"));
+    test("/src/api", "https://acme.gitlab.com/team/api")
+        .stdout(predicate::str::contains("No matching rules"));
+
+    gid(&["rule", "add", "-t", "all-of", "-p", "x", "-i", "work"])
+        .assert()
+        .code(64);
+    gid(&[
+        "rule", "add", "-t", "any-of", "--when", "size=big", "-i", "work",
+    ])
+    .assert()
+    .code(64);
+}
+
+#[test]
+fn test_scenario_rule_ids_survive_reordering() {
+    // Scenario: A rule added with a higher priority does not change the id of existing rules
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "");
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .args(args);
+        cmd
+    };
+    let id_of = |pattern: &str| {
+        let output = gid(&["rule", "list"]).output().unwrap();
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|l| l.contains(pattern))
+            .and_then(|l| l.split_whitespace().next())
+            .unwrap()
+            .to_string()
+    };
+
+    gid(&["rule", "add", "-t", "path", "-p", "~/work/**", "-i", "work"])
+        .assert()
+        .success();
+    let id = id_of("~/work/**");
+    assert_eq!(id.len(), 6);
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(config.contains(&format!("id = \"{id}\"")));
+
+    gid(&[
+        "rule",
+        "add",
+        "-t",
+        "branch",
+        "-p",
+        "main",
+        "-i",
+        "work",
+        "--priority",
+        "1",
+    ])
+    .assert()
+    .success();
+    assert_eq!(id_of("~/work/**"), id);
+    assert_ne!(id_of("main"), id);
+
+    gid(&["rule", "remove", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Rule 0 does not exist"));
+}
+
+#[test]
+fn test_scenario_rule_edit() {
+    // Scenario: User retargets a rule without deleting and re-adding it
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "");
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .args(args);
+        cmd
+    };
+
+    gid(&["rule", "add", "-t", "path", "-p", "~/work/**", "-i", "work"])
+        .assert()
+        .success();
+    let output = gid(&["rule", "list"]).output().unwrap();
+    let id = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find(|l| l.contains("~/work/**"))
+        .and_then(|l| l.split_whitespace().next())
+        .unwrap()
+        .to_string();
+
+    gid(&[
+        "rule",
+        "edit",
+        &id,
+        "-t",
+        "remote",
+        "-p",
+        "",
+        "--org",
+        "acme",
+        "--priority",
+        "5",
+        "-d",
+        "Acme repositories",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(format!(
+        "Updated rule {id}: [remote] org=acme -> work"
+    )));
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(config.contains(&format!("id = \"{id}\"")));
+    assert!(config.contains("type = \"remote\""));
+    assert!(config.contains("org = \"acme\""));
+    assert!(config.contains("priority = 5"));
+    assert!(config.contains("description = \"Acme repositories\""));
+
+    // Unknown identities are rejected and nothing is saved
+    gid(&["rule", "edit", &id, "-i", "nobody"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Identity 'nobody' does not exist"));
+    // Conditions belong to all-of / any-of rules only
+    gid(&["rule", "edit", &id, "--when", "branch=main"])
+        .assert()
+        .code(64);
+
+    gid(&["rule", "edit", &id, "-t", "branch", "-p", "main", "-d", ""])
+        .assert()
+        .success();
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(config.contains("type = \"branch\""));
+    assert!(!config.contains("org = "));
+    assert!(!config.contains("description"));
+}
+
+#[test]
+fn test_scenario_rule_enable_disable_move() {
+    // Scenario: User switches a rule off to experiment, then reorders it
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "");
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .args(args);
+        cmd
+    };
+    let id_of = |pattern: &str| {
+        let output = gid(&["rule", "list"]).output().unwrap();
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|l| l.contains(pattern))
+            .and_then(|l| l.split_whitespace().next())
+            .unwrap()
+            .to_string()
+    };
+
+    gid(&["rule", "add", "-t", "path", "-p", "/work/**", "-i", "work"])
+        .assert()
+        .success();
+    gid(&[
+        "rule",
+        "add",
+        "-t",
+        "path",
+        "-p",
+        "/work/oss/**",
+        "-i",
+        "work",
+        "--priority",
+        "200",
+    ])
+    .assert()
+    .success();
+    let broad = id_of("/work/**");
+    let narrow = id_of("/work/oss/**");
+
+    gid(&["rule", "disable", &broad])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!("Rule {broad} disabled")));
+    gid(&["rule", "disable", &broad])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("already disabled"));
+    gid(&["rule", "test", "--path", "/work/oss/gid", "--remote", "x"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("/work/**").not());
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(config.contains("enabled = false"));
+
+    gid(&["rule", "enable", &broad]).assert().success();
+    gid(&["rule", "move", &narrow, "--priority", "10"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "Rule {narrow} priority 200 → 10 (now 1 of 2)"
+        )));
+    gid(&["rule", "test", "--path", "/work/oss/gid", "--remote", "x"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("→ [path] /work/oss/** -> [work]"));
+}
+
+#[test]
+fn test_scenario_concurrent_rule_adds_are_all_kept() {
+    // Scenario: A setup script adds several rules in parallel
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "");
+
+    let children: Vec<_> = (0..8)
+        .map(|n| {
+            std::process::Command::new(assert_cmd::cargo::cargo_bin("gid"))
+                .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+                .env("HOME", home_dir.path())
+                .env("NO_COLOR", "1")
+                .args(["rule", "add", "-t", "path", "-p"])
+                .arg(format!("/team-{n}/**"))
+                .args(["-i", "work"])
+                .stdout(std::process::Stdio::null())
+                .spawn()
+                .unwrap()
+        })
+        .collect();
+    for mut child in children {
+        assert!(child.wait().unwrap().success());
+    }
+
+    // Each add reloaded the config under the lock, so none overwrote another
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    for n in 0..8 {
+        assert!(config.contains(&format!("/team-{n}/**")), "rule {n} lost");
+    }
+}
+
+#[test]
+fn test_scenario_most_specific_match_strategy() {
+    // Scenario: A broad high-priority rule no longer shadows a narrower one
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir, "match_strategy = \"most-specific\"");
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .args(args);
+        cmd
+    };
+
+    gid(&[
+        "rule",
+        "add",
+        "-t",
+        "path",
+        "-p",
+        "/src/**",
+        "-i",
+        "work",
+        "--priority",
+        "1",
+    ])
+    .assert()
+    .success();
+    gid(&[
+        "rule",
+        "add",
+        "-t",
+        "path",
+        "-p",
+        "/src/oss/gid",
+        "-i",
+        "work",
+    ])
+    .assert()
+    .success();
+
+    gid(&["rule", "test", "--path", "/src/oss/gid", "--remote", "x"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Matched Rules (most specific first):",
+        ))
+        .stdout(predicate::str::contains(
+            "→ [path] /src/oss/gid -> [work] (Priority: 100)",
+        ));
+}
@@ -0,0 +1,99 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_bare_mirror_via_git_dir() {
+    // Scenario: A server-side mirror has no working tree but still needs an identity and audits
+    let (temp_dir, repo) = common::setup_repo();
+    common::create_commit(&repo, "First");
+    common::create_commit(&repo, "Second");
+    let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+
+    let home_dir = TempDir::new().unwrap();
+    let mirror_path = home_dir.path().join("mirror.git");
+    let mirror = git2::build::RepoBuilder::new()
+        .bare(true)
+        .clone(temp_dir.path().to_str().unwrap(), &mirror_path)
+        .unwrap();
+
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+
+    // Run from a directory outside any repository
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(home_dir.path())
+            .arg("--git-dir")
+            .arg(&mirror_path)
+            .args(args);
+        cmd
+    };
+
+    gid(&["switch", "work"]).assert().success();
+    assert_eq!(
+        mirror.config().unwrap().get_string("user.email").unwrap(),
+        "work@example.com"
+    );
+
+    gid(&["current", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"in_repo\": true"))
+        .stdout(predicate::str::contains("work@example.com"));
+
+    gid(&["audit"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Audit complete: 1 repositories"));
+
+    // The branch named at the end of the range is the one rewritten
+    let range = format!("{branch}~1..{branch}");
+    gid(&[
+        "fix-commit",
+        "--range",
+        &range,
+        "--identity",
+        "work",
+        "--yes",
+    ])
+    .assert()
+    .success();
+    let tip = mirror
+        .find_branch(&branch, git2::BranchType::Local)
+        .unwrap()
+        .get()
+        .peel_to_commit()
+        .unwrap();
+    assert_eq!(tip.author().email(), Some("work@example.com"));
+    assert_eq!(
+        tip.parent(0).unwrap().author().email(),
+        Some("test@example.com")
+    );
+
+    gid(&["check"]).assert().success();
+
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .args(["--git-dir", "/nonexistent", "current"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is not a Git repository"));
+}
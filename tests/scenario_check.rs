@@ -0,0 +1,98 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_check_exit_code() {
+    // Scenario: A pre-commit hook only needs to know whether the identity is right
+    let (temp_dir, repo) = common::setup_repo();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+
+    // Nothing expected yet
+    gid(&["check"]).assert().success().stdout("");
+    gid(&["check", "-v"])
+        .assert()
+        .success()
+        .stdout("ok: no identity expected here\n");
+
+    fs::write(temp_dir.path().join(".gid"), "work\n").unwrap();
+    gid(&["check"]).assert().code(3).stdout("").stderr("");
+    gid(&["check", "--verbose"]).assert().code(3).stdout(
+        predicate::str::starts_with("mismatch: test@example.com is in use, project config (")
+            .and(predicate::str::ends_with("expects work\n")),
+    );
+
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Work User").unwrap();
+    config.set_str("user.email", "work@example.com").unwrap();
+    gid(&["check"]).assert().success().stdout("");
+    gid(&["check", "-v"])
+        .assert()
+        .success()
+        .stdout("ok: work\n");
+}
+
+#[test]
+fn test_scenario_exit_codes_per_failure() {
+    // Scenario: CI branches on why gid failed
+    let outside = TempDir::new().unwrap();
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(outside.path())
+            .args(args);
+        cmd
+    };
+
+    gid(&["switch", "work"])
+        .assert()
+        .code(4)
+        .stderr(predicate::str::contains("not a Git repository"));
+    gid(&["switch", "--no-such-flag"]).assert().code(64);
+    gid(&["--help"]).assert().success();
+
+    fs::write(config_dir.join("config.toml"), "[[identities]\n").unwrap();
+    gid(&["list"]).assert().code(2);
+}
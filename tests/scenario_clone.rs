@@ -0,0 +1,70 @@
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_clone_with_rule_identity() {
+    // Scenario: Clone into a work directory and get the work identity before the first commit
+    let (source_dir, repo) = common::setup_repo();
+    common::create_commit(&repo, "Initial commit");
+
+    let workspace = TempDir::new().unwrap();
+    let config_dir = TempDir::new().unwrap();
+    fs::write(
+        config_dir.path().join("config.toml"),
+        format!(
+            r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@company.com"
+
+[[rules]]
+type = "path"
+pattern = "{}/work/**"
+identity = "work"
+priority = 100
+"#,
+            workspace.path().display()
+        ),
+    )
+    .unwrap();
+    fs::create_dir(workspace.path().join("work")).unwrap();
+
+    Command::cargo_bin("gid")
+        .unwrap()
+        .current_dir(workspace.path())
+        .env("GID_CONFIG_DIR", config_dir.path())
+        .env("NO_COLOR", "1")
+        .args(["clone", source_dir.path().to_str().unwrap(), "work/project"])
+        .arg("--write-gid")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Cloning as [work]"));
+
+    let clone_path = workspace.path().join("work/project");
+    let cloned = git2::Repository::open(&clone_path).unwrap();
+    let local = cloned
+        .config()
+        .unwrap()
+        .open_level(git2::ConfigLevel::Local)
+        .unwrap();
+    assert_eq!(local.get_string("user.email").unwrap(), "work@company.com");
+    assert_eq!(
+        fs::read_to_string(clone_path.join(".gid")).unwrap().trim(),
+        "work"
+    );
+
+    // Without a matching rule or --identity nothing is cloned
+    Command::cargo_bin("gid")
+        .unwrap()
+        .current_dir(workspace.path())
+        .env("GID_CONFIG_DIR", config_dir.path())
+        .args(["clone", source_dir.path().to_str().unwrap(), "elsewhere"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--identity"));
+    assert!(!workspace.path().join("elsewhere").exists());
+}
@@ -0,0 +1,118 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_scenario_dynamic_completion() {
+    // Scenario: User presses <TAB> after `gid switch` and `gid rule edit|enable|disable|move|remove`
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[identities]]
+id = "personal"
+name = "Personal User"
+email = "personal@example.com"
+
+[[rules]]
+type = "path"
+pattern = "~/work/**"
+identity = "work"
+priority = 100
+
+[[rules]]
+type = "remote"
+pattern = "github.com/acme/*"
+identity = "work"
+priority = 100
+source = "acme"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .args(args);
+        cmd
+    };
+
+    gid(&["__complete", "identities"])
+        .assert()
+        .success()
+        .stdout(
+            "work\tWork User <work@example.com>\npersonal\tPersonal User <personal@example.com>\n",
+        );
+
+    // Subscribed rules are read-only and not offered for removal
+    gid(&["__complete", "rules"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::is_match("^[0-9a-f]{6}\t\\[path\\] ~/work/\\*\\* -> work\n$").unwrap(),
+        )
+        .stdout(predicate::str::contains("acme").not());
+
+    // The helper is not part of the visible command list
+    gid(&["--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("__complete").not());
+
+    for shell in ["bash", "zsh", "fish"] {
+        gid(&["completions", shell])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("command gid __complete"));
+    }
+    gid(&["completions", "fish"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "__fish_seen_subcommand_from edit enable disable move remove",
+        ));
+
+    // Every rule subcommand taking an id completes it in bash
+    let output = gid(&["completions", "bash"]).output().unwrap();
+    let script = home_dir.path().join("gid.bash");
+    fs::write(&script, output.stdout).unwrap();
+    let bin_dir = assert_cmd::cargo::cargo_bin("gid")
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let path = format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    for action in ["edit", "enable", "disable", "move", "remove"] {
+        let complete = format!(
+            "source {}; COMP_WORDS=(gid rule {action} ''); COMP_CWORD=3; _gid_dynamic; echo \"${{COMPREPLY[@]}}\"",
+            script.display()
+        );
+        let output = std::process::Command::new("bash")
+            .args(["-c", &complete])
+            .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("PATH", &path)
+            .output()
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(
+            predicate::str::is_match("^[0-9a-f]{6}\n$")
+                .unwrap()
+                .eval(&stdout),
+            "rule {action}: {stdout:?}"
+        );
+    }
+}
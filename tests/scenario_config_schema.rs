@@ -0,0 +1,47 @@
+use assert_cmd::Command;
+use serde_json::Value;
+
+#[test]
+fn test_scenario_config_schema() {
+    // Scenario: User points their editor's TOML plugin at gid's config schema
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .args(["config", "schema"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let schema: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    assert_eq!(schema["$schema"], "http://json-schema.org/draft-07/schema#");
+    for key in ["identities", "rules", "rulesets", "settings"] {
+        assert!(schema["properties"][key].is_object(), "missing {key}");
+    }
+
+    let definitions = &schema["definitions"];
+    assert_eq!(
+        definitions["Identity"]["required"],
+        serde_json::json!(["id", "name", "email"])
+    );
+    assert_eq!(
+        definitions["Identity"]["properties"]["ssh_key"]["type"],
+        "string"
+    );
+
+    // Rules are tagged by type
+    let variants: Vec<&Value> = definitions["Rule"]["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|v| &v["properties"]["type"]["const"])
+        .collect();
+    assert_eq!(variants, ["path", "remote", "branch", "all_of", "any_of"]);
+
+    // Every setting gid writes is described
+    let settings = toml::Value::try_from(gid_core::config::Settings::default()).unwrap();
+    for key in settings.as_table().unwrap().keys() {
+        assert!(
+            definitions["Settings"]["properties"][key].is_object(),
+            "settings.{key} is missing from the schema"
+        );
+    }
+}
@@ -0,0 +1,160 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_https_credentials_follow_switch() {
+    // Scenario: User pushes over HTTPS with a different token per identity
+    let (temp_dir, repo) = common::setup_repo();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[identities]]
+id = "personal"
+name = "Personal User"
+email = "personal@example.com"
+"#,
+    )
+    .unwrap();
+
+    // git runs `gid credential helper` itself, so gid has to be on PATH
+    let bin_dir = assert_cmd::cargo::cargo_bin("gid")
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let path = format!(
+        "{}:{}",
+        bin_dir.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+    let git_fill = || {
+        let mut cmd = Command::new("git");
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("PATH", &path)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .current_dir(temp_dir.path())
+            .args(["credential", "fill"])
+            .write_stdin("protocol=https\nhost=github.com\n\n");
+        cmd
+    };
+
+    gid(&["credential", "set", "missing", "-u", "someone"])
+        .write_stdin("token")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Identity 'missing' not found"));
+
+    gid(&["credential", "set", "work", "-u", "work-bot"])
+        .write_stdin("ghp_worktoken1234\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Stored HTTPS credential for [work]",
+        ));
+    gid(&["credential", "set", "personal", "-u", "me"])
+        .write_stdin("ghp_personal5678")
+        .assert()
+        .success();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(config_dir.join("credentials.toml"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    // Tokens never show up in the listing
+    gid(&["credential", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("work-bot"))
+        .stdout(predicate::str::contains("****1234"))
+        .stdout(predicate::str::contains("ghp_worktoken1234").not());
+
+    gid(&["switch", "work"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "credential.https://github.com.username",
+        ));
+
+    let config = repo.config().unwrap();
+    assert_eq!(
+        config
+            .get_string("credential.https://github.com.username")
+            .unwrap(),
+        "work-bot"
+    );
+
+    git_fill()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("username=work-bot"))
+        .stdout(predicate::str::contains("password=ghp_worktoken1234"));
+
+    // Switching replaces the helper instead of stacking a second one
+    gid(&["switch", "personal"]).assert().success();
+    git_fill()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("username=me"))
+        .stdout(predicate::str::contains("password=ghp_personal5678"));
+
+    let local = fs::read_to_string(temp_dir.path().join(".git/config")).unwrap();
+    assert_eq!(local.matches("gid credential helper").count(), 1);
+    assert!(!local.contains("ghp_"));
+
+    // The helper ignores other hosts and protocols
+    gid(&["credential", "helper", "personal", "get"])
+        .write_stdin("protocol=https\nhost=gitlab.com\n\n")
+        .assert()
+        .success()
+        .stdout("");
+
+    // Git reports a rejected token with erase
+    gid(&["credential", "helper", "personal", "erase"])
+        .write_stdin("protocol=https\nhost=github.com\nusername=me\npassword=ghp_personal5678\n\n")
+        .assert()
+        .success();
+    gid(&["credential", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("****5678").not());
+
+    // Without a stored credential the next switch drops gid's helper
+    gid(&["switch", "personal"]).assert().success();
+    let local = fs::read_to_string(temp_dir.path().join(".git/config")).unwrap();
+    assert!(!local.contains("gid credential helper"));
+
+    gid(&["credential", "unset", "work"]).assert().success();
+    gid(&["credential", "unset", "work"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No credential stored for [work]"));
+}
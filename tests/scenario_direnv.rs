@@ -0,0 +1,57 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_direnv_export_writes_envrc() {
+    // Scenario: A team standardized on direnv adopts gid without installing hooks
+    let (temp_dir, _repo) = common::setup_repo();
+    fs::write(temp_dir.path().join(".gid"), "work\n").unwrap();
+    fs::write(temp_dir.path().join(".envrc"), "export FOO=bar\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "O'Brien"
+email = "work@example.com"
+ssh_auth_sock = "/run/agents/work.sock"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .current_dir(temp_dir.path())
+        .args(["direnv", "export"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "export GIT_AUTHOR_NAME='O'\\''Brien'",
+        ))
+        .stdout(predicate::str::contains(
+            "export SSH_AUTH_SOCK='/run/agents/work.sock'",
+        ));
+
+    // Writing twice keeps a single block and the user's own lines
+    for _ in 0..2 {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .current_dir(temp_dir.path())
+            .args(["direnv", "export", "--write"])
+            .assert()
+            .success();
+    }
+
+    let envrc = fs::read_to_string(temp_dir.path().join(".envrc")).unwrap();
+    assert!(envrc.starts_with("export FOO=bar\n"));
+    assert_eq!(envrc.matches("# >>> gid >>>").count(), 1);
+    assert!(envrc.contains("export GIT_COMMITTER_EMAIL='work@example.com'"));
+}
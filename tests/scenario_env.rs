@@ -0,0 +1,55 @@
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_env_eval_in_shell() {
+    // Scenario: A CI job evals `gid env work` and commits as the work identity
+    let (temp_dir, _repo) = common::setup_repo();
+
+    let config_dir = TempDir::new().unwrap();
+    fs::write(
+        config_dir.path().join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "O'Brien"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["env", "work"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let exports = String::from_utf8(output.stdout).unwrap();
+
+    let ident = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(format!("{exports}\ngit var GIT_AUTHOR_IDENT"))
+        .current_dir(temp_dir.path())
+        .output()
+        .unwrap();
+    assert!(String::from_utf8(ident.stdout)
+        .unwrap()
+        .starts_with("O'Brien <work@example.com>"));
+
+    // Other shells get their own syntax
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.path())
+        .current_dir(temp_dir.path())
+        .args(["env", "work", "--shell", "powershell"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "$env:GIT_AUTHOR_EMAIL = 'work@example.com'",
+        ));
+}
@@ -0,0 +1,338 @@
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_scenario_partial_export() {
+    // Scenario: Share only the work identities and their rules with a teammate
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "me@company.com"
+
+[[identities]]
+id = "work-ci"
+name = "Company CI"
+email = "ci@company.com"
+
+[[identities]]
+id = "personal"
+name = "Me"
+email = "me@example.com"
+
+[[rules]]
+type = "remote"
+pattern = "github.com/company/*"
+identity = "work"
+
+[[rules]]
+type = "path"
+pattern = "~/personal/**"
+identity = "personal"
+
+[settings]
+default_identity = "personal"
+"#,
+    )
+    .unwrap();
+
+    let export = |args: &[&str]| {
+        let file = home_dir.path().join("export.toml");
+        Command::cargo_bin("gid")
+            .unwrap()
+            .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .arg("export")
+            .arg(&file)
+            .args(args)
+            .assert()
+            .success();
+        let content = fs::read_to_string(&file).unwrap();
+        content.parse::<toml::Table>().unwrap()
+    };
+
+    let exported = export(&["--identity", "work", "--identity", "work-ci"]);
+    let ids: Vec<&str> = exported["identities"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|i| i["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, ["work", "work-ci"]);
+    assert_eq!(exported["rules"].as_array().unwrap().len(), 1);
+    assert_eq!(exported["rules"][0]["identity"].as_str(), Some("work"));
+    assert!(!exported.contains_key("settings"));
+
+    let exported = export(&["--rules-only"]);
+    assert!(exported["identities"].as_array().unwrap().is_empty());
+    assert_eq!(exported["rules"].as_array().unwrap().len(), 2);
+
+    let exported = export(&["--identities-only"]);
+    assert_eq!(exported["identities"].as_array().unwrap().len(), 3);
+    assert!(exported["rules"].as_array().unwrap().is_empty());
+
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .args(["export", "out.toml", "--identity", "missing"])
+        .current_dir(home_dir.path())
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_scenario_sanitized_export() {
+    // Scenario: Build a team onboarding template without leaking personal paths
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "me@company.com"
+description = "Company account"
+ssh_key = "{home}/.ssh/id_work"
+gpg_key = "ABCD1234EF567890"
+gpg_sign = true
+gh_account = "me-at-work"
+post_switch_command = "{home}/bin/vpn-up"
+
+[[identities]]
+id = "side"
+name = "Me"
+email = "me@example.com"
+description = "Side project for a friend"
+private = true
+
+[[rules]]
+type = "path"
+pattern = "{home}/work/**"
+identity = "work"
+
+[settings]
+workspace_roots = ["{home}/src"]
+"#,
+            home = home_dir.path().display()
+        ),
+    )
+    .unwrap();
+
+    let file = home_dir.path().join("template.toml");
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .arg("export")
+        .arg(&file)
+        .arg("--sanitized")
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(&file).unwrap();
+    assert!(!content.contains(home_dir.path().to_str().unwrap()));
+
+    let exported: toml::Table = content.parse().unwrap();
+    let work = &exported["identities"][0];
+    assert_eq!(work["ssh_key"].as_str(), Some("~/.ssh/id_work"));
+    assert!(work.get("gpg_key").is_none());
+    assert!(work.get("gh_account").is_none());
+    assert!(work.get("post_switch_command").is_none());
+    assert_eq!(work["gpg_sign"].as_bool(), Some(true));
+    assert_eq!(work["description"].as_str(), Some("Company account"));
+    assert!(exported["identities"][1].get("description").is_none());
+    assert_eq!(exported["rules"][0]["pattern"].as_str(), Some("~/work/**"));
+    assert!(!exported.contains_key("settings"));
+}
+
+#[test]
+fn test_scenario_export_script() {
+    // Scenario: Reproduce identities on a locked-down server without installing gid
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "me@company.com"
+ssh_key = "~/.ssh/id_work"
+
+[[rules]]
+type = "path"
+pattern = "~/work/**"
+identity = "work"
+
+[[rules]]
+type = "remote"
+pattern = "github.com/company/*"
+identity = "work"
+"#,
+    )
+    .unwrap();
+
+    let file = home_dir.path().join("identities.sh");
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .arg("export")
+        .arg(&file)
+        .args(["--format", "script"])
+        .assert()
+        .success();
+
+    let script = fs::read_to_string(&file).unwrap();
+    assert!(script.starts_with("#!/bin/sh"));
+    assert!(script.contains("work)\n    git config \"$scope\" user.name 'Work User'"));
+    assert!(script.contains(
+        "git config \"$scope\" core.sshCommand 'ssh -i ~/.ssh/id_work -o IdentitiesOnly=yes'"
+    ));
+    assert!(script.contains(
+        "git config --global 'includeIf.gitdir:~/work/.path' \"$HOME\"/.gitconfig-gid-work"
+    ));
+    assert!(script.contains("# remote rule github.com/company/* → work"));
+}
+
+#[test]
+fn test_scenario_json_and_yaml_round_trip() {
+    // Scenario: Infra tooling generates JSON / YAML configs that gid imports directly
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "me@company.com"
+
+[[rules]]
+type = "remote"
+pattern = "github.com/company/*"
+identity = "work"
+"#,
+    )
+    .unwrap();
+
+    let gid = |config_dir: &std::path::Path, args: &[&str]| {
+        Command::cargo_bin("gid")
+            .unwrap()
+            .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .current_dir(home_dir.path())
+            .args(args)
+            .assert()
+            .success();
+    };
+
+    gid(&config_dir, &["export", "config.json"]);
+    gid(&config_dir, &["export", "config.yml"]);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(home_dir.path().join("config.json")).unwrap())
+            .unwrap();
+    assert_eq!(json["identities"][0]["id"], "work");
+    assert_eq!(json["rules"][0]["type"], "remote");
+
+    let yaml = fs::read_to_string(home_dir.path().join("config.yml")).unwrap();
+    assert!(yaml.contains("email: me@company.com"));
+
+    for file in ["config.json", "config.yml"] {
+        let target = TempDir::new().unwrap();
+        gid(target.path(), &["import", file]);
+        let imported = fs::read_to_string(target.path().join("config.toml")).unwrap();
+        assert!(imported.contains("email = \"me@company.com\""));
+        assert!(imported.contains("pattern = \"github.com/company/*\""));
+    }
+
+    // --format overrides the extension
+    fs::copy(
+        home_dir.path().join("config.json"),
+        home_dir.path().join("generated.txt"),
+    )
+    .unwrap();
+    let target = TempDir::new().unwrap();
+    gid(
+        target.path(),
+        &["import", "generated.txt", "--format", "json"],
+    );
+    assert!(target.path().join("config.toml").exists());
+}
+
+#[test]
+fn test_scenario_export_rules_matching() {
+    // Scenario: Hand one client's setup to a contractor
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "acme"
+name = "Acme Dev"
+email = "dev@acme.com"
+
+[[identities]]
+id = "work"
+name = "Work User"
+email = "me@company.com"
+
+[[rules]]
+type = "remote"
+pattern = "github.com/acme/*"
+identity = "acme"
+
+[[rules]]
+type = "path"
+pattern = "~/clients/acme/**"
+identity = "acme"
+
+[[rules]]
+type = "remote"
+pattern = "github.com/company/*"
+identity = "work"
+"#,
+    )
+    .unwrap();
+
+    let file = home_dir.path().join("acme.toml");
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .arg("export")
+        .arg(&file)
+        .args(["--rules-matching", "github.com/acme/*"])
+        .assert()
+        .success();
+
+    let exported: toml::Table = fs::read_to_string(&file).unwrap().parse().unwrap();
+    let rules = exported["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0]["pattern"].as_str(), Some("github.com/acme/*"));
+    let identities = exported["identities"].as_array().unwrap();
+    assert_eq!(identities.len(), 1);
+    assert_eq!(identities[0]["id"].as_str(), Some("acme"));
+
+    // Matching the identity selects all of its rules
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .arg("export")
+        .arg(&file)
+        .args(["--rules-matching", "acme"])
+        .assert()
+        .success();
+    let exported: toml::Table = fs::read_to_string(&file).unwrap().parse().unwrap();
+    assert_eq!(exported["rules"].as_array().unwrap().len(), 2);
+}
@@ -45,3 +45,334 @@ email = "correct@example.com"
     assert_eq!(author.name().unwrap(), "Correct User");
     assert_eq!(author.email().unwrap(), "correct@example.com");
 }
+
+#[test]
+fn test_scenario_fix_commit_range() {
+    // Scenario: The last two commits were made with the wrong identity
+    let (temp_dir, repo) = common::setup_repo();
+    common::create_commit(&repo, "First");
+    common::create_commit(&repo, "Second");
+    common::create_commit(&repo, "Third");
+    let old_head = repo.head().unwrap().peel_to_commit().unwrap();
+    let old_time = old_head.author().when().seconds();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Correct User"
+email = "correct@example.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+
+    gid(&[
+        "fix-commit",
+        "--range",
+        "HEAD~2..HEAD~1",
+        "-i",
+        "work",
+        "-y",
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains("The range must end at HEAD"));
+
+    gid(&["fix-commit", "--range", "HEAD~2..HEAD", "-i", "work", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Fixed 2 commits"))
+        .stdout(predicate::str::contains("refs/gid/backup/"));
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_ne!(head.id(), old_head.id());
+    assert_eq!(head.message().unwrap(), "Third");
+    assert_eq!(head.author().email().unwrap(), "correct@example.com");
+    assert_eq!(head.author().when().seconds(), old_time);
+    assert_eq!(head.tree_id(), old_head.tree_id());
+
+    let second = head.parent(0).unwrap();
+    assert_eq!(second.message().unwrap(), "Second");
+    assert_eq!(second.author().email().unwrap(), "correct@example.com");
+
+    // Commits outside the range keep their identity and hash
+    let first = second.parent(0).unwrap();
+    assert_eq!(first.author().email().unwrap(), "test@example.com");
+    assert_eq!(
+        first.id(),
+        old_head.parent(0).unwrap().parent(0).unwrap().id()
+    );
+
+    // The old history is kept under a backup ref
+    let backups: Vec<_> = repo
+        .references_glob("refs/gid/backup/*")
+        .unwrap()
+        .map(|r| r.unwrap().target().unwrap())
+        .collect();
+    assert_eq!(backups, vec![old_head.id()]);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_scenario_fix_commit_committer_dates_and_resign() {
+    // Scenario: Rewritten commits must carry the right committer and a fresh signature
+    use std::os::unix::fs::PermissionsExt;
+
+    let (temp_dir, repo) = common::setup_repo();
+    common::create_commit(&repo, "First");
+
+    // A signed commit on top, as `git commit -S` would have made it
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    let sig = git2::Signature::new(
+        "Test User",
+        "test@example.com",
+        &git2::Time::new(1_000_000, 0),
+    )
+    .unwrap();
+    let buffer = repo
+        .commit_create_buffer(&sig, &sig, "Signed", &parent.tree().unwrap(), &[&parent])
+        .unwrap();
+    let signed = repo
+        .commit_signed(
+            buffer.as_str().unwrap(),
+            "-----BEGIN PGP SIGNATURE-----\nold\n-----END PGP SIGNATURE-----",
+            None,
+        )
+        .unwrap();
+    repo.reference("refs/heads/master", signed, true, "test")
+        .unwrap();
+    repo.set_head("refs/heads/master").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Correct User"
+email = "correct@example.com"
+gpg_key = "ABCD1234"
+
+[[identities]]
+id = "nokey"
+name = "No Key"
+email = "nokey@example.com"
+"#,
+    )
+    .unwrap();
+
+    // Stand-in for gpg that signs with a recognizable signature
+    let bin = home_dir.path().join("bin");
+    fs::create_dir_all(&bin).unwrap();
+    let gpg = bin.join("gpg");
+    fs::write(
+        &gpg,
+        "#!/bin/sh\ncat > /dev/null\nprintf -- '-----BEGIN PGP SIGNATURE-----\\nnew %s\\n-----END PGP SIGNATURE-----\\n' \"$3\"\n",
+    )
+    .unwrap();
+    fs::set_permissions(&gpg, fs::Permissions::from_mode(0o755)).unwrap();
+    let path = format!(
+        "{}:{}",
+        bin.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("PATH", &path)
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+    let head = || repo.head().unwrap().peel_to_commit().unwrap();
+
+    gid(&["fix-commit", "-i", "nokey", "--resign", "-y"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("[nokey] has no gpg_key"));
+    gid(&["fix-commit", "--keep-dates", "--reset-dates"])
+        .assert()
+        .failure();
+
+    // Default: author fixed, committer and dates kept, old signature dropped with a warning
+    gid(&["fix-commit", "-i", "work", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "1 rewritten commits were signed; their signatures were dropped (use --resign)",
+        ));
+    let commit = head();
+    assert_eq!(commit.author().email().unwrap(), "correct@example.com");
+    assert_eq!(commit.author().when().seconds(), 1_000_000);
+    assert_eq!(commit.committer().email().unwrap(), "test@example.com");
+    assert_eq!(commit.committer().when().seconds(), 1_000_000);
+    assert!(repo.extract_signature(&commit.id(), None).is_err());
+
+    // --committer --resign: both signatures are the identity's, signed with its key
+    gid(&["fix-commit", "-i", "work", "--committer", "--resign", "-y"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("signatures were dropped").not());
+    let commit = head();
+    assert_eq!(commit.committer().name().unwrap(), "Correct User");
+    assert_eq!(commit.committer().when().seconds(), 1_000_000);
+    let (signature, _) = repo.extract_signature(&commit.id(), None).unwrap();
+    assert!(signature.as_str().unwrap().contains("new ABCD1234"));
+
+    // --reset-dates moves both dates to now
+    gid(&["fix-commit", "-i", "work", "--reset-dates", "-y"])
+        .assert()
+        .success();
+    let commit = head();
+    assert!(commit.author().when().seconds() > 1_000_000);
+    assert!(commit.committer().when().seconds() > 1_000_000);
+}
+
+#[test]
+fn test_scenario_fix_commit_all_by_email() {
+    // Scenario: An old laptop email slipped into history next to correct commits
+    let (temp_dir, repo) = common::setup_repo();
+    let commit_as = |email: &str, message: &str| {
+        repo.config().unwrap().set_str("user.email", email).unwrap();
+        common::create_commit(&repo, message);
+    };
+    commit_as("wrong@old-email.com", "First");
+    commit_as("test@example.com", "Second");
+    commit_as("Wrong@Old-Email.com", "Third");
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Correct User"
+email = "correct@example.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+
+    gid(&[
+        "fix-commit",
+        "--all-by",
+        "nobody@example.com",
+        "-i",
+        "work",
+        "-y",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains(
+        "No commits authored by nobody@example.com",
+    ));
+
+    gid(&[
+        "fix-commit",
+        "--all-by",
+        "wrong@old-email.com",
+        "-i",
+        "work",
+        "-y",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Will fix 2 commits"))
+    .stdout(predicate::str::contains("force push"))
+    .stdout(predicate::str::contains("refs/gid/backup/"));
+
+    let third = repo.head().unwrap().peel_to_commit().unwrap();
+    let second = third.parent(0).unwrap();
+    let first = second.parent(0).unwrap();
+    assert_eq!(third.message().unwrap(), "Third");
+    assert_eq!(third.author().email().unwrap(), "correct@example.com");
+    assert_eq!(second.message().unwrap(), "Second");
+    assert_eq!(second.author().email().unwrap(), "test@example.com");
+    assert_eq!(first.message().unwrap(), "First");
+    assert_eq!(first.author().email().unwrap(), "correct@example.com");
+    assert_eq!(first.parent_count(), 0);
+}
+
+#[test]
+fn test_scenario_refuse_during_merge_or_rebase() {
+    // Scenario: A merge stopped on conflicts; gid must not rewrite HEAD or switch under it
+    let (temp_dir, repo) = common::setup_repo();
+    common::create_commit(&repo, "Initial commit");
+    let head = repo.head().unwrap().target().unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Correct User"
+email = "correct@example.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+
+    let git_dir = repo.path().to_path_buf();
+    fs::write(git_dir.join("MERGE_HEAD"), format!("{head}\n")).unwrap();
+    gid(&["fix-commit", "--identity", "work", "--yes"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("A merge is in progress"))
+        .stderr(predicate::str::contains("git merge --abort"));
+    fs::remove_file(git_dir.join("MERGE_HEAD")).unwrap();
+
+    fs::create_dir_all(git_dir.join("rebase-merge")).unwrap();
+    gid(&["switch", "work"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("A rebase is in progress"));
+    assert_eq!(repo.head().unwrap().target().unwrap(), head);
+    assert_eq!(
+        repo.config().unwrap().get_string("user.email").unwrap(),
+        "test@example.com"
+    );
+
+    fs::remove_dir_all(git_dir.join("rebase-merge")).unwrap();
+    gid(&["switch", "work"]).assert().success();
+}
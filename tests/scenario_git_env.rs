@@ -0,0 +1,97 @@
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+fn write_config(config_dir: &std::path::Path) {
+    fs::create_dir_all(config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_scenario_git_dir_and_work_tree_env() {
+    // Scenario: A dotfiles setup keeps the Git directory apart from the tree it tracks
+    let home_dir = TempDir::new().unwrap();
+    let tree = home_dir.path().join("tree");
+    let git_dir = home_dir.path().join("meta.git");
+    git2::Repository::init(&tree).unwrap();
+    fs::rename(tree.join(".git"), &git_dir).unwrap();
+    fs::write(tree.join(".gid"), "work\n").unwrap();
+
+    let config_dir = home_dir.path().join(".config/gid");
+    write_config(&config_dir);
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .env("GIT_DIR", &git_dir)
+            .env("GIT_WORK_TREE", &tree)
+            .current_dir(&tree)
+            .args(args);
+        cmd
+    };
+
+    // The .gid in the work tree is found through GIT_WORK_TREE
+    gid(&["check"]).assert().code(3);
+    gid(&["switch", "work"]).assert().success();
+    gid(&["check"]).assert().success();
+
+    let repo = git2::Repository::open_bare(&git_dir).unwrap();
+    assert_eq!(
+        repo.config().unwrap().get_string("user.email").unwrap(),
+        "work@example.com"
+    );
+}
+
+#[test]
+fn test_scenario_linked_worktree_common_dir() {
+    // Scenario: Values set in the main repository show up with the right file in a worktree
+    let (temp_dir, repo) = common::setup_repo();
+    common::create_commit(&repo, "Initial commit");
+    let worktree = temp_dir.path().join("wt");
+    Command::new("git")
+        .current_dir(temp_dir.path())
+        .args(["worktree", "add", "-q"])
+        .arg(&worktree)
+        .assert()
+        .success();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    write_config(&config_dir);
+
+    let main_git_dir = temp_dir.path().join(".git");
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .env("GIT_DIR", main_git_dir.join("worktrees/wt"))
+        .env("GIT_COMMON_DIR", &main_git_dir)
+        .current_dir(&worktree)
+        .args(["current", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let source = &json["sources"]["user.email"];
+    assert_eq!(source["value"], "test@example.com");
+    assert_eq!(source["level"], "local");
+    let file = std::path::PathBuf::from(source["file"].as_str().unwrap());
+    assert_eq!(
+        file.canonicalize().unwrap(),
+        main_git_dir.join("config").canonicalize().unwrap()
+    );
+}
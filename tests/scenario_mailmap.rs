@@ -0,0 +1,132 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_mailmap_generate() {
+    // Scenario: Old commits carry outdated names; a .mailmap fixes attribution without rewriting
+    let (temp_dir, repo) = common::setup_repo();
+    let commit_as = |name: &str, email: &str| {
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", name).unwrap();
+        config.set_str("user.email", email).unwrap();
+        common::create_commit(&repo, "change");
+    };
+    commit_as("wu", "work@example.com");
+    commit_as("W. User", "work@example.com");
+    commit_as("Work User", "work@example.com");
+    commit_as("Stranger", "stranger@example.com");
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+    let mailmap = temp_dir.path().join(".mailmap");
+
+    gid(&["mailmap", "generate", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Work User <work@example.com> wu <work@example.com>",
+        ))
+        .stderr(predicate::str::contains(
+            "Stranger <stranger@example.com> (1 commits)",
+        ));
+    assert!(!mailmap.exists());
+
+    // Hand-written entries outside gid's block survive regeneration
+    fs::write(&mailmap, "Someone <someone@example.com>\n").unwrap();
+    gid(&["mailmap", "generate"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("with 2 mappings"));
+    gid(&["mailmap", "generate"]).assert().success();
+
+    let content = fs::read_to_string(&mailmap).unwrap();
+    assert!(content.starts_with("Someone <someone@example.com>\n"));
+    assert_eq!(content.matches("# >>> gid >>>").count(), 1);
+    assert!(content.contains("Work User <work@example.com> W. User <work@example.com>"));
+    assert!(!content.contains("Work User <work@example.com> Work User"));
+    assert!(!content.contains("stranger@example.com"));
+
+    // Git itself applies the mapping
+    Command::new("git")
+        .current_dir(temp_dir.path())
+        .args(["check-mailmap", "wu <work@example.com>"])
+        .assert()
+        .success()
+        .stdout("Work User <work@example.com>\n");
+}
+
+#[test]
+fn test_scenario_audit_uses_mailmap() {
+    // Scenario: An old email is already mapped by hand; audit and generate honor it
+    let (temp_dir, repo) = common::setup_repo();
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Old Me").unwrap();
+    config.set_str("user.email", "old@example.com").unwrap();
+    common::create_commit(&repo, "change");
+    fs::write(
+        temp_dir.path().join(".mailmap"),
+        "Work User <work@example.com> <old@example.com>\n",
+    )
+    .unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+
+    gid(&["audit"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("work@example.com"))
+        .stdout(predicate::str::contains("Unknown Identity").not());
+
+    gid(&["mailmap", "generate", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("old@example.com").not())
+        .stderr("");
+}
@@ -0,0 +1,86 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_scenario_offline_mode() {
+    // Scenario: On an air-gapped machine gid must not touch the network
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    let config = r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[rules]]
+type = "remote"
+pattern = "github.com/company/*"
+identity = "work"
+source = "company"
+
+[[rulesets]]
+name = "company"
+url = "https://rules.example.invalid/company.toml"
+"#;
+    fs::write(config_dir.join("config.toml"), config).unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.current_dir(home_dir.path())
+            .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .env_remove("GID_OFFLINE")
+            .args(args);
+        cmd
+    };
+
+    // Subscriptions keep their last download
+    gid(&["--offline", "ruleset", "update"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("company: skipped (offline)"));
+    let saved = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(saved.contains("github.com/company/*"));
+
+    // Explicit network operations fail with the network exit code
+    gid(&[
+        "--offline",
+        "clone",
+        "https://github.com/company/api.git",
+        "--identity",
+        "work",
+    ])
+    .assert()
+    .code(5)
+    .stderr(predicate::str::contains("gid is offline"));
+    assert!(!home_dir.path().join("api").exists());
+
+    gid(&[
+        "ruleset",
+        "subscribe",
+        "https://rules.example.invalid/more.toml",
+        "--name",
+        "more",
+    ])
+    .env("GID_OFFLINE", "1")
+    .assert()
+    .code(5)
+    .stderr(predicate::str::contains(
+        "Downloading https://rules.example.invalid/more.toml needs the network",
+    ));
+
+    // settings.offline works without the flag
+    fs::write(
+        config_dir.join("config.toml"),
+        format!("{config}\n[settings]\noffline = true\n"),
+    )
+    .unwrap();
+    gid(&["ruleset", "update"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("skipped (offline)"));
+}
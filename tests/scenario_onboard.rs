@@ -0,0 +1,65 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_scenario_onboard_first_run() {
+    // Scenario: New user with an existing ~/.gitconfig runs the setup wizard once
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::write(
+        home_dir.path().join(".gitconfig"),
+        "[user]\n\tname = Jane Doe\n\temail = jane@example.com\n",
+    )
+    .unwrap();
+
+    let gid = || {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("XDG_CONFIG_HOME", home_dir.path().join(".config"))
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env("SHELL", "/bin/bash")
+            .env("NO_COLOR", "1")
+            .current_dir(home_dir.path())
+            .arg("onboard");
+        cmd
+    };
+
+    // Prompts need a terminal
+    gid()
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--yes"));
+
+    gid()
+        .arg("--yes")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Added identity [jane]"))
+        .stdout(predicate::str::contains("gid is ready"));
+
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(config.contains(r#"email = "jane@example.com""#));
+
+    let hook = home_dir.path().join(".config/git/hooks/pre-commit");
+    assert!(hook.exists());
+    let gitconfig = fs::read_to_string(home_dir.path().join(".gitconfig")).unwrap();
+    assert!(gitconfig.contains("hooksPath"));
+
+    let bashrc = fs::read_to_string(home_dir.path().join(".bashrc")).unwrap();
+    assert!(bashrc.contains(r#"eval "$(gid shell-init bash)""#));
+
+    // Running again adds nothing twice
+    gid()
+        .arg("--yes")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No new identities found"))
+        .stdout(predicate::str::contains(
+            "Shell integration already in ~/.bashrc",
+        ));
+    let bashrc = fs::read_to_string(home_dir.path().join(".bashrc")).unwrap();
+    assert_eq!(bashrc.matches("gid shell-init").count(), 1);
+}
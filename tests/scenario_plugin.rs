@@ -0,0 +1,85 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+#[cfg(unix)]
+fn test_scenario_external_subcommand() {
+    // Scenario: A team ships `gid-sync` with company-specific steps
+    use std::os::unix::fs::PermissionsExt;
+
+    let (temp_dir, repo) = common::setup_repo();
+    let mut local = repo.config().unwrap();
+    local.set_str("user.name", "Work User").unwrap();
+    local.set_str("user.email", "work@example.com").unwrap();
+    fs::write(temp_dir.path().join(".gid"), "work\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+
+    let bin = home_dir.path().join("bin");
+    fs::create_dir_all(&bin).unwrap();
+    let plugin = bin.join("gid-sync");
+    fs::write(
+        &plugin,
+        "#!/bin/sh\n\
+         echo \"args=$*\"\n\
+         echo \"identity=$GID_IDENTITY <$GID_IDENTITY_EMAIL>\"\n\
+         echo \"expected=$GID_EXPECTED_IDENTITY\"\n\
+         echo \"config=$GID_CONFIG\"\n\
+         \"$GID\" current --json >/dev/null && echo callback=ok\n\
+         exit 7\n",
+    )
+    .unwrap();
+    fs::set_permissions(&plugin, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let path = format!(
+        "{}:{}",
+        bin.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .env("PATH", &path)
+            .env_remove("GID_CONFIG")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+
+    gid(&["sync", "--dry-run", "origin"])
+        .assert()
+        .code(7)
+        .stdout(
+            predicate::str::contains("args=--dry-run origin")
+                .and(predicate::str::contains("identity=work <work@example.com>"))
+                .and(predicate::str::contains("expected=work"))
+                .and(predicate::str::contains(format!(
+                    "config={}",
+                    config_dir.join("config.toml").display()
+                )))
+                .and(predicate::str::contains("callback=ok")),
+        );
+
+    gid(&["nosuch"])
+        .assert()
+        .code(64)
+        .stderr(predicate::str::contains("no gid-nosuch on PATH"));
+}
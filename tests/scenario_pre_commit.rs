@@ -0,0 +1,85 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_pre_commit_framework_hook() {
+    // Scenario: The repo's hooks are managed by pre-commit, which runs gid-check
+    let (temp_dir, repo) = common::setup_repo();
+    fs::write(temp_dir.path().join(".gid"), "work\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+
+    // The entry pre-commit runs, with the staged files it appends
+    let hook = |files: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.current_dir(temp_dir.path())
+            .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .env_remove("GID_SKIP")
+            .args(["hook", "pre-commit-framework"])
+            .args(files);
+        cmd
+    };
+
+    // Test User is committing where .gid expects work
+    hook(&["src/main.rs", "README.md"])
+        .assert()
+        .code(3)
+        .stdout(predicate::str::contains(
+            "Committing as Test User <test@example.com>, but project config",
+        ))
+        .stdout(predicate::str::contains(
+            "expects [work] Work User <work@example.com>",
+        ))
+        .stdout(predicate::str::contains("gid auto"));
+
+    hook(&[]).env("GID_SKIP", "1").assert().success();
+
+    let mut local = repo.config().unwrap();
+    local.set_str("user.name", "Work User").unwrap();
+    local.set_str("user.email", "work@example.com").unwrap();
+    hook(&["README.md"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    // CI runners have no gid identities to compare against
+    fs::write(config_dir.join("config.toml"), "").unwrap();
+    hook(&[])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+}
+
+#[test]
+fn test_pre_commit_hooks_manifest() {
+    // The published hooks must call the subcommand this binary provides
+    let manifest = fs::read_to_string(
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(".pre-commit-hooks.yaml"),
+    )
+    .unwrap();
+    assert!(manifest.contains("- id: gid-check\n"));
+    assert_eq!(
+        manifest
+            .matches("entry: gid hook pre-commit-framework\n")
+            .count(),
+        2
+    );
+}
@@ -0,0 +1,150 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_scenario_prune_removes_orphaned_artifacts() {
+    // Scenario: User removed the "old" identity and wants everything it left behind gone
+    let home_dir = TempDir::new().unwrap();
+    let home = home_dir.path();
+    let config_dir = home.join(".config/gid");
+    fs::create_dir_all(config_dir.join("identities")).unwrap();
+    fs::create_dir_all(home.join(".ssh")).unwrap();
+
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[settings]
+default_identity = "old"
+
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[rules]]
+type = "path"
+pattern = "~/work/**"
+identity = "work"
+priority = 100
+
+[[rules]]
+type = "path"
+pattern = "~/old/**"
+identity = "old"
+priority = 100
+
+[[rules]]
+type = "remote"
+pattern = "github.com/team/*"
+identity = "old"
+priority = 100
+source = "team"
+"#,
+    )
+    .unwrap();
+
+    let ssh_config = home.join(".ssh/config");
+    fs::write(
+        &ssh_config,
+        r#"Host personal-box
+    HostName example.org
+
+# gid managed - github-com-work
+Host github-com-work
+    HostName github.com
+    User git
+    IdentityFile ~/.ssh/id_work
+    IdentitiesOnly yes
+
+# gid managed - github-com-old
+Host github-com-old
+    HostName github.com
+    User git
+    IdentityFile ~/.ssh/id_old
+    IdentitiesOnly yes
+"#,
+    )
+    .unwrap();
+
+    let work_fragment = config_dir.join("identities/work.gitconfig");
+    let old_fragment = config_dir.join("identities/old.gitconfig");
+    fs::write(&work_fragment, "[user]\n\tname = Work User\n").unwrap();
+    fs::write(&old_fragment, "[user]\n\tname = Old User\n").unwrap();
+    fs::write(
+        home.join(".gitconfig"),
+        format!(
+            "[includeIf \"gitdir:/src/work/\"]\n\tpath = {}\n[includeIf \"gitdir:/src/old/\"]\n\tpath = {}\n",
+            work_fragment.display(),
+            old_fragment.display()
+        ),
+    )
+    .unwrap();
+
+    fs::write(
+        config_dir.join("state.toml"),
+        "[usage.work]\ncount = 3\n\n[usage.old]\ncount = 7\n",
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home)
+            .env("XDG_CONFIG_HOME", home.join(".config"))
+            .env("NO_COLOR", "1")
+            .current_dir(home)
+            .args(args);
+        cmd
+    };
+
+    gid(&["prune", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("~/old/** -> old"))
+        .stdout(predicate::str::contains("settings.default_identity = old"))
+        .stdout(predicate::str::contains("Host github-com-old"))
+        .stdout(predicate::str::contains("old.gitconfig"))
+        .stdout(predicate::str::contains("gitdir:/src/old/"))
+        .stdout(predicate::str::contains("switch statistics of old"))
+        .stdout(predicate::str::contains("1 subscribed rules"))
+        .stdout(predicate::str::contains("github-com-work").not())
+        .stdout(predicate::str::contains("Dry run: 6 artifacts"));
+    assert!(old_fragment.exists());
+
+    gid(&["prune", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Removed 6 artifacts"));
+
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(!config.contains("~/old/**"));
+    assert!(!config.contains("default_identity"));
+    assert!(config.contains("~/work/**"));
+    assert!(config.contains("github.com/team/*"));
+
+    let ssh = fs::read_to_string(&ssh_config).unwrap();
+    assert!(!ssh.contains("github-com-old"));
+    assert!(ssh.contains("Host github-com-work"));
+    assert!(ssh.contains("Host personal-box"));
+
+    assert!(!old_fragment.exists());
+    assert!(work_fragment.exists());
+
+    let gitconfig = fs::read_to_string(home.join(".gitconfig")).unwrap();
+    assert!(!gitconfig.contains("old.gitconfig"));
+    assert!(gitconfig.contains("work.gitconfig"));
+
+    let state = fs::read_to_string(config_dir.join("state.toml")).unwrap();
+    assert!(!state.contains("old"));
+
+    gid(&["prune"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to prune"));
+
+    // Everything prune removed can be restored
+    gid(&["undo"]).assert().success();
+    assert!(old_fragment.exists());
+}
@@ -0,0 +1,68 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_scenario_repos_inventory() {
+    // Scenario: One screen answers whether every checkout uses the right identity
+    let workspace = TempDir::new().unwrap();
+    let client = workspace.path().join("client");
+    git2::Repository::init(&client).unwrap();
+    fs::write(client.join(".gid"), "work\n").unwrap();
+    let scratch = git2::Repository::init(workspace.path().join("scratch")).unwrap();
+    scratch
+        .remote("origin", "git@github.com:me/scratch.git")
+        .unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[settings]
+workspace_roots = ["{}"]
+"#,
+            workspace.path().display()
+        ),
+    )
+    .unwrap();
+    fs::write(home_dir.path().join(".gitconfig"), "").unwrap();
+
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .env("NO_COLOR", "1")
+        .arg("repos")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("work (project)"))
+        .stdout(predicate::str::contains("git@github.com:me/scratch.git"))
+        .stdout(predicate::str::contains("0 ok, 1 drifted, 1 unmatched"));
+
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .args(["repos", "--json", "--scan"])
+        .arg(workspace.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let repos: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let repos = repos.as_array().unwrap();
+    assert_eq!(repos.len(), 2);
+    assert_eq!(repos[0]["expected"]["identity"], "work");
+    assert_eq!(repos[0]["drifted"], true);
+    assert_eq!(repos[1]["remote"], "git@github.com:me/scratch.git");
+    assert!(repos[1]["expected"].is_null());
+}
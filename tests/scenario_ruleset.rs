@@ -0,0 +1,110 @@
+use assert_cmd::Command;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_scenario_ruleset_subscription() {
+    // Scenario: The org publishes its org→identity mapping and updates it quarterly
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "me@company.com"
+
+[[rules]]
+type = "path"
+pattern = "~/personal/**"
+identity = "work"
+"#,
+    )
+    .unwrap();
+
+    let ruleset = home_dir.path().join("company-rules.toml");
+    fs::write(
+        &ruleset,
+        r#"
+[[rules]]
+type = "remote"
+pattern = "github.com/company/*"
+identity = "work"
+
+[[rules]]
+type = "remote"
+pattern = "gitlab.company.com/*"
+identity = "work"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        Command::cargo_bin("gid")
+            .unwrap()
+            .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("NO_COLOR", "1")
+            .args(args)
+            .output()
+            .unwrap()
+    };
+
+    let output = gid(&[
+        "ruleset",
+        "subscribe",
+        ruleset.to_str().unwrap(),
+        "--name",
+        "company",
+    ]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Subscribed to company: 2 rules"));
+
+    // Subscribed rules are read-only
+    let output = gid(&["rule", "list"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("github.com/company/* -> [work] (ruleset company)"));
+    let id = stdout
+        .lines()
+        .find(|l| l.contains("(ruleset company)"))
+        .and_then(|l| l.split_whitespace().next())
+        .unwrap()
+        .to_string();
+    let output = gid(&["rule", "remove", &id]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("read-only"));
+
+    // The org changes its mapping
+    fs::write(
+        &ruleset,
+        r#"
+[[rules]]
+type = "remote"
+pattern = "github.com/company/*"
+identity = "work"
+
+[[rules]]
+type = "remote"
+pattern = "github.com/company-labs/*"
+identity = "work"
+"#,
+    )
+    .unwrap();
+
+    let output = gid(&["ruleset", "update"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("company: 2 rules (+1, -1)"));
+
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(config.contains("github.com/company-labs/*"));
+    assert!(!config.contains("gitlab.company.com/*"));
+    assert!(config.contains("~/personal/**"));
+
+    let output = gid(&["ruleset", "unsubscribe", "company"]);
+    assert!(output.status.success());
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(!config.contains("github.com/company"));
+    assert!(!config.contains("[[rulesets]]"));
+    assert!(config.contains("~/personal/**"));
+}
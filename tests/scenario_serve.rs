@@ -0,0 +1,153 @@
+use assert_cmd::Command;
+use serde_json::{json, Value};
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+const CONFIG: &str = r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[identities]]
+id = "personal"
+name = "Personal User"
+email = "personal@example.com"
+"#;
+
+#[test]
+fn test_scenario_serve_stdio() {
+    // Scenario: An editor plugin spawns `gid serve --stdio` and queries the open project
+    let (temp_dir, repo) = common::setup_repo();
+    let repo_path = temp_dir.path().canonicalize().unwrap();
+    fs::write(repo_path.join(".gid"), "work\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(config_dir.join("config.toml"), CONFIG).unwrap();
+
+    let path = repo_path.to_str().unwrap();
+    let requests = [
+        json!({"jsonrpc": "2.0", "id": 1, "method": "identities"}),
+        json!({"jsonrpc": "2.0", "id": 2, "method": "resolve", "params": {"path": path}}),
+        json!({"jsonrpc": "2.0", "id": 3, "method": "current", "params": {"path": path}}),
+        json!({"jsonrpc": "2.0", "id": 4, "method": "switch", "params": {"path": path}}),
+        json!({"jsonrpc": "2.0", "id": 5, "method": "current", "params": {"path": path}}),
+        json!({"jsonrpc": "2.0", "id": 6, "method": "switch", "params": {"path": path, "identity": "missing"}}),
+        json!({"jsonrpc": "2.0", "id": 7, "method": "unknown"}),
+    ];
+    let input: String = requests.iter().map(|r| format!("{r}\n")).collect();
+
+    let output = Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .args(["serve", "--stdio"])
+        .write_stdin(input)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let responses: Vec<Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(responses.len(), requests.len());
+
+    assert_eq!(responses[0]["id"], 1);
+    assert_eq!(responses[0]["result"][0]["id"], "work");
+    assert_eq!(responses[0]["result"][1]["email"], "personal@example.com");
+
+    assert_eq!(responses[1]["result"]["identity"], "work");
+    assert_eq!(responses[1]["result"]["source"], "project");
+
+    assert_eq!(
+        responses[2]["result"]["effective"]["email"],
+        "test@example.com"
+    );
+    assert_eq!(responses[2]["result"]["expected"]["matches"], false);
+
+    assert_eq!(responses[3]["result"]["identity"], "work");
+    assert_eq!(
+        responses[3]["result"]["changed"],
+        json!(["user.name", "user.email"])
+    );
+    assert_eq!(
+        repo.config().unwrap().get_string("user.email").unwrap(),
+        "work@example.com"
+    );
+
+    assert_eq!(responses[4]["result"]["effective"]["identity"], "work");
+    assert_eq!(responses[4]["result"]["expected"]["matches"], true);
+
+    assert_eq!(responses[5]["error"]["code"], -32602);
+    assert_eq!(
+        responses[5]["error"]["message"],
+        "Identity 'missing' not found"
+    );
+    assert_eq!(responses[6]["error"]["code"], -32601);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_scenario_serve_socket() {
+    // Scenario: Several tools share one long-running server over a Unix socket
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(config_dir.join("config.toml"), CONFIG).unwrap();
+    let socket = config_dir.join("gid.sock");
+
+    let mut server = std::process::Command::new(assert_cmd::cargo::cargo_bin("gid"))
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .arg("serve")
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let mut stream = None;
+    for _ in 0..100 {
+        if let Ok(s) = UnixStream::connect(&socket) {
+            stream = Some(s);
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    let mut stream = stream.expect("server did not start");
+
+    // Only the owner may connect
+    let mode = fs::metadata(&socket).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+
+    // A second server refuses to take over a live socket
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .arg("serve")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("already listening"));
+
+    writeln!(stream, r#"{{"jsonrpc":"2.0","id":"v","method":"version"}}"#).unwrap();
+    let mut line = String::new();
+    BufReader::new(stream.try_clone().unwrap())
+        .read_line(&mut line)
+        .unwrap();
+    let response: Value = serde_json::from_str(&line).unwrap();
+    assert_eq!(response["id"], "v");
+    assert_eq!(response["result"], env!("CARGO_PKG_VERSION"));
+
+    server.kill().unwrap();
+    server.wait().unwrap();
+}
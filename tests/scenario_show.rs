@@ -0,0 +1,85 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_scenario_show_identity_detail() {
+    // Scenario: User inspects everything gid knows about one identity
+    let workspace = TempDir::new().unwrap();
+    let client = workspace.path().join("client");
+    let repo = git2::Repository::init(&client).unwrap();
+    let mut local = repo.config().unwrap();
+    local.set_str("user.name", "Work User").unwrap();
+    local.set_str("user.email", "work@example.com").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            r#"
+[[identities]]
+id = "personal"
+name = "Me"
+email = "me@example.com"
+
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+description = "Day job"
+gpg_key = "ABCD1234"
+gpg_sign = true
+tags = ["client"]
+
+[[rules]]
+type = "remote"
+pattern = "github.com/me/*"
+identity = "personal"
+priority = 50
+
+[[rules]]
+type = "path"
+pattern = "~/work/**"
+identity = "work"
+priority = 100
+
+[settings]
+workspace_roots = ["{}"]
+"#,
+            workspace.path().display()
+        ),
+    )
+    .unwrap();
+
+    let gid = || {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1");
+        cmd
+    };
+
+    gid()
+        .args(["show", "work"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Day job"))
+        .stdout(predicate::str::contains("gpg ABCD1234 (commits signed)"))
+        .stdout(predicate::str::contains("commit.gpgsign"))
+        .stdout(predicate::str::contains("1. [path] ~/work/** -> work"))
+        .stdout(predicate::str::contains("client"))
+        .stdout(predicate::str::contains("Last used:  never"));
+
+    let output = gid().args(["show", "work", "--json"]).output().unwrap();
+    assert!(output.status.success());
+    let detail: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(detail["identity"]["email"], "work@example.com");
+    assert_eq!(detail["git_config"]["user.signingkey"], "ABCD1234");
+    assert_eq!(detail["rules"][0]["index"], 1);
+    assert_eq!(detail["repos"].as_array().unwrap().len(), 1);
+
+    gid().args(["show", "missing"]).assert().failure();
+}
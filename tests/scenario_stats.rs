@@ -0,0 +1,82 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_scenario_stats_report() {
+    // Scenario: User looks for unused identities and gaps in rules
+    let workspace = TempDir::new().unwrap();
+    let client = workspace.path().join("work/client");
+    let repo = git2::Repository::init(&client).unwrap();
+    let mut local = repo.config().unwrap();
+    local.set_str("user.name", "Work User").unwrap();
+    local.set_str("user.email", "work@example.com").unwrap();
+    git2::Repository::init(workspace.path().join("scratch")).unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[identities]]
+id = "old"
+name = "Old User"
+email = "old@example.com"
+
+[[rules]]
+type = "path"
+pattern = "{root}/work/**"
+identity = "work"
+priority = 100
+
+[[rules]]
+type = "remote"
+pattern = "github.com/old/*"
+identity = "old"
+priority = 50
+"#,
+            root = workspace.path().display()
+        ),
+    )
+    .unwrap();
+
+    let gid = |json: bool| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .arg("stats")
+            .arg("--scan")
+            .arg(workspace.path());
+        if json {
+            cmd.arg("--json");
+        }
+        cmd
+    };
+
+    gid(false)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "2 repositories scanned, 1 use a configured identity",
+        ))
+        .stdout(predicate::str::contains("Unmatched Repositories (1):"));
+
+    let output = gid(true).output().unwrap();
+    assert!(output.status.success());
+    let stats: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["scanned"], 2);
+    assert_eq!(stats["identities"][0]["repos"], 1);
+    assert_eq!(stats["rules"][0]["hits"], 1);
+    assert_eq!(stats["rules"][1]["hits"], 0);
+    assert_eq!(stats["unused"], serde_json::json!(["old"]));
+    assert_eq!(stats["unmatched"].as_array().unwrap().len(), 1);
+}
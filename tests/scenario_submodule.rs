@@ -0,0 +1,104 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_submodule_gets_its_own_identity() {
+    // Scenario: A work project vendors an open-source library as a submodule
+    let (lib_dir, lib_repo) = common::setup_repo();
+    common::create_commit(&lib_repo, "Library");
+
+    let (temp_dir, repo) = common::setup_repo();
+    common::create_commit(&repo, "Initial commit");
+    Command::new("git")
+        .current_dir(temp_dir.path())
+        .args(["-c", "protocol.file.allow=always", "submodule", "add", "-q"])
+        .arg(lib_dir.path())
+        .arg("lib")
+        .assert()
+        .success();
+    let lib = temp_dir.path().join("lib");
+    Command::new("git")
+        .current_dir(&lib)
+        .args([
+            "remote",
+            "set-url",
+            "origin",
+            "git@github.com:oss-org/lib.git",
+        ])
+        .assert()
+        .success();
+    fs::write(temp_dir.path().join(".gid"), "work\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[identities]]
+id = "oss"
+name = "OSS User"
+email = "oss@example.com"
+
+[[rules]]
+type = "remote"
+pattern = "github.com/oss-org/*"
+identity = "oss"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+    let lib_email = || {
+        git2::Repository::open(&lib)
+            .unwrap()
+            .config()
+            .unwrap()
+            .get_string("user.email")
+            .ok()
+    };
+
+    gid(&["doctor", "--recurse-submodules"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Submodule lib does not use"));
+
+    gid(&["auto", "--dry-run", "--recurse-submodules"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("lib [oss]"));
+    assert_ne!(lib_email().as_deref(), Some("oss@example.com"));
+
+    // The superproject's .gid is not inherited; the submodule's remote rule wins
+    gid(&["switch", "work", "--recurse-submodules"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("lib [oss]"));
+    assert_eq!(
+        repo.config().unwrap().get_string("user.email").unwrap(),
+        "work@example.com"
+    );
+    assert_eq!(lib_email().as_deref(), Some("oss@example.com"));
+
+    gid(&["doctor", "--recurse-submodules"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No issues found"));
+}
@@ -0,0 +1,683 @@
+use assert_cmd::Command;
+use git2::Repository;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_recursive_switch() {
+    // Scenario: User onboards a client directory with several existing checkouts
+    let workspace = TempDir::new().unwrap();
+    let repo_a = Repository::init(workspace.path().join("app")).unwrap();
+    let repo_b = Repository::init(workspace.path().join("libs/core")).unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+
+    let config_content = r#"
+[[identities]]
+id = "acme"
+name = "Acme Dev"
+email = "dev@acme.com"
+"#;
+    fs::write(config_dir.join("config.toml"), config_content).unwrap();
+
+    // User switches every repository under the client directory
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .arg("switch")
+        .arg("acme")
+        .arg("--recursive")
+        .arg(workspace.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Switched 2 repositories"));
+
+    // Verify both repositories got the local identity
+    for repo in [&repo_a, &repo_b] {
+        let config = repo
+            .config()
+            .unwrap()
+            .open_level(git2::ConfigLevel::Local)
+            .unwrap();
+        assert_eq!(config.get_string("user.email").unwrap(), "dev@acme.com");
+    }
+}
+
+#[test]
+fn test_scenario_ssh_only_for_remote_hosts() {
+    // Scenario: Repository lives on a self-hosted GitLab, only that host should get an alias
+    let repo_dir = TempDir::new().unwrap();
+    let repo = Repository::init(repo_dir.path()).unwrap();
+    repo.remote("origin", "git@gitlab.acme.com:team/app.git")
+        .unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let ssh_dir = home_dir.path().join(".ssh");
+    fs::create_dir_all(&ssh_dir).unwrap();
+    fs::write(ssh_dir.join("id_acme"), "dummy key").unwrap();
+
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    let config_content = format!(
+        r#"
+[[identities]]
+id = "acme"
+name = "Acme Dev"
+email = "dev@acme.com"
+ssh_key = "{}"
+"#,
+        ssh_dir.join("id_acme").display()
+    );
+    fs::write(config_dir.join("config.toml"), config_content).unwrap();
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .current_dir(repo_dir.path())
+        .arg("switch")
+        .arg("acme")
+        .assert()
+        .success();
+
+    let ssh_config = fs::read_to_string(ssh_dir.join("config")).unwrap();
+    assert!(ssh_config.contains("HostName gitlab.acme.com"));
+    assert!(!ssh_config.contains("github.com"));
+}
+
+#[test]
+fn test_scenario_global_switch_requires_confirmation() {
+    // Scenario: A script accidentally switches the global identity without --yes
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "personal"
+name = "Me"
+email = "me@example.com"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .current_dir(home_dir.path())
+        .args(["switch", "personal", "--global"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--yes"));
+
+    // An answer piped into stdin is not a confirmation
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .current_dir(home_dir.path())
+        .args(["switch", "personal", "--global"])
+        .write_stdin("y\n")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("requires confirmation"));
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .current_dir(home_dir.path())
+        .args(["switch", "personal", "--global", "--yes"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Switched to global identity"));
+
+    let gitconfig = fs::read_to_string(home_dir.path().join(".gitconfig")).unwrap();
+    assert!(gitconfig.contains("me@example.com"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_scenario_post_switch_command() {
+    // Scenario: User updates their terminal title after every switch
+    let repo_dir = TempDir::new().unwrap();
+    Repository::init(repo_dir.path()).unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    let marker = home_dir.path().join("switched");
+    fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[settings]
+post_switch_command = "echo \"$GID_IDENTITY $GID_IDENTITY_EMAIL $GID_SCOPE\" > {}"
+"#,
+            marker.display()
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .current_dir(repo_dir.path())
+        .args(["switch", "work"])
+        .assert()
+        .success();
+
+    let output = fs::read_to_string(marker).unwrap();
+    assert_eq!(output.trim(), "work work@example.com project");
+}
+
+#[test]
+fn test_scenario_watch_once_enforces_project_identity() {
+    // Scenario: A workspace contains a checkout pinned to an identity via .gid
+    let workspace = TempDir::new().unwrap();
+    let repo_path = workspace.path().join("client-app");
+    let repo = Repository::init(&repo_path).unwrap();
+    fs::write(repo_path.join(".gid"), "acme\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "acme"
+name = "Acme Dev"
+email = "dev@acme.com"
+"#,
+    )
+    .unwrap();
+
+    // Check mode only reports the violation
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .args(["watch", "--once", "--check"])
+        .arg(workspace.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("expected [acme]"));
+
+    let local = repo
+        .config()
+        .unwrap()
+        .open_level(git2::ConfigLevel::Local)
+        .unwrap();
+    assert!(local.get_string("user.email").is_err());
+
+    // Enforce mode applies the identity
+    let mut cmd = Command::cargo_bin("gid").unwrap();
+    cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .args(["watch", "--once"])
+        .arg(workspace.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[acme]"));
+
+    let local = repo
+        .config()
+        .unwrap()
+        .open_level(git2::ConfigLevel::Local)
+        .unwrap();
+    assert_eq!(local.get_string("user.email").unwrap(), "dev@acme.com");
+}
+
+#[test]
+fn test_scenario_watch_picks_up_gid_and_config_changes() {
+    // Scenario: The daemon is running when a .gid file is added and config.toml edited
+    let workspace = TempDir::new().unwrap();
+    let repo_path = workspace.path().join("client-app");
+    let repo = Repository::init(&repo_path).unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    let write_config = |email: &str| {
+        fs::write(
+            config_dir.join("config.toml"),
+            format!("[[identities]]\nid = \"acme\"\nname = \"Acme Dev\"\nemail = \"{email}\"\n"),
+        )
+        .unwrap()
+    };
+    write_config("dev@acme.com");
+
+    let mut daemon = std::process::Command::new(assert_cmd::cargo::cargo_bin("gid"))
+        .env("GID_CONFIG_DIR", &config_dir)
+        .env("HOME", home_dir.path())
+        .args(["watch", "--interval", "1"])
+        .arg(workspace.path())
+        .stdout(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+    let email = || {
+        repo.config()
+            .unwrap()
+            .open_level(git2::ConfigLevel::Local)
+            .unwrap()
+            .get_string("user.email")
+            .ok()
+    };
+    let wait_for = |expected: &str| {
+        for _ in 0..100 {
+            if email().as_deref() == Some(expected) {
+                return true;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        false
+    };
+
+    // The repository was seen without a .gid; adding one is picked up
+    std::thread::sleep(std::time::Duration::from_millis(1500));
+    fs::write(repo_path.join(".gid"), "acme\n").unwrap();
+    let applied = wait_for("dev@acme.com");
+
+    // A half-written config does not stop the daemon
+    fs::write(config_dir.join("config.toml"), "[[identities").unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(2500));
+    let survived = daemon.try_wait().unwrap().is_none();
+
+    // The fixed config applies to repositories already seen
+    write_config("team@acme.com");
+    let reloaded = wait_for("team@acme.com");
+
+    let _ = daemon.kill();
+    let _ = daemon.wait();
+    assert!(applied, "the new .gid was not applied");
+    assert!(survived, "the daemon exited on an invalid config");
+    assert!(reloaded, "the edited identity was not applied");
+}
+
+#[test]
+fn test_scenario_chdir_flag() {
+    // Scenario: A script switches a checkout without cd-ing into it first
+    let workspace = TempDir::new().unwrap();
+    let repo = Repository::init(workspace.path().join("app")).unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "acme"
+name = "Acme Dev"
+email = "dev@acme.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(workspace.path())
+            .args(args);
+        cmd
+    };
+
+    gid(&["-C", "app", "switch", "acme"]).assert().success();
+    assert_eq!(
+        repo.config()
+            .unwrap()
+            .open_level(git2::ConfigLevel::Local)
+            .unwrap()
+            .get_string("user.email")
+            .unwrap(),
+        "dev@acme.com"
+    );
+
+    // The flag is global, so it may follow the subcommand too
+    gid(&["current", "--chdir", "app"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("dev@acme.com"));
+
+    gid(&["-C", "missing", "current"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Cannot change to missing"));
+}
+
+#[test]
+fn test_scenario_verbose_and_quiet_flags() {
+    // Scenario: Debugging what a switch touched, then running it from a script
+    let temp_dir = TempDir::new().unwrap();
+    let repo = Repository::init(temp_dir.path()).unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[identities]]
+id = "personal"
+name = "Personal User"
+email = "me@example.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .env_remove("GID_LOG")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+
+    gid(&["switch", "work", "-v"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "gid: info: set user.email = work@example.com in local config",
+        ));
+
+    gid(&["-q", "switch", "personal"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::is_empty());
+    assert_eq!(
+        repo.config().unwrap().get_string("user.email").unwrap(),
+        "me@example.com"
+    );
+
+    gid(&["-q", "-v", "current"]).assert().failure();
+}
+
+#[test]
+fn test_scenario_messages_follow_language() {
+    // Scenario: A user with a Chinese locale gets Chinese messages unless the config pins English
+    let temp_dir = TempDir::new().unwrap();
+    Repository::init(temp_dir.path()).unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    let identities = r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#;
+    fs::write(config_dir.join("config.toml"), identities).unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .env_remove("LC_ALL")
+            .env_remove("LC_MESSAGES")
+            .env("LANG", "zh_CN.UTF-8")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+
+    gid(&["switch", "work"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "已切换到项目身份: [work] Work User <work@example.com>",
+        ));
+    gid(&["switch", "nobody"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("找不到身份 'nobody'"));
+    gid(&["rule", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("没有配置规则"));
+
+    fs::write(
+        config_dir.join("config.toml"),
+        format!("{identities}\n[settings]\nlanguage = \"en\"\n"),
+    )
+    .unwrap();
+    gid(&["switch", "work"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Switched to project identity: [work]",
+        ));
+}
+
+#[test]
+fn test_scenario_include_if_switch() {
+    // Scenario: User pins a checkout to an identity through the global config
+    let (temp_dir, repo) = common::setup_repo();
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+    fs::write(
+        home_dir.path().join(".gitconfig"),
+        "[core]\n\teditor = vim\n",
+    )
+    .unwrap();
+
+    let switch = || {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(["switch", "work", "--include-if"]);
+        cmd
+    };
+
+    switch()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Switched to includeIf identity: [work] Work User <work@example.com>",
+        ))
+        .stdout(predicate::str::contains(
+            "user.name (local) Test User → (unset)",
+        ))
+        .stdout(predicate::str::contains(
+            "user.email (local) test@example.com → (unset)",
+        ));
+
+    let workdir = temp_dir.path().canonicalize().unwrap();
+    let fragment = config_dir.join("identities/work.gitconfig");
+    let gitconfig = fs::read_to_string(home_dir.path().join(".gitconfig")).unwrap();
+    assert!(gitconfig.contains("editor = vim"), "{gitconfig}");
+    assert!(
+        gitconfig.contains(&format!("[includeIf \"gitdir:{}/\"]", workdir.display())),
+        "{gitconfig}"
+    );
+    assert!(
+        gitconfig.contains(&format!("path = {}", fragment.display())),
+        "{gitconfig}"
+    );
+    let values = fs::read_to_string(&fragment).unwrap();
+    assert!(values.contains("name = Work User"), "{values}");
+    assert!(values.contains("email = work@example.com"), "{values}");
+
+    // The local identity keys are gone, so the fragment applies (a fresh
+    // handle, as libgit2 may not notice the emptied section was dropped)
+    let local = git2::Repository::open(repo.path())
+        .unwrap()
+        .config()
+        .unwrap()
+        .open_level(git2::ConfigLevel::Local)
+        .unwrap();
+    assert!(local.get_string("user.name").is_err());
+    assert!(local.get_string("user.email").is_err());
+
+    // Running it again changes nothing and adds no second block
+    switch()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No configuration changes"));
+    let again = fs::read_to_string(home_dir.path().join(".gitconfig")).unwrap();
+    assert_eq!(again, gitconfig);
+    assert_eq!(fs::read_to_string(&fragment).unwrap(), values);
+}
+
+#[test]
+fn test_scenario_switch_warns_about_unpushed_commits() {
+    // Scenario: User committed with the wrong identity, then switches before pushing
+    let (temp_dir, repo) = common::setup_repo();
+    common::create_commit(&repo, "Initial commit");
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[identities]]
+id = "test"
+name = "Test User"
+email = "test@example.com"
+"#,
+    )
+    .unwrap();
+
+    let switch = |identity: &str| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(["switch", identity]);
+        cmd
+    };
+
+    // No upstream: nothing to compare against, no warning
+    common::create_commit(&repo, "Local only");
+    switch("work")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("unpushed").not());
+    switch("test").assert().success();
+
+    // origin/<branch> is behind by two commits authored as test@example.com
+    let head = repo.head().unwrap();
+    let branch = head.shorthand().unwrap().to_string();
+    let pushed = head.target().unwrap();
+    repo.remote("origin", "https://example.com/app.git")
+        .unwrap();
+    repo.reference(
+        &format!("refs/remotes/origin/{branch}"),
+        pushed,
+        true,
+        "test",
+    )
+    .unwrap();
+    let mut config = repo.config().unwrap();
+    config
+        .set_str(&format!("branch.{branch}.remote"), "origin")
+        .unwrap();
+    config
+        .set_str(
+            &format!("branch.{branch}.merge"),
+            &format!("refs/heads/{branch}"),
+        )
+        .unwrap();
+    common::create_commit(&repo, "Fix typo");
+    common::create_commit(&repo, "Add feature");
+
+    switch("work")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "2 unpushed commits were authored as test@example.com:",
+        ))
+        .stdout(predicate::str::contains("Add feature"))
+        .stdout(predicate::str::contains("Fix typo"))
+        .stdout(predicate::str::contains("Local only").not())
+        .stdout(predicate::str::contains(
+            "gid fix-commit --range @{u}..HEAD --identity work",
+        ));
+
+    // Switching back: none of them were authored as work@example.com
+    switch("test")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("unpushed").not());
+}
+
+#[test]
+fn test_scenario_switch_prints_config_diff() {
+    // Scenario: Only the email differs between the current and the new identity
+    let (temp_dir, _repo) = common::setup_repo();
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Test User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+
+    let switch = || {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(["switch", "work"]);
+        cmd
+    };
+
+    switch()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("  Changes:\n"))
+        .stdout(predicate::str::contains(
+            "    user.email       test@example.com → work@example.com\n",
+        ))
+        .stdout(predicate::str::contains("user.name").not());
+
+    switch()
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No configuration changes"))
+        .stdout(predicate::str::contains("user.email").not());
+}
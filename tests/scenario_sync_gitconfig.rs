@@ -0,0 +1,129 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// user.email as plain git resolves it inside a repository
+fn git_email(home: &Path, repo: &Path) -> String {
+    let output = std::process::Command::new("git")
+        .args(["config", "user.email"])
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("GIT_CONFIG_NOSYSTEM", "1")
+        .current_dir(repo)
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+#[test]
+fn test_scenario_sync_gitconfig_from_path_rules() {
+    // Scenario: User wants path rules to apply even in tools that never run gid
+    let home_dir = TempDir::new().unwrap();
+    let home = home_dir.path();
+    let config_dir = home.join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[identities]]
+id = "personal"
+name = "Personal User"
+email = "personal@example.com"
+
+[[rules]]
+type = "path"
+pattern = "~/work/oss/**"
+identity = "personal"
+priority = 50
+
+[[rules]]
+type = "path"
+pattern = "~/work/**"
+identity = "work"
+priority = 100
+"#,
+    )
+    .unwrap();
+    fs::write(home.join(".gitconfig"), "[core]\n\tautocrlf = false\n").unwrap();
+
+    let app = home.join("work/app");
+    let lib = home.join("work/oss/lib");
+    let side = home.join("side/tool");
+    for repo in [&app, &lib, &side] {
+        git2::Repository::init(repo).unwrap();
+    }
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home)
+            .env("XDG_CONFIG_HOME", home.join(".config"))
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .env("NO_COLOR", "1")
+            .current_dir(home)
+            .args(args);
+        cmd
+    };
+
+    gid(&["sync-gitconfig", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("+ gitdir:~/work/  → work"))
+        .stdout(predicate::str::contains("Dry run"));
+    assert_eq!(git_email(home, &app), "");
+
+    gid(&["sync-gitconfig"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Synced 2 includeIf blocks"));
+
+    // The higher-priority rule is written last so it wins in Git
+    assert_eq!(git_email(home, &app), "work@example.com");
+    assert_eq!(git_email(home, &lib), "personal@example.com");
+    assert_eq!(git_email(home, &side), "");
+    let gitconfig = fs::read_to_string(home.join(".gitconfig")).unwrap();
+    assert!(gitconfig.contains("autocrlf"));
+
+    gid(&["sync-gitconfig"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Already in sync"));
+
+    // Rule changes are picked up without running sync-gitconfig again
+    gid(&[
+        "rule",
+        "add",
+        "-r",
+        "path",
+        "-p",
+        "~/side/**",
+        "-i",
+        "personal",
+    ])
+    .assert()
+    .success()
+    .stdout(predicate::str::contains("Synced includeIf blocks"));
+    assert_eq!(git_email(home, &side), "personal@example.com");
+
+    let fragment = config_dir.join("identities/work.gitconfig");
+    assert!(fragment.exists());
+
+    gid(&["sync-gitconfig", "--remove"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Removed 3 includeIf blocks and 2 unused fragments",
+        ));
+    assert_eq!(git_email(home, &app), "");
+    let gitconfig = fs::read_to_string(home.join(".gitconfig")).unwrap();
+    assert!(!gitconfig.contains("includeIf"));
+    assert!(gitconfig.contains("autocrlf"));
+    assert!(!fragment.exists());
+}
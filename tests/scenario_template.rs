@@ -0,0 +1,98 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_commit_template_follows_identity() {
+    // Scenario: Client work needs a ticket-prefix template the personal identity must not get
+    let (temp_dir, repo) = common::setup_repo();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "client"
+name = "Client User"
+email = "me@client.example"
+
+[[identities]]
+id = "personal"
+name = "Personal User"
+email = "personal@example.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            // Accept the starter template as is
+            .env("EDITOR", "true")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+    let template = config_dir.join("templates/client.txt");
+    let commit_template = || repo.config().unwrap().get_string("commit.template").ok();
+
+    gid(&["template", "show", "client"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("gid template edit client"));
+
+    gid(&["template", "edit", "client"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Commit template saved"));
+    assert!(fs::read_to_string(&template)
+        .unwrap()
+        .contains("# Commit message template for client"));
+    assert!(fs::read_to_string(config_dir.join("config.toml"))
+        .unwrap()
+        .contains("commit_template"));
+
+    fs::write(&template, "[PROJ-] \n").unwrap();
+    gid(&["template", "show", "client"])
+        .assert()
+        .success()
+        .stdout("[PROJ-] \n");
+
+    gid(&["switch", "client"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("commit.template"));
+    assert_eq!(
+        commit_template().as_deref(),
+        Some(template.to_str().unwrap())
+    );
+
+    // Switching away removes it
+    gid(&["switch", "personal"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(unset)"));
+    assert_eq!(commit_template(), None);
+
+    // A template the user set by hand is not gid's to remove
+    repo.config()
+        .unwrap()
+        .set_str("commit.template", "/opt/team/template.txt")
+        .unwrap();
+    gid(&["switch", "personal"]).assert().success();
+    assert_eq!(commit_template().as_deref(), Some("/opt/team/template.txt"));
+
+    gid(&["template", "remove", "client"]).assert().success();
+    assert!(!template.exists());
+    gid(&["template", "remove", "client"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("[client] has no commit template"));
+}
@@ -0,0 +1,225 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+mod common;
+
+#[test]
+fn test_scenario_undo_reverts_last_operation() {
+    // Scenario: User adds a bad rule and switches the wrong repository, then backs out
+    let (temp_dir, repo) = common::setup_repo();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+
+[[rules]]
+type = "path"
+pattern = "~/work/**"
+identity = "work"
+priority = 100
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+
+    gid(&[
+        "rule",
+        "add",
+        "-r",
+        "remote",
+        "-p",
+        "github.com/acme/*",
+        "-i",
+        "work",
+    ])
+    .assert()
+    .success();
+    gid(&["switch", "work"]).assert().success();
+
+    let local_email = || {
+        repo.config()
+            .unwrap()
+            .open_level(git2::ConfigLevel::Local)
+            .unwrap()
+            .get_string("user.email")
+            .unwrap()
+    };
+    assert_eq!(local_email(), "work@example.com");
+
+    gid(&["undo", "--list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("gid switch work"))
+        .stdout(predicate::str::contains("gid rule add"));
+
+    // Newest first: the switch, then the rule
+    gid(&["undo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Undid gid switch work"));
+    assert_eq!(local_email(), "test@example.com");
+
+    gid(&["undo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Undid gid rule add"));
+    let config = fs::read_to_string(config_dir.join("config.toml")).unwrap();
+    assert!(config.contains("~/work/**"));
+    assert!(!config.contains("github.com/acme/*"));
+
+    gid(&["undo"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Nothing to undo"));
+}
+
+#[test]
+fn test_scenario_undo_refuses_to_discard_later_edits() {
+    // Scenario: User adds a rule, then edits config.toml by hand before running undo
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    let config_path = config_dir.join("config.toml");
+    fs::write(
+        &config_path,
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .args(args);
+        cmd
+    };
+
+    gid(&["rule", "add", "-r", "path", "-p", "~/work/**", "-i", "work"])
+        .assert()
+        .success();
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(config_dir.join("journal.toml"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o077, 0, "journal.toml is readable by others");
+    }
+
+    let edited = fs::read_to_string(&config_path).unwrap() + "# edited by hand\n";
+    fs::write(&config_path, &edited).unwrap();
+
+    gid(&["undo"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("edited"))
+        .stderr(predicate::str::contains("changed after gid rule add"))
+        .stderr(predicate::str::contains("--force"));
+    assert_eq!(fs::read_to_string(&config_path).unwrap(), edited);
+
+    gid(&["undo", "--force"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Undid gid rule add"));
+    assert!(!fs::read_to_string(&config_path)
+        .unwrap()
+        .contains("~/work/**"));
+}
+
+#[test]
+fn test_scenario_undo_after_long_running_watch() {
+    // Scenario: The watch daemon fixes a checkout, the user edits its config, then runs undo
+    let workspace = TempDir::new().unwrap();
+    let repo_path = workspace.path().join("client-app");
+    let repo = git2::Repository::init(&repo_path).unwrap();
+    fs::write(repo_path.join(".gid"), "work\n").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+"#,
+    )
+    .unwrap();
+
+    let local_email = || {
+        repo.config()
+            .unwrap()
+            .open_level(git2::ConfigLevel::Local)
+            .unwrap()
+            .get_string("user.email")
+            .ok()
+    };
+
+    // The daemon is still running (and never exits cleanly) when the user edits the repo
+    let mut daemon = std::process::Command::new(assert_cmd::cargo::cargo_bin("gid"))
+        .env("GID_CONFIG_DIR", &config_dir)
+        .env("HOME", home_dir.path())
+        .args(["watch", "--interval", "1"])
+        .arg(workspace.path())
+        .stdout(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+    let mut applied = false;
+    for _ in 0..100 {
+        if local_email().as_deref() == Some("work@example.com") {
+            applied = true;
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    let _ = daemon.kill();
+    let _ = daemon.wait();
+    assert!(applied, "the daemon did not apply the identity");
+
+    repo.config()
+        .unwrap()
+        .open_level(git2::ConfigLevel::Local)
+        .unwrap()
+        .set_str("core.editor", "vim")
+        .unwrap();
+
+    Command::cargo_bin("gid")
+        .unwrap()
+        .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+        .env("HOME", home_dir.path())
+        .env("NO_COLOR", "1")
+        .arg("undo")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("changed after gid watch"));
+    assert_eq!(local_email().as_deref(), Some("work@example.com"));
+}
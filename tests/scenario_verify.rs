@@ -0,0 +1,122 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use tempfile::TempDir;
+
+#[test]
+fn test_scenario_verify_identity() {
+    // Scenario: User checks a new identity before relying on it
+    let home_dir = TempDir::new().unwrap();
+    let key = home_dir.path().join("id_work");
+    let status = std::process::Command::new("ssh-keygen")
+        .args([
+            "-q",
+            "-t",
+            "ed25519",
+            "-N",
+            "",
+            "-C",
+            "work@example.com",
+            "-f",
+        ])
+        .arg(&key)
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        format!(
+            r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+ssh_key = "{}"
+
+[[identities]]
+id = "broken"
+name = "Broken"
+email = "not-an-email"
+gpg_sign = true
+"#,
+            key.display()
+        ),
+    )
+    .unwrap();
+
+    let gid = |id: &str| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("NO_COLOR", "1")
+            .env_remove("SSH_AUTH_SOCK")
+            .args(["verify", id]);
+        cmd
+    };
+
+    gid("work")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Fingerprint"))
+        .stdout(predicate::str::contains("SHA256:"))
+        .stdout(predicate::str::contains("- Forge github.com"))
+        .stdout(predicate::str::contains("0 failed"));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::set_permissions(&key, fs::Permissions::from_mode(0o644)).unwrap();
+        gid("work")
+            .assert()
+            .failure()
+            .stdout(predicate::str::contains("644 is too open"));
+
+        // doctor reports the key and --fix restricts it again
+        let repo_dir = home_dir.path().join("repo");
+        let repo = git2::Repository::init(&repo_dir).unwrap();
+        let mut local = repo.config().unwrap();
+        local.set_str("user.name", "Work User").unwrap();
+        local.set_str("user.email", "work@example.com").unwrap();
+        let doctor = |fix: bool| {
+            let mut cmd = Command::cargo_bin("gid").unwrap();
+            cmd.current_dir(&repo_dir)
+                .env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+                .env("HOME", home_dir.path())
+                .env("NO_COLOR", "1")
+                .arg("doctor");
+            if fix {
+                cmd.arg("--fix");
+            }
+            cmd
+        };
+        doctor(false)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("is not owner-only (mode 644)"));
+        doctor(true)
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("Restricted"));
+        let mode = fs::metadata(&key).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        gid("work")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("✓ Permissions"));
+    }
+
+    gid("broken")
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "'not-an-email' is not a valid address",
+        ))
+        .stdout(predicate::str::contains(
+            "gpg_sign is enabled but no gpg_key is set",
+        ))
+        .stderr(predicate::str::contains("[broken] failed verification"));
+}
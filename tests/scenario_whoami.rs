@@ -0,0 +1,126 @@
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+mod common;
+
+/// Stand-ins for ssh and gh that answer like GitHub would
+#[cfg(unix)]
+fn fake_forge(bin: &Path, ssh_login: &str, api_login: &str, emails: &str) {
+    use std::os::unix::fs::PermissionsExt;
+
+    let scripts = [
+        (
+            "ssh",
+            format!(
+                "#!/bin/sh\ncase \"$1\" in -G) exit 255 ;; esac\necho \"Hi {ssh_login}! You've successfully authenticated, but GitHub does not provide shell access.\" >&2\nexit 1\n"
+            ),
+        ),
+        (
+            "gh",
+            format!(
+                "#!/bin/sh\ncase \"$*\" in\n  --version) echo 'gh version 2.0.0' ;;\n  *user/emails*) echo '{emails}' ;;\n  *user*) echo '{{\"login\": \"{api_login}\", \"email\": null}}' ;;\nesac\n"
+            ),
+        ),
+    ];
+    for (name, content) in scripts {
+        let path = bin.join(name);
+        fs::write(&path, content).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+}
+
+#[test]
+#[cfg(unix)]
+fn test_scenario_whoami_remote() {
+    // Scenario: User checks that pushes from this repo really go out as their work account
+    let (temp_dir, repo) = common::setup_repo();
+    repo.remote("origin", "git@github.com:acme/app.git")
+        .unwrap();
+    let mut local = repo.config().unwrap();
+    local.set_str("user.name", "Work User").unwrap();
+    local.set_str("user.email", "work@example.com").unwrap();
+
+    let home_dir = TempDir::new().unwrap();
+    let config_dir = home_dir.path().join(".config/gid");
+    fs::create_dir_all(&config_dir).unwrap();
+    fs::write(
+        config_dir.join("config.toml"),
+        r#"
+[[identities]]
+id = "work"
+name = "Work User"
+email = "work@example.com"
+gh_account = "work-octocat"
+"#,
+    )
+    .unwrap();
+
+    let bin = home_dir.path().join("bin");
+    fs::create_dir_all(&bin).unwrap();
+    let path = format!(
+        "{}:{}",
+        bin.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+
+    let gid = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("gid").unwrap();
+        cmd.env("GID_CONFIG_DIR", config_dir.to_str().unwrap())
+            .env("HOME", home_dir.path())
+            .env("PATH", &path)
+            .env("NO_COLOR", "1")
+            .current_dir(temp_dir.path())
+            .args(args);
+        cmd
+    };
+
+    gid(&["whoami"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "[work] Work User <work@example.com>",
+        ));
+
+    // Both the SSH key and the token belong to the expected account
+    fake_forge(
+        &bin,
+        "work-octocat",
+        "work-octocat",
+        r#"[{"email": "work@example.com", "verified": true}]"#,
+    );
+    gid(&["whoami", "--remote"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("SSH (github.com)"))
+        .stdout(predicate::str::contains("work-octocat"))
+        .stdout(predicate::str::contains("Remote accounts match [work]"));
+
+    // The SSH key is the personal one and the email is not on the account
+    fake_forge(
+        &bin,
+        "octocat",
+        "work-octocat",
+        r#"[{"email": "octocat@example.com", "verified": true}]"#,
+    );
+    gid(&["whoami", "--remote"])
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains(
+            "The SSH key authenticates as octocat, [work] expects work-octocat",
+        ))
+        .stdout(predicate::str::contains(
+            "work@example.com is not an email of work-octocat",
+        ))
+        .stderr(predicate::str::contains(
+            "Remote accounts do not match [work]",
+        ));
+
+    let output = gid(&["whoami", "--remote", "--json"]).output().unwrap();
+    let value: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(value["ssh"]["login"], "octocat");
+    assert_eq!(value["api"]["login"], "work-octocat");
+    assert_eq!(value["mismatches"].as_array().unwrap().len(), 2);
+}